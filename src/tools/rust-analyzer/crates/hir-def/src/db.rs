@@ -2,7 +2,7 @@
 use base_db::{Crate, RootQueryDb, SourceDatabase};
 use hir_expand::{
     EditionedFileId, HirFileId, InFile, Lookup, MacroCallId, MacroDefId, MacroDefKind,
-    db::ExpandDatabase,
+    db::ExpandDatabase, mod_path::ModPath,
 };
 use la_arena::ArenaMap;
 use triomphe::Arc;
@@ -10,16 +10,18 @@
 use crate::{
     AssocItemId, AttrDefId, BlockId, BlockLoc, ConstId, ConstLoc, DefWithBodyId, EnumId, EnumLoc,
     EnumVariantId, EnumVariantLoc, ExternBlockId, ExternBlockLoc, ExternCrateId, ExternCrateLoc,
-    FunctionId, FunctionLoc, GenericDefId, ImplId, ImplLoc, LocalFieldId, Macro2Id, Macro2Loc,
-    MacroExpander, MacroId, MacroRulesId, MacroRulesLoc, MacroRulesLocFlags, ProcMacroId,
-    ProcMacroLoc, StaticId, StaticLoc, StructId, StructLoc, TraitId, TraitLoc, TypeAliasId,
-    TypeAliasLoc, UnionId, UnionLoc, UseId, UseLoc, VariantId,
+    FindPathConfig, FunctionId, FunctionLoc, GenericDefId, ImplId, ImplLoc, LocalFieldId, Macro2Id,
+    Macro2Loc, MacroExpander, MacroId, MacroRulesId, MacroRulesLoc, MacroRulesLocFlags, ModuleId,
+    ProcMacroId, ProcMacroLoc, StaticId, StaticLoc, StructId, StructLoc, TraitId, TraitLoc,
+    TypeAliasId, TypeAliasLoc, UnionId, UnionLoc, UseId, UseLoc, VariantId,
     attrs::AttrFlags,
     expr_store::{
         Body, BodySourceMap, ExpressionStore, ExpressionStoreSourceMap, scope::ExprScopes,
     },
+    find_path::{self, PrefixKind},
     hir::generics::GenericParams,
     import_map::ImportMap,
+    item_scope::ItemInNs,
     item_tree::{ItemTree, file_item_tree_query},
     nameres::crate_def_map,
     signatures::{
@@ -236,6 +238,18 @@ fn generic_params_and_store_and_source_map(
     #[salsa::invoke(ImportMap::import_map_query)]
     fn import_map(&self, krate: Crate) -> Arc<ImportMap>;
 
+    /// Finds a path to refer to `item` from the module `from`, see [`find_path::find_path`].
+    #[salsa::invoke(find_path::find_path_query)]
+    #[salsa::lru(1024)]
+    fn find_path(
+        &self,
+        item: ItemInNs,
+        from: ModuleId,
+        prefix_kind: PrefixKind,
+        ignore_local_imports: bool,
+        cfg: FindPathConfig,
+    ) -> Option<ModPath>;
+
     // region:visibilities
 
     #[salsa::invoke(visibility::field_visibilities_query)]
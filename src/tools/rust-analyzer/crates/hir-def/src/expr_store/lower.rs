@@ -1056,7 +1056,9 @@ fn maybe_collect_expr(&mut self, expr: ast::Expr) -> Option<ExprId> {
                     })
                 }
                 Some(ast::BlockModifier::Async(_)) => {
-                    self.with_label_rib(RibKind::Closure, |this| {
+                    // `?` returns from the async block, not from an enclosing `try` block
+                    let prev_try_block_label = self.current_try_block_label.take();
+                    let expr = self.with_label_rib(RibKind::Closure, |this| {
                         this.with_awaitable_block(Awaitable::Yes, |this| {
                             this.collect_block_(e, |id, statements, tail| Expr::Async {
                                 id,
@@ -1064,17 +1066,22 @@ fn maybe_collect_expr(&mut self, expr: ast::Expr) -> Option<ExprId> {
                                 tail,
                             })
                         })
-                    })
+                    });
+                    self.current_try_block_label = prev_try_block_label;
+                    expr
                 }
                 Some(ast::BlockModifier::Const(_)) => {
-                    self.with_label_rib(RibKind::Constant, |this| {
+                    let prev_try_block_label = self.current_try_block_label.take();
+                    let expr = self.with_label_rib(RibKind::Constant, |this| {
                         this.with_awaitable_block(Awaitable::No("constant block"), |this| {
                             this.with_binding_owner(|this| {
                                 let inner_expr = this.collect_block(e);
                                 this.alloc_expr(Expr::Const(inner_expr), syntax_ptr)
                             })
                         })
-                    })
+                    });
+                    self.current_try_block_label = prev_try_block_label;
+                    expr
                 }
                 // FIXME
                 Some(ast::BlockModifier::AsyncGen(_)) => {
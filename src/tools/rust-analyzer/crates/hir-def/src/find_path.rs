@@ -21,7 +21,21 @@
 
 /// Find a path that can be used to refer to a certain item. This can depend on
 /// *from where* you're referring to the item, hence the `from` parameter.
+///
+/// The result is memoized by [`DefDatabase::find_path`], so looking up the same item repeatedly,
+/// as completions and assists do for every candidate, only searches the def maps once.
 pub fn find_path(
+    db: &dyn DefDatabase,
+    item: ItemInNs,
+    from: ModuleId,
+    prefix_kind: PrefixKind,
+    ignore_local_imports: bool,
+    cfg: FindPathConfig,
+) -> Option<ModPath> {
+    db.find_path(item, from, prefix_kind, ignore_local_imports, cfg)
+}
+
+pub(crate) fn find_path_query(
     db: &dyn DefDatabase,
     item: ItemInNs,
     from: ModuleId,
@@ -29,7 +43,7 @@ pub fn find_path(
     ignore_local_imports: bool,
     mut cfg: FindPathConfig,
 ) -> Option<ModPath> {
-    let _p = tracing::info_span!("find_path").entered();
+    let _p = tracing::info_span!("find_path_query").entered();
 
     // - if the item is a builtin, it's in scope
     if let ItemInNs::Types(ModuleDefId::BuiltinType(builtin)) = item {
@@ -73,7 +87,7 @@ enum Stability {
 const MAX_PATH_LEN: usize = 15;
 const FIND_PATH_FUEL: usize = 10000;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum PrefixKind {
     /// Causes paths to always start with either `self`, `super`, `crate` or a crate-name.
     /// This is the same as plain, just that paths will start with `self` prepended if the path
@@ -177,6 +191,7 @@ fn find_path_for_module(
                 path_text_len: 5,
                 stability: Stable,
                 prefer_due_to_prelude: false,
+                is_local: false,
             });
         }
         // - otherwise if the item is the crate root of a dependency crate, return the name from the extern prelude
@@ -240,6 +255,7 @@ fn find_path_for_module(
             path_text_len: path_kind_len(kind),
             stability: Stable,
             prefer_due_to_prelude: false,
+            is_local: false,
         });
     }
 
@@ -364,11 +380,13 @@ fn calculate_best_path(
     } else {
         // Item was defined in some upstream crate. This means that it must be exported from one,
         // too (unless we can't name it at all). It could *also* be (re)exported by the same crate
-        // that wants to import it here, but we always prefer to use the external path here.
+        // that wants to import it here, in which case that path wins over an equally long one
+        // through a dependency, as it doesn't leak where the item originally comes from.
 
         ctx.from_crate.data(ctx.db).dependencies.iter().for_each(|dep| {
             find_in_dep(ctx, visited_modules, item, max_len, best_choice, dep.crate_id)
         });
+        calculate_best_path_local(ctx, visited_modules, item, max_len, best_choice);
     }
 }
 
@@ -449,6 +467,7 @@ fn find_in_dep(
             continue;
         };
         cov_mark::hit!(partially_imported);
+        choice.is_local = false;
         if info.is_unstable {
             if !ctx.cfg.allow_unstable {
                 // the item is unstable and we are not allowed to use unstable items
@@ -472,13 +491,14 @@ fn calculate_best_path_local(
     find_local_import_locations(ctx, item, visited_modules, |visited_modules, name, module_id| {
         // we are looking for paths of length up to best_path_len, any longer will make it be
         // less optimal. The -1 is due to us pushing name onto it afterwards.
-        if let Some(choice) = find_path_for_module(
+        if let Some(mut choice) = find_path_for_module(
             ctx,
             visited_modules,
             module_id,
             false,
             best_choice.as_ref().map_or(max_len, |it| it.path.len()) - 1,
         ) {
+            choice.is_local = true;
             Choice::try_select(best_choice, choice, ctx.cfg.prefer_prelude, name.clone());
         }
     });
@@ -493,6 +513,8 @@ struct Choice {
     stability: Stability,
     /// Whether this path contains a prelude segment and preference for it has been signaled
     prefer_due_to_prelude: bool,
+    /// Whether the item was found in a module of the crate we are searching from
+    is_local: bool,
 }
 
 impl Choice {
@@ -501,6 +523,7 @@ fn new(prefer_prelude: bool, kind: PathKind, name: Name, stability: Stability) -
             path_text_len: path_kind_len(kind) + name.as_str().len(),
             stability,
             prefer_due_to_prelude: prefer_prelude && name == sym::prelude,
+            is_local: false,
             path: ModPath::from_segments(kind, iter::once(name)),
         }
     }
@@ -527,12 +550,21 @@ fn try_select(
             .cmp(&current.stability)
             .then_with(|| other.prefer_due_to_prelude.cmp(&current.prefer_due_to_prelude))
             .then_with(|| (current.path.len()).cmp(&(other.path.len() + 1)))
+            // Of two equally long paths, prefer the one through the crate's own re-exports.
+            .then_with(|| other.is_local.cmp(&current.is_local))
         {
             Ordering::Less => return,
             Ordering::Equal => {
                 other.path_text_len += name.as_str().len();
-                if let Ordering::Less | Ordering::Equal =
-                    current.path_text_len.cmp(&other.path_text_len)
+                // Fall back to comparing the segments so that the result doesn't depend on the
+                // order in which the candidates were found.
+                let current_segments = current.path.segments().iter().map(Name::as_str);
+                let other_segments =
+                    other.path.segments().iter().map(Name::as_str).chain(iter::once(name.as_str()));
+                if let Ordering::Less | Ordering::Equal = current
+                    .path_text_len
+                    .cmp(&other.path_text_len)
+                    .then_with(|| current_segments.cmp(other_segments))
                 {
                     return;
                 }
@@ -651,8 +683,9 @@ fn find_local_import_locations(
 
 #[cfg(test)]
 mod tests {
+    use base_db::SourceDatabase;
     use expect_test::{Expect, expect};
-    use hir_expand::db::ExpandDatabase;
+    use hir_expand::{db::ExpandDatabase, files::FilePosition};
     use itertools::Itertools;
     use span::Edition;
     use stdx::format_to;
@@ -678,31 +711,7 @@ fn check_found_path_(
     ) {
         let (db, pos) = TestDB::with_position(ra_fixture);
         let module = db.module_at_position(pos);
-        let parsed_path_file =
-            syntax::SourceFile::parse(&format!("use {path};"), span::Edition::CURRENT);
-        let ast_path =
-            parsed_path_file.syntax_node().descendants().find_map(syntax::ast::Path::cast).unwrap();
-        let mod_path = ModPath::from_src(&db, ast_path, &mut |range| {
-            db.span_map(pos.file_id.into()).as_ref().span_for_range(range).ctx
-        })
-        .unwrap();
-
-        let (def_map, local_def_map) = module.local_def_map(&db);
-        let resolved = def_map
-            .resolve_path(
-                local_def_map,
-                &db,
-                module,
-                &mod_path,
-                crate::item_scope::BuiltinShadowMode::Module,
-                None,
-            )
-            .0;
-        let resolved = resolved
-            .take_types()
-            .map(ItemInNs::Types)
-            .or_else(|| resolved.take_values().map(ItemInNs::Values))
-            .expect("path does not resolve to a type or value");
+        let resolved = resolve_item(&db, pos, module, path);
 
         let mut res = String::new();
         for (prefix, ignore_local_imports) in
@@ -732,6 +741,35 @@ fn check_found_path_(
         expect.assert_eq(&res);
     }
 
+    fn resolve_item(db: &TestDB, pos: FilePosition, module: ModuleId, path: &str) -> ItemInNs {
+        let parsed_path_file =
+            syntax::SourceFile::parse(&format!("use {path};"), span::Edition::CURRENT);
+        let ast_path =
+            parsed_path_file.syntax_node().descendants().find_map(syntax::ast::Path::cast).unwrap();
+        let mod_path = ModPath::from_src(db, ast_path, &mut |range| {
+            db.span_map(pos.file_id.into()).as_ref().span_for_range(range).ctx
+        })
+        .unwrap();
+
+        let (def_map, local_def_map) = module.local_def_map(db);
+        let resolved = def_map
+            .resolve_path(
+                local_def_map,
+                db,
+                module,
+                &mod_path,
+                crate::item_scope::BuiltinShadowMode::Module,
+                None,
+            )
+            .0;
+        resolved
+            .take_types()
+            .map(ItemInNs::Types)
+            .or_else(|| resolved.take_values().map(ItemInNs::Values))
+            .or_else(|| resolved.take_macros().map(ItemInNs::Macros))
+            .expect("path does not resolve to a type, value or macro")
+    }
+
     fn check_found_path(
         #[rust_analyzer::rust_fixture] ra_fixture: &str,
         path: &str,
@@ -772,6 +810,48 @@ fn check_found_path_prefer_no_std_allow_unstable(
         check_found_path_(ra_fixture, path, false, false, true, true, expect);
     }
 
+    #[test]
+    fn find_path_is_memoized_until_def_map_changes() {
+        let (mut db, pos) = TestDB::with_position(
+            r#"
+//- /main.rs
+mod foo;
+$0
+//- /foo.rs
+pub struct S;
+"#,
+        );
+        let module = db.module_at_position(pos);
+        let item = resolve_item(&db, pos, module, "foo::S");
+        let cfg = FindPathConfig {
+            prefer_no_std: false,
+            prefer_prelude: false,
+            prefer_absolute: false,
+            allow_unstable: false,
+        };
+        let find = |db: &TestDB| {
+            find_path(db, item, module, PrefixKind::Plain, false, cfg)
+                .unwrap()
+                .display(db, Edition::CURRENT)
+                .to_string()
+        };
+        let executed =
+            |events: Vec<String>| events.iter().filter(|it| it.contains("find_path")).count();
+
+        let events = db.log_executed(|| {
+            assert_eq!(find(&db), "foo::S");
+            assert_eq!(find(&db), "foo::S");
+        });
+        assert_eq!(executed(events), 1);
+
+        db.set_file_text(pos.file_id.file_id(&db), "mod foo;\nuse foo::S;\n");
+        let events = db.log_executed(|| {
+            assert_eq!(find(&db), "S");
+            assert_eq!(find(&db), "S");
+        });
+        assert_eq!(executed(events), 1);
+    }
+
     #[test]
     fn same_module() {
         check_found_path(
@@ -940,6 +1020,32 @@ fn different_crate() {
         );
     }
 
+    #[test]
+    fn macro_exported_from_submodule() {
+        check_found_path(
+            r#"
+//- /main.rs crate:main deps:dep
+$0
+//- /dep.rs crate:dep
+pub mod inner {
+    #[macro_export]
+    macro_rules! the_macro {
+        () => {};
+    }
+}
+        "#,
+            "dep::the_macro",
+            expect![[r#"
+                Plain  (imports ✔): dep::the_macro
+                Plain  (imports ✖): dep::the_macro
+                ByCrate(imports ✔): dep::the_macro
+                ByCrate(imports ✖): dep::the_macro
+                BySelf (imports ✔): dep::the_macro
+                BySelf (imports ✖): dep::the_macro
+            "#]],
+        );
+    }
+
     #[test]
     fn different_crate_renamed() {
         check_found_path(
@@ -1116,6 +1222,115 @@ fn different_crate_reexport() {
         );
     }
 
+    #[test]
+    fn local_reexport_of_dependency_item() {
+        check_found_path(
+            r#"
+//- /main.rs crate:main deps:dep
+pub use dep::Thing;
+mod m {
+    $0
+}
+//- /dep.rs crate:dep
+pub struct Thing;
+        "#,
+            "crate::Thing",
+            expect![[r#"
+                Plain  (imports ✔): crate::Thing
+                Plain  (imports ✖): crate::Thing
+                ByCrate(imports ✔): crate::Thing
+                ByCrate(imports ✖): crate::Thing
+                BySelf (imports ✔): crate::Thing
+                BySelf (imports ✖): crate::Thing
+            "#]],
+        );
+    }
+
+    #[test]
+    fn prefer_local_reexport_over_dependency_path_of_equal_length() {
+        check_found_path(
+            r#"
+//- /main.rs crate:main deps:dep
+mod reexports {
+    pub use dep::Thing;
+}
+$0
+//- /dep.rs crate:dep
+pub struct Thing;
+        "#,
+            "reexports::Thing",
+            expect![[r#"
+                Plain  (imports ✔): reexports::Thing
+                Plain  (imports ✖): reexports::Thing
+                ByCrate(imports ✔): dep::Thing
+                ByCrate(imports ✖): dep::Thing
+                BySelf (imports ✔): self::reexports::Thing
+                BySelf (imports ✖): self::reexports::Thing
+            "#]],
+        );
+    }
+
+    #[test]
+    fn prefer_shorter_dependency_path_over_local_reexport() {
+        check_found_path(
+            r#"
+//- /main.rs crate:main deps:dep
+mod m {
+    pub use dep::Thing;
+}
+mod n {
+    $0
+}
+//- /dep.rs crate:dep
+pub struct Thing;
+        "#,
+            "dep::Thing",
+            expect![[r#"
+                Plain  (imports ✔): dep::Thing
+                Plain  (imports ✖): dep::Thing
+                ByCrate(imports ✔): dep::Thing
+                ByCrate(imports ✖): dep::Thing
+                BySelf (imports ✔): dep::Thing
+                BySelf (imports ✖): dep::Thing
+            "#]],
+        );
+    }
+
+    #[test]
+    fn equally_good_paths_are_chosen_independently_of_declaration_order() {
+        for fixture in [
+            r#"
+//- /main.rs crate:main deps:dep
+mod a { pub use dep::Thing; }
+mod b { pub use dep::Thing; }
+$0
+//- /dep.rs crate:dep
+pub struct Thing;
+        "#,
+            r#"
+//- /main.rs crate:main deps:dep
+mod b { pub use dep::Thing; }
+mod a { pub use dep::Thing; }
+$0
+//- /dep.rs crate:dep
+pub struct Thing;
+        "#,
+        ] {
+            check_found_path(
+                fixture,
+                "a::Thing",
+                expect![[r#"
+                    Plain  (imports ✔): a::Thing
+                    Plain  (imports ✖): a::Thing
+                    ByCrate(imports ✔): dep::Thing
+                    ByCrate(imports ✖): dep::Thing
+                    BySelf (imports ✔): self::a::Thing
+                    BySelf (imports ✖): self::a::Thing
+                "#]],
+            );
+        }
+    }
+
     #[test]
     fn prelude() {
         check_found_path(
@@ -377,7 +377,7 @@ pub fn query(db: &dyn DefDatabase, id: ImplId) -> (Arc<Self>, Arc<ExpressionStor
         if src.value.unsafe_token().is_some() {
             flags.insert(ImplFlags::UNSAFE);
         }
-        if src.value.excl_token().is_some() {
+        if src.value.is_negative() {
             flags.insert(ImplFlags::NEGATIVE);
         }
         if src.value.default_token().is_some() {
@@ -2508,6 +2508,20 @@ enum E { F1, }
     "#,
         0,
     );
+    check_number(
+        r#"
+    enum E { F1 = -2, F2, F3 = 0x10, F4 }
+    const GOAL: i8 = E::F2 as i8;
+    "#,
+        -1,
+    );
+    check_number(
+        r#"
+    enum E { F1 = -2, F2, F3 = 0x10, F4 }
+    const GOAL: i8 = E::F4 as i8;
+    "#,
+        17,
+    );
     let (db, file_id) = TestDB::with_single_file(
         r#"
         enum E { A = 1, B }
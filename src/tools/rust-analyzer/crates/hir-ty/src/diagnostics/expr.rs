@@ -8,6 +8,7 @@
 use either::Either;
 use hir_def::{
     AdtId, AssocItemId, DefWithBodyId, HasModule, ItemContainerId, Lookup,
+    attrs::AttrFlags,
     lang_item::LangItems,
     resolver::{HasResolver, ValueNs},
 };
@@ -125,6 +126,7 @@ fn validate_body(&mut self) {
         for (id, expr) in body.exprs() {
             if let Some((variant, missed_fields, true)) =
                 record_literal_missing_fields(db, self.infer, id, expr)
+                && !self.is_foreign_non_exhaustive(variant)
             {
                 self.diagnostics.push(BodyValidationDiagnostic::RecordMissingFields {
                     record: Either::Left(id),
@@ -156,6 +158,7 @@ fn validate_body(&mut self) {
         for (id, pat) in body.pats() {
             if let Some((variant, missed_fields, true)) =
                 record_pattern_missing_fields(db, self.infer, id, pat)
+                && !self.is_foreign_non_exhaustive(variant)
             {
                 self.diagnostics.push(BodyValidationDiagnostic::RecordMissingFields {
                     record: Either::Right(id),
@@ -166,6 +169,16 @@ fn validate_body(&mut self) {
         }
     }
 
+    /// Returns whether the given variant is from another crate and declared `#[non_exhaustive]`.
+    ///
+    /// Such variants can't be constructed with a record literal or matched without `..` at all,
+    /// so pointing out the missing fields would be misleading.
+    fn is_foreign_non_exhaustive(&self, variant: VariantId) -> bool {
+        let db = self.db();
+        variant.module(db).krate(db) != self.owner.krate(db)
+            && AttrFlags::query(db, variant.into()).contains(AttrFlags::NON_EXHAUSTIVE)
+    }
+
     fn validate_call(
         &mut self,
         call_id: ExprId,
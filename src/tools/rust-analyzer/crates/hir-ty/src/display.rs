@@ -10,11 +10,14 @@
 use base_db::Crate;
 use either::Either;
 use hir_def::{
-    FindPathConfig, GenericDefId, HasModule, LocalFieldId, Lookup, ModuleDefId, ModuleId, TraitId,
+    FindPathConfig, FunctionId, GenericDefId, HasModule, LocalFieldId, Lookup, ModuleDefId,
+    ModuleId, TraitId, TypeParamId,
     db::DefDatabase,
     expr_store::{ExpressionStore, path::Path},
     find_path::{self, PrefixKind},
-    hir::generics::{TypeOrConstParamData, TypeParamProvenance, WherePredicate},
+    hir::generics::{
+        LocalTypeOrConstParamId, TypeOrConstParamData, TypeParamProvenance, WherePredicate,
+    },
     item_scope::ItemInNs,
     item_tree::FieldsShape,
     lang_item::LangItems,
@@ -62,6 +65,10 @@
     utils::{detect_variant_from_bytes, fn_traits},
 };
 
+mod compact_signature;
+
+pub use compact_signature::inlined_type_params;
+
 pub type Result<T = (), E = HirDisplayError> = std::result::Result<T, E>;
 
 pub trait HirWrite: fmt::Write {
@@ -97,6 +104,12 @@ pub struct HirFormatter<'a, 'db> {
     display_lifetimes: DisplayLifetime,
     display_kind: DisplayKind,
     display_target: DisplayTarget,
+    /// Whether to render type parameters of functions as `impl Bound` where that is faithful, see
+    /// [`inlined_type_params`].
+    compact_signature: bool,
+    /// The function whose signature is being rendered, its type parameters are referred to by
+    /// name in the signature.
+    signature_owner: Option<FunctionId>,
     bounds_formatting_ctx: BoundsFormattingCtx<'db>,
 }
 
@@ -223,6 +236,7 @@ fn into_displayable<'a>(
             closure_style,
             show_container_bounds,
             display_lifetimes: DisplayLifetime::OnlyNamedOrStatic,
+            compact_signature: false,
         }
     }
 
@@ -247,6 +261,7 @@ fn display<'a>(
             display_kind: DisplayKind::Diagnostics,
             show_container_bounds: false,
             display_lifetimes: DisplayLifetime::OnlyNamedOrStatic,
+            compact_signature: false,
         }
     }
 
@@ -272,6 +287,7 @@ fn display_truncated<'a>(
             display_kind: DisplayKind::Diagnostics,
             show_container_bounds: false,
             display_lifetimes: DisplayLifetime::OnlyNamedOrStatic,
+            compact_signature: false,
         }
     }
 
@@ -297,6 +313,7 @@ fn display_limited<'a>(
             display_kind: DisplayKind::Diagnostics,
             show_container_bounds: false,
             display_lifetimes: DisplayLifetime::OnlyNamedOrStatic,
+            compact_signature: false,
         }
     }
 
@@ -324,6 +341,8 @@ fn display_source_code<'a>(
             display_kind: DisplayKind::SourceCode { target_module_id: module_id, allow_opaque },
             show_container_bounds: false,
             display_lifetimes: DisplayLifetime::OnlyNamedOrStatic,
+            compact_signature: false,
+            signature_owner: None,
             bounds_formatting_ctx: Default::default(),
         }) {
             Ok(()) => {}
@@ -353,6 +372,7 @@ fn display_test<'a>(
             display_kind: DisplayKind::Test,
             show_container_bounds: false,
             display_lifetimes: DisplayLifetime::Always,
+            compact_signature: false,
         }
     }
 
@@ -378,6 +398,7 @@ fn display_with_container_bounds<'a>(
             display_kind: DisplayKind::Diagnostics,
             show_container_bounds,
             display_lifetimes: DisplayLifetime::OnlyNamedOrStatic,
+            compact_signature: false,
         }
     }
 }
@@ -453,6 +474,70 @@ pub fn omit_verbose_types(&self) -> bool {
     pub fn show_container_bounds(&self) -> bool {
         self.show_container_bounds
     }
+
+    pub fn compact_signature(&self) -> bool {
+        self.compact_signature
+    }
+
+    /// Whether `param` is rendered as `impl Bound` in the compact signature of its function.
+    fn is_inlined_param(&self, param: TypeParamId) -> bool {
+        match param.parent() {
+            GenericDefId::FunctionId(func) if self.compact_signature => {
+                inlined_type_params(self.db, func).contains(&param.local_id())
+            }
+            _ => false,
+        }
+    }
+
+    /// The type parameter inlined into the compact signature being rendered that `path` names.
+    fn inlined_param_named(&self, path: &Path) -> Option<(FunctionId, LocalTypeOrConstParamId)> {
+        let func = self.signature_owner.filter(|_| self.compact_signature)?;
+        let params = self.db.generic_params(func.into());
+        inlined_type_params(self.db, func)
+            .iter()
+            .find(|&&id| {
+                params[id].name().is_some_and(|name| compact_signature::is_param_name(path, name))
+            })
+            .map(|&id| (func, id))
+    }
+
+    /// Whether `ty` refers to a type parameter inlined into the compact signature being rendered.
+    pub fn is_inlined_param_ref(&self, ty: TypeRefId, store: &ExpressionStore) -> bool {
+        match &store[ty] {
+            TypeRef::TypeParam(param) => {
+                self.signature_owner.is_some() && self.is_inlined_param(*param)
+            }
+            TypeRef::Path(path) => self.inlined_param_named(path).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Whether `path` is a projection on a type parameter inlined into the compact signature
+    /// being rendered, which can't be named.
+    fn is_projection_on_inlined_param(&self, path: &Path, store: &ExpressionStore) -> bool {
+        let Some(func) = self.signature_owner.filter(|_| self.compact_signature) else {
+            return false;
+        };
+        let params = self.db.generic_params(func.into());
+        inlined_type_params(self.db, func).iter().any(|&id| {
+            params[id]
+                .name()
+                .is_some_and(|name| compact_signature::is_projection_on(path, store, name))
+        })
+    }
+
+    /// Formats parts of the signature of `func` with `format`, so that its type parameters can be
+    /// inlined in the compact signature.
+    pub fn format_signature_of<T>(
+        &mut self,
+        func: FunctionId,
+        format: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let prev = self.signature_owner.replace(func);
+        let res = format(self);
+        self.signature_owner = prev;
+        res
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -524,6 +609,7 @@ pub struct HirDisplayWrapper<'a, 'db, T> {
     display_target: DisplayTarget,
     show_container_bounds: bool,
     display_lifetimes: DisplayLifetime,
+    compact_signature: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -559,6 +645,8 @@ pub fn write_to<F: HirWrite>(&self, f: &mut F) -> Result {
             closure_style: self.closure_style,
             show_container_bounds: self.show_container_bounds,
             display_lifetimes: self.display_lifetimes,
+            compact_signature: self.compact_signature,
+            signature_owner: None,
             bounds_formatting_ctx: Default::default(),
         })
     }
@@ -572,6 +660,11 @@ pub fn with_lifetime_display(mut self, l: DisplayLifetime) -> Self {
         self.display_lifetimes = l;
         self
     }
+
+    pub fn with_compact_signature(mut self, compact: bool) -> Self {
+        self.compact_signature = compact;
+        self
+    }
 }
 
 impl<'db, T> fmt::Display for HirDisplayWrapper<'_, 'db, T>
@@ -606,6 +699,20 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>) -> Result {
     }
 }
 
+/// The bounds on the type parameter `param`.
+fn param_bounds<'db>(db: &'db dyn HirDatabase, param: TypeParamId) -> Vec<Clause<'db>> {
+    let is_param = |ty: Ty<'db>| matches!(ty.kind(), TyKind::Param(it) if it.id == param);
+    GenericPredicates::query_all(db, param.parent())
+        .iter_identity_copied()
+        .filter(|wc| match wc.kind().skip_binder() {
+            ClauseKind::Trait(tr) => is_param(tr.self_ty()),
+            ClauseKind::Projection(proj) => is_param(proj.self_ty()),
+            ClauseKind::TypeOutlives(to) => is_param(to.0),
+            _ => false,
+        })
+        .collect()
+}
+
 fn write_projection<'db>(f: &mut HirFormatter<'_, 'db>, alias: &AliasTy<'db>) -> Result {
     if f.should_truncate() {
         return write!(f, "{TYPE_HINT_TRUNCATION}");
@@ -613,6 +720,13 @@ fn write_projection<'db>(f: &mut HirFormatter<'_, 'db>, alias: &AliasTy<'db>) ->
     let trait_ref = alias.trait_ref(f.interner);
     let self_ty = trait_ref.self_ty();
 
+    // Type parameters inlined as `impl Bound` have no name to project on.
+    if let TyKind::Param(param) = self_ty.kind()
+        && f.is_inlined_param(param.id)
+    {
+        return write!(f, "{TYPE_HINT_TRUNCATION}");
+    }
+
     // if we are projection on a type parameter, check if the projection target has bounds
     // itself, if so, we render them directly as `impl Bound` instead of the less useful
     // `<Param as Trait>::Assoc`
@@ -1152,6 +1266,24 @@ fn hir_fmt(&self, f @ &mut HirFormatter { db, .. }: &mut HirFormatter<'_, 'db>)
                             (0, false)
                         }
                     }
+                    TyKind::Param(param) if f.is_inlined_param(param.id) => {
+                        let bounds = param_bounds(db, param.id);
+                        let default_sized = SizedByDefault::Sized {
+                            anchor: param.id.parent().module(db).krate(db),
+                        };
+                        let sized_bounds = bounds
+                            .iter()
+                            .filter(|b| {
+                                matches!(
+                                    b.kind().skip_binder(),
+                                    ClauseKind::Trait(trait_ref)
+                                        if default_sized.is_sized_trait(trait_ref.def_id().0, db)
+                                )
+                            })
+                            .count();
+                        // `?Sized` is printed when there is no `Sized` bound.
+                        (bounds.len() - sized_bounds + (sized_bounds == 0) as usize, false)
+                    }
                     _ => (0, false),
                 };
 
@@ -1495,7 +1627,9 @@ fn hir_fmt(&self, f @ &mut HirFormatter { db, .. }: &mut HirFormatter<'_, 'db>)
                 let param_data = &generics[param.id.local_id()];
                 match param_data {
                     TypeOrConstParamData::TypeParamData(p) => match p.provenance {
-                        TypeParamProvenance::TypeParamList | TypeParamProvenance::TraitSelf => {
+                        TypeParamProvenance::TypeParamList | TypeParamProvenance::TraitSelf
+                            if !f.is_inlined_param(param.id) =>
+                        {
                             write!(
                                 f,
                                 "{}",
@@ -1505,16 +1639,10 @@ fn hir_fmt(&self, f @ &mut HirFormatter { db, .. }: &mut HirFormatter<'_, 'db>)
                                     .display(f.db, f.edition())
                             )?
                         }
-                        TypeParamProvenance::ArgumentImplTrait => {
-                            let bounds = GenericPredicates::query_all(f.db, param.id.parent())
-                                .iter_identity_copied()
-                                .filter(|wc| match wc.kind().skip_binder() {
-                                    ClauseKind::Trait(tr) => tr.self_ty() == *self,
-                                    ClauseKind::Projection(proj) => proj.self_ty() == *self,
-                                    ClauseKind::TypeOutlives(to) => to.0 == *self,
-                                    _ => false,
-                                })
-                                .collect::<Vec<_>>();
+                        // Parameters inlined into a compact signature are rendered like
+                        // `impl Trait` arguments.
+                        _ => {
+                            let bounds = param_bounds(f.db, param.id);
                             let krate = param.id.parent().module(db).krate(db);
                             write_bounds_like_dyn_trait_with_prefix(
                                 f,
@@ -2139,14 +2267,22 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>, store: &ExpressionStore) -> Res
                 }
                 write!(f, ")")?;
             }
-            TypeRef::Path(path) => path.hir_fmt(f, store)?,
+            TypeRef::Path(path) => {
+                if let Some((func, param)) = f.inlined_param_named(path) {
+                    write_inlined_param(f, func, param, false)?;
+                } else if f.is_projection_on_inlined_param(path, store) {
+                    write!(f, "{TYPE_HINT_TRUNCATION}")?;
+                } else {
+                    path.hir_fmt(f, store)?;
+                }
+            }
             TypeRef::RawPtr(inner, mutability) => {
                 let mutability = match mutability {
                     hir_def::type_ref::Mutability::Shared => "*const ",
                     hir_def::type_ref::Mutability::Mut => "*mut ",
                 };
                 write!(f, "{mutability}")?;
-                inner.hir_fmt(f, store)?;
+                write_pointee(f, *inner, store)?;
             }
             TypeRef::Reference(ref_) => {
                 let mutability = match ref_.mutability {
@@ -2159,7 +2295,7 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>, store: &ExpressionStore) -> Res
                     write!(f, " ")?;
                 }
                 write!(f, "{mutability}")?;
-                ref_.ty.hir_fmt(f, store)?;
+                write_pointee(f, ref_.ty, store)?;
             }
             TypeRef::Array(array) => {
                 write!(f, "[")?;
@@ -2232,6 +2368,65 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>, _store: &ExpressionStore) -> Re
     }
 }
 
+/// Writes the pointee of a reference or raw pointer, parenthesizing an inlined type parameter
+/// with multiple bounds.
+fn write_pointee<'db>(
+    f: &mut HirFormatter<'_, 'db>,
+    ty: TypeRefId,
+    store: &ExpressionStore,
+) -> Result {
+    match &store[ty] {
+        TypeRef::Path(path) => match f.inlined_param_named(path) {
+            Some((func, param)) => write_inlined_param(f, func, param, true),
+            None => ty.hir_fmt(f, store),
+        },
+        _ => ty.hir_fmt(f, store),
+    }
+}
+
+/// Writes a type parameter inlined into the compact signature of `func` as `impl Bound`.
+fn write_inlined_param<'db>(
+    f: &mut HirFormatter<'_, 'db>,
+    func: FunctionId,
+    param: LocalTypeOrConstParamId,
+    parenthesize: bool,
+) -> Result {
+    let data = f.db.function_signature(func);
+    let store = &data.store;
+    let name = data.generic_params[param].name();
+    let bounds = data
+        .generic_params
+        .where_predicates()
+        .iter()
+        .filter_map(|pred| match pred {
+            WherePredicate::TypeBound { target, bound } => match &store[*target] {
+                TypeRef::TypeParam(it) if it.local_id() == param => Some(bound),
+                TypeRef::Path(path)
+                    if name.is_some_and(|name| compact_signature::is_param_name(path, name)) =>
+                {
+                    Some(bound)
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let parenthesize = parenthesize && bounds.len() > 1;
+    if parenthesize {
+        write!(f, "(")?;
+    }
+    write!(f, "impl ")?;
+    if bounds.is_empty() {
+        write!(f, "Sized")?;
+    } else {
+        f.write_joined(bounds.into_iter().map(ExpressionStoreAdapter::wrap(store)), " + ")?;
+    }
+    if parenthesize {
+        write!(f, ")")?;
+    }
+    Ok(())
+}
+
 impl<'db> HirDisplayWithExpressionStore<'db> for TypeBound {
     fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>, store: &ExpressionStore) -> Result {
         match self {
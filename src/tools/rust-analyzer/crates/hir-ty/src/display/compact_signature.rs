@@ -0,0 +1,249 @@
+//! Compact rendering of function signatures for places with little room, like completion details
+//! and signature help.
+//!
+//! Type parameters that are used exactly once in argument position are inlined as `impl Bound`,
+//! so `fn zip<U>(self, other: U) -> Zip<Self, U::IntoIter> where U: IntoIterator` is rendered as
+//! `fn zip(self, other: impl IntoIterator) -> Zip<Self, …>`. The where-clauses of inlined
+//! parameters are dropped and associated type projections on them in the return type are
+//! abbreviated. Whenever inlining would not be faithful, the parameter keeps its name.
+
+use hir_def::{
+    FunctionId,
+    expr_store::{
+        ExpressionStore,
+        path::{GenericArg, GenericArgs, GenericArgsParentheses, Path},
+    },
+    hir::generics::{
+        GenericParams, LocalTypeOrConstParamId, TypeOrConstParamData, TypeParamProvenance,
+        WherePredicate,
+    },
+    type_ref::{TypeBound, TypeRef, TypeRefId},
+};
+use hir_expand::{mod_path::PathKind, name::Name};
+
+use crate::db::HirDatabase;
+
+/// The type parameters of `func` that its compact signature renders as `impl Bound`.
+#[salsa::tracked(returns(ref))]
+pub fn inlined_type_params(
+    db: &dyn HirDatabase,
+    func: FunctionId,
+) -> Box<[LocalTypeOrConstParamId]> {
+    let data = db.function_signature(func);
+    let params = &*data.generic_params;
+    params
+        .iter_type_or_consts()
+        .filter_map(|(id, param)| match param {
+            TypeOrConstParamData::TypeParamData(param)
+                if param.provenance == TypeParamProvenance::TypeParamList =>
+            {
+                let name = param.name.as_ref()?;
+                is_inlinable(
+                    params,
+                    &data.store,
+                    &data.params,
+                    data.has_self_param(),
+                    data.ret_type,
+                    id,
+                    name,
+                )
+                .then_some(id)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_inlinable(
+    params: &GenericParams,
+    store: &ExpressionStore,
+    fn_params: &[TypeRefId],
+    has_self_param: bool,
+    ret_type: Option<TypeRefId>,
+    id: LocalTypeOrConstParamId,
+    name: &Name,
+) -> bool {
+    let is_param = |ty: TypeRefId| match &store[ty] {
+        TypeRef::TypeParam(param) => param.local_id() == id,
+        TypeRef::Path(path) => is_param_name(path, name),
+        _ => false,
+    };
+
+    // Everything besides the parameter's own bounds and its use in argument position must not
+    // mention it.
+    let mut elsewhere = Mentions::new(store, name);
+    for (other, param) in params.iter_type_or_consts() {
+        match param {
+            TypeOrConstParamData::TypeParamData(param) if other != id => {
+                if let Some(default) = param.default {
+                    elsewhere.visit_type(default, false);
+                }
+            }
+            TypeOrConstParamData::ConstParamData(param) => elsewhere.visit_type(param.ty, false),
+            TypeOrConstParamData::TypeParamData(_) => (),
+        }
+    }
+    for pred in params.where_predicates() {
+        match pred {
+            WherePredicate::TypeBound { target, bound } if is_param(*target) => {
+                elsewhere.visit_bound(bound)
+            }
+            WherePredicate::TypeBound { target, bound }
+            | WherePredicate::ForLifetime { target, bound, .. } => {
+                elsewhere.visit_type(*target, false);
+                elsewhere.visit_bound(bound);
+            }
+            WherePredicate::Lifetime { .. } => (),
+        }
+    }
+    let (self_param, fn_params) = match fn_params.split_first() {
+        Some((self_param, rest)) if has_self_param => (Some(*self_param), rest),
+        _ => (None, fn_params),
+    };
+    if let Some(self_param) = self_param {
+        elsewhere.visit_type(self_param, false);
+    }
+    if elsewhere.any() {
+        return false;
+    }
+
+    // Projections on an inlined parameter are abbreviated in the return type, other mentions
+    // would lose the connection to the argument.
+    if let Some(ret_type) = ret_type {
+        let mut in_ret = Mentions::new(store, name);
+        in_ret.visit_type(ret_type, false);
+        if in_ret.plain != 0 || in_ret.other != 0 {
+            return false;
+        }
+    }
+
+    let mut in_params = Mentions::new(store, name);
+    for &ty in fn_params {
+        in_params.visit_type(ty, true);
+    }
+    in_params.plain == 1 && in_params.projections == 0 && in_params.other == 0
+}
+
+/// Whether `path` is nothing but the name of the type parameter `name`.
+pub(super) fn is_param_name(path: &Path, name: &Name) -> bool {
+    path.type_anchor().is_none()
+        && *path.kind() == PathKind::Plain
+        && path.segments().len() == 1
+        && path
+            .segments()
+            .first()
+            .is_some_and(|segment| segment.args_and_bindings.is_none() && segment.name == name)
+}
+
+/// Whether `path` is an associated type projection like `T::Assoc` or `<T as Trait>::Assoc` on
+/// the type parameter `name`.
+pub(super) fn is_projection_on(path: &Path, store: &ExpressionStore, name: &Name) -> bool {
+    let is_param =
+        |ty: TypeRefId| matches!(&store[ty], TypeRef::Path(path) if is_param_name(path, name));
+    if let Some(anchor) = path.type_anchor() {
+        return is_param(anchor);
+    }
+    let trait_self_ty = path.segments().iter().find_map(|segment| {
+        let args = segment.args_and_bindings?;
+        match args.args.first() {
+            Some(GenericArg::Type(ty)) if args.has_self_type => Some(*ty),
+            _ => None,
+        }
+    });
+    match trait_self_ty {
+        Some(self_ty) => is_param(self_ty),
+        None => {
+            *path.kind() == PathKind::Plain
+                && path.segments().len() > 1
+                && path.segments().first().is_some_and(|segment| segment.name == name)
+        }
+    }
+}
+
+/// Counts the mentions of a type parameter, by how they can be rendered compactly.
+struct Mentions<'a> {
+    store: &'a ExpressionStore,
+    name: &'a Name,
+    /// Uses of the parameter as a type on its own, where `impl Trait` could replace it.
+    plain: usize,
+    /// Associated type projections on the parameter.
+    projections: usize,
+    other: usize,
+}
+
+impl<'a> Mentions<'a> {
+    fn new(store: &'a ExpressionStore, name: &'a Name) -> Self {
+        Mentions { store, name, plain: 0, projections: 0, other: 0 }
+    }
+
+    fn any(&self) -> bool {
+        self.plain + self.projections + self.other != 0
+    }
+
+    /// `impl_trait_allowed` tells whether `impl Trait` may replace the parameter at `ty`.
+    fn visit_type(&mut self, ty: TypeRefId, impl_trait_allowed: bool) {
+        match &self.store[ty] {
+            TypeRef::Path(path) => {
+                if is_param_name(path, self.name) {
+                    match impl_trait_allowed {
+                        true => self.plain += 1,
+                        false => self.other += 1,
+                    }
+                } else if is_projection_on(path, self.store, self.name) {
+                    self.projections += 1;
+                } else {
+                    self.visit_path(path, impl_trait_allowed);
+                }
+            }
+            TypeRef::Tuple(types) => {
+                types.iter().for_each(|&ty| self.visit_type(ty, impl_trait_allowed))
+            }
+            &TypeRef::RawPtr(ty, _) | &TypeRef::Slice(ty) => {
+                self.visit_type(ty, impl_trait_allowed)
+            }
+            TypeRef::Reference(ref_) => self.visit_type(ref_.ty, impl_trait_allowed),
+            TypeRef::Array(array) => self.visit_type(array.ty, impl_trait_allowed),
+            TypeRef::Fn(fn_) => fn_.params.iter().for_each(|&(_, ty)| self.visit_type(ty, false)),
+            TypeRef::ImplTrait(bounds) | TypeRef::DynTrait(bounds) => {
+                bounds.iter().for_each(|bound| self.visit_bound(bound))
+            }
+            TypeRef::Never | TypeRef::Placeholder | TypeRef::TypeParam(_) | TypeRef::Error => (),
+        }
+    }
+
+    fn visit_path(&mut self, path: &Path, impl_trait_allowed: bool) {
+        if let Some(anchor) = path.type_anchor() {
+            self.visit_type(anchor, false);
+        }
+        for segment in path.segments().iter() {
+            if let Some(args) = segment.args_and_bindings {
+                self.visit_args(args, impl_trait_allowed);
+            }
+        }
+    }
+
+    fn visit_args(&mut self, args: &GenericArgs, impl_trait_allowed: bool) {
+        let impl_trait_allowed =
+            impl_trait_allowed && args.parenthesized == GenericArgsParentheses::No;
+        for arg in &args.args {
+            if let GenericArg::Type(ty) = arg {
+                self.visit_type(*ty, impl_trait_allowed);
+            }
+        }
+        for binding in &args.bindings {
+            if let Some(args) = &binding.args {
+                self.visit_args(args, false);
+            }
+            if let Some(ty) = binding.type_ref {
+                self.visit_type(ty, false);
+            }
+            binding.bounds.iter().for_each(|bound| self.visit_bound(bound));
+        }
+    }
+
+    fn visit_bound(&mut self, bound: &TypeBound) {
+        if let Some((path, _)) = bound.as_path(self.store) {
+            self.visit_path(path, false);
+        }
+    }
+}
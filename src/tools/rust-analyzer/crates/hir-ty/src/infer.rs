@@ -102,7 +102,13 @@ pub fn infer_query_with_inspect<'db>(
     def: DefWithBodyId,
     inspect: Option<ObligationInspector<'db>>,
 ) -> InferenceResult {
-    let _p = tracing::info_span!("infer_query").entered();
+    let _p = tracing::info_span!(
+        "infer_query",
+        solver_cache_hits = tracing::field::Empty,
+        solver_cache_misses = tracing::field::Empty,
+    )
+    .entered();
+    let cache_stats = crate::traits::solver_cache_stats();
     let resolver = def.resolver(db);
     let body = db.body(def);
     let mut ctx = InferenceContext::new(db, def, &body, resolver);
@@ -169,7 +175,15 @@ pub fn infer_query_with_inspect<'db>(
 
     ctx.handle_opaque_type_uses();
 
-    ctx.resolve_all()
+    let result = ctx.resolve_all();
+
+    // Report how many root goals the per-revision cache saved us from solving, as part of the
+    // profile of this query.
+    let new_cache_stats = crate::traits::solver_cache_stats();
+    _p.record("solver_cache_hits", new_cache_stats.hits - cache_stats.hits);
+    _p.record("solver_cache_misses", new_cache_stats.misses - cache_stats.misses);
+
+    result
 }
 
 fn infer_cycle_result(db: &dyn HirDatabase, _: salsa::Id, _: DefWithBodyId) -> InferenceResult {
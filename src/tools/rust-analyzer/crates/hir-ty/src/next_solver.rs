@@ -10,6 +10,7 @@
 mod def_id;
 pub mod fold;
 pub mod format_proof_tree;
+pub(crate) mod fuel;
 pub mod fulfill;
 mod generic_arg;
 pub mod generics;
@@ -32,6 +33,7 @@
 pub use binder::*;
 pub use consts::*;
 pub use def_id::*;
+pub use fuel::{DEFAULT_SOLVER_FUEL, TraitSolvingOverflows, trait_solving_overflows};
 pub use generic_arg::*;
 pub use interner::*;
 pub use opaques::*;
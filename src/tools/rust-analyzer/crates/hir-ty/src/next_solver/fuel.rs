@@ -0,0 +1,175 @@
+//! A budget for the work the trait solver may spend on a single root goal.
+//!
+//! Deeply generic code can make the solver explore an exponential number of
+//! candidates, hanging analysis of the whole file. Every outermost root goal
+//! therefore gets a fixed amount of fuel, and every impl candidate lookup
+//! consumes some of it.
+//! Once the fuel runs out the solver stops assembling impl candidates and the
+//! root goal is treated as ambiguous, which inference turns into an unknown type
+//! rather than an error.
+//!
+//! Fuel is counted in operations, not wall clock time, so that results are
+//! reproducible. For the same reason, a budgeted root goal doesn't share the
+//! solver cache with other root goals: a nested goal found in a cache filled by
+//! some other body would cost nothing, and whether the budget suffices would
+//! depend on which bodies happened to be analyzed before.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    sync::Mutex,
+};
+
+use rustc_next_trait_solver::solve::{
+    GoalEvaluation, GoalStalledOn, HasChanged, SolverDelegateEvalExt,
+};
+use rustc_type_ir::{
+    inherent::Span as _,
+    search_graph::GlobalCache,
+    solve::{Certainty, NoSolution},
+};
+
+use crate::next_solver::{DbInterner, Goal, Predicate, SolverContext, Span};
+
+/// The fuel a root goal starts with. This is far more than any reasonable
+/// program needs.
+pub const DEFAULT_SOLVER_FUEL: u32 = 1 << 16;
+
+/// How many of the goals that ran out of fuel we remember for diagnostics.
+const MAX_RECORDED_OVERFLOWS: usize = 16;
+
+#[derive(Clone, Copy)]
+struct Fuel {
+    remaining: u32,
+    exhausted: bool,
+}
+
+thread_local! {
+    static BUDGET: Cell<u32> = const { Cell::new(DEFAULT_SOLVER_FUEL) };
+    static FUEL: Cell<Option<Fuel>> = const { Cell::new(None) };
+    /// The solver cache of the root goal being charged, see the module docs.
+    static ROOT_GOAL_CACHE: RefCell<Option<GlobalCache<DbInterner<'static>>>> =
+        const { RefCell::new(None) };
+    static OUT_OF_FUEL: Cell<u64> = const { Cell::new(0) };
+}
+
+static OVERFLOWS: Mutex<TraitSolvingOverflows> =
+    Mutex::new(TraitSolvingOverflows { count: 0, recent: VecDeque::new() });
+
+/// Root goals the solver gave up on because they ran out of fuel.
+#[derive(Debug, Clone, Default)]
+pub struct TraitSolvingOverflows {
+    /// How often a root goal ran out of fuel since the server started.
+    pub count: usize,
+    /// The most recent goals that ran out of fuel, oldest first.
+    pub recent: VecDeque<String>,
+}
+
+pub fn trait_solving_overflows() -> TraitSolvingOverflows {
+    OVERFLOWS.lock().unwrap().clone()
+}
+
+/// Consumes one unit of fuel of the root goal being evaluated. Returns `false` if
+/// it has run out, in which case the caller should not do any further work.
+pub(crate) fn consume() -> bool {
+    FUEL.with(|fuel| match fuel.get() {
+        None => true,
+        Some(Fuel { remaining: 0, .. }) => {
+            fuel.set(Some(Fuel { remaining: 0, exhausted: true }));
+            false
+        }
+        Some(Fuel { remaining, exhausted }) => {
+            fuel.set(Some(Fuel { remaining: remaining - 1, exhausted }));
+            true
+        }
+    })
+}
+
+/// Whether a root goal is being charged, in which case the solver must use
+/// [`with_budgeted_cache`] instead of the shared cache.
+pub(crate) fn is_budgeted() -> bool {
+    ROOT_GOAL_CACHE.with_borrow(|cache| cache.is_some())
+}
+
+/// Runs `f` with the solver cache of the root goal being charged.
+pub(crate) fn with_budgeted_cache<'db, R>(
+    f: impl FnOnce(&mut GlobalCache<DbInterner<'db>>) -> R,
+) -> R {
+    ROOT_GOAL_CACHE.with_borrow_mut(|cache| {
+        let cache = cache.as_mut().expect("no root goal is being charged");
+        // SAFETY: Same as for the thread-local solver cache, the lifetimes of interned
+        // values are advisory.
+        f(unsafe {
+            std::mem::transmute::<
+                &mut GlobalCache<DbInterner<'static>>,
+                &mut GlobalCache<DbInterner<'db>>,
+            >(cache)
+        })
+    })
+}
+
+/// Runs `f` and returns whether any root goal evaluated by it ran out of fuel.
+pub(crate) fn ran_out_of_fuel<T>(f: impl FnOnce() -> T) -> (T, bool) {
+    let before = OUT_OF_FUEL.get();
+    let res = f();
+    (res, OUT_OF_FUEL.get() != before)
+}
+
+/// Evaluates a root goal with a fresh budget, treating it as ambiguous if the
+/// budget runs out.
+///
+/// Only the outermost root goal is charged. Root goals evaluated while solving it,
+/// e.g. by a query computed on demand, run without a budget: their results get
+/// memoized, so they must not depend on how much fuel their caller had left.
+pub(crate) fn evaluate_root_goal<'db>(
+    context: &SolverContext<'db>,
+    goal: Goal<'db, Predicate<'db>>,
+    stalled_on: Option<GoalStalledOn<DbInterner<'db>>>,
+) -> Result<GoalEvaluation<DbInterner<'db>>, NoSolution> {
+    if let Some(outer) = FUEL.take() {
+        let outer_cache = ROOT_GOAL_CACHE.take();
+        let res = context.evaluate_root_goal(goal, Span::dummy(), stalled_on);
+        ROOT_GOAL_CACHE.set(outer_cache);
+        FUEL.set(Some(outer));
+        return res;
+    }
+
+    FUEL.set(Some(Fuel { remaining: BUDGET.get(), exhausted: false }));
+    ROOT_GOAL_CACHE.set(Some(GlobalCache::default()));
+    let res = context.evaluate_root_goal(goal, Span::dummy(), stalled_on);
+    // Nested goals evaluated without impl candidates may have gotten bogus results,
+    // dropping the cache makes sure nobody reuses them.
+    ROOT_GOAL_CACHE.take();
+    let exhausted = FUEL.take().is_some_and(|fuel| fuel.exhausted);
+    if !exhausted {
+        return res;
+    }
+
+    tracing::debug!(?goal, "trait solving ran out of fuel");
+    record_overflow(format!("{:?}", goal.predicate));
+    OUT_OF_FUEL.set(OUT_OF_FUEL.get() + 1);
+    Ok(GoalEvaluation {
+        goal,
+        certainty: Certainty::AMBIGUOUS,
+        has_changed: HasChanged::No,
+        stalled_on: None,
+    })
+}
+
+fn record_overflow(goal: String) {
+    let mut overflows = OVERFLOWS.lock().unwrap();
+    overflows.count += 1;
+    if overflows.recent.len() == MAX_RECORDED_OVERFLOWS {
+        overflows.recent.pop_front();
+    }
+    overflows.recent.push_back(goal);
+}
+
+/// Runs `f` with every root goal getting `budget` fuel instead of the default.
+#[cfg(test)]
+pub(crate) fn with_solver_fuel<T>(budget: u32, f: impl FnOnce() -> T) -> T {
+    let prev = BUDGET.replace(budget);
+    let res = f();
+    BUDGET.set(prev);
+    res
+}
@@ -16,7 +16,7 @@
 };
 
 use crate::next_solver::{
-    DbInterner, SolverContext, SolverDefId, Span, Ty, TyKind, TypingMode,
+    DbInterner, SolverContext, SolverDefId, Span, Ty, TyKind, TypingMode, fuel,
     infer::{
         InferCtxt,
         traits::{PredicateObligation, PredicateObligations},
@@ -186,7 +186,7 @@ pub(crate) fn try_evaluate_obligations(
                     continue;
                 }
 
-                let result = delegate.evaluate_root_goal(goal, Span::dummy(), stalled_on);
+                let result = fuel::evaluate_root_goal(delegate, goal, stalled_on);
                 infcx.inspect_evaluated_obligation(&obligation, &result, || {
                     Some(delegate.evaluate_root_goal_for_proof_tree(goal, Span::dummy()).1)
                 });
@@ -1238,6 +1238,10 @@ pub fn attach_obligation_inspector(&self, inspector: ObligationInspector<'db>) {
         self.obligation_inspector.set(Some(inspector));
     }
 
+    pub(crate) fn has_obligation_inspector(&self) -> bool {
+        self.obligation_inspector.get().is_some()
+    }
+
     pub fn inspect_evaluated_obligation(
         &self,
         obligation: &PredicateObligation<'db>,
@@ -6,6 +6,7 @@
 use macros::GenericTypeVisitable;
 use rustc_ast_ir::{FloatTy, IntTy, UintTy};
 pub use tls_cache::clear_tls_solver_cache;
+pub(crate) use tls_cache::with_root_goal_cache;
 pub use tls_db::{attach_db, attach_db_allow_change, with_attached_db};
 
 use base_db::Crate;
@@ -1143,7 +1144,11 @@ fn with_global_cache<R>(
         self,
         f: impl FnOnce(&mut rustc_type_ir::search_graph::GlobalCache<Self>) -> R,
     ) -> R {
-        tls_cache::with_cache(self.db, f)
+        if super::fuel::is_budgeted() {
+            super::fuel::with_budgeted_cache(f)
+        } else {
+            tls_cache::with_cache(self.db, f)
+        }
     }
 
     fn canonical_param_env_cache_get_or_insert<R>(
@@ -1777,6 +1782,9 @@ fn for_each_relevant_impl(
         self_ty: Self::Ty,
         mut f: impl FnMut(Self::ImplId),
     ) {
+        if !super::fuel::consume() {
+            return;
+        }
         let krate = self.krate.expect("trait solving requires setting `DbInterner::krate`");
         let trait_block = trait_def_id.0.loc(self.db).container.block(self.db);
         let mut consider_impls_for_simplified_type = |simp: SimplifiedType| {
@@ -2528,16 +2536,26 @@ pub fn with_attached_db<R>(op: impl FnOnce(&dyn HirDatabase) -> R) -> R {
 }
 
 mod tls_cache {
-    use crate::db::HirDatabase;
+    use crate::{
+        db::HirDatabase,
+        next_solver::{Canonical, Goal, Predicate, TypingMode},
+        traits::NextTraitSolveResult,
+    };
 
     use super::DbInterner;
-    use base_db::Nonce;
+    use base_db::{Crate, Nonce};
+    use rustc_hash::FxHashMap;
     use rustc_type_ir::search_graph::GlobalCache;
     use salsa::Revision;
     use std::cell::RefCell;
 
+    /// A root goal, together with everything besides the database its result depends on.
+    pub(crate) type RootGoalKey<'db> =
+        (Canonical<'db, Goal<'db, Predicate<'db>>>, Option<Crate>, TypingMode<'db>);
+
     struct Cache {
         cache: GlobalCache<DbInterner<'static>>,
+        root_goals: FxHashMap<RootGoalKey<'static>, NextTraitSolveResult>,
         revision: Revision,
         db_nonce: Nonce,
     }
@@ -2546,22 +2564,33 @@ struct Cache {
         static GLOBAL_CACHE: RefCell<Option<Cache>> = const { RefCell::new(None) };
     }
 
-    pub(super) fn with_cache<'db, T>(
-        db: &'db dyn HirDatabase,
-        f: impl FnOnce(&mut GlobalCache<DbInterner<'db>>) -> T,
-    ) -> T {
+    fn with_current_cache<T>(db: &dyn HirDatabase, f: impl FnOnce(&mut Cache) -> T) -> T {
         GLOBAL_CACHE.with_borrow_mut(|handle| {
             let (db_nonce, revision) = db.nonce_and_revision();
+            let new_cache = || Cache {
+                cache: GlobalCache::default(),
+                root_goals: FxHashMap::default(),
+                revision,
+                db_nonce,
+            };
             let handle = match handle {
                 Some(handle) => {
                     if handle.revision != revision || db_nonce != handle.db_nonce {
-                        *handle = Cache { cache: GlobalCache::default(), revision, db_nonce };
+                        *handle = new_cache();
                     }
                     handle
                 }
-                None => handle.insert(Cache { cache: GlobalCache::default(), revision, db_nonce }),
+                None => handle.insert(new_cache()),
             };
+            f(handle)
+        })
+    }
 
+    pub(super) fn with_cache<'db, T>(
+        db: &'db dyn HirDatabase,
+        f: impl FnOnce(&mut GlobalCache<DbInterner<'db>>) -> T,
+    ) -> T {
+        with_current_cache(db, |handle| {
             // SAFETY: No idea
             f(unsafe {
                 std::mem::transmute::<
@@ -2572,6 +2601,23 @@ pub(super) fn with_cache<'db, T>(
         })
     }
 
+    /// Caches the results of root goals across all bodies analyzed in the current revision.
+    pub(crate) fn with_root_goal_cache<'db, T>(
+        interner: DbInterner<'db>,
+        f: impl FnOnce(&mut FxHashMap<RootGoalKey<'db>, NextTraitSolveResult>, Option<Crate>) -> T,
+    ) -> T {
+        with_current_cache(interner.db, |handle| {
+            // SAFETY: Same as above, the lifetimes of interned values are advisory.
+            let root_goals = unsafe {
+                std::mem::transmute::<
+                    &mut FxHashMap<RootGoalKey<'static>, NextTraitSolveResult>,
+                    &mut FxHashMap<RootGoalKey<'db>, NextTraitSolveResult>,
+                >(&mut handle.root_goals)
+            };
+            f(root_goals, interner.krate)
+        })
+    }
+
     /// Clears the thread-local trait solver cache.
     ///
     /// Should be called before getting memory usage estimations, as the solver cache
@@ -10,6 +10,7 @@
 mod patterns;
 mod regression;
 mod simple;
+mod solver_limits;
 mod trait_aliases;
 mod traits;
 
@@ -37,7 +38,7 @@
 use triomphe::Arc;
 
 use crate::{
-    InferenceResult,
+    InferenceDiagnostic, InferenceResult,
     display::{DisplayTarget, HirDisplay},
     infer::{Adjustment, TypeMismatch},
     next_solver::Ty,
@@ -314,6 +315,14 @@ fn infer(#[rust_analyzer::rust_fixture] ra_fixture: &str) -> String {
 }
 
 fn infer_with_mismatches(content: &str, include_mismatches: bool) -> String {
+    infer_with_mismatches_and_diagnostics(content, include_mismatches, false)
+}
+
+fn infer_with_mismatches_and_diagnostics(
+    content: &str,
+    include_mismatches: bool,
+    include_diagnostics: bool,
+) -> String {
     let _tracing = setup_tracing();
     let (db, file_id) = TestDB::with_single_file(content);
 
@@ -408,6 +417,41 @@ fn infer_with_mismatches(content: &str, include_mismatches: bool) -> String {
                     );
                 }
             }
+            if include_diagnostics {
+                let mut diagnostics: Vec<(InFile<SyntaxNode>, String)> = inference_result
+                    .diagnostics()
+                    .iter()
+                    .filter_map(|diagnostic| {
+                        let (expr, kind, name, receiver) = match diagnostic {
+                            InferenceDiagnostic::UnresolvedMethodCall {
+                                expr,
+                                receiver,
+                                name,
+                                ..
+                            } => (*expr, "method", name, receiver),
+                            InferenceDiagnostic::UnresolvedField {
+                                expr, receiver, name, ..
+                            } => (*expr, "field", name, receiver),
+                            _ => return None,
+                        };
+                        let node = expr_node(&body_source_map, expr, &db)?;
+                        let message = format!(
+                            "cannot resolve {kind} '{}' on type '{}'",
+                            name.as_str(),
+                            receiver.as_ref().display_test(&db, display_target)
+                        );
+                        Some((node, message))
+                    })
+                    .collect();
+                diagnostics.sort_by_key(|(node, _)| {
+                    let range = node.value.text_range();
+                    (range.start(), range.end())
+                });
+                for (node, message) in &diagnostics {
+                    let macro_prefix = if node.file_id != file_id { "!" } else { "" };
+                    format_to!(buf, "{}{:?}: {}\n", macro_prefix, node.value.text_range(), message);
+                }
+            }
         };
 
         let module = db.module_for_file(file_id.file_id(&db));
@@ -564,6 +608,14 @@ fn check_infer_with_mismatches(#[rust_analyzer::rust_fixture] ra_fixture: &str,
     expect.assert_eq(&actual);
 }
 
+/// Like [`check_infer_with_mismatches`], but also lists unresolved method calls and field
+/// accesses, which otherwise only show up as `{unknown}` types.
+fn check_infer_with_diagnostics(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+    let mut actual = infer_with_mismatches_and_diagnostics(ra_fixture, true, true);
+    actual.push('\n');
+    expect.assert_eq(&actual);
+}
+
 #[test]
 fn salsa_bug() {
     let (mut db, pos) = TestDB::with_position(
@@ -1,4 +1,6 @@
-use super::{check, check_no_mismatches, check_types};
+use expect_test::expect;
+
+use super::{check, check_infer_with_diagnostics, check_no_mismatches, check_types};
 
 #[test]
 fn block_expr_type_mismatch() {
@@ -59,7 +61,7 @@ fn test() {
 
 #[test]
 fn custom_coerce_unsized() {
-    check(
+    check_infer_with_diagnostics(
         r#"
 //- minicore: coerce_unsized
 use core::{marker::Unsize, ops::CoerceUnsized};
@@ -77,13 +79,38 @@ fn foo3<T>(x: C<[T]>) -> C<[T]> { x }
 
 fn test(a: A<[u8; 2]>, b: B<[u8; 2]>, c: C<[u8; 2]>) {
     let d = foo1(a);
-              // ^ expected A<[{unknown}]>, got A<[u8; 2]>
     let e = foo2(b);
-     // ^ type: B<[u8]>
     let f = foo3(c);
-     // ^ type: C<[u8]>
 }
 "#,
+        expect![[r#"
+            306..307 'x': A<[T]>
+            327..332 '{ x }': A<[T]>
+            329..330 'x': A<[T]>
+            344..345 'x': B<[T]>
+            365..370 '{ x }': B<[T]>
+            367..368 'x': B<[T]>
+            382..383 'x': C<[T]>
+            403..408 '{ x }': C<[T]>
+            405..406 'x': C<[T]>
+            418..419 'a': A<[u8; 2]>
+            433..434 'b': B<[u8; 2]>
+            448..449 'c': C<[u8; 2]>
+            463..529 '{     ...(c); }': ()
+            473..474 'd': A<[{unknown}]>
+            477..481 'foo1': fn foo1<{unknown}>(A<[{unknown}]>) -> A<[{unknown}]>
+            477..484 'foo1(a)': A<[{unknown}]>
+            482..483 'a': A<[u8; 2]>
+            494..495 'e': B<[u8]>
+            498..502 'foo2': fn foo2<u8>(B<[u8]>) -> B<[u8]>
+            498..505 'foo2(b)': B<[u8]>
+            503..504 'b': B<[u8; 2]>
+            515..516 'f': C<[u8]>
+            519..523 'foo3': fn foo3<u8>(C<[u8]>) -> C<[u8]>
+            519..526 'foo3(c)': C<[u8]>
+            524..525 'c': C<[u8; 2]>
+            482..483: expected A<[{unknown}]>, got A<[u8; 2]>
+        "#]],
     );
 }
 
@@ -317,7 +344,7 @@ fn test() {
 
 #[test]
 fn coerce_autoderef_implication_1() {
-    check_no_mismatches(
+    check_infer_with_diagnostics(
         r"
 //- minicore: deref, phantom_data
 use core::marker::PhantomData;
@@ -328,19 +355,37 @@ fn coerce_autoderef_implication_1() {
 fn takes_ref_foo<T>(x: &Foo<T>) {}
 fn test() {
     let foo = Foo(PhantomData);
-      //^^^ type: Foo<{unknown}>
     takes_ref_foo(&foo);
 
     let foo = Foo(PhantomData);
-      //^^^ type: Foo<u32>
     let _: &() = &foo;
 }",
+        expect![[r#"
+            141..142 'x': &'? Foo<T>
+            153..155 '{}': ()
+            166..282 '{     ...foo; }': ()
+            176..179 'foo': Foo<{unknown}>
+            182..185 'Foo': fn Foo<{unknown}>(PhantomData<{unknown}>) -> Foo<{unknown}>
+            182..198 'Foo(Ph...mData)': Foo<{unknown}>
+            186..197 'PhantomData': PhantomData<{unknown}>
+            204..217 'takes_ref_foo': fn takes_ref_foo<{unknown}>(&'? Foo<{unknown}>)
+            204..223 'takes_...(&foo)': ()
+            218..222 '&foo': &'? Foo<{unknown}>
+            219..222 'foo': Foo<{unknown}>
+            234..237 'foo': Foo<u32>
+            240..243 'Foo': fn Foo<u32>(PhantomData<u32>) -> Foo<u32>
+            240..256 'Foo(Ph...mData)': Foo<u32>
+            244..255 'PhantomData': PhantomData<u32>
+            266..267 '_': &'? ()
+            275..279 '&foo': &'? Foo<u32>
+            276..279 'foo': Foo<u32>
+        "#]],
     );
 }
 
 #[test]
 fn coerce_autoderef_implication_2() {
-    check(
+    check_infer_with_diagnostics(
         r"
 //- minicore: deref, phantom_data
 use core::marker::PhantomData;
@@ -351,10 +396,23 @@ fn coerce_autoderef_implication_2() {
 fn takes_ref_foo<T>(x: &Foo<T>) {}
 fn test() {
     let foo = Foo(PhantomData);
-      //^^^ type: Foo<{unknown}>
     let _: &u32 = &Foo(PhantomData);
-                //^^^^^^^^^^^^^^^^^ expected &'? u32, got &'? Foo<{unknown}>
 }",
+        expect![[r#"
+            141..142 'x': &'? Foo<T>
+            153..155 '{}': ()
+            166..238 '{     ...ta); }': ()
+            176..179 'foo': Foo<{unknown}>
+            182..185 'Foo': fn Foo<{unknown}>(PhantomData<{unknown}>) -> Foo<{unknown}>
+            182..198 'Foo(Ph...mData)': Foo<{unknown}>
+            186..197 'PhantomData': PhantomData<{unknown}>
+            208..209 '_': &'? u32
+            218..235 '&Foo(P...mData)': &'? Foo<{unknown}>
+            219..222 'Foo': fn Foo<{unknown}>(PhantomData<{unknown}>) -> Foo<{unknown}>
+            219..235 'Foo(Ph...mData)': Foo<{unknown}>
+            223..234 'PhantomData': PhantomData<{unknown}>
+            218..235: expected &'? u32, got &'? Foo<{unknown}>
+        "#]],
     );
 }
 
@@ -533,7 +591,7 @@ fn test() {
 // where unsized is successful if all unsizing trait goals are certain (and non-unsizing goals are delayed).
 #[test]
 fn coerce_unsize_trait_object_simple() {
-    check_types(
+    check_infer_with_diagnostics(
         r#"
 //- minicore: coerce_unsized
 trait Foo<T, U> {}
@@ -547,12 +605,21 @@ impl<T, X> Baz<T, X> for S<T, X> {}
 
 fn test() {
     let obj: &dyn Baz<i8, i16> = &S;
-                                //^ S<i8, i16>
     let obj: &dyn Bar<_, i8, i16> = &S;
-                                   //^ S<i8, i16>
-    //let obj: &dyn Foo<i8, _> = &S;
-                              // S<{unknown}, {unknown}>
+    let obj: &dyn Foo<i8, _> = &S;
 }"#,
+        expect![[r#"
+            236..351 '{     ... &S; }': ()
+            246..249 'obj': &'? (dyn Baz<i8, i16> + 'static)
+            271..273 '&S': &'? S<i8, i16>
+            272..273 'S': S<i8, i16>
+            283..286 'obj': &'? (dyn Bar<usize, i8, i16> + 'static)
+            311..313 '&S': &'? S<i8, i16>
+            312..313 'S': S<i8, i16>
+            323..326 'obj': &'? (dyn Foo<i8, usize> + 'static)
+            346..348 '&S': &'? S<i8, {unknown}>
+            347..348 'S': S<i8, {unknown}>
+        "#]],
     );
 }
 
@@ -906,6 +906,32 @@ fn bar() -> u32 {0}
     );
 }
 
+#[test]
+fn infer_builtin_macros_include_concat_with_env() {
+    check_types(
+        r#"
+//- /main.rs crate:main env:OUT_DIR=out
+#[rustc_builtin_macro]
+macro_rules! include {() => {}}
+
+#[rustc_builtin_macro]
+macro_rules! concat {() => {}}
+
+#[rustc_builtin_macro]
+macro_rules! env {() => {}}
+
+include!(concat!(env!("OUT_DIR"), "/foo.rs"));
+
+fn main() {
+    bar();
+} //^^^^^ u32
+
+//- /out/foo.rs
+fn bar() -> u32 {0}
+"#,
+    );
+}
+
 #[test]
 fn infer_builtin_macros_include_concat_with_bad_env_should_failed() {
     check_types(
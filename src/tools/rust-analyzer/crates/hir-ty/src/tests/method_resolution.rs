@@ -2,7 +2,7 @@
 
 use crate::tests::check;
 
-use super::{check_infer, check_no_mismatches, check_types};
+use super::{check_infer, check_infer_with_diagnostics, check_no_mismatches, check_types};
 
 #[test]
 fn infer_slice_method() {
@@ -1889,6 +1889,26 @@ fn test() {
     );
 }
 
+#[test]
+fn receiver_adjustment_autoderef_chain() {
+    check(
+        r#"
+struct Foo;
+impl Foo {
+    fn foo(&self) {}
+    fn bar(&mut self) {}
+}
+fn test() {
+    (&&Foo).foo();
+  // ^^^^^ adjustments: Deref(None), Deref(None), Borrow(Ref(Not))
+    let mut foo = Foo;
+    foo.bar();
+  //^^^ adjustments: Borrow(Ref(Mut { allow_two_phase_borrow: Yes }))
+}
+"#,
+    );
+}
+
 #[test]
 fn receiver_adjustment_unsize_array() {
     check(
@@ -2258,3 +2278,25 @@ fn main() {
     "#,
     );
 }
+
+#[test]
+fn unresolved_method_and_field_diagnostics() {
+    check_infer_with_diagnostics(
+        r#"
+struct Foo { x: u32 }
+fn test(foo: Foo) {
+    foo.missing();
+    foo.y;
+}"#,
+        expect![[r#"
+            30..33 'foo': Foo
+            40..73 '{     ...o.y; }': ()
+            46..49 'foo': Foo
+            46..59 'foo.missing()': {unknown}
+            65..68 'foo': Foo
+            65..70 'foo.y': {unknown}
+            46..59: cannot resolve method 'missing' on type 'Foo'
+            65..70: cannot resolve field 'y' on type 'Foo'
+        "#]],
+    );
+}
@@ -0,0 +1,93 @@
+use crate::{
+    next_solver::{fuel::with_solver_fuel, trait_solving_overflows},
+    traits::solver_cache_stats,
+};
+
+use super::{check_no_mismatches, check_types, infer};
+
+const NESTED_GENERICS: &str = r#"
+trait Tr {
+    type Out;
+}
+impl Tr for u8 {
+    type Out = u16;
+}
+impl Tr for u16 {
+    type Out = u32;
+}
+impl Tr for u32 {
+    type Out = u64;
+}
+impl<A: Tr, B: Tr> Tr for (A, B) {
+    type Out = (A::Out, B::Out);
+}
+
+fn out<T: Tr>(_: T) -> T::Out {
+    loop {}
+}
+
+fn test() {
+    let x = out((((0u8, 0u16), (0u32, 0u8)), ((0u16, 0u32), (0u8, 0u16))));
+    x;
+  //^ (((u16, u32), (u64, u16)), ((u32, u64), (u16, u32)))
+}
+"#;
+
+#[test]
+fn nested_generics_are_solved_with_the_default_fuel() {
+    check_types(NESTED_GENERICS);
+}
+
+#[test]
+fn trait_solving_gives_up_when_out_of_fuel() {
+    let before = trait_solving_overflows().count;
+    let result = with_solver_fuel(4, || infer(NESTED_GENERICS));
+    assert!(
+        trait_solving_overflows().count > before,
+        "no goal ran out of fuel, inferred:\n{result}"
+    );
+    with_solver_fuel(4, || {
+        check_types(
+            &NESTED_GENERICS.replace(
+                "//^ (((u16, u32), (u64, u16)), ((u32, u64), (u16, u32)))",
+                "//^ {unknown}",
+            ),
+        )
+    });
+}
+
+#[test]
+fn running_out_of_fuel_does_not_depend_on_other_bodies() {
+    // `warm_up` solves the nested goals of `test` first. Whether `test` runs out of
+    // fuel must not depend on that.
+    let warm_up = r#"
+fn warm_up() {
+    let a = out(((0u8, 0u16), (0u32, 0u8)));
+    let b = out(((0u16, 0u32), (0u8, 0u16)));
+}
+"#;
+    for fuel in (4..=32).step_by(4) {
+        let alone = with_solver_fuel(fuel, || infer(NESTED_GENERICS));
+        let warmed_up = with_solver_fuel(fuel, || infer(&format!("{warm_up}{NESTED_GENERICS}")));
+        let x = |result: &str| {
+            result.lines().find_map(|line| line.split_once(" 'x': ")).unwrap().1.to_owned()
+        };
+        assert_eq!(x(&alone), x(&warmed_up), "with {fuel} fuel");
+    }
+}
+
+#[test]
+fn solver_cache_is_shared_across_bodies() {
+    let before = solver_cache_stats();
+    check_no_mismatches(
+        r#"
+//- minicore: fn
+fn a(f: &dyn Fn(u32) -> u32) -> u32 { f(1) }
+fn b(f: &dyn Fn(u32) -> u32) -> u32 { f(2) }
+fn c(f: &dyn Fn(u32) -> u32) -> u32 { f(3) }
+"#,
+    );
+    let after = solver_cache_stats();
+    assert!(after.misses > before.misses, "no root goal went through the cache");
+    assert!(after.hits > before.hits, "identical goals in different bodies were solved again");
+}
@@ -78,6 +78,9 @@ async fn test() {
     let _: Option<u64> = c.await;
     c;
 //  ^ impl Future<Output = Option<u64>>
+    let d = async { 1u8 }.await;
+    d;
+//  ^ u8
 }
 "#,
     );
@@ -232,6 +235,96 @@ fn test() {
     );
 }
 
+#[test]
+fn try_block_captures_try_operator() {
+    check_no_mismatches(
+        r#"
+//- minicore: try, option, result
+fn test(o: Option<u32>) -> Result<u8, ()> {
+    let x: Option<u32> = try {
+        let v = o?;
+        v
+    };
+    Ok(0)
+}
+"#,
+    );
+}
+
+#[test]
+fn async_block_inside_try_block_captures_try_operator() {
+    check_types(
+        r#"
+//- minicore: future, try, option, result
+async fn test(o: Option<u8>) {
+    let x: Result<u32, ()> = try {
+        let f = async {
+            let v = o?;
+            Some(v)
+        };
+        let w = f.await;
+        w;
+      //^ Option<u8>
+        3
+    };
+}
+"#,
+    );
+}
+
+#[test]
+fn infer_try_custom_impl() {
+    check_types(
+        r#"
+//- minicore: try, result, from
+use core::ops::{ControlFlow, FromResidual, Try};
+
+struct Residual;
+struct Wrapper(u32);
+
+impl Try for Wrapper {
+    type Output = u32;
+    type Residual = Residual;
+    fn from_output(output: u32) -> Self { Wrapper(output) }
+    fn branch(self) -> ControlFlow<Residual, u32> { ControlFlow::Continue(self.0) }
+}
+
+impl FromResidual<Residual> for Wrapper {
+    fn from_residual(_: Residual) -> Self { Wrapper(0) }
+}
+
+fn test(w: Wrapper) -> Wrapper {
+    let v = w?;
+    v;
+  //^ u32
+    Wrapper(v)
+}
+"#,
+    );
+}
+
+#[test]
+fn infer_try_converts_residual_via_from() {
+    check_no_mismatches(
+        r#"
+//- minicore: try, result, from
+struct ParseError;
+struct AppError;
+
+impl From<ParseError> for AppError {
+    fn from(_: ParseError) -> Self { AppError }
+}
+
+fn parse() -> Result<u32, ParseError> { Ok(0) }
+
+fn run() -> Result<u64, AppError> {
+    let n: u32 = parse()?;
+    Ok(n as u64)
+}
+"#,
+    );
+}
+
 #[test]
 fn infer_for_loop() {
     check_types(
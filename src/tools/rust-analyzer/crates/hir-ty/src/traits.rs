@@ -1,6 +1,6 @@
 //! Trait solving using next trait solver.
 
-use std::hash::Hash;
+use std::{cell::Cell, hash::Hash};
 
 use base_db::Crate;
 use hir_def::{
@@ -22,12 +22,13 @@
     db::HirDatabase,
     next_solver::{
         Canonical, DbInterner, GenericArgs, Goal, ParamEnv, Predicate, SolverContext, Span,
-        StoredClauses, Ty, TyKind,
+        StoredClauses, Ty, TyKind, fuel,
         infer::{
             DbInternerInferExt, InferCtxt,
             traits::{Obligation, ObligationCause},
         },
         obligation_ctxt::ObligationCtxt,
+        with_root_goal_cache,
     },
 };
 
@@ -75,7 +76,7 @@ pub fn structurally_normalize_ty<'db>(
     ty.replace_infer_with_error(infcx.interner)
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum NextTraitSolveResult {
     Certain,
     Uncertain,
@@ -96,9 +97,69 @@ pub fn uncertain(&self) -> bool {
     }
 }
 
+thread_local! {
+    static ROOT_GOAL_CACHE_STATS: Cell<SolverCacheStats> = const {
+        Cell::new(SolverCacheStats { hits: 0, misses: 0 })
+    };
+}
+
+/// How often canonical root goals were answered from the per-revision cache on this thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolverCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl SolverCacheStats {
+    pub fn hit_rate(self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 { 0.0 } else { self.hits as f64 / total as f64 }
+    }
+}
+
+pub fn solver_cache_stats() -> SolverCacheStats {
+    ROOT_GOAL_CACHE_STATS.get()
+}
+
 pub fn next_trait_solve_canonical_in_ctxt<'db>(
     infer_ctxt: &InferCtxt<'db>,
     goal: Canonical<'db, Goal<'db, Predicate<'db>>>,
+) -> NextTraitSolveResult {
+    // The key doesn't capture the opaque types registered in the context, and the
+    // inspector needs to see every goal that gets evaluated.
+    let cacheable = !infer_ctxt.has_obligation_inspector()
+        && infer_ctxt.inner.borrow().opaque_type_storage.is_empty();
+    if !cacheable {
+        return solve_canonical_goal(infer_ctxt, goal);
+    }
+
+    let typing_mode = infer_ctxt.typing_mode();
+    let cached = with_root_goal_cache(infer_ctxt.interner, |cache, krate| {
+        cache.get(&(goal, krate, typing_mode)).copied()
+    });
+    let mut stats = ROOT_GOAL_CACHE_STATS.get();
+    if let Some(res) = cached {
+        stats.hits += 1;
+        ROOT_GOAL_CACHE_STATS.set(stats);
+        return res;
+    }
+    stats.misses += 1;
+    ROOT_GOAL_CACHE_STATS.set(stats);
+
+    let (res, ran_out_of_fuel) = fuel::ran_out_of_fuel(|| solve_canonical_goal(infer_ctxt, goal));
+    // The ambiguity of a goal that ran out of fuel says nothing about the goal itself,
+    // don't let it leak into other bodies.
+    if !ran_out_of_fuel {
+        with_root_goal_cache(infer_ctxt.interner, |cache, krate| {
+            cache.insert((goal, krate, typing_mode), res);
+        });
+    }
+    res
+}
+
+fn solve_canonical_goal<'db>(
+    infer_ctxt: &InferCtxt<'db>,
+    goal: Canonical<'db, Goal<'db, Predicate<'db>>>,
 ) -> NextTraitSolveResult {
     infer_ctxt.probe(|_| {
         let context = <&SolverContext<'db>>::from(infer_ctxt);
@@ -108,7 +169,7 @@ pub fn next_trait_solve_canonical_in_ctxt<'db>(
         let (goal, var_values) = context.instantiate_canonical(&goal);
         tracing::info!(?var_values);
 
-        let res = context.evaluate_root_goal(goal, Span::dummy(), None);
+        let res = fuel::evaluate_root_goal(context, goal, None);
 
         let obligation = Obligation {
             cause: ObligationCause::dummy(),
@@ -141,7 +202,7 @@ pub fn next_trait_solve_in_ctxt<'db, 'a>(
 
     let context = <&SolverContext<'db>>::from(infer_ctxt);
 
-    let res = context.evaluate_root_goal(goal, Span::dummy(), None);
+    let res = fuel::evaluate_root_goal(context, goal, None);
 
     let obligation = Obligation {
         cause: ObligationCause::dummy(),
@@ -11,10 +11,10 @@
 };
 use hir_ty::{
     GenericPredicates,
-    db::HirDatabase,
     display::{
         HirDisplay, HirDisplayWithExpressionStore, HirFormatter, Result, SizedByDefault,
-        hir_display_with_store, write_bounds_like_dyn_trait_with_prefix, write_visibility,
+        hir_display_with_store, inlined_type_params, write_bounds_like_dyn_trait_with_prefix,
+        write_visibility,
     },
     next_solver::ClauseKind,
 };
@@ -42,7 +42,7 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>) -> Result {
                 if f.show_container_bounds() && !params.is_empty() {
                     write_trait_header(&trait_, f)?;
                     f.write_char('\n')?;
-                    has_disaplayable_predicates(f.db, &params, &params_store)
+                    has_disaplayable_predicates(f, &params, &params_store)
                         .then_some((params, params_store))
                 } else {
                     None
@@ -53,7 +53,7 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>) -> Result {
                 if f.show_container_bounds() && !params.is_empty() {
                     write_impl_header(&impl_, f)?;
                     f.write_char('\n')?;
-                    has_disaplayable_predicates(f.db, &params, &params_store)
+                    has_disaplayable_predicates(f, &params, &params_store)
                         .then_some((params, params_store))
                 } else {
                     None
@@ -117,7 +117,7 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>) -> Result {
             f.write_str(&pat_str)?;
 
             f.write_str(": ")?;
-            type_ref.hir_fmt(f, &data.store)?;
+            f.format_signature_of(self.id, |f| type_ref.hir_fmt(f, &data.store))?;
         }
 
         if data.is_varargs() {
@@ -163,13 +163,15 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>) -> Result {
                 TypeRef::Tuple(tup) if tup.is_empty() => {}
                 _ => {
                     f.write_str(" -> ")?;
-                    ret_type.hir_fmt(f, &data.store)?;
+                    f.format_signature_of(self.id, |f| ret_type.hir_fmt(f, &data.store))?;
                 }
             }
         }
 
         // Write where clauses
-        let has_written_where = write_where_clause(GenericDefId::FunctionId(self.id), f)?;
+        let has_written_where = f.format_signature_of(self.id, |f| {
+            write_where_clause(GenericDefId::FunctionId(self.id), f)
+        })?;
         if let Some((container_params, container_params_store)) = container_params {
             if !has_written_where {
                 f.write_str("\nwhere")?;
@@ -554,12 +556,18 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_, 'db>) -> Result {
 
 fn write_generic_params<'db>(def: GenericDefId, f: &mut HirFormatter<'_, 'db>) -> Result {
     let (params, store) = f.db.generic_params_and_store(def);
+    // Type parameters inlined into a compact signature are rendered as `impl Bound` instead.
+    let inlined: &[_] = match def {
+        GenericDefId::FunctionId(func) if f.compact_signature() => inlined_type_params(f.db, func),
+        _ => &[],
+    };
     if params.iter_lt().next().is_none()
-        && params.iter_type_or_consts().all(|it| it.1.const_param().is_none())
-        && params
-            .iter_type_or_consts()
-            .filter_map(|it| it.1.type_param())
-            .all(|param| !matches!(param.provenance, TypeParamProvenance::TypeParamList))
+        && params.iter_type_or_consts().all(|(id, param)| match param {
+            TypeOrConstParamData::TypeParamData(param) => {
+                param.provenance != TypeParamProvenance::TypeParamList || inlined.contains(&id)
+            }
+            TypeOrConstParamData::ConstParamData(_) => false,
+        })
     {
         return Ok(());
     }
@@ -578,16 +586,20 @@ fn write_generic_params<'db>(def: GenericDefId, f: &mut HirFormatter<'_, 'db>) -
         delim(f)?;
         write!(f, "{}", lifetime.name.display(f.db, f.edition()))?;
     }
-    for (_, ty) in params.iter_type_or_consts() {
+    for (id, ty) in params.iter_type_or_consts() {
         if let Some(name) = &ty.name() {
             match ty {
                 TypeOrConstParamData::TypeParamData(ty) => {
-                    if ty.provenance != TypeParamProvenance::TypeParamList {
+                    if ty.provenance != TypeParamProvenance::TypeParamList || inlined.contains(&id)
+                    {
                         continue;
                     }
                     delim(f)?;
                     write!(f, "{}", name.display(f.db, f.edition()))?;
-                    if let Some(default) = &ty.default {
+                    // Defaults don't matter for calling a function.
+                    if let Some(default) = &ty.default
+                        && !f.compact_signature()
+                    {
                         f.write_str(" = ")?;
                         default.hir_fmt(f, &store)?;
                     }
@@ -597,7 +609,9 @@ fn write_generic_params<'db>(def: GenericDefId, f: &mut HirFormatter<'_, 'db>) -
                     write!(f, "const {}: ", name.display(f.db, f.edition()))?;
                     c.ty.hir_fmt(f, &store)?;
 
-                    if let Some(default) = &c.default {
+                    if let Some(default) = &c.default
+                        && !f.compact_signature()
+                    {
                         f.write_str(" = ")?;
                         default.hir_fmt(f, &store)?;
                     }
@@ -612,32 +626,41 @@ fn write_generic_params<'db>(def: GenericDefId, f: &mut HirFormatter<'_, 'db>) -
 
 fn write_where_clause<'db>(def: GenericDefId, f: &mut HirFormatter<'_, 'db>) -> Result<bool> {
     let (params, store) = f.db.generic_params_and_store(def);
-    if !has_disaplayable_predicates(f.db, &params, &store) {
+    if !has_disaplayable_predicates(f, &params, &store) {
         return Ok(false);
     }
 
-    f.write_str("\nwhere")?;
+    f.write_str(if f.compact_signature() { " where" } else { "\nwhere" })?;
     write_where_predicates(&params, &store, f)?;
 
     Ok(true)
 }
 
 fn has_disaplayable_predicates(
-    db: &dyn HirDatabase,
+    f: &HirFormatter<'_, '_>,
     params: &GenericParams,
     store: &ExpressionStore,
 ) -> bool {
     params.where_predicates().iter().any(|pred| {
         !matches!(
             pred,
-            WherePredicate::TypeBound { target, .. }
-            if  matches!(store[*target],
-                TypeRef::TypeParam(id) if db.generic_params(id.parent())[id.local_id()].name().is_none()
-            )
+            WherePredicate::TypeBound { target, .. } if is_displayed_inline(f, *target, store)
         )
     })
 }
 
+/// Whether the bounds on `target` are displayed inline with the argument itself, e.g. `f: impl Y`,
+/// instead of in the where clause.
+fn is_displayed_inline(
+    f: &HirFormatter<'_, '_>,
+    target: TypeRefId,
+    store: &ExpressionStore,
+) -> bool {
+    matches!(store[target],
+        TypeRef::TypeParam(id) if f.db.generic_params(id.parent())[id.local_id()].name().is_none()
+    ) || f.is_inlined_param_ref(target, store)
+}
+
 fn write_where_predicates<'db>(
     params: &GenericParams,
     store: &ExpressionStore,
@@ -645,13 +668,6 @@ fn write_where_predicates<'db>(
 ) -> Result {
     use WherePredicate::*;
 
-    // unnamed type targets are displayed inline with the argument itself, e.g. `f: impl Y`.
-    let is_unnamed_type_target = |target: TypeRefId| {
-        matches!(store[target],
-            TypeRef::TypeParam(id) if f.db.generic_params(id.parent())[id.local_id()].name().is_none()
-        )
-    };
-
     let check_same_target = |pred1: &WherePredicate, pred2: &WherePredicate| match (pred1, pred2) {
         (TypeBound { target: t1, .. }, TypeBound { target: t2, .. }) => t1 == t2,
         (Lifetime { target: t1, .. }, Lifetime { target: t2, .. }) => t1 == t2,
@@ -662,13 +678,23 @@ fn write_where_predicates<'db>(
         _ => false,
     };
 
+    // Compact signatures are written on a single line.
+    let compact = f.compact_signature();
+    let mut first = true;
     let mut iter = params.where_predicates().iter().peekable();
     while let Some(pred) = iter.next() {
-        if matches!(pred, TypeBound { target, .. } if is_unnamed_type_target(*target)) {
+        if matches!(pred, TypeBound { target, .. } if is_displayed_inline(f, *target, store)) {
             continue;
         }
 
-        f.write_str("\n    ")?;
+        if !compact {
+            f.write_str("\n    ")?;
+        } else if first {
+            f.write_char(' ')?;
+        } else {
+            f.write_str(", ")?;
+        }
+        first = false;
         match pred {
             TypeBound { target, bound } => {
                 target.hir_fmt(f, store)?;
@@ -696,7 +722,9 @@ fn write_where_predicates<'db>(
                 Lifetime { bound, .. } => bound.hir_fmt(f, store)?,
             }
         }
-        f.write_str(",")?;
+        if !compact {
+            f.write_str(",")?;
+        }
     }
 
     Ok(())
@@ -175,7 +175,10 @@
         layout::LayoutError,
         mir::{MirEvalError, MirLowerError},
         next_solver::abi::Safety,
-        next_solver::{clear_tls_solver_cache, collect_ty_garbage},
+        next_solver::{
+            TraitSolvingOverflows, clear_tls_solver_cache, collect_ty_garbage,
+            trait_solving_overflows,
+        },
     },
     // FIXME: These are needed for import assets, properly encapsulate them.
     hir_ty::{method_resolution::TraitImpls, next_solver::SimplifiedType},
@@ -2875,6 +2878,14 @@ pub fn function(self, db: &dyn HirDatabase, name: impl PartialEq<Name>) -> Optio
         })
     }
 
+    pub fn type_alias(self, db: &dyn HirDatabase, name: impl PartialEq<Name>) -> Option<TypeAlias> {
+        self.id.trait_items(db).items.iter().find(|(n, _)| name == *n).and_then(|&(_, it)| match it
+        {
+            AssocItemId::TypeAliasId(id) => Some(TypeAlias { id }),
+            _ => None,
+        })
+    }
+
     pub fn items(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
         self.id.trait_items(db).items.iter().map(|(_name, it)| (*it).into()).collect()
     }
@@ -3258,6 +3269,30 @@ pub fn attrs(&self, db: &dyn HirDatabase) -> Option<AttrsWithOwner> {
             ItemInNs::Macros(it) => Some(it.attrs(db)),
         }
     }
+
+    /// Returns the names under which the dependencies of `krate` publicly export this item,
+    /// together with the module that exports it under that name.
+    ///
+    /// Re-exports are taken into account, so an item re-exported as `pub use foo::Bar as Baz;`
+    /// is listed under `Baz` (and under `Bar`, if `foo` is public itself).
+    pub fn exported_names(&self, db: &dyn HirDatabase, krate: Crate) -> Vec<(Module, Name)> {
+        let item = hir_def::item_scope::ItemInNs::from(*self);
+        krate
+            .id
+            .data(db)
+            .dependencies
+            .iter()
+            .flat_map(|dep| {
+                let import_map = db.import_map(dep.crate_id);
+                import_map
+                    .import_info_for(item)
+                    .into_iter()
+                    .flatten()
+                    .map(|info| (Module { id: info.container }, info.name.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
 }
 
 /// Invariant: `inner.as_extern_assoc_item(db).is_some()`
@@ -4133,6 +4168,16 @@ pub fn is_implicit(self, db: &dyn HirDatabase) -> bool {
         }
     }
 
+    /// Is this type parameter rendered as `impl Bound` in the compact signature of its function?
+    pub fn is_inlined_in_compact_signature(self, db: &dyn HirDatabase) -> bool {
+        match self.id.parent() {
+            GenericDefId::FunctionId(func) => {
+                hir_ty::display::inlined_type_params(db, func).contains(&self.id.local_id())
+            }
+            _ => false,
+        }
+    }
+
     pub fn ty(self, db: &dyn HirDatabase) -> Type<'_> {
         let resolver = self.id.parent().resolver(db);
         let interner = DbInterner::new_no_crate(db);
@@ -1732,6 +1732,15 @@ pub fn resolve_method_call(&self, call: &ast::MethodCallExpr) -> Option<Function
         self.analyze(call.syntax())?.resolve_method_call(self.db, call)
     }
 
+    /// Returns the adjustments applied to the receiver of this method call, e.g. the auto-derefs
+    /// and the auto-ref that make `(&&foo).method()` call `Foo::method(&**(&&foo))`.
+    pub fn resolve_method_call_adjustments(
+        &self,
+        call: &ast::MethodCallExpr,
+    ) -> Option<Vec<Adjustment<'db>>> {
+        self.expr_adjustments(&call.receiver()?)
+    }
+
     /// Attempts to resolve this call expression as a method call falling back to resolving it as a field.
     pub fn resolve_method_call_fallback(
         &self,
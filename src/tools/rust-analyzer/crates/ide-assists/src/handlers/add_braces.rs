@@ -62,7 +62,8 @@ pub(crate) fn add_braces(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<(
             let new_expr = expr.reset_indent().indent(1.into());
             let block_expr = make.block_expr(None, Some(new_expr));
 
-            editor.replace(expr.syntax(), block_expr.indent(expr.indent_level()).syntax());
+            let block_expr = block_expr.indent_like(expr.indent_level(), expr.syntax());
+            editor.replace(expr.syntax(), block_expr.syntax());
 
             editor.add_mappings(make.finish_with_mappings());
             builder.add_file_edits(ctx.vfs_file_id(), editor);
@@ -159,6 +160,33 @@ fn foo() {
         );
     }
 
+    #[test]
+    fn suggest_add_braces_with_tab_indentation() {
+        check_assist(
+            add_braces,
+            r#"
+fn foo() {
+	match () {
+		() => {
+			t(|n|$0 n + 100);
+		}
+	}
+}
+"#,
+            r#"
+fn foo() {
+	match () {
+		() => {
+			t(|n| {
+				n + 100
+			});
+		}
+	}
+}
+"#,
+        );
+    }
+
     #[test]
     fn suggest_add_braces_for_assignment() {
         check_assist(
@@ -41,7 +41,7 @@ pub(crate) fn add_explicit_method_call_deref(
     let method_call_expr = dot_token.parent().and_then(ast::MethodCallExpr::cast)?;
     let receiver = method_call_expr.receiver()?;
 
-    let adjustments = ctx.sema.expr_adjustments(&receiver)?;
+    let adjustments = ctx.sema.resolve_method_call_adjustments(&method_call_expr)?;
     let adjustments =
         adjustments.into_iter().filter_map(|adjust| simple_adjust_kind(adjust.kind)).collect_vec();
     if adjustments.is_empty() {
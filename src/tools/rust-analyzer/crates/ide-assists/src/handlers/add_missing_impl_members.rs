@@ -236,10 +236,45 @@ fn try_gen_trait_body(
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::tests::{check_assist, check_assist_no_snippet_cap, check_assist_not_applicable};
 
     use super::*;
 
+    #[test]
+    fn test_add_missing_impl_members_with_tab_indentation() {
+        check_assist(
+            add_missing_impl_members,
+            r#"
+trait Foo {
+	type Output;
+	fn foo(&self) -> Self::Output;
+}
+mod m {
+	struct S;
+	impl crate::Foo for S {$0
+		type Output = ();
+	}
+}
+"#,
+            r#"
+trait Foo {
+	type Output;
+	fn foo(&self) -> Self::Output;
+}
+mod m {
+	struct S;
+	impl crate::Foo for S {
+		type Output = ();
+
+		fn foo(&self) -> Self::Output {
+			${0:todo!()}
+		}
+	}
+}
+"#,
+        );
+    }
+
     #[test]
     fn test_add_missing_impl_members() {
         check_assist(
@@ -351,6 +386,25 @@ fn foo(&self) {
         );
     }
 
+    #[test]
+    fn test_empty_impl_def_no_snippet_cap() {
+        check_assist_no_snippet_cap(
+            add_missing_impl_members,
+            r#"
+trait Foo { fn foo(&self); }
+struct S;
+impl Foo for S { $0 }"#,
+            r#"
+trait Foo { fn foo(&self); }
+struct S;
+impl Foo for S {
+    fn foo(&self) {
+        todo!()
+    }
+}"#,
+        );
+    }
+
     #[test]
     fn test_impl_def_without_braces_macro() {
         check_assist(
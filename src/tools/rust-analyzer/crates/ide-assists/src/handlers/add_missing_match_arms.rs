@@ -1583,6 +1583,28 @@ fn foo(opt: Option<i32>) {
         );
     }
 
+    #[test]
+    fn result_order() {
+        check_assist(
+            add_missing_match_arms,
+            r#"
+//- minicore: result
+fn foo(res: Result<i32, ()>) {
+    match res$0 {
+    }
+}
+"#,
+            r#"
+fn foo(res: Result<i32, ()>) {
+    match res {
+        Ok(${1:_}) => ${2:todo!()},
+        Err(_) => ${3:todo!()},$0
+    }
+}
+"#,
+        );
+    }
+
     #[test]
     fn works_inside_macro_call() {
         check_assist(
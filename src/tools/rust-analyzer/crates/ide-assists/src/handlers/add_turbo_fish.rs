@@ -195,8 +195,8 @@ fn get_fish_head(make: &SyntaxFactory, number_of_arguments: usize) -> ast::Gener
 #[cfg(test)]
 mod tests {
     use crate::tests::{
-        check_assist, check_assist_by_label, check_assist_not_applicable,
-        check_assist_not_applicable_by_label,
+        check_assist, check_assist_by_label, check_assist_no_snippet_cap,
+        check_assist_not_applicable, check_assist_not_applicable_by_label,
     };
 
     use super::*;
@@ -220,6 +220,25 @@ fn main() {
         );
     }
 
+    #[test]
+    fn add_turbo_fish_function_no_snippet_cap() {
+        check_assist_no_snippet_cap(
+            add_turbo_fish,
+            r#"
+fn make<T>() -> T {}
+fn main() {
+    make$0();
+}
+"#,
+            r#"
+fn make<T>() -> T {}
+fn main() {
+    make::<_>();
+}
+"#,
+        );
+    }
+
     #[test]
     fn add_turbo_fish_function_multiple_generic_types() {
         check_assist(
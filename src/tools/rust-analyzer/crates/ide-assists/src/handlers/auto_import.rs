@@ -432,6 +432,22 @@ pub mod module {
         check_auto_import_order(before, &["Import `foo::module::HashMap`", "Import `bar::HashMap`"])
     }
 
+    #[test]
+    fn label_names_the_dependency() {
+        let before = r"
+//- /main.rs crate:main deps:serde
+Serialize$0;
+
+//- /lib.rs crate:serde@1.0.0,https://github.com/serde-rs/serde library
+pub mod ser {
+    pub trait Serialize {}
+}
+pub use ser::Serialize;
+        ";
+
+        check_auto_import_order(before, &["Import `serde::Serialize`"])
+    }
+
     #[test]
     fn prefer_non_local_over_long_path() {
         let before = r"
@@ -714,6 +730,33 @@ fn main() {
         );
     }
 
+    #[test]
+    fn macro_call_import_from_submodule() {
+        check_assist(
+            auto_import,
+            r"
+//- /lib.rs crate:serde_json
+mod macros {
+    #[macro_export]
+    macro_rules! json {
+        ($($tt:tt)*) => {};
+    }
+}
+
+//- /main.rs crate:main deps:serde_json
+fn main() {
+    let _ = json$0!({});
+}
+",
+            r"use serde_json::json;
+
+fn main() {
+    let _ = json!({});
+}
+",
+        );
+    }
+
     #[test]
     fn auto_import_target() {
         check_assist_target(
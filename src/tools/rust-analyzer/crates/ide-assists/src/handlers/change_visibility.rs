@@ -5,7 +5,7 @@
         TRAIT, TYPE_ALIAS, USE, VISIBILITY,
     },
     SyntaxNode, T,
-    ast::{self, HasName, HasVisibility},
+    ast::{self, HasName, HasVisibility, VisibilityKind},
 };
 
 use crate::{AssistContext, AssistId, Assists, utils::vis_offset};
@@ -111,29 +111,15 @@ fn can_add(node: &SyntaxNode) -> bool {
 }
 
 fn change_vis(acc: &mut Assists, vis: ast::Visibility) -> Option<()> {
-    if vis.syntax().text() == "pub" {
-        let target = vis.syntax().text_range();
-        return acc.add(
-            AssistId::refactor_rewrite("change_visibility"),
-            "Change Visibility to pub(crate)",
-            target,
-            |edit| {
-                edit.replace(vis.syntax().text_range(), "pub(crate)");
-            },
-        );
-    }
-    if vis.syntax().text() == "pub(crate)" {
-        let target = vis.syntax().text_range();
-        return acc.add(
-            AssistId::refactor_rewrite("change_visibility"),
-            "Change visibility to pub",
-            target,
-            |edit| {
-                edit.replace(vis.syntax().text_range(), "pub");
-            },
-        );
-    }
-    None
+    let (label, replacement) = match vis.kind() {
+        VisibilityKind::Pub => ("Change Visibility to pub(crate)", "pub(crate)"),
+        VisibilityKind::PubCrate => ("Change visibility to pub", "pub"),
+        VisibilityKind::In(_) | VisibilityKind::PubSuper | VisibilityKind::PubSelf => return None,
+    };
+    let target = vis.syntax().text_range();
+    acc.add(AssistId::refactor_rewrite("change_visibility"), label, target, |edit| {
+        edit.replace(target, replacement);
+    })
 }
 
 fn check_is_not_variant(field: &impl AstNode) -> Option<()> {
@@ -199,6 +185,29 @@ fn change_visibility_pub_crate_to_pub() {
         check_assist(change_visibility, "$0pub(crate) fn foo() {}", "pub fn foo() {}")
     }
 
+    #[test]
+    fn change_visibility_pub_crate_with_whitespace_to_pub() {
+        check_assist(change_visibility, "$0pub ( crate ) fn foo() {}", "pub fn foo() {}")
+    }
+
+    #[test]
+    fn change_visibility_not_applicable_for_restricted_visibilities() {
+        check_assist_not_applicable(change_visibility, "$0pub(super) fn foo() {}");
+        check_assist_not_applicable(change_visibility, "$0pub(self) fn foo() {}");
+        check_assist_not_applicable(change_visibility, "mod m { $0pub(in crate::m) fn foo() {} }");
+    }
+
+    #[test]
+    fn change_visibility_toggles_field_visibility() {
+        check_assist(change_visibility, "struct S ( $0pub u32 )", "struct S ( pub(crate) u32 )");
+        check_assist(change_visibility, "struct S ( $0pub(crate) u32 )", "struct S ( pub u32 )");
+        check_assist(
+            change_visibility,
+            "struct S { $0pub(crate) field: u32 }",
+            "struct S { pub field: u32 }",
+        );
+    }
+
     #[test]
     fn change_visibility_const() {
         check_assist(change_visibility, "$0const FOO = 3u8;", "pub(crate) const FOO = 3u8;");
@@ -237,6 +246,23 @@ fn change_visibility_handles_comment_attrs() {
         )
     }
 
+    #[test]
+    fn change_visibility_toggles_after_attrs() {
+        check_assist(
+            change_visibility,
+            r"
+/// docs
+#[derive(Debug)]
+$0pub struct Foo;
+",
+            r"
+/// docs
+#[derive(Debug)]
+pub(crate) struct Foo;
+",
+        )
+    }
+
     #[test]
     fn not_applicable_for_enum_variants() {
         check_assist_not_applicable(
@@ -5,7 +5,8 @@
 use syntax::{
     SyntaxElement, SyntaxKind, SyntaxNode, T, TextSize,
     ast::{
-        self, AstNode, HasAttrs, HasGenericParams, HasVisibility, syntax_factory::SyntaxFactory,
+        self, AstNode, HasAttrs, HasGenericParams, HasName, HasVisibility,
+        syntax_factory::SyntaxFactory,
     },
     match_ast,
     syntax_editor::{Element, Position, SyntaxEditor},
@@ -83,10 +84,20 @@ pub(crate) fn convert_tuple_struct_to_named_struct(
         target,
         |edit| {
             let names = generate_names(tuple_fields.fields());
-            edit_field_references(ctx, edit, tuple_fields.fields(), &names);
+            let fields_used = edit_field_references(ctx, edit, tuple_fields.fields(), &names);
             let mut editor = edit.make_editor(syntax);
-            edit_struct_references(ctx, edit, strukt_def, &names);
-            edit_struct_def(&mut editor, &strukt_or_variant, tuple_fields, names);
+            let struct_used = edit_struct_references(ctx, edit, strukt_def, &names);
+            let record_fields =
+                edit_struct_def(&mut editor, &strukt_or_variant, tuple_fields, names);
+            // The generated names can only be renamed in place if they don't appear anywhere else
+            if let Some(cap) = ctx.config.snippet_cap
+                && !fields_used
+                && !struct_used
+            {
+                for name in record_fields.fields().filter_map(|field| field.name()) {
+                    editor.add_annotation(name.syntax(), edit.make_placeholder_snippet(cap));
+                }
+            }
             edit.add_file_edits(ctx.vfs_file_id(), editor);
         },
     )
@@ -97,7 +108,7 @@ fn edit_struct_def(
     strukt: &Either<ast::Struct, ast::Variant>,
     tuple_fields: ast::TupleFieldList,
     names: Vec<ast::Name>,
-) {
+) -> ast::RecordFieldList {
     let record_fields = tuple_fields.fields().zip(names).filter_map(|(f, name)| {
         let field = ast::make::record_field(f.visibility(), name, f.ty()?);
         let mut field_editor = SyntaxEditor::new(field.syntax().clone());
@@ -133,14 +144,16 @@ fn edit_struct_def(
     }
 
     editor.replace(tuple_fields.syntax(), record_fields.syntax());
+    record_fields
 }
 
+/// Returns whether any constructor or pattern was edited.
 fn edit_struct_references(
     ctx: &AssistContext<'_>,
     edit: &mut SourceChangeBuilder,
     strukt: Either<hir::Struct, hir::Variant>,
     names: &[ast::Name],
-) {
+) -> bool {
     let strukt_def = match strukt {
         Either::Left(s) => Definition::Adt(hir::Adt::Struct(s)),
         Either::Right(v) => Definition::Variant(v),
@@ -190,6 +203,7 @@ fn edit_struct_references(
         }
     };
 
+    let mut edited = false;
     for (file_id, refs) in usages {
         let source = ctx.sema.parse(file_id);
         let source = source.syntax();
@@ -202,6 +216,7 @@ fn edit_struct_references(
                 .ancestors()
                 .find_map(|node| Some((node.clone(), edit_node(node.clone())?)))
             {
+                edited = true;
                 if let Some(old_node) = ctx.sema.original_syntax_node_rooted(&old_node) {
                     editor.replace(old_node, new_node);
                 } else {
@@ -235,14 +250,17 @@ fn edit_struct_references(
         }
         edit.add_file_edits(file_id.file_id(ctx.db()), editor);
     }
+    edited
 }
 
+/// Returns whether any reference was edited.
 fn edit_field_references(
     ctx: &AssistContext<'_>,
     edit: &mut SourceChangeBuilder,
     fields: impl Iterator<Item = ast::TupleField>,
     names: &[ast::Name],
-) {
+) -> bool {
+    let mut edited = false;
     for (field, name) in fields.zip(names) {
         let field = match ctx.sema.to_def(&field) {
             Some(it) => it,
@@ -259,11 +277,13 @@ fn edit_field_references(
                     && let Some(original) = ctx.sema.original_ast_node(name_ref.clone())
                 {
                     editor.replace(original.syntax(), name.syntax());
+                    edited = true;
                 }
             }
             edit.add_file_edits(file_id.file_id(ctx.db()), editor);
         }
     }
+    edited
 }
 
 fn generate_names(fields: impl Iterator<Item = ast::TupleField>) -> Vec<ast::Name> {
@@ -721,6 +741,56 @@ fn f() {
         );
     }
 
+    #[test]
+    fn convert_struct_with_multi_file_field_and_pattern_references() {
+        check_assist(
+            convert_tuple_struct_to_named_struct,
+            r#"
+//- /main.rs
+pub struct A$0(pub u32, pub String);
+
+mod foo;
+
+//- /foo.rs
+use crate::A;
+fn f(s: String) -> u32 {
+    let a = A(1, s);
+    let A(x, _) = a;
+    x + a.0
+}
+"#,
+            r#"
+//- /main.rs
+pub struct A { pub field1: u32, pub field2: String }
+
+mod foo;
+
+//- /foo.rs
+use crate::A;
+fn f(s: String) -> u32 {
+    let a = A { field1: 1, field2: s };
+    let A { field1: x, field2: _ } = a;
+    x + a.field1
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unused_field_names_are_placeholders() {
+        check_assist(
+            convert_tuple_struct_to_named_struct,
+            r#"
+struct A$0(u32, String);
+fn f(a: A) {}
+"#,
+            r#"
+struct A { ${1:field1}: u32, ${0:field2}: String }
+fn f(a: A) {}
+"#,
+        );
+    }
+
     #[test]
     fn convert_struct_with_where_clause() {
         check_assist(
@@ -734,7 +804,7 @@ struct Wrap$0<T>(T)
 struct Wrap<T>
 where
     T: Display,
-{ field1: T }
+{ ${0:field1}: T }
 
 "#,
         );
@@ -1081,7 +1151,7 @@ trait Foo {}
 struct Bar<T>
 where
     T: Foo,
-{ pub field1: T }
+{ pub ${0:field1}: T }
 
 "#,
         );
@@ -1095,7 +1165,7 @@ fn fields_with_attrs() {
 pub struct $0Foo(#[my_custom_attr] u32);
 "#,
             r#"
-pub struct Foo { #[my_custom_attr]field1: u32 }
+pub struct Foo { #[my_custom_attr]${0:field1}: u32 }
 "#,
         );
     }
@@ -2959,6 +2959,36 @@ fn $0fun_name(n: i32) -> i32 {
         );
     }
 
+    #[test]
+    fn two_params_and_variable_defined_inside_and_used_after() {
+        check_assist(
+            extract_function,
+            r#"
+fn foo() {
+    let n = 1;
+    let m = 2;
+    $0let k = n * m;
+    let l = k + n;$0
+    let h = l * 2;
+}
+"#,
+            r#"
+fn foo() {
+    let n = 1;
+    let m = 2;
+    let l = fun_name(n, m);
+    let h = l * 2;
+}
+
+fn $0fun_name(n: i32, m: i32) -> i32 {
+    let k = n * m;
+    let l = k + n;
+    l
+}
+"#,
+        );
+    }
+
     #[test]
     fn two_variables_defined_inside_and_used_after_no_ret() {
         check_assist(
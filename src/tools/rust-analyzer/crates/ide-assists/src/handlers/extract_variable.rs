@@ -2690,6 +2690,41 @@ fn main() {
     let t2 = t;
     let x = s;
 }
+"#,
+            "Extract into variable",
+        );
+    }
+
+    #[test]
+    fn extract_var_nested_call_name_conflicts() {
+        check_assist_by_label(
+            extract_variable,
+            r#"
+struct S;
+impl S {
+    fn value(&self) -> i32 { 0 }
+}
+fn bar(value: i32) -> i32 { value }
+
+fn main() {
+    let value = 1;
+    let s = S;
+    bar($0s.value()$0) + value;
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn value(&self) -> i32 { 0 }
+}
+fn bar(value: i32) -> i32 { value }
+
+fn main() {
+    let value = 1;
+    let s = S;
+    let $0value1 = s.value();
+    bar(value1) + value;
+}
 "#,
             "Extract into variable",
         );
@@ -0,0 +1,400 @@
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    NodeOrToken,
+    SyntaxKind::{ATTR, COMMENT, WHITESPACE},
+    SyntaxToken, T,
+    ast::{
+        self, AstNode, HasAttrs, HasName,
+        edit::{AstNodeEdit, IndentLevel},
+        make,
+    },
+    syntax_editor::{Element, Position},
+};
+
+use crate::{AssistContext, AssistId, Assists, utils::generate_trait_impl_intransitive};
+
+// Assist: generate_default_impl
+//
+// Derives `Default` for a struct if all of its fields implement it, and generates
+// a `Default` impl otherwise.
+//
+// ```
+// # //- minicore: default, derive, builtin_impls
+// #[derive(Clone)]
+// struct Size$0 {
+//     width: u32,
+//     height: u32,
+// }
+// ```
+// ->
+// ```
+// #[derive(Clone, Default)]
+// struct Size {
+//     width: u32,
+//     height: u32,
+// }
+// ```
+pub(crate) fn generate_default_impl(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    let name = strukt.name()?;
+    if !name.syntax().text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+
+    let struct_def = ctx.sema.to_def(&strukt)?;
+    let krate = struct_def.module(ctx.db()).krate(ctx.db());
+    let default_trait = FamousDefs(&ctx.sema, krate).core_default_Default()?;
+
+    let derive_attr = strukt
+        .attrs()
+        .filter_map(|attr| attr.as_simple_call())
+        .find(|(name, _)| name == "derive")
+        .map(|(_, tt)| tt);
+    let derives_default = derive_attr.as_ref().is_some_and(|tt| {
+        tt.syntax().children_with_tokens().any(|it| {
+            it.as_token().is_some_and(|it| it.kind() == T![ident] && it.text() == "Default")
+        })
+    });
+    if derives_default || struct_def.ty(ctx.db()).impls_trait(ctx.db(), default_trait, &[]) {
+        cov_mark::hit!(generate_default_impl_already_implemented);
+        return None;
+    }
+
+    // Which fields implement `Default`, in declaration order.
+    let field_is_default = |ty: Option<ast::Type>| {
+        ty.and_then(|ty| ctx.sema.resolve_type(&ty))
+            .is_some_and(|ty| ty.impls_trait(ctx.db(), default_trait, &[]))
+    };
+    let fields = match strukt.field_list() {
+        Some(ast::FieldList::RecordFieldList(fields)) => fields
+            .fields()
+            .map(|field| (field.name().map(|it| it.to_string()), field_is_default(field.ty())))
+            .collect(),
+        Some(ast::FieldList::TupleFieldList(fields)) => {
+            fields.fields().map(|field| (None, field_is_default(field.ty()))).collect()
+        }
+        None => Vec::new(),
+    };
+
+    let target = strukt.syntax().text_range();
+    if fields.iter().all(|&(_, is_default)| is_default) {
+        return acc.add(
+            AssistId::generate("generate_default_impl"),
+            "Derive `Default`",
+            target,
+            |edit| {
+                let mut editor = edit.make_editor(strukt.syntax());
+                match &derive_attr {
+                    Some(tt) => {
+                        let l_delim = tt.left_delimiter_token();
+                        let r_delim = tt.right_delimiter_token();
+                        let mut tokens = tt
+                            .token_trees_and_tokens()
+                            .filter(|it| {
+                                let token = it.as_token();
+                                token != l_delim.as_ref() && token != r_delim.as_ref()
+                            })
+                            .collect::<Vec<_>>();
+                        let kind =
+                            |it: &NodeOrToken<_, SyntaxToken>| it.as_token().map(|it| it.kind());
+                        let last = tokens.iter().rev().find(|it| kind(it) != Some(WHITESPACE));
+                        if last.is_some_and(|it| kind(it) != Some(T![,])) {
+                            tokens.push(NodeOrToken::Token(make::token(T![,])));
+                            tokens.push(NodeOrToken::Token(make::tokens::single_space()));
+                        }
+                        tokens.push(NodeOrToken::Token(make::tokens::ident("Default")));
+                        let delimiter = l_delim.map_or(T!['('], |it| it.kind());
+                        let new_tt = make::token_tree(delimiter, tokens).clone_for_update();
+                        editor.replace(tt.syntax(), new_tt.syntax());
+                    }
+                    None => {
+                        let derive = make::attr_outer(make::meta_token_tree(
+                            make::ext::ident_path("derive"),
+                            make::token_tree(
+                                T!['('],
+                                vec![NodeOrToken::Token(make::tokens::ident("Default"))],
+                            ),
+                        ))
+                        .clone_for_update();
+                        let indent = IndentLevel::from_node(strukt.syntax());
+                        let after_attrs_and_comments = strukt
+                            .syntax()
+                            .children_with_tokens()
+                            .find(|it| !matches!(it.kind(), WHITESPACE | COMMENT | ATTR))
+                            .map_or(Position::first_child_of(strukt.syntax()), Position::before);
+                        editor.insert_all(
+                            after_attrs_and_comments,
+                            vec![
+                                derive.syntax().syntax_element(),
+                                make::tokens::whitespace(&format!("\n{indent}")).syntax_element(),
+                            ],
+                        );
+                    }
+                }
+                edit.add_file_edits(ctx.vfs_file_id(), editor);
+            },
+        );
+    }
+
+    acc.add(
+        AssistId::generate("generate_default_impl"),
+        "Generate `Default` impl",
+        target,
+        |edit| {
+            let value = |is_default: bool| {
+                if is_default {
+                    make::expr_call(
+                        make::expr_path(make::path_from_text("Default::default")),
+                        make::arg_list([]),
+                    )
+                    .into()
+                } else {
+                    make::ext::expr_todo()
+                }
+            };
+            let tail_expr: ast::Expr = match strukt.field_list() {
+                Some(ast::FieldList::RecordFieldList(_)) => {
+                    let fields = fields.iter().map(|(name, is_default)| {
+                        let name = make::name_ref(name.as_deref().unwrap_or("_"));
+                        make::record_expr_field(name, Some(value(*is_default)))
+                    });
+                    make::record_expr(
+                        make::ext::ident_path("Self"),
+                        make::record_expr_field_list(fields),
+                    )
+                    .into()
+                }
+                _ => {
+                    let args = fields.iter().map(|&(_, is_default)| value(is_default));
+                    make::expr_call(
+                        make::expr_path(make::ext::ident_path("Self")),
+                        make::arg_list(args),
+                    )
+                    .into()
+                }
+            };
+            let fn_ = make::fn_(
+                None,
+                None,
+                make::name("default"),
+                None,
+                None,
+                make::param_list(None, []),
+                make::block_expr([], Some(tail_expr)),
+                Some(make::ret_type(make::ty("Self"))),
+                false,
+                false,
+                false,
+                false,
+            )
+            .indent(1.into());
+
+            let impl_ = generate_trait_impl_intransitive(
+                &ast::Adt::Struct(strukt.clone()),
+                make::ty("Default"),
+            );
+            impl_.get_or_create_assoc_item_list().add_item(fn_.into());
+            let indent = strukt.indent_level();
+            let impl_ = impl_.indent(indent);
+
+            let mut editor = edit.make_editor(strukt.syntax());
+            editor.insert_all(
+                Position::after(strukt.syntax()),
+                vec![
+                    make::tokens::whitespace(&format!("\n\n{indent}")).syntax_element(),
+                    impl_.syntax().syntax_element(),
+                ],
+            );
+
+            // Only the fields without a `Default` impl are left to fill in.
+            if let Some(cap) = ctx.config.snippet_cap {
+                for todo in impl_.syntax().descendants().filter_map(ast::MacroExpr::cast) {
+                    let placeholder = edit.make_placeholder_snippet(cap);
+                    editor.add_annotation(todo.syntax(), placeholder);
+                }
+            }
+            edit.add_file_edits(ctx.vfs_file_id(), editor);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn derive_default_into_existing_derive() {
+        check_assist(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+#[derive(Clone, Debug)]
+struct Foo$0 {
+    a: u32,
+    b: i64,
+}
+"#,
+            r#"
+#[derive(Clone, Debug, Default)]
+struct Foo {
+    a: u32,
+    b: i64,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn derive_default_without_derive() {
+        check_assist(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+/// Docs.
+struct Foo$0(u32, i64);
+"#,
+            r#"
+/// Docs.
+#[derive(Default)]
+struct Foo(u32, i64);
+"#,
+        );
+    }
+
+    #[test]
+    fn manual_impl_with_non_default_field() {
+        check_assist(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+struct NoDefault;
+
+#[derive(Clone)]
+struct Foo$0 {
+    a: u32,
+    b: NoDefault,
+}
+"#,
+            r#"
+struct NoDefault;
+
+#[derive(Clone)]
+struct Foo {
+    a: u32,
+    b: NoDefault,
+}
+
+impl Default for Foo {
+    fn default() -> Self {
+        Self { a: Default::default(), b: ${0:todo!()} }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn manual_impl_for_tuple_struct() {
+        check_assist(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+struct NoDefault;
+
+struct Foo$0(NoDefault, u8);
+"#,
+            r#"
+struct NoDefault;
+
+struct Foo(NoDefault, u8);
+
+impl Default for Foo {
+    fn default() -> Self {
+        Self(${0:todo!()}, Default::default())
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn manual_impl_in_nested_module() {
+        check_assist(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+mod m {
+    struct NoDefault;
+
+    struct Foo$0 {
+        a: NoDefault,
+        b: u32,
+        c: NoDefault,
+    }
+}
+"#,
+            r#"
+mod m {
+    struct NoDefault;
+
+    struct Foo {
+        a: NoDefault,
+        b: u32,
+        c: NoDefault,
+    }
+
+    impl Default for Foo {
+        fn default() -> Self {
+            Self { a: ${1:todo!()}, b: Default::default(), c: ${0:todo!()} }
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_already_implemented() {
+        cov_mark::check!(generate_default_impl_already_implemented);
+        check_assist_not_applicable(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+#[derive(Default)]
+struct Foo$0 {
+    a: u32,
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+struct Foo$0 {
+    a: u32,
+}
+
+impl Default for Foo {
+    fn default() -> Self {
+        Foo { a: 0 }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_name() {
+        check_assist_not_applicable(
+            generate_default_impl,
+            r#"
+//- minicore: default, derive, builtin_impls
+struct Foo {
+    a: u32,$0
+}
+"#,
+        );
+    }
+}
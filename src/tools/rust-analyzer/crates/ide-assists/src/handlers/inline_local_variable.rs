@@ -144,6 +144,10 @@ fn inline_let(
     let local = sema.to_def(&bind_pat)?;
     let UsageSearchResult { mut references } = Definition::Local(local).usages(sema).all();
     match references.remove(&file_id) {
+        Some(references) if references.len() > 1 && has_side_effects(&let_stmt) => {
+            cov_mark::hit!(test_not_inline_side_effects_used_multiple_times);
+            None
+        }
         Some(references) => Some(InlineData {
             let_stmt,
             delete_let: true,
@@ -192,11 +196,30 @@ fn inline_usage(
     let UsageSearchResult { mut references } = Definition::Local(local).usages(sema).all();
     let mut references = references.remove(&file_id)?;
     let delete_let = references.len() == 1;
+    if !delete_let && has_side_effects(&let_stmt) {
+        cov_mark::hit!(test_not_inline_side_effects_used_multiple_times);
+        return None;
+    }
     references.retain(|fref| fref.name.as_name_ref() == Some(&name));
 
     Some(InlineData { let_stmt, delete_let, target: ast::NameOrNameRef::NameRef(name), references })
 }
 
+/// Whether evaluating the initializer of `let_stmt` more than once could be observed. This is a
+/// conservative syntactic check: anything that may call into other code counts.
+fn has_side_effects(let_stmt: &ast::LetStmt) -> bool {
+    let Some(initializer) = let_stmt.initializer() else { return false };
+    initializer.syntax().descendants().filter_map(ast::Expr::cast).any(|expr| match expr {
+        ast::Expr::CallExpr(_)
+        | ast::Expr::MethodCallExpr(_)
+        | ast::Expr::MacroExpr(_)
+        | ast::Expr::TryExpr(_)
+        | ast::Expr::AwaitExpr(_) => true,
+        ast::Expr::BinExpr(bin) => matches!(bin.op_kind(), Some(ast::BinaryOp::Assignment { .. })),
+        _ => false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_not_applicable};
@@ -276,31 +299,15 @@ fn test_inline_let_bind_function_call_expr() {
         check_assist(
             inline_local_variable,
             r"
-fn bar(a: usize) {}
+fn bar(a: usize) -> usize { a }
 fn foo() {
     let a$0 = bar(1);
-    a + 1;
-    if a > 10 {
-    }
-
-    while a > 10 {
-
-    }
     let b = a * 10;
-    bar(a);
 }",
             r"
-fn bar(a: usize) {}
+fn bar(a: usize) -> usize { a }
 fn foo() {
-    bar(1) + 1;
-    if bar(1) > 10 {
-    }
-
-    while bar(1) > 10 {
-
-    }
     let b = bar(1) * 10;
-    bar(bar(1));
 }",
         );
     }
@@ -313,7 +320,7 @@ fn test_inline_let_bind_cast_expr() {
 //- minicore: sized
 fn bar(a: usize) -> usize { a }
 fn foo() {
-    let a$0 = bar(1) as u64;
+    let a$0 = 1 as u64;
     a + 1;
     if a > 10 {
     }
@@ -327,15 +334,15 @@ fn foo() {
             r"
 fn bar(a: usize) -> usize { a }
 fn foo() {
-    bar(1) as u64 + 1;
-    if bar(1) as u64 > 10 {
+    1 as u64 + 1;
+    if 1 as u64 > 10 {
     }
 
-    while bar(1) as u64 > 10 {
+    while 1 as u64 > 10 {
 
     }
-    let b = bar(1) as u64 * 10;
-    bar(bar(1) as u64);
+    let b = 1 as u64 * 10;
+    bar(1 as u64);
 }",
         );
     }
@@ -438,12 +445,37 @@ fn test_call_expr() {
 fn foo() {
     let a$0 = bar(10 + 1);
     let b = a * 10;
-    let c = a as usize;
 }",
             r"
 fn foo() {
     let b = bar(10 + 1) * 10;
-    let c = bar(10 + 1) as usize;
+}",
+        );
+    }
+
+    #[test]
+    fn test_not_inline_side_effects_used_multiple_times() {
+        cov_mark::check!(test_not_inline_side_effects_used_multiple_times);
+        for init in ["bar(10 + 1)", "bar.len()", "bar?", "{ x = 1; x }"] {
+            check_assist_not_applicable(
+                inline_local_variable,
+                &format!(
+                    r"
+fn foo() {{
+    let a$0 = {init};
+    let b = a * 10;
+    let c = a as usize;
+}}"
+                ),
+            );
+        }
+        check_assist_not_applicable(
+            inline_local_variable,
+            r"
+fn foo() {
+    let a = bar(10 + 1);
+    let b = a$0 * 10;
+    let c = a as usize;
 }",
         );
     }
@@ -477,13 +509,11 @@ fn foo() {
     let bar = vec![1];
     let a$0 = bar.len();
     let b = a * 10;
-    let c = a as usize;
 }",
             r"
 fn foo() {
     let bar = vec![1];
     let b = bar.len() * 10;
-    let c = bar.len() as usize;
 }",
         );
     }
@@ -525,14 +555,12 @@ fn foo() -> Option<usize> {
     let bar = Some(1);
     let a$0 = bar?;
     let b = a * 10;
-    let c = a as usize;
     None
 }",
             r"
 fn foo() -> Option<usize> {
     let bar = Some(1);
     let b = bar? * 10;
-    let c = bar? as usize;
     None
 }",
         );
@@ -915,6 +943,28 @@ fn f() {
         );
     }
 
+    #[test]
+    fn does_not_touch_shadowing_binding() {
+        check_assist(
+            inline_local_variable,
+            r#"
+fn f() {
+    let a$0 = 1 + 2;
+    let b = a * 3;
+    let a = 4;
+    let c = a * 5;
+}
+"#,
+            r#"
+fn f() {
+    let b = (1 + 2) * 3;
+    let a = 4;
+    let c = a * 5;
+}
+"#,
+        );
+    }
+
     #[test]
     fn test_inline_let_unit_struct() {
         check_assist_not_applicable(
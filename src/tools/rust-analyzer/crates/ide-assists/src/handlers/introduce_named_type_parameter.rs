@@ -51,7 +51,13 @@ pub(crate) fn introduce_named_type_parameter(
             let type_param = make.type_param(make.name(&type_param_name), Some(type_bound_list));
             let new_ty = make.ty(&type_param_name);
 
-            editor.replace(impl_trait_type.syntax(), new_ty.syntax());
+            // `&(impl A + B)` becomes `&T`, not `&(T)`
+            let replaced = impl_trait_type
+                .syntax()
+                .parent()
+                .filter(|it| ast::ParenType::can_cast(it.kind()))
+                .unwrap_or_else(|| impl_trait_type.syntax().clone());
+            editor.replace(replaced, new_ty.syntax());
             editor.add_generic_param(&fn_, type_param.clone().into());
 
             if let Some(cap) = ctx.config.snippet_cap {
@@ -186,4 +192,27 @@ fn replace_impl_inside() {
             r#"fn f<$0I: Iterator<Item = i32>>(x: &mut Vec<I>) {}"#,
         );
     }
+
+    #[test]
+    fn replace_impl_trait_multiple_with_existing_generic_params() {
+        check_assist(
+            introduce_named_type_parameter,
+            r#"fn foo<F: Clone, B>(f: F, bar: $0impl Foo + Bar, b: B) {}"#,
+            r#"fn foo<F: Clone, B, $0F1: Foo + Bar>(f: F, bar: F1, b: B) {}"#,
+        );
+    }
+
+    #[test]
+    fn replace_impl_trait_maybe_sized() {
+        check_assist(
+            introduce_named_type_parameter,
+            r#"fn foo(bar: &(impl ?Sized + $0Debug)) {}"#,
+            r#"fn foo<$0D: ?Sized + Debug>(bar: &D) {}"#,
+        );
+        check_assist(
+            introduce_named_type_parameter,
+            r#"fn foo(bar: &$0impl ?Sized) {}"#,
+            r#"fn foo<$0T: ?Sized>(bar: &T) {}"#,
+        );
+    }
 }
@@ -563,6 +563,25 @@ fn removes_just_enough_whitespace() {
         );
     }
 
+    #[test]
+    fn keeps_single_blank_line_after_removed_import() {
+        check_assist(
+            merge_imports,
+            r"
+use foo$0::bar;
+
+use foo::baz;
+
+fn f() {}
+",
+            r"
+use foo::{bar, baz};
+
+fn f() {}
+",
+        );
+    }
+
     #[test]
     fn works_with_trailing_comma() {
         check_assist(
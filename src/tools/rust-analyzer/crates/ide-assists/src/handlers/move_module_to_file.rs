@@ -1,7 +1,7 @@
 use std::iter;
 
 use ast::edit::IndentLevel;
-use ide_db::base_db::AnchoredPathBuf;
+use ide_db::base_db::{AnchoredPath, AnchoredPathBuf, SourceDatabase};
 use itertools::Itertools;
 use stdx::format_to;
 use syntax::{
@@ -43,36 +43,48 @@ pub(crate) fn move_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) ->
     let module_def = ctx.sema.to_def(&outermost_mod_decl)?;
     let parent_module = module_def.parent(ctx.db())?;
 
+    let mut candidates = {
+        let mut buf = String::from("./");
+        let db = ctx.db();
+        match parent_module.name(db) {
+            Some(name) if !parent_module.is_mod_rs(db) && !parent_module.has_path(db) => {
+                format_to!(buf, "{}/", name.as_str())
+            }
+            _ => (),
+        }
+        let segments = iter::successors(Some(module_ast.clone()), |module| module.parent())
+            .filter_map(|it| it.name())
+            .map(|name| SmolStr::from(name.text().trim_start_matches("r#")))
+            .collect::<Vec<_>>();
+
+        format_to!(buf, "{}", segments.into_iter().rev().format("/"));
+
+        // We need to special case mod named `r#mod` and place the file in a
+        // subdirectory as "mod.rs" would be of its parent module otherwise.
+        // Otherwise, follow the `foo/mod.rs` layout if the sibling modules use it,
+        // and check the other layout for clashes as well.
+        if module_name.text() == "r#mod" {
+            vec![format!("{buf}/mod.rs")]
+        } else if module_ast.parent().is_none() && siblings_use_mod_rs(ctx, parent_module) {
+            vec![format!("{buf}/mod.rs"), format!("{buf}.rs")]
+        } else {
+            vec![format!("{buf}.rs"), format!("{buf}/mod.rs")]
+        }
+    };
+
+    if candidates.iter().any(|path| {
+        ctx.db().resolve_path(AnchoredPath { anchor: ctx.vfs_file_id(), path }).is_some()
+    }) {
+        cov_mark::hit!(target_file_exists);
+        return None;
+    }
+    let path = candidates.swap_remove(0);
+
     acc.add(
         AssistId::refactor_extract("move_module_to_file"),
         "Extract module to file",
         target,
         |builder| {
-            let path = {
-                let mut buf = String::from("./");
-                let db = ctx.db();
-                match parent_module.name(db) {
-                    Some(name) if !parent_module.is_mod_rs(db) && !parent_module.has_path(db) => {
-                        format_to!(buf, "{}/", name.as_str())
-                    }
-                    _ => (),
-                }
-                let segments = iter::successors(Some(module_ast.clone()), |module| module.parent())
-                    .filter_map(|it| it.name())
-                    .map(|name| SmolStr::from(name.text().trim_start_matches("r#")))
-                    .collect::<Vec<_>>();
-
-                format_to!(buf, "{}", segments.into_iter().rev().format("/"));
-
-                // We need to special case mod named `r#mod` and place the file in a
-                // subdirectory as "mod.rs" would be of its parent module otherwise.
-                if module_name.text() == "r#mod" {
-                    format_to!(buf, "/mod.rs");
-                } else {
-                    format_to!(buf, ".rs");
-                }
-                buf
-            };
             let contents = {
                 let items = module_items.dedent(IndentLevel(1)).to_string();
                 let mut items =
@@ -96,12 +108,20 @@ pub(crate) fn move_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) ->
                 buf,
             );
 
-            let dst = AnchoredPathBuf { anchor: ctx.vfs_file_id(), path };
+            let dst = AnchoredPathBuf { anchor: ctx.vfs_file_id(), path: path.clone() };
             builder.create_file(dst, contents);
         },
     )
 }
 
+/// Whether any out-of-line child of `module` lives in a `mod.rs` file.
+fn siblings_use_mod_rs(ctx: &AssistContext<'_>, module: hir::Module) -> bool {
+    let db = ctx.db();
+    module
+        .children(db)
+        .any(|child| !child.is_inline(db) && !child.has_path(db) && child.is_mod_rs(db))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_not_applicable};
@@ -238,6 +258,106 @@ fn f() {}
         );
     }
 
+    #[test]
+    fn extract_follows_mod_rs_siblings() {
+        check_assist(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod sibling;
+mod $0inner {
+    fn f() {}
+}
+//- /sibling/mod.rs
+"#,
+            r#"
+//- /main.rs
+mod sibling;
+mod inner;
+//- /inner/mod.rs
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_follows_mod_rs_siblings_in_submodule() {
+        check_assist(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod submod;
+//- /submod.rs
+mod sibling;
+mod $0inner {
+    fn f() {}
+}
+//- /submod/sibling/mod.rs
+"#,
+            r#"
+//- /submod.rs
+mod sibling;
+mod inner;
+//- /submod/inner/mod.rs
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_ignores_non_mod_rs_siblings() {
+        check_assist(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod sibling;
+mod $0inner {
+    fn f() {}
+}
+//- /sibling.rs
+"#,
+            r#"
+//- /main.rs
+mod sibling;
+mod inner;
+//- /inner.rs
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_target_file_exists() {
+        cov_mark::check!(target_file_exists);
+        check_assist_not_applicable(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod $0inner {
+    fn f() {}
+}
+//- /inner.rs
+fn g() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_file_with_other_layout_exists() {
+        cov_mark::check!(target_file_exists);
+        check_assist_not_applicable(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod $0inner {
+    fn f() {}
+}
+//- /inner/mod.rs
+fn g() {}
+"#,
+        );
+    }
+
     #[test]
     fn extract_public() {
         check_assist(
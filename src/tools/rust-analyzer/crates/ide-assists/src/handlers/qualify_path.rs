@@ -246,7 +246,9 @@ fn label(
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable, check_assist_target};
+    use crate::tests::{
+        check_assist, check_assist_by_label, check_assist_not_applicable, check_assist_target,
+    };
 
     use super::*;
 
@@ -361,6 +363,34 @@ pub mod PubMod3 {
         );
     }
 
+    #[test]
+    fn multiple_imports_are_offered_separately() {
+        check_assist_by_label(
+            qualify_path,
+            r#"
+PubSt$0ruct
+
+pub mod PubMod1 {
+    pub struct PubStruct;
+}
+pub mod PubMod2 {
+    pub struct PubStruct;
+}
+"#,
+            r#"
+PubMod2::PubStruct
+
+pub mod PubMod1 {
+    pub struct PubStruct;
+}
+pub mod PubMod2 {
+    pub struct PubStruct;
+}
+"#,
+            "Qualify as `PubMod2::PubStruct`",
+        );
+    }
+
     #[test]
     fn not_applicable_for_already_imported_types() {
         check_assist_not_applicable(
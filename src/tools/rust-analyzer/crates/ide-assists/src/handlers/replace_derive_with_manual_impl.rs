@@ -1286,6 +1286,41 @@ impl<T: Default, U: Default> Default for Foo<T, U> {
         )
     }
 
+    #[test]
+    fn add_custom_impl_debug_generic_struct_with_where_clause() {
+        check_assist(
+            replace_derive_with_manual_impl,
+            r#"
+//- minicore: fmt, clone, derive
+#[derive(Clone, Debu$0g)]
+struct Foo<T>
+where
+    T: Copy,
+{
+    bar: T,
+}
+"#,
+            r#"
+#[derive(Clone)]
+struct Foo<T>
+where
+    T: Copy,
+{
+    bar: T,
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for Foo<T>
+where
+    T: Copy,
+{
+    $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Foo").field("bar", &self.bar).finish()
+    }
+}
+"#,
+        )
+    }
+
     #[test]
     fn add_custom_impl_clone_generic_tuple_struct_with_bounds() {
         check_assist(
@@ -3,10 +3,11 @@
 use ide_db::{
     EditionedFileId, RootDatabase,
     defs::Definition,
-    search::{SearchScope, UsageSearchResult},
+    search::{FileReference, SearchScope, UsageSearchResult},
 };
+use itertools::Itertools;
 use syntax::{
-    AstNode,
+    AstNode, SyntaxKind,
     ast::{self, HasGenericParams, HasName, HasTypeBounds, Name, NameLike, PathType, make},
     match_ast,
 };
@@ -33,36 +34,38 @@ pub(crate) fn replace_named_generic_with_impl(
     // returns `P`
     let type_param_name = type_param.name()?;
 
-    // The list of type bounds / traits: `AsRef<Path>`
-    let type_bound_list = type_param.type_bound_list()?;
-
     let fn_ = type_param.syntax().ancestors().find_map(ast::Fn::cast)?;
-    let param_list_text_range = fn_.param_list()?.syntax().text_range();
+    let param_list = fn_.param_list()?;
+
+    // `where P: Send` predicates only bounding the type param itself, their bounds are merged
+    let own_where_preds = find_own_where_preds(&fn_, &type_param_name);
+
+    // The list of type bounds / traits: `AsRef<Path>`, in source order
+    let type_bound_list = make::type_bound_list(
+        type_param
+            .type_bound_list()
+            .into_iter()
+            .chain(own_where_preds.iter().filter_map(|pred| pred.type_bound_list()))
+            .flat_map(|bounds| bounds.bounds()),
+    )?;
 
     let type_param_hir_def = ctx.sema.to_def(&type_param)?;
     let type_param_def = Definition::GenericParam(hir::GenericParam::TypeParam(type_param_hir_def));
 
     // get all usage references for the type param
     let usage_refs = find_usages(&ctx.sema, &fn_, type_param_def, ctx.file_id());
-    if usage_refs.is_empty() {
-        return None;
-    }
 
-    // All usage references need to be valid (inside the function param list)
-    if !check_valid_usages(&usage_refs, param_list_text_range) {
+    // Apart from its own where predicates, the type param has to be used exactly once, inside
+    // the function param list. Otherwise replacing it would change the meaning of the signature.
+    let own_where_pred_ranges: Vec<TextRange> =
+        own_where_preds.iter().filter_map(|pred| Some(pred.ty()?.syntax().text_range())).collect();
+    let usage_ref = single_param_usage(&usage_refs, &own_where_pred_ranges)?;
+    if !param_list.syntax().text_range().contains_range(usage_ref.range) {
         return None;
     }
 
-    let mut path_types_to_replace = Vec::new();
-    for (_a, refs) in usage_refs.iter() {
-        for usage_ref in refs {
-            let Some(name_like) = usage_ref.name.clone().into_name_like() else {
-                continue;
-            };
-            let param_node = find_path_type(&ctx.sema, &type_param_name, &name_like)?;
-            path_types_to_replace.push(param_node);
-        }
-    }
+    let name_like = usage_ref.name.clone().into_name_like()?;
+    let path_type_to_replace = find_path_type(&ctx.sema, &type_param_name, &name_like)?;
 
     let target = type_param.syntax().text_range();
 
@@ -91,10 +94,41 @@ pub(crate) fn replace_named_generic_with_impl(
                 }
             }
 
-            let new_bounds = make::impl_trait_type(type_bound_list);
-            for path_type in path_types_to_replace.iter().rev() {
-                editor.replace(path_type.syntax(), new_bounds.clone_for_update().syntax());
+            // remove the merged where predicates
+            if let Some(where_clause) = fn_.where_clause().filter(|_| !own_where_preds.is_empty()) {
+                let preds: Vec<ast::WherePred> =
+                    where_clause.predicates().filter(|it| !own_where_preds.contains(it)).collect();
+                if preds.is_empty() {
+                    if let Some(ws) = where_clause
+                        .syntax()
+                        .prev_sibling_or_token()
+                        .filter(|it| it.kind() == SyntaxKind::WHITESPACE)
+                    {
+                        editor.delete(ws);
+                    }
+                    editor.delete(where_clause.syntax());
+                } else {
+                    let new_where_clause = make::where_clause(preds);
+                    editor.replace(
+                        where_clause.syntax(),
+                        new_where_clause.syntax().clone_for_update(),
+                    );
+                }
             }
+
+            let new_bounds = make::impl_trait_type(type_bound_list);
+            // `&impl A + B` is ambiguous, it has to be written as `&(impl A + B)`
+            let needs_parens =
+                new_bounds.type_bound_list().is_some_and(|it| it.bounds().count() > 1)
+                    && path_type_to_replace.syntax().parent().is_some_and(|it| {
+                        ast::RefType::can_cast(it.kind()) || ast::PtrType::can_cast(it.kind())
+                    });
+            let new_ty = if needs_parens {
+                make::ty(&format!("({new_bounds})"))
+            } else {
+                ast::Type::ImplTraitType(new_bounds)
+            };
+            editor.replace(path_type_to_replace.syntax(), new_ty.clone_for_update().syntax());
             edit.add_file_edits(ctx.vfs_file_id(), editor);
         },
     )
@@ -165,11 +199,35 @@ fn find_usages(
     type_param_def.usages(sema).in_scope(&SearchScope::file_range(file_range)).all()
 }
 
-fn check_valid_usages(usages: &UsageSearchResult, param_list_range: TextRange) -> bool {
+/// Returns the where predicates of `fn_` of the form `P: Bound`, where `P` is the type param.
+fn find_own_where_preds(fn_: &ast::Fn, type_param_name: &Name) -> Vec<ast::WherePred> {
+    let Some(where_clause) = fn_.where_clause() else {
+        return Vec::new();
+    };
+    where_clause
+        .predicates()
+        .filter(|pred| pred.for_binder().is_none())
+        .filter(|pred| match pred.ty() {
+            Some(ast::Type::PathType(path_type)) => path_type
+                .path()
+                .and_then(|path| path.as_single_name_ref())
+                .is_some_and(|name_ref| name_ref.text() == type_param_name.text()),
+            _ => false,
+        })
+        .collect()
+}
+
+/// Returns the only usage outside of `ignored_ranges`, if there is exactly one.
+fn single_param_usage<'a>(
+    usages: &'a UsageSearchResult,
+    ignored_ranges: &[TextRange],
+) -> Option<&'a FileReference> {
     usages
         .iter()
         .flat_map(|(_, usage_refs)| usage_refs)
-        .all(|usage_ref| param_list_range.contains_range(usage_ref.range))
+        .filter(|usage_ref| !ignored_ranges.iter().any(|it| it.contains_range(usage_ref.range)))
+        .exactly_one()
+        .ok()
 }
 
 #[cfg(test)]
@@ -197,11 +255,14 @@ fn replace_generic_with_inner_associated_type() {
     }
 
     #[test]
-    fn replace_generic_trait_applies_to_all_matching_params() {
-        check_assist(
+    fn replace_generic_not_applicable_when_used_in_multiple_params() {
+        check_assist_not_applicable(
             replace_named_generic_with_impl,
             r#"fn new<T$0: ToString>(a: T, b: T) -> Self {}"#,
-            r#"fn new(a: impl ToString, b: impl ToString) -> Self {}"#,
+        );
+        check_assist_not_applicable(
+            replace_named_generic_with_impl,
+            r#"fn new<T$0: ToString>(a: (T, Option<T>)) -> Self {}"#,
         );
     }
 
@@ -209,24 +270,18 @@ fn replace_generic_trait_applies_to_all_matching_params() {
     fn replace_generic_trait_applies_to_generic_arguments_in_params() {
         check_assist(
             replace_named_generic_with_impl,
-            r#"
-            fn foo<P$0: Trait>(
-                _: P,
-                _: Option<P>,
-                _: Option<Option<P>>,
-                _: impl Iterator<Item = P>,
-                _: &dyn Iterator<Item = P>,
-            ) {}
-            "#,
-            r#"
-            fn foo(
-                _: impl Trait,
-                _: Option<impl Trait>,
-                _: Option<Option<impl Trait>>,
-                _: impl Iterator<Item = impl Trait>,
-                _: &dyn Iterator<Item = impl Trait>,
-            ) {}
-            "#,
+            r#"fn foo<P$0: Trait>(_: Option<Option<P>>) {}"#,
+            r#"fn foo(_: Option<Option<impl Trait>>) {}"#,
+        );
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn foo<P$0: Trait>(_: impl Iterator<Item = P>) {}"#,
+            r#"fn foo(_: impl Iterator<Item = impl Trait>) {}"#,
+        );
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn foo<P$0: Trait>(_: &dyn Iterator<Item = P>) {}"#,
+            r#"fn foo(_: &dyn Iterator<Item = impl Trait>) {}"#,
         );
     }
 
@@ -353,4 +408,53 @@ fn new(p: impl Send + Sync) {}
             "#,
         );
     }
+
+    #[test]
+    fn replace_generic_not_applicable_if_param_used_in_turbofish() {
+        check_assist_not_applicable(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0: Default>(p: P) { let _ = Default::default::<P>(); }"#,
+        );
+    }
+
+    #[test]
+    fn replace_generic_not_applicable_without_bounds() {
+        check_assist_not_applicable(replace_named_generic_with_impl, r#"fn new<P$0>(p: P) {}"#);
+    }
+
+    #[test]
+    fn replace_generic_merges_own_where_clause_bounds() {
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0: Send>(p: P) where P: Sync + Debug {}"#,
+            r#"fn new(p: impl Send + Sync + Debug) {}"#,
+        );
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0, Q>(p: P, q: Q) where P: Sync, Q: Debug {}"#,
+            r#"fn new<Q>(p: impl Sync, q: Q) where Q: Debug {}"#,
+        );
+    }
+
+    #[test]
+    fn replace_generic_not_applicable_when_used_in_other_where_predicate() {
+        check_assist_not_applicable(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0: Send>(p: P) where P: PartialEq<P> {}"#,
+        );
+    }
+
+    #[test]
+    fn replace_generic_preserves_maybe_sized() {
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0: ?Sized + Debug>(p: &P) {}"#,
+            r#"fn new(p: &(impl ?Sized + Debug)) {}"#,
+        );
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0: ?Sized>(p: &P) {}"#,
+            r#"fn new(p: &impl ?Sized) {}"#,
+        );
+    }
 }
@@ -160,6 +160,7 @@ mod handlers {
     mod generate_constant;
     mod generate_default_from_enum_variant;
     mod generate_default_from_new;
+    mod generate_default_impl;
     mod generate_delegate_methods;
     mod generate_delegate_trait;
     mod generate_deref;
@@ -297,6 +298,7 @@ pub(crate) fn all() -> &'static [Handler] {
             generate_constant::generate_constant,
             generate_default_from_enum_variant::generate_default_from_enum_variant,
             generate_default_from_new::generate_default_from_new,
+            generate_default_impl::generate_default_impl,
             generate_delegate_trait::generate_delegate_trait,
             generate_derive::generate_derive,
             generate_documentation_template::generate_doc_example,
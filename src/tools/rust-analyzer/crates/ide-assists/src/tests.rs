@@ -1176,3 +1176,39 @@ pub fn test_some_range(a: int) -> bool {
         .assert_debug_eq(&extract_into_function_assist);
     }
 }
+
+#[test]
+fn adding_match_arm_only_inserts_the_arm() {
+    let (db, frange) = RootDatabase::with_range(
+        r#"
+enum E { A, B }
+fn f(e: E) {
+    match $0e$0 {
+        E::A => {}
+    }
+}
+"#,
+    );
+    let file_id = frange.file_id.file_id(&db);
+    let text = db.file_text(file_id).text(&db).to_string();
+    let first_arm_end = text.find("E::A => {}").unwrap() + "E::A => {}".len();
+    let match_end = text.rfind("}\n}").unwrap();
+
+    let assist = assists(
+        &db,
+        &TEST_CONFIG,
+        AssistResolveStrategy::All,
+        FileRange { file_id, range: frange.range },
+    )
+    .into_iter()
+    .find(|it| it.id.0 == "add_missing_match_arms")
+    .unwrap();
+    let (edit, _) = assist.source_change.unwrap().source_file_edits.remove(&file_id).unwrap();
+
+    assert!(!edit.is_empty());
+    for indel in edit.iter() {
+        assert!(indel.delete.is_empty(), "{indel:?}");
+        let offset = usize::from(indel.delete.start());
+        assert!(first_arm_end <= offset && offset <= match_end, "{indel:?}");
+    }
+}
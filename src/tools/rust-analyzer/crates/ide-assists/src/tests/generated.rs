@@ -1596,6 +1596,28 @@ fn default() -> Self {
     )
 }
 
+#[test]
+fn doctest_generate_default_impl() {
+    check_doc_test(
+        "generate_default_impl",
+        r#####"
+//- minicore: default, derive, builtin_impls
+#[derive(Clone)]
+struct Size$0 {
+    width: u32,
+    height: u32,
+}
+"#####,
+        r#####"
+#[derive(Clone, Default)]
+struct Size {
+    width: u32,
+    height: u32,
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_delegate_methods() {
     check_doc_test(
@@ -249,7 +249,7 @@ pub fn add_trait_assoc_items_to_impl(
             }
             item => Some(item),
         })
-        .map(|item| AstNodeEdit::indent(&item, new_indent_level))
+        .map(|item| item.indent_like(new_indent_level, impl_.syntax()))
         .collect()
 }
 
@@ -1,6 +1,7 @@
 //! This module defines an accumulator for completions which are going to be presented to user.
 
 pub(crate) mod attribute;
+pub(crate) mod doc_link;
 pub(crate) mod dot;
 pub(crate) mod env_vars;
 pub(crate) mod expr;
@@ -43,8 +44,8 @@
         literal::{render_struct_literal, render_variant_lit},
         macro_::render_macro,
         pattern::{render_struct_pat, render_variant_pat},
-        render_expr, render_field, render_path_resolution, render_pattern_resolution,
-        render_tuple_field,
+        render_doc_link_resolution, render_expr, render_field, render_path_resolution,
+        render_pattern_resolution, render_tuple_field,
         type_alias::{render_type_alias, render_type_alias_with_eq},
         union_literal::render_union_literal,
     },
@@ -246,6 +247,27 @@ pub(crate) fn add_pattern_resolution(
         );
     }
 
+    pub(crate) fn add_doc_link_resolution(
+        &mut self,
+        ctx: &CompletionContext<'_>,
+        local_name: hir::Name,
+        resolution: hir::ScopeDef,
+    ) {
+        let is_private_editable = match ctx.def_is_visible(&resolution) {
+            Visible::Yes => false,
+            Visible::Editable => true,
+            Visible::No => return,
+        };
+        self.add(
+            render_doc_link_resolution(
+                RenderContext::new(ctx).private_editable(is_private_editable),
+                local_name,
+                resolution,
+            )
+            .build(ctx.db),
+        );
+    }
+
     pub(crate) fn add_enum_variants(
         &mut self,
         ctx: &CompletionContext<'_>,
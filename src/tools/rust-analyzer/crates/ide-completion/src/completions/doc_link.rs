@@ -0,0 +1,220 @@
+//! Completes paths inside of intra-doc links in doc comments, like `[Foo::ba$0]`.
+
+use hir::{PathResolution, ScopeDef};
+use ide_db::{SymbolKind, documentation::HasDocs};
+use syntax::{ToSmolStr, ast};
+
+use crate::{
+    CompletionItem,
+    completions::Completions,
+    context::{CompletionContext, Visible},
+};
+
+pub(crate) fn complete_doc_link(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    qualifier: Option<&ast::Path>,
+) {
+    let Some(qualifier) = qualifier else {
+        acc.add_keyword(ctx, "crate");
+        acc.add_keyword(ctx, "self");
+        if ctx.module.parent(ctx.db).is_some() {
+            acc.add_keyword(ctx, "super");
+        }
+        ctx.process_all_names(&mut |name, def, _| {
+            if !matches!(def, ScopeDef::Local(_) | ScopeDef::Label(_) | ScopeDef::Unknown) {
+                acc.add_doc_link_resolution(ctx, name, def);
+            }
+        });
+        return;
+    };
+
+    let Some(resolution) = ctx.scope.speculative_resolve(qualifier) else {
+        return;
+    };
+    let ty = match resolution {
+        PathResolution::Def(hir::ModuleDef::Module(module)) => {
+            for (name, def) in module.scope(ctx.db, Some(ctx.module)) {
+                acc.add_doc_link_resolution(ctx, name, def);
+            }
+            return;
+        }
+        PathResolution::Def(hir::ModuleDef::Trait(t)) => {
+            for item in t.items(ctx.db) {
+                add_assoc_item(acc, ctx, item);
+            }
+            return;
+        }
+        PathResolution::Def(hir::ModuleDef::Adt(adt)) => adt.ty(ctx.db),
+        PathResolution::Def(hir::ModuleDef::TypeAlias(alias)) => alias.ty(ctx.db),
+        PathResolution::Def(hir::ModuleDef::BuiltinType(builtin)) => builtin.ty(ctx.db),
+        PathResolution::SelfType(impl_) => impl_.self_ty(ctx.db),
+        _ => return,
+    };
+
+    match ty.as_adt() {
+        Some(hir::Adt::Enum(e)) => {
+            for variant in e.variants(ctx.db) {
+                let name = variant.name(ctx.db);
+                acc.add_doc_link_resolution(ctx, name, ScopeDef::ModuleDef(variant.into()));
+            }
+        }
+        Some(hir::Adt::Struct(strukt)) => add_fields(acc, ctx, strukt.fields(ctx.db)),
+        Some(hir::Adt::Union(un)) => add_fields(acc, ctx, un.fields(ctx.db)),
+        None => (),
+    }
+    ctx.iterate_path_candidates(&ty, |item| add_assoc_item(acc, ctx, item));
+}
+
+fn add_assoc_item(acc: &mut Completions, ctx: &CompletionContext<'_>, item: hir::AssocItem) {
+    if let Some(name) = item.name(ctx.db) {
+        acc.add_doc_link_resolution(ctx, name, ScopeDef::ModuleDef(item.into()));
+    }
+}
+
+fn add_fields(acc: &mut Completions, ctx: &CompletionContext<'_>, fields: Vec<hir::Field>) {
+    for field in fields {
+        if matches!(ctx.is_visible(&field), Visible::No) {
+            continue;
+        }
+        let mut item = CompletionItem::new(
+            SymbolKind::Field,
+            ctx.source_range(),
+            field.name(ctx.db).display_no_db(ctx.edition).to_smolstr(),
+            ctx.edition,
+        );
+        item.set_documentation(field.docs(ctx.db));
+        item.add_to(acc, ctx.db);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_edit, completion_list};
+
+    #[test]
+    fn no_completions_outside_of_doc_links() {
+        assert_eq!(
+            completion_list(
+                r#"
+// [Fo$0]
+struct Foo;
+"#,
+            ),
+            String::new(),
+        );
+        assert_eq!(
+            completion_list(
+                r#"
+struct Foo;
+fn f() { let _ = "[Fo$0]"; }
+"#,
+            ),
+            String::new(),
+        );
+        assert_eq!(
+            completion_list(
+                r#"
+/// See Fo$0
+struct Foo;
+"#,
+            ),
+            String::new(),
+        );
+    }
+
+    #[test]
+    fn completes_crate_keyword() {
+        check_edit(
+            "crate",
+            r#"
+/// [cra$0]
+struct Foo;
+"#,
+            r#"
+/// [crate]
+struct Foo;
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_items_in_scope_of_documented_item() {
+        check_edit(
+            "Bar",
+            r#"
+mod m {
+    pub struct Bar;
+    /// [`B$0`]
+    pub fn foo() {}
+}
+"#,
+            r#"
+mod m {
+    pub struct Bar;
+    /// [`Bar`]
+    pub fn foo() {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_methods_without_parens() {
+        check_edit(
+            "method",
+            r#"
+struct Foo;
+impl Foo {
+    fn method(&self) {}
+}
+/// [Foo::me$0]
+fn bar() {}
+"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn method(&self) {}
+}
+/// [Foo::method]
+fn bar() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_qualified_link_destination() {
+        check_edit(
+            "field",
+            r#"
+mod m {
+    pub struct Foo {
+        pub field: u32,
+    }
+}
+/// [the field](crate::m::Foo::$0)
+fn bar() {}
+"#,
+            r#"
+mod m {
+    pub struct Foo {
+        pub field: u32,
+    }
+}
+/// [the field](crate::m::Foo::field)
+fn bar() {}
+"#,
+        );
+        check_edit(
+            "Variant",
+            r#"
+//! [E::Va$0riant]
+enum E { Variant }
+"#,
+            r#"
+//! [E::Variant]
+enum E { Variant }
+"#,
+        );
+    }
+}
@@ -1317,6 +1317,97 @@ fn fn required()
         );
     }
 
+    #[test]
+    fn works_directly_in_impl_of_generic_trait() {
+        check_no_kw(
+            r#"
+trait Tr<T> {
+    type Item;
+    const C: T;
+    fn provided() {}
+    fn required(&self, t: T) -> Option<Self::Item>;
+}
+
+impl Tr<u32> for () {
+    $0
+}
+"#,
+            expect![[r#"
+                ct const C: u32 =
+                fn fn provided()
+                me fn required(..)
+                ta type Item =
+            "#]],
+        );
+        check_edit(
+            "fn required",
+            r#"
+trait Tr<T> {
+    type Item;
+    fn required(&self, t: T) -> Option<Self::Item>;
+}
+
+impl Tr<u32> for () {
+    $0
+}
+"#,
+            r#"
+trait Tr<T> {
+    type Item;
+    fn required(&self, t: T) -> Option<Self::Item>;
+}
+
+impl Tr<u32> for () {
+    fn required(&self, t: u32) -> Option<Self::Item> {
+    $0
+}
+}
+"#,
+        );
+        check_edit(
+            "const C",
+            r#"
+trait Tr<T> {
+    const C: T;
+}
+
+impl Tr<u32> for () {
+    $0
+}
+"#,
+            r#"
+trait Tr<T> {
+    const C: T;
+}
+
+impl Tr<u32> for () {
+    const C: u32 = $0;
+}
+"#,
+        );
+        check_edit(
+            "type Item",
+            r#"
+trait Tr<T> {
+    type Item;
+}
+
+impl Tr<u32> for () {
+    $0
+}
+"#,
+            r#"
+trait Tr<T> {
+    type Item;
+}
+
+impl Tr<u32> for () {
+    type Item = $0;
+}
+"#,
+        );
+    }
+
     #[test]
     fn fixes_up_macro_generated() {
         check_edit(
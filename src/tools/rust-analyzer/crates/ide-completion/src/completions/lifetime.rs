@@ -128,6 +128,18 @@ fn foo<'func>(&'a$0 self) {}
         );
     }
 
+    #[test]
+    fn dont_complete_lifetime_in_char_literal() {
+        check(
+            r#"
+fn foo<'lifetime>() {
+    let c = 'a$0';
+}
+"#,
+            expect![[r#""#]],
+        );
+    }
+
     #[test]
     fn complete_lifetime_in_arg_list() {
         check(
@@ -20,31 +20,51 @@ pub(crate) fn complete_use_path(
         Qualified::With { path, resolution: Some(resolution), super_chain_len } => {
             acc.add_super_keyword(ctx, *super_chain_len);
 
-            // only show `self` in a new use-tree when the qualifier doesn't end in self
-            let not_preceded_by_self = *use_tree_parent
-                && !matches!(
-                    path.segment().and_then(|it| it.kind()),
-                    Some(ast::PathSegmentKind::SelfKw)
-                );
-            if not_preceded_by_self {
-                acc.add_keyword(ctx, "self");
-            }
-
+            // Names already imported by a sibling in the same use tree list, importing them a
+            // second time would be an error.
             let mut already_imported_names = FxHashSet::default();
+            let mut self_already_imported = false;
             if let Some(list) = ctx.token.parent_ancestors().find_map(ast::UseTreeList::cast) {
                 let use_tree = list.parent_use_tree();
                 if use_tree.path().as_ref() == Some(path) {
                     for tree in list.use_trees().filter(|tree| tree.is_simple_path()) {
-                        if let Some(name) = tree.path().and_then(|path| path.as_single_name_ref()) {
-                            already_imported_names.insert(name.to_string());
+                        let Some(segment) = tree.path().and_then(|path| path.segment()) else {
+                            continue;
+                        };
+                        if tree.path().is_some_and(|path| path.qualifier().is_some())
+                            || segment.syntax().text_range().contains_inclusive(ctx.position.offset)
+                        {
+                            continue;
+                        }
+                        match segment.kind() {
+                            Some(ast::PathSegmentKind::SelfKw) => self_already_imported = true,
+                            Some(ast::PathSegmentKind::Name(name)) => {
+                                already_imported_names.insert(name.to_string());
+                            }
+                            _ => (),
                         }
                     }
                 }
             }
 
+            // only show `self` in a new use-tree when the qualifier doesn't end in self
+            let not_preceded_by_self = *use_tree_parent
+                && !matches!(
+                    path.segment().and_then(|it| it.kind()),
+                    Some(ast::PathSegmentKind::SelfKw)
+                );
+            if not_preceded_by_self && !self_already_imported {
+                acc.add_keyword(ctx, "self");
+            }
+
             match resolution {
                 hir::PathResolution::Def(hir::ModuleDef::Module(module)) => {
                     let module_scope = module.scope(ctx.db, Some(ctx.module));
+                    // Names the current module already uses for other items, importing an item
+                    // under one of them would conflict.
+                    let scope = ctx.module.scope(ctx.db, None);
+                    let names_in_scope =
+                        scope.iter().map(|(name, _)| name).collect::<FxHashSet<_>>();
                     let unknown_is_current = |name: &hir::Name| {
                         matches!(
                             name_ref,
@@ -59,8 +79,10 @@ pub(crate) fn complete_use_path(
                         {
                             continue;
                         }
-                        let is_name_already_imported =
-                            already_imported_names.contains(name.as_str());
+                        if already_imported_names.contains(name.as_str()) {
+                            cov_mark::hit!(skip_already_imported_sibling);
+                            continue;
+                        }
 
                         let add_resolution = match def {
                             ScopeDef::Unknown if unknown_is_current(&name) => {
@@ -73,6 +95,8 @@ pub(crate) fn complete_use_path(
                         };
 
                         if add_resolution {
+                            let is_name_already_imported = names_in_scope.contains(&name)
+                                && !scope.contains(&(name.clone(), def));
                             let mut builder = Builder::from_resolution(ctx, path_ctx, name, def);
                             builder.with_relevance(|r| CompletionRelevance {
                                 is_name_already_imported,
@@ -17,7 +17,7 @@
 };
 use itertools::Either;
 use syntax::{
-    AstNode, Edition, SmolStr,
+    AstNode, AstToken, Edition, SmolStr,
     SyntaxKind::{self, *},
     SyntaxToken, T, TextRange, TextSize,
     ast::{self, AttrKind, NameOrNameRef},
@@ -389,6 +389,11 @@ pub(crate) enum CompletionAnalysis<'db> {
         fake_attribute_under_caret: Option<ast::Attr>,
         extern_crate: Option<ast::ExternCrate>,
     },
+    /// Set if we are currently completing an intra-doc link in a doc comment, like `[Foo::ba$0]`
+    DocLink {
+        /// The path preceding the segment under the cursor, `Foo` in `[Foo::ba$0]`
+        qualifier: Option<ast::Path>,
+    },
 }
 
 /// Information about the field or method access we are completing.
@@ -511,6 +516,19 @@ pub(crate) fn source_range(&self) -> TextRange {
             LIFETIME_IDENT | UNDERSCORE | INT_NUMBER => self.original_token.text_range(),
             // We want to consider all keywords in all editions.
             _ if kind.is_any_identifier() => self.original_token.text_range(),
+            COMMENT => {
+                // We only complete intra-doc links in comments, replace the word under the cursor
+                let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+                let start = self.original_token.text_range().start();
+                let (before, after) =
+                    self.original_token.text().split_at(usize::from(self.position.offset - start));
+                let before = before.trim_end_matches(is_ident_char).len();
+                let after = after.len() - after.trim_start_matches(is_ident_char).len();
+                TextRange::new(
+                    start + TextSize::from(before as u32),
+                    self.position.offset + TextSize::from(after as u32),
+                )
+            }
             _ => TextRange::empty(self.position.offset),
         }
     }
@@ -759,8 +777,20 @@ pub(crate) fn new(
             &original_token,
         )?;
 
-        // adjust for macro input, this still fails if there is no token written yet
-        let scope = sema.scope_at_offset(&token.parent()?, original_offset)?;
+        let scope = match &analysis {
+            // intra-doc links resolve in the scope the documented item is defined in
+            CompletionAnalysis::DocLink { .. } => {
+                let parent = token.parent()?;
+                let is_outer = ast::Comment::cast(token.clone()).is_some_and(|it| it.is_outer());
+                let scope_node = match parent.parent() {
+                    Some(grand_parent) if is_outer => grand_parent,
+                    _ => parent,
+                };
+                sema.scope(&scope_node)?
+            }
+            // adjust for macro input, this still fails if there is no token written yet
+            _ => sema.scope_at_offset(&token.parent()?, original_offset)?,
+        };
 
         let krate = scope.krate();
         let module = scope.module();
@@ -8,15 +8,15 @@
 use itertools::Either;
 use stdx::always;
 use syntax::{
-    AstNode, AstToken, Direction, NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken,
-    T, TextRange, TextSize,
+    AstNode, AstToken, Direction, Edition, NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+    SyntaxToken, T, TextRange, TextSize,
     algo::{
         self, ancestors_at_offset, find_node_at_offset, non_trivia_sibling,
         previous_non_trivia_token,
     },
     ast::{
         self, AttrKind, HasArgList, HasGenericArgs, HasGenericParams, HasLoopBody, HasName,
-        NameOrNameRef,
+        NameOrNameRef, make,
     },
     match_ast,
 };
@@ -61,6 +61,18 @@ pub(super) fn expand_and_analyze<'db>(
     offset: TextSize,
     original_token: &SyntaxToken,
 ) -> Option<AnalysisResult<'db>> {
+    if let Some(comment) = ast::Comment::cast(original_token.clone()) {
+        // The only thing we complete inside of comments are intra-doc links
+        let analysis = analyze_doc_link(&comment, offset)?;
+        return Some(AnalysisResult {
+            analysis,
+            expected: (None, None),
+            qualifier_ctx: QualifierCtx::default(),
+            token: original_token.clone(),
+            original_offset: offset,
+        });
+    }
+
     // as we insert after the offset, right biased will *always* pick the identifier no matter
     // if there is an ident already typed or not
     let fake_ident_token = speculative_file.token_at_offset(offset).right_biased()?;
@@ -95,6 +107,45 @@ pub(super) fn expand_and_analyze<'db>(
     })
 }
 
+/// Checks whether `offset` is inside the link of an intra-doc link, like `[Foo::ba$0]`,
+/// ``[`Foo::ba$0`]`` or `[text](Foo::ba$0)`.
+fn analyze_doc_link<'db>(
+    comment: &ast::Comment,
+    offset: TextSize,
+) -> Option<CompletionAnalysis<'db>> {
+    if !comment.is_doc() {
+        return None;
+    }
+    let before_cursor = comment
+        .text()
+        .get(..usize::from(offset.checked_sub(comment.syntax().text_range().start())?))?;
+    let link_start =
+        before_cursor.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_' || c == ':');
+    let link = &before_cursor[link_start.len()..];
+    let link_start = link_start.strip_suffix('`').unwrap_or(link_start);
+    if !link_start.ends_with('[') && !link_start.ends_with("](") {
+        return None;
+    }
+
+    let qualifier = match link.rsplit_once("::") {
+        Some((qualifier, _)) => {
+            let is_path_segment = |segment: &str| {
+                segment != "_"
+                    && segment.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    && (matches!(segment, "crate" | "self" | "super" | "Self")
+                        || SyntaxKind::from_keyword(segment, Edition::CURRENT).is_none())
+            };
+            if !qualifier.split("::").all(is_path_segment) {
+                return None;
+            }
+            Some(make::path_from_text(qualifier))
+        }
+        None => None,
+    };
+    Some(CompletionAnalysis::DocLink { qualifier })
+}
+
 fn token_at_offset_ignore_whitespace(file: &SyntaxNode, offset: TextSize) -> Option<SyntaxToken> {
     let token = file.token_at_offset(offset).left_biased()?;
     algo::skip_whitespace_token(token, Direction::Prev)
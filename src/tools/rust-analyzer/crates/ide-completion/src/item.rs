@@ -63,6 +63,9 @@ pub struct CompletionItem {
     /// Whether this item is marked as deprecated
     pub deprecated: bool,
 
+    /// Whether using this item requires an `unsafe` block, like calling an `unsafe fn`.
+    pub requires_unsafe: bool,
+
     /// If completing a function call, ask the editor to show parameter popup
     /// after completion.
     pub trigger_call_info: bool,
@@ -124,6 +127,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.deprecated {
             s.field("deprecated", &true);
         }
+        if self.requires_unsafe {
+            s.field("requires_unsafe", &true);
+        }
 
         if self.relevance != CompletionRelevance::default() {
             s.field("relevance", &self.relevance);
@@ -184,6 +190,8 @@ pub struct CompletionRelevance {
     pub function: Option<CompletionRelevanceFn>,
     /// true when there is an `await.method()` or `iter().method()` completion.
     pub is_skipping_completion: bool,
+    /// Set for items marked with `#[deprecated]`, mirrors [`CompletionItem::deprecated`].
+    pub is_deprecated: bool,
 }
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct CompletionRelevanceTraitInfo {
@@ -275,6 +283,7 @@ pub fn score(self) -> u32 {
             trait_,
             function,
             is_skipping_completion,
+            is_deprecated,
         } = self;
 
         // only applicable for completions within use items
@@ -308,6 +317,11 @@ pub fn score(self) -> u32 {
             score -= 7;
         }
 
+        // lower rank deprecated items
+        if is_deprecated {
+            score -= 5;
+        }
+
         // lower rank for items that need an import
         if requires_import {
             score -= 1;
@@ -444,6 +458,7 @@ pub(crate) fn new(
             kind: kind.into(),
             text_edit: None,
             deprecated: false,
+            requires_unsafe: false,
             trigger_call_info: false,
             relevance: CompletionRelevance::default(),
             ref_match: None,
@@ -495,6 +510,7 @@ pub(crate) struct Builder {
     kind: CompletionItemKind,
     text_edit: Option<TextEdit>,
     deprecated: bool,
+    requires_unsafe: bool,
     trigger_call_info: bool,
     relevance: CompletionRelevance,
     ref_match: Option<(CompletionItemRefMode, TextSize)>,
@@ -590,8 +606,9 @@ pub(crate) fn build(self, db: &RootDatabase) -> CompletionItem {
             lookup,
             kind: self.kind,
             deprecated: self.deprecated,
+            requires_unsafe: self.requires_unsafe,
             trigger_call_info: self.trigger_call_info,
-            relevance: self.relevance,
+            relevance: CompletionRelevance { is_deprecated: self.deprecated, ..self.relevance },
             ref_match: self.ref_match,
             import_to_add,
         }
@@ -657,6 +674,10 @@ pub(crate) fn set_deprecated(&mut self, deprecated: bool) -> &mut Builder {
         self.deprecated = deprecated;
         self
     }
+    pub(crate) fn set_requires_unsafe(&mut self, requires_unsafe: bool) -> &mut Builder {
+        self.requires_unsafe = requires_unsafe;
+        self
+    }
     pub(crate) fn set_relevance(&mut self, relevance: CompletionRelevance) -> &mut Builder {
         self.relevance = relevance;
         self
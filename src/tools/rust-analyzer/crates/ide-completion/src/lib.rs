@@ -263,6 +263,9 @@ pub fn completions(
                     extern_crate.as_ref(),
                 );
             }
+            CompletionAnalysis::DocLink { qualifier } => {
+                completions::doc_link::complete_doc_link(acc, ctx, qualifier.as_ref());
+            }
             CompletionAnalysis::UnexpandedAttrTT { .. } | CompletionAnalysis::String { .. } => (),
         }
     }
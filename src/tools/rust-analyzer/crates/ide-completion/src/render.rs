@@ -253,6 +253,16 @@ pub(crate) fn render_pattern_resolution(
     render_resolution_pat(ctx, pattern_ctx, local_name, None, resolution)
 }
 
+/// Renders a resolution as a plain path without call parentheses or snippets, as used in
+/// intra-doc links.
+pub(crate) fn render_doc_link_resolution(
+    ctx: RenderContext<'_>,
+    local_name: hir::Name,
+    resolution: ScopeDef,
+) -> Builder {
+    render_resolution_simple_(ctx, &local_name, None, resolution)
+}
+
 pub(crate) fn render_resolution_with_import(
     ctx: RenderContext<'_>,
     path_ctx: &PathCompletionCtx<'_>,
@@ -787,6 +797,8 @@ fn display_relevance(relevance: CompletionRelevance) -> String {
                 ),
                 (relevance.trait_.is_some_and(|it| it.is_op_method), "op_method"),
                 (relevance.requires_import, "requires_import"),
+                (relevance.is_deprecated, "deprecated"),
+                (relevance.is_name_already_imported, "already_imported"),
             ]
             .into_iter()
             .filter_map(|(cond, desc)| if cond { Some(desc) } else { None })
@@ -796,6 +808,24 @@ fn display_relevance(relevance: CompletionRelevance) -> String {
         }
     }
 
+    #[test]
+    fn conflicting_use_tree_name_is_ranked_lower() {
+        check_relevance(
+            r#"
+mod foo {
+    pub struct Bar;
+    pub struct Baz;
+}
+struct Bar;
+use foo::$0;
+"#,
+            expect![[r#"
+                st Baz Baz []
+                st Bar Bar [already_imported]
+            "#]],
+        );
+    }
+
     #[test]
     fn set_struct_type_completion_info() {
         check_relevance(
@@ -1169,6 +1199,7 @@ fn main() { Foo::Fo$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         trigger_call_info: true,
                     },
@@ -1219,6 +1250,7 @@ fn main() { Foo::Fo$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         trigger_call_info: true,
                     },
@@ -1299,6 +1331,7 @@ fn main() { fo$0 }
                         ),
                         lookup: "foo",
                         detail: "unsafe fn(u32, u32, ...)",
+                        requires_unsafe: true,
                         trigger_call_info: true,
                     },
                     CompletionItem {
@@ -1362,6 +1395,7 @@ fn main() { Foo::Fo$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         trigger_call_info: true,
                     },
@@ -1445,6 +1479,7 @@ fn main() { let _: m::Spam = S$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         trigger_call_info: true,
                     },
@@ -1481,6 +1516,7 @@ fn main() { let _: m::Spam = S$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         trigger_call_info: true,
                     },
@@ -1489,6 +1525,23 @@ fn main() { let _: m::Spam = S$0 }
         )
     }
 
+    #[test]
+    fn deprecated_items_rank_below_others() {
+        check_relevance(
+            r#"
+#[deprecated]
+fn old_function() {}
+fn new_function() {}
+fn main() { $0 }
+"#,
+            expect![[r#"
+                fn main() fn() []
+                fn new_function() fn() []
+                fn old_function() fn() [deprecated]
+            "#]],
+        );
+    }
+
     #[test]
     fn sets_deprecated_flag_in_items() {
         check(
@@ -1531,6 +1584,19 @@ fn main() { som$0 }
                         lookup: "something_deprecated",
                         detail: "fn()",
                         deprecated: true,
+                        relevance: CompletionRelevance {
+                            exact_name_match: false,
+                            type_match: None,
+                            is_local: false,
+                            trait_: None,
+                            is_name_already_imported: false,
+                            requires_import: false,
+                            is_private_editable: false,
+                            postfix_match: None,
+                            function: None,
+                            is_skipping_completion: false,
+                            is_deprecated: true,
+                        },
                     },
                 ]
             "#]],
@@ -1571,6 +1637,7 @@ fn foo() { A { the$0 } }
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            is_deprecated: true,
                         },
                     },
                 ]
@@ -1630,6 +1697,7 @@ fn bar(self) { self.$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                     },
                     CompletionItem {
@@ -1721,6 +1789,7 @@ enum E {
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         trigger_call_info: true,
                     },
@@ -1791,6 +1860,7 @@ fn foo(s: S) { s.$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                     },
                 ]
@@ -2003,6 +2073,7 @@ fn f() -> i32 {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                     },
                 ]
@@ -2817,6 +2888,7 @@ fn foo(f: Foo) { let _: &u32 = f.b$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         ref_match: "&@107",
                     },
@@ -2904,6 +2976,7 @@ fn foo() {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                     },
                 ]
@@ -2962,6 +3035,7 @@ fn main() {
                                 },
                             ),
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                         ref_match: "&@92",
                     },
@@ -3381,6 +3455,7 @@ fn main() {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                     },
                     CompletionItem {
@@ -3415,6 +3490,7 @@ fn main() {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            is_deprecated: false,
                         },
                     },
                 ]
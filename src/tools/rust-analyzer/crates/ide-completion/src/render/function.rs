@@ -3,7 +3,7 @@
 use hir::{AsAssocItem, HirDisplay, db::HirDatabase};
 use ide_db::{SnippetCap, SymbolKind};
 use itertools::Itertools;
-use stdx::{format_to, to_lower_snake_case};
+use stdx::{format_to, to_lower_snake_case, truncate_with_ellipsis};
 use syntax::{AstNode, SmolStr, ToSmolStr, format_smolstr};
 
 use crate::{
@@ -148,6 +148,11 @@ fn render(
     };
     item.set_documentation(ctx.docs(func))
         .set_deprecated(ctx.is_deprecated(func) || ctx.is_deprecated_assoc_item(func))
+        .set_requires_unsafe(func.is_unsafe_to_call(
+            completion.db,
+            completion.containing_function,
+            completion.edition,
+        ))
         .detail(detail)
         .lookup_by(name.as_str().to_smolstr());
 
@@ -301,6 +306,9 @@ fn ref_of_param(ctx: &CompletionContext<'_>, arg: &str, ty: &hir::Type<'_>) -> &
     ""
 }
 
+/// Longest function detail we show, longer ones are cut off with `…`.
+const MAX_DETAIL_LEN: usize = 100;
+
 fn detail(ctx: &CompletionContext<'_>, func: hir::Function) -> String {
     let mut ret_ty = func.ret_type(ctx.db);
     let mut detail = String::new();
@@ -322,44 +330,38 @@ fn detail(ctx: &CompletionContext<'_>, func: hir::Function) -> String {
     params_display(ctx, &mut detail, func);
     detail.push(')');
     if !ret_ty.is_unit() {
-        format_to!(detail, " -> {}", ret_ty.display(ctx.db, ctx.display_target));
+        format_to!(
+            detail,
+            " -> {}",
+            ret_ty.display(ctx.db, ctx.display_target).with_compact_signature(true)
+        );
     }
-    detail
+    truncate_with_ellipsis(&detail, MAX_DETAIL_LEN)
 }
 
 fn detail_full(ctx: &CompletionContext<'_>, func: hir::Function) -> String {
-    let signature = format!("{}", func.display(ctx.db, ctx.display_target));
-    let mut detail = String::with_capacity(signature.len());
-
-    for segment in signature.split_whitespace() {
-        if !detail.is_empty() {
-            detail.push(' ');
-        }
-
-        detail.push_str(segment);
-    }
-
-    detail
+    let signature =
+        format!("{}", func.display(ctx.db, ctx.display_target).with_compact_signature(true));
+    truncate_with_ellipsis(&signature, MAX_DETAIL_LEN)
 }
 
 fn params_display(ctx: &CompletionContext<'_>, detail: &mut String, func: hir::Function) {
+    let param_ty = |p: &hir::Param<'_>| {
+        p.ty().display(ctx.db, ctx.display_target).with_compact_signature(true).to_string()
+    };
     if let Some(self_param) = func.self_param(ctx.db) {
         format_to!(detail, "{}", self_param.display(ctx.db, ctx.display_target));
         let assoc_fn_params = func.assoc_fn_params(ctx.db);
         let params = assoc_fn_params
             .iter()
             .skip(1) // skip the self param because we are manually handling that
-            .map(|p| p.ty().display(ctx.db, ctx.display_target));
+            .map(param_ty);
         for param in params {
             format_to!(detail, ", {}", param);
         }
     } else {
         let assoc_fn_params = func.assoc_fn_params(ctx.db);
-        format_to!(
-            detail,
-            "{}",
-            assoc_fn_params.iter().map(|p| p.ty().display(ctx.db, ctx.display_target)).format(", ")
-        );
+        format_to!(detail, "{}", assoc_fn_params.iter().map(param_ty).format(", "));
     }
 
     if func.is_varargs(ctx.db) {
@@ -526,6 +528,81 @@ fn with_args(x: i32, y: String) {}
         );
     }
 
+    #[test]
+    fn no_parens_without_callable_snippets() {
+        check_edit_with_config(
+            CompletionConfig { callable: None, ..TEST_CONFIG },
+            "with_args",
+            r#"
+fn with_args(x: i32, y: String) {}
+fn main() { with_$0 }
+"#,
+            r#"
+fn with_args(x: i32, y: String) {}
+fn main() { with_args }
+"#,
+        );
+        check_edit_with_config(
+            CompletionConfig { callable: None, ..TEST_CONFIG },
+            "Tuple",
+            r#"
+enum E { Tuple(i32, u32) }
+fn main() { let _ = E::T$0 }
+"#,
+            r#"
+enum E { Tuple(i32, u32) }
+fn main() { let _ = E::Tuple$0 }
+"#,
+        );
+    }
+
+    #[test]
+    fn callable_snippets_matrix() {
+        // (add parentheses, fill arguments) -> expected edits of a function and a tuple variant
+        let cases = [
+            (true, true, "with_args(${1:x}, ${2:y});$0", "E::Tuple(${1:()}, ${2:()})$0"),
+            (true, false, "with_args($0);", "E::Tuple($1)$0"),
+            (false, true, "with_args", "E::Tuple$0"),
+            (false, false, "with_args", "E::Tuple$0"),
+        ];
+        for (add_parens, fill_arguments, expected_fn, expected_variant) in cases {
+            let variant_lookup = if add_parens { "Tuple()" } else { "Tuple" };
+            let callable = match (add_parens, fill_arguments) {
+                (true, true) => Some(CallableSnippets::FillArguments),
+                (true, false) => Some(CallableSnippets::AddParentheses),
+                (false, _) => None,
+            };
+            check_edit_with_config(
+                CompletionConfig { callable: callable.clone(), ..TEST_CONFIG },
+                "with_args",
+                r#"
+fn with_args(x: i32, y: String) {}
+fn main() { with_$0 }
+"#,
+                &format!(
+                    r#"
+fn with_args(x: i32, y: String) {{}}
+fn main() {{ {expected_fn} }}
+"#
+                ),
+            );
+            check_edit_with_config(
+                CompletionConfig { callable, ..TEST_CONFIG },
+                variant_lookup,
+                r#"
+enum E { Tuple(i32, u32) }
+fn main() { let _ = E::T$0 }
+"#,
+                &format!(
+                    r#"
+enum E {{ Tuple(i32, u32) }}
+fn main() {{ let _ = {expected_variant} }}
+"#
+                ),
+            );
+        }
+    }
+
     #[test]
     fn strips_underscores_from_args() {
         check_edit(
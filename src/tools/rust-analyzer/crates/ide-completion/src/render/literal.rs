@@ -56,11 +56,13 @@ fn render(
 ) -> Option<Builder> {
     let db = completion.db;
     let mut kind = thing.kind(db);
+    // Tuple literals are calls, so they are subject to the callable snippets setting.
     let should_add_parens = !matches!(
         path_ctx,
         PathCompletionCtx { has_call_parens: true, .. }
             | PathCompletionCtx { kind: PathKind::Use | PathKind::Type { .. }, .. }
-    );
+    ) && (kind != StructKind::Tuple
+        || completion.config.callable.is_some());
 
     let fields = thing.fields(completion)?;
     let (qualified_name, short_qualified_name, qualified) = match path {
@@ -96,6 +98,12 @@ fn render(
         rendered.literal.push_str("$0");
     }
 
+    if let Variant::EnumVariant(variant) = thing
+        && let Some(discriminant) = explicit_discriminant(db, variant)
+    {
+        rendered.detail = format!("{} = {discriminant}", rendered.detail);
+    }
+
     // only show name in label if not adding parens
     if !should_add_parens {
         kind = StructKind::Unit;
@@ -147,6 +155,16 @@ fn render(
     Some(item)
 }
 
+/// The discriminant of `variant`, if it's worth showing: only fieldless enums that set at least
+/// one discriminant explicitly get them, implicit ones included.
+fn explicit_discriminant(db: &dyn HirDatabase, variant: hir::Variant) -> Option<i128> {
+    let enum_ = variant.parent_enum(db);
+    if enum_.is_data_carrying(db) || enum_.variants(db).iter().all(|it| it.value(db).is_none()) {
+        return None;
+    }
+    variant.eval(db).ok()
+}
+
 #[derive(Clone, Copy)]
 enum Variant {
     Struct(hir::Struct),
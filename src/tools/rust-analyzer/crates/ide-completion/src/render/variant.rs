@@ -1,6 +1,6 @@
 //! Code common to structs, unions, and enum variants.
 
-use crate::context::CompletionContext;
+use crate::{CallableSnippets, context::CompletionContext};
 use hir::{HasAttrs, HasCrate, HasVisibility, HirDisplay, StructKind};
 use ide_db::SnippetCap;
 use itertools::Itertools;
@@ -78,15 +78,17 @@ pub(crate) fn render_tuple_lit(
             f(&format_args!("()"))
         }
     });
+    // Like for function calls, leave the fields to the user unless asked to fill them in.
+    let literal = match ctx.config.callable {
+        Some(CallableSnippets::AddParentheses) if !fields.is_empty() => format!("{path}($1)"),
+        _ => format!("{path}({completions})"),
+    };
 
     let types = fields
         .iter()
         .format_with(", ", |field, f| f(&field.ty(ctx.db).display(ctx.db, ctx.display_target)));
 
-    RenderedLiteral {
-        literal: format!("{path}({completions})"),
-        detail: format!("{path}({types})"),
-    }
+    RenderedLiteral { literal, detail: format!("{path}({types})") }
 }
 
 /// Find all the visible fields in a given list. Returns the list of visible
@@ -894,6 +894,27 @@ fn assoc_fn()                    fn()
     );
 }
 
+#[test]
+fn enum_qualified_with_discriminants() {
+    check(
+        r#"
+enum E { A = 1, B = 0x10, C }
+fn func() -> E {
+    E::$0
+}
+"#,
+        expect![[r#"
+            ev A  A = 1
+            ev B B = 16
+            ev C C = 17
+            ex E::A
+            ex E::B
+            ex E::C
+            ex func()
+        "#]],
+    );
+}
+
 #[test]
 fn ty_qualified_no_drop() {
     check(
@@ -951,6 +972,26 @@ fn main()                 fn()
     );
 }
 
+#[test]
+fn detail_const_and_unsafe_fn() {
+    check(
+        r#"
+const fn foo() {}
+unsafe fn bar() {}
+const unsafe fn baz() {}
+fn main() {
+    self::$0
+}
+"#,
+        expect![[r#"
+            fn bar()       unsafe fn()
+            fn baz() const unsafe fn()
+            fn foo()        const fn()
+            fn main()             fn()
+        "#]],
+    );
+}
+
 #[test]
 fn detail_async_fn() {
     check(
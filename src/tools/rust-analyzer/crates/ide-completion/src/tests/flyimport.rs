@@ -1769,11 +1769,99 @@ fn function() {
     );
 }
 
+#[test]
+fn re_export_aliased_from_dependency() {
+    check(
+        r#"
+//- /lib.rs crate:dep
+pub struct RawThing;
+pub mod aliases {
+    pub use crate::RawThing as Thing;
+}
+//- /main.rs crate:main deps:dep
+fn function() {
+    Thin$0
+}
+"#,
+        expect![[r#"
+            st Thing (use dep::aliases::Thing) RawThing
+        "#]],
+    );
+    check_edit(
+        "Thing",
+        r#"
+//- /lib.rs crate:dep
+pub struct RawThing;
+pub mod aliases {
+    pub use crate::RawThing as Thing;
+}
+//- /main.rs crate:main deps:dep
+fn function() {
+    Thin$0
+}
+"#,
+        r#"
+use dep::aliases::Thing;
+
+fn function() {
+    Thing
+}
+"#,
+    );
+}
+
+#[test]
+fn re_export_aliased_fuzzy_match() {
+    check_edit(
+        "Widget",
+        r#"
+//- /lib.rs crate:dep
+pub struct RawThing;
+pub mod aliases {
+    pub use crate::RawThing as Widget;
+}
+//- /main.rs crate:main deps:dep
+fn function() {
+    Wdgt$0
+}
+"#,
+        r#"
+use dep::aliases::Widget;
+
+fn function() {
+    Widget
+}
+"#,
+    );
+}
+
+#[test]
+fn re_export_aliased_prefers_typed_name() {
+    check(
+        r#"
+//- /lib.rs crate:dep
+pub struct RawThing;
+pub mod aliases {
+    pub use crate::RawThing as Thing;
+}
+//- /main.rs crate:main deps:dep
+fn function() {
+    RawThi$0
+}
+"#,
+        expect![[r#"
+            st RawThing (use dep::RawThing) RawThing
+        "#]],
+    );
+}
+
 #[test]
 fn intrinsics() {
     check(
         r#"
     //- /core.rs crate:core
+    #[lang = "sized"]
+    pub trait Sized {}
     pub mod intrinsics {
         extern "rust-intrinsic" {
             pub fn transmute<Src, Dst>(src: Src) -> Dst;
@@ -1788,12 +1876,14 @@ fn function() {
     }
 "#,
         expect![[r#"
-            fn transmute(…) (use core::mem::transmute) unsafe fn(Src) -> Dst
+            fn transmute(…) (use core::mem::transmute) unsafe fn(impl Sized) -> Dst
         "#]],
     );
     check(
         r#"
 //- /core.rs crate:core
+#[lang = "sized"]
+pub trait Sized {}
 pub mod intrinsics {
     extern "rust-intrinsic" {
         pub fn transmute<Src, Dst>(src: Src) -> Dst;
@@ -1808,7 +1898,7 @@ fn function() {
 }
 "#,
         expect![[r#"
-            fn transmute(…) (use core::mem) unsafe fn(Src) -> Dst
+            fn transmute(…) (use core::mem) unsafe fn(impl Sized) -> Dst
         "#]],
     );
 }
@@ -213,6 +213,24 @@ fn public_method()                     fn()
     );
 }
 
+#[test]
+fn qualified_path_shows_re_export_alias() {
+    check_no_kw(
+        r#"
+//- /lib.rs crate:dep
+mod internal {
+    pub struct RawThing;
+}
+pub use internal::RawThing as Thing;
+//- /main.rs crate:main deps:dep
+fn foo() { let _ = dep::$0 }
+"#,
+        expect![[r#"
+            st Thing RawThing
+        "#]],
+    );
+}
+
 #[test]
 fn completes_union_associated_method() {
     check_no_kw(
@@ -1357,12 +1375,13 @@ fn check_signatures(src: &str, kind: CompletionItemKind, reduced: Expect, full:
 fn respects_full_function_signatures() {
     check_signatures(
         r#"
+//- minicore: sized, clone
 pub fn foo<'x, T>(x: &'x mut T) -> u8 where T: Clone, { 0u8 }
 fn main() { fo$0 }
 "#,
         CompletionItemKind::SymbolKind(ide_db::SymbolKind::Function),
-        expect!("fn(&'x mut T) -> u8"),
-        expect!("pub fn foo<'x, T>(x: &'x mut T) -> u8 where T: Clone,"),
+        expect!("fn(&'x mut impl Clone) -> u8"),
+        expect!("pub fn foo<'x>(x: &'x mut impl Clone) -> u8"),
     );
 
     check_signatures(
@@ -1399,6 +1418,141 @@ fn main() {
     );
 }
 
+#[test]
+fn compacts_generic_function_signatures() {
+    check_signatures(
+        r#"
+//- minicore: sized
+trait IntoIterator { type IntoIter; }
+struct Zip<A, B>(A, B);
+struct Iter;
+impl Iter {
+    pub fn zip<U>(self, other: U) -> Zip<Self, <U as IntoIterator>::IntoIter>
+    where
+        U: IntoIterator,
+    {
+        loop {}
+    }
+}
+
+fn main() { Iter.z$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Method),
+        expect!("fn(self, impl IntoIterator) -> Zip<Iter, …>"),
+        expect!("pub fn zip(self, other: impl IntoIterator) -> Zip<Self, …>"),
+    );
+
+    check_signatures(
+        r#"
+trait Serialize {}
+pub fn combine<T: Serialize>(a: T, b: T) -> (T, T) { (a, b) }
+
+fn run() { comb$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Function),
+        expect!("fn(T, T) -> (T, T)"),
+        expect!("pub fn combine<T>(a: T, b: T) -> (T, T) where T: Serialize"),
+    );
+}
+
+#[test]
+fn compacts_serde_style_signatures() {
+    check_signatures(
+        r#"
+//- minicore: sized, result
+mod io { pub trait Write {} }
+trait Serialize {}
+struct Error;
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+{
+    loop {}
+}
+
+fn write() { to_w$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Function),
+        expect!("fn(impl Write, &(impl Serialize + ?Sized)) -> Result<(), Error>"),
+        expect!(
+            "pub fn to_writer(writer: impl io::Write, value: &(impl ?Sized + Serialize)) -> Result<(), Error>"
+        ),
+    );
+
+    check_signatures(
+        r#"
+//- minicore: sized, result
+trait Serializer { type Ok; type Error; }
+struct Value;
+impl Value {
+    pub fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        loop {}
+    }
+}
+
+fn run() { Value.ser$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Method),
+        expect!("fn(&self, impl Serializer) -> Result<…, …>"),
+        expect!("pub fn serialize(&self, serializer: impl Serializer) -> Result<…, …>"),
+    );
+}
+
+#[test]
+fn keeps_type_params_that_cannot_be_inlined() {
+    check_signatures(
+        r#"
+//- minicore: sized, fn
+pub fn pick<T, U>(x: T, f: U, g: fn(T)) where U: Fn(T) {}
+
+fn run() { pi$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Function),
+        expect!("fn(T, impl Fn(T), fn(T))"),
+        expect!("pub fn pick<T>(x: T, f: impl Fn(T), g: fn(T))"),
+    );
+
+    check_signatures(
+        r#"
+//- minicore: sized, iterator
+pub fn extend<I>(iter: I, first: I::Item) where I: IntoIterator {}
+
+fn run() { ext$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Function),
+        expect!("fn(I, <I as IntoIterator>::Item)"),
+        expect!("pub fn extend<I>(iter: I, first: I::Item) where I: IntoIterator"),
+    );
+
+    check_signatures(
+        r#"
+//- minicore: sized, fn
+pub fn higher_ranked<F>(f: F) where for<'a> F: Fn(&'a str) {}
+
+fn run() { hig$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Function),
+        expect!("fn(F)"),
+        expect!("pub fn higher_ranked<F>(f: F) where for<'a> F: Fn(&'a str)"),
+    );
+
+    check_signatures(
+        r#"
+//- minicore: sized, option
+pub fn defaulted<T = u32, const N: usize = 4>(x: T, ys: Option<T>) {}
+
+fn run() { def$0 }
+"#,
+        CompletionItemKind::SymbolKind(SymbolKind::Function),
+        expect!("fn(T, Option<T>)"),
+        expect!("pub fn defaulted<T, const N: usize>(x: T, ys: Option<T>)"),
+    );
+}
+
 #[test]
 fn skips_underscore() {
     check_with_trigger_character(
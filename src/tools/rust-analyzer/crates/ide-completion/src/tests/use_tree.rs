@@ -76,6 +76,39 @@ mod foo {}
     );
 }
 
+#[test]
+fn use_tree_start_path_roots() {
+    check(
+        r#"
+//- /lib.rs crate:main deps:serde
+use $0
+//- /serde/lib.rs crate:serde
+pub struct Serializer;
+"#,
+        expect![[r#"
+            md serde
+            kw crate::
+            kw self::
+        "#]],
+    );
+    check(
+        r#"
+//- /lib.rs crate:main deps:serde
+mod foo {
+    use s$0
+}
+//- /serde/lib.rs crate:serde
+pub struct Serializer;
+"#,
+        expect![[r#"
+            md serde
+            kw crate::
+            kw self::
+            kw super::
+        "#]],
+    );
+}
+
 #[test]
 fn dont_complete_current_use() {
     cov_mark::check!(dont_complete_current_use);
@@ -468,3 +501,39 @@ fn use_tree_doc_hidden() {
         "#]],
     );
 }
+
+#[test]
+fn use_tree_list_skips_imported_siblings() {
+    cov_mark::check!(skip_already_imported_sibling);
+    check(
+        r#"
+mod foo {
+    pub struct Bar;
+    pub struct Baz;
+    pub fn qux() {}
+}
+use foo::{Bar, self, $0};
+"#,
+        expect![[r#"
+            fn qux fn()
+            st Baz  Baz
+        "#]],
+    );
+}
+
+#[test]
+fn use_tree_list_keeps_sibling_under_cursor() {
+    check(
+        r#"
+mod foo {
+    pub struct Bar;
+    pub struct Baz;
+}
+use foo::{Ba$0, Baz};
+"#,
+        expect![[r#"
+            st Bar Bar
+            kw self
+        "#]],
+    );
+}
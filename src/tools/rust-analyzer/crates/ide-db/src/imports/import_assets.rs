@@ -5,7 +5,7 @@
 use hir::{
     AsAssocItem, AssocItem, AssocItemContainer, Complete, Crate, FindPathConfig, HasCrate,
     ItemInNs, ModPath, Module, ModuleDef, Name, PathResolution, PrefixKind, ScopeDef, Semantics,
-    SemanticsScope, Trait, Type,
+    SemanticsScope, Trait, Type, import_map::SearchMode,
 };
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -101,6 +101,24 @@ pub fn fuzzy(s: String) -> NameToImport {
         NameToImport::Fuzzy(s, case_sensitive)
     }
 
+    /// Checks whether `candidate` matches this query, in the query's own search mode.
+    pub fn matches(&self, candidate: &str) -> bool {
+        let mode = match self {
+            NameToImport::Exact(..) => SearchMode::Exact,
+            NameToImport::Prefix(..) => SearchMode::Prefix,
+            NameToImport::Fuzzy(..) => SearchMode::Fuzzy,
+        };
+        self.matches_in(mode, candidate)
+    }
+
+    fn matches_in(&self, mode: SearchMode, candidate: &str) -> bool {
+        let (NameToImport::Exact(query, case_sensitive)
+        | NameToImport::Prefix(query, case_sensitive)
+        | NameToImport::Fuzzy(query, case_sensitive)) = self;
+        let candidate = candidate.strip_prefix("r#").unwrap_or(candidate);
+        mode.check(query, *case_sensitive, candidate)
+    }
+
     pub fn text(&self) -> &str {
         match self {
             NameToImport::Prefix(text, _)
@@ -327,6 +345,15 @@ fn search_for(
             )
             .filter(|path| path.len() > 1)
         };
+        let module_path = |module: Module| {
+            get_mod_path(
+                db,
+                ItemInNs::Types(module.into()),
+                &self.module_with_candidate,
+                prefixed,
+                cfg,
+            )
+        };
 
         match &self.import_candidate {
             ImportCandidate::Path(path_candidate) => path_applicable_imports(
@@ -335,6 +362,7 @@ fn search_for(
                 krate,
                 path_candidate,
                 mod_path,
+                module_path,
                 |item_to_import| !scope_definitions.contains(&ScopeDef::from(item_to_import)),
             ),
             ImportCandidate::TraitAssocItem(trait_candidate)
@@ -372,6 +400,7 @@ fn path_applicable_imports(
     current_crate: Crate,
     path_candidate: &PathImportCandidate,
     mod_path: impl Fn(ItemInNs) -> Option<ModPath> + Copy,
+    module_path: impl Fn(Module) -> Option<ModPath> + Copy,
     scope_filter: impl Fn(ItemInNs) -> bool + Copy,
 ) -> FxIndexSet<LocatedImport> {
     let _p = tracing::info_span!("ImportAssets::path_applicable_imports").entered();
@@ -397,6 +426,14 @@ fn path_applicable_imports(
                     return None;
                 }
                 let mod_path = mod_path(item)?;
+                let mod_path = prefer_matching_export_name(
+                    db,
+                    current_crate,
+                    &path_candidate.name,
+                    item,
+                    mod_path,
+                    module_path,
+                );
                 Some(LocatedImport::new(
                     mod_path,
                     item,
@@ -436,6 +473,43 @@ fn path_applicable_imports(
     }
 }
 
+/// If `item` was found under an alias (`pub use foo::Bar as Baz;`) but `found_path` refers to it
+/// by a name that matches the user input worse, rewrites the path to go through the better
+/// matching re-export instead, so that the name the user typed is the name that ends up being
+/// imported. Names the input is a prefix of match better than names it only fuzzily matches.
+fn prefer_matching_export_name(
+    db: &RootDatabase,
+    current_crate: Crate,
+    name: &NameToImport,
+    item: ItemInNs,
+    found_path: ModPath,
+    module_path: impl Fn(Module) -> Option<ModPath>,
+) -> ModPath {
+    // Lower is better, `None` for names that don't match the input at all.
+    let rank = |export_name: &str| {
+        name.matches(export_name)
+            .then(|| if name.matches_in(SearchMode::Prefix, export_name) { 0 } else { 1 })
+    };
+    let found_rank = match found_path.segments().last() {
+        Some(last) => rank(last.as_str()),
+        None => return found_path,
+    };
+    if found_rank == Some(0) {
+        return found_path;
+    }
+    item.exported_names(db, current_crate)
+        .into_iter()
+        .filter_map(|(container, export_name)| {
+            let rank = rank(export_name.as_str())
+                .filter(|&rank| found_rank.is_none_or(|found_rank| rank < found_rank))?;
+            let mut path = module_path(container)?;
+            path.push_segment(export_name);
+            Some((rank, path))
+        })
+        .min_by_key(|(rank, path)| (*rank, path.len()))
+        .map_or(found_path, |(_, path)| path)
+}
+
 /// Validates and builds an import for `resolved_qualifier` if the `unresolved_qualifier` appended
 /// to it resolves and there is a validate `candidate` after that.
 fn validate_resolvable(
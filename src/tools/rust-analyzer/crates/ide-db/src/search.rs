@@ -140,6 +140,9 @@ pub struct ReferenceCategory: u8 {
         const READ = 1 << 1;
         const IMPORT = 1 << 2;
         const TEST = 1 << 3;
+        /// A field init or field pattern shorthand like `S { field }`, which refers to both the
+        /// field and the local of the same name.
+        const FIELD_SHORTHAND = 1 << 4;
     }
 }
 
@@ -1241,7 +1244,7 @@ fn found_name_ref(
                 let reference = FileReference {
                     range,
                     name: FileReferenceNode::NameRef(name_ref.clone()),
-                    category: access,
+                    category: access | ReferenceCategory::FIELD_SHORTHAND,
                 };
                 sink(file_id, reference)
             }
@@ -1265,7 +1268,7 @@ fn found_name(
                     range,
                     name: FileReferenceNode::Name(name.clone()),
                     // FIXME: mutable patterns should have `Write` access
-                    category: ReferenceCategory::READ,
+                    category: ReferenceCategory::READ | ReferenceCategory::FIELD_SHORTHAND,
                 };
                 sink(file_id, reference)
             }
@@ -1351,6 +1354,13 @@ fn new(
                         }
                         Some(ReferenceCategory::READ)
                     },
+                    ast::RefExpr(expr) => {
+                        // `&mut place` hands out write access to the place it borrows.
+                        if expr.mut_token().is_some() && is_borrowed_place(&expr.expr()?, r) {
+                            return Some(ReferenceCategory::WRITE)
+                        }
+                        None
+                    },
                     _ => None,
                 }
             }
@@ -1360,6 +1370,26 @@ fn new(
     }
 }
 
+/// Whether `r` names the place `expr` refers to, or the base of one of its field or index
+/// projections like `bar` in `&mut bar.field`.
+fn is_borrowed_place(expr: &ast::Expr, r: &ast::NameRef) -> bool {
+    let mut expr = expr.clone();
+    loop {
+        let next = match expr {
+            ast::Expr::PathExpr(it) => {
+                return it.path().and_then(|it| it.as_single_name_ref()).as_ref() == Some(r);
+            }
+            ast::Expr::FieldExpr(it) if it.name_ref().as_ref() == Some(r) => return true,
+            ast::Expr::FieldExpr(it) => it.expr(),
+            ast::Expr::IndexExpr(it) => it.base(),
+            ast::Expr::ParenExpr(it) => it.expr(),
+            _ => None,
+        };
+        let Some(next) = next else { return false };
+        expr = next;
+    }
+}
+
 fn is_name_ref_in_import(name_ref: &ast::NameRef) -> bool {
     name_ref
         .syntax()
@@ -39,7 +39,7 @@
 use rustc_hash::FxHashSet;
 use salsa::Update;
 
-use crate::RootDatabase;
+use crate::{RootDatabase, SymbolKind};
 
 #[derive(Debug, Clone)]
 pub struct Query {
@@ -51,6 +51,7 @@ pub struct Query {
     only_types: bool,
     libs: bool,
     exclude_imports: bool,
+    kinds: Vec<SymbolKind>,
 }
 
 impl Query {
@@ -65,6 +66,7 @@ pub fn new(query: String) -> Query {
             assoc_mode: AssocSearchMode::Include,
             case_sensitive: false,
             exclude_imports: false,
+            kinds: Vec::new(),
         }
     }
 
@@ -100,6 +102,11 @@ pub fn case_sensitive(&mut self) {
     pub fn exclude_imports(&mut self) {
         self.exclude_imports = true;
     }
+
+    /// Restricts the results to symbols of the given kinds.
+    pub fn kinds(&mut self, kinds: impl IntoIterator<Item = SymbolKind>) {
+        self.kinds.extend(kinds);
+    }
 }
 
 /// The set of roots for crates.io libraries.
@@ -134,11 +141,17 @@ pub fn crate_symbols(db: &dyn HirDatabase, krate: Crate) -> Box<[&SymbolIndex<'_
 //
 // - `Foo` searches for `Foo` type in the current workspace
 // - `foo#` searches for `foo` function in the current workspace
-// - `Foo*` searches for `Foo` type among dependencies, including `stdlib`
-// - `foo#*` searches for `foo` function among dependencies
+// - `Foo*` searches for `Foo` type in the workspace and among dependencies, including `stdlib`
+// - `foo#*` searches for `foo` function in the workspace and among dependencies
+// - `fn foo` searches for functions only, `struct Foo` for structs only
 //
-// That is, `#` switches from "types" to all symbols, `*` switches from the current
-// workspace to dependencies.
+// That is, `#` switches from "types" to all symbols, `*` extends the search from the
+// current workspace to dependencies. All symbols include associated items of impls and
+// traits, reported with the impl's self type or the trait as their container; they can be
+// left out with the `rust-analyzer.workspace.symbol.search.excludeAssocItems` setting.
+// A trailing `#` additionally requires the name to match exactly, so `Foo#` finds `Foo`
+// but not `FooBar`. A leading item keyword like `fn`, `struct`, `enum`, `trait`, `type`,
+// `const`, `static`, `mod` or `macro` restricts the search to symbols of that kind.
 //
 // Note that filtering does not currently work in VSCode due to the editor never
 // sending the special symbols to the language server. Instead, you can configure
@@ -152,27 +165,29 @@ pub fn crate_symbols(db: &dyn HirDatabase, krate: Crate) -> Box<[&SymbolIndex<'_
 pub fn world_symbols(db: &RootDatabase, query: Query) -> Vec<FileSymbol<'_>> {
     let _p = tracing::info_span!("world_symbols", query = ?query.query).entered();
 
-    let indices: Vec<_> = if query.libs {
+    let mut crates = Vec::new();
+    for &root in LocalRoots::get(db).roots(db).iter() {
+        crates.extend(db.source_root_crates(root).iter().copied())
+    }
+    crates
+        .par_iter()
+        .for_each_with(db.clone(), |snap, &krate| _ = crate_symbols(snap, krate.into()));
+    let mut indices: Vec<_> =
+        crates.into_iter().flat_map(|krate| Vec::from(crate_symbols(db, krate.into()))).collect();
+
+    // Dependencies are only searched when asked for, in addition to the workspace.
+    if query.libs {
         LibraryRoots::get(db)
             .roots(db)
             .par_iter()
             .for_each_with(db.clone(), |snap, &root| _ = SymbolIndex::library_symbols(snap, root));
-        LibraryRoots::get(db)
-            .roots(db)
-            .iter()
-            .map(|&root| SymbolIndex::library_symbols(db, root))
-            .collect()
-    } else {
-        let mut crates = Vec::new();
-
-        for &root in LocalRoots::get(db).roots(db).iter() {
-            crates.extend(db.source_root_crates(root).iter().copied())
-        }
-        crates
-            .par_iter()
-            .for_each_with(db.clone(), |snap, &krate| _ = crate_symbols(snap, krate.into()));
-        crates.into_iter().flat_map(|krate| Vec::from(crate_symbols(db, krate.into()))).collect()
-    };
+        indices.extend(
+            LibraryRoots::get(db)
+                .roots(db)
+                .iter()
+                .map(|&root| SymbolIndex::library_symbols(db, root)),
+        );
+    }
 
     let mut res = vec![];
     query.search::<()>(&indices, |f| {
@@ -420,6 +435,9 @@ fn search_maps<'db, T>(
                     if self.exclude_imports && symbol.is_import {
                         continue;
                     }
+                    if !self.kinds.is_empty() && !self.kinds.contains(&symbol.def.into()) {
+                        continue;
+                    }
                     if self.mode.check(&self.query, self.case_sensitive, symbol_name)
                         && let Some(b) = cb(symbol).break_value()
                     {
@@ -171,9 +171,16 @@ pub fn for_type<'db>(
     /// If the name conflicts with existing generic parameters, it will try to
     /// resolve the conflict with `for_unique_generic_name`.
     pub fn for_impl_trait_as_generic(&mut self, ty: &ast::ImplTraitType) -> SmolStr {
+        // `?Sized` and lifetime bounds don't make for good names, use the first trait instead
         let c = ty
             .type_bound_list()
-            .and_then(|bounds| bounds.syntax().text().char_at(0.into()))
+            .and_then(|bounds| {
+                bounds.bounds().filter(|bound| bound.question_mark_token().is_none()).find_map(
+                    |bound| {
+                        bound.syntax().text().char_at(0.into()).filter(char::is_ascii_alphabetic)
+                    },
+                )
+            })
             .unwrap_or('T');
 
         self.suggest_name(&c.to_string())
@@ -156,7 +156,7 @@ mod tests {
     use expect_test::{Expect, expect};
     use itertools::Itertools;
     use parser::{Edition, SyntaxKind};
-    use syntax::{AstNode, SourceFile, SyntaxElement};
+    use syntax::{AstNode, NodeOrToken, SourceFile, SyntaxElement, TextRange, ast};
 
     use crate::text_edit::TextEdit;
 
@@ -506,6 +506,67 @@ fn main() {
         )
     }
 
+    #[test]
+    fn diff_applies_to_mutations() {
+        let fixtures = [
+            r#"
+use std::{collections::HashMap, fmt};
+
+fn main() {
+    let mut map = HashMap::new();
+    map.insert(1, "one");
+    match map.get(&1) {
+        Some(it) => println!("{it}"),
+        None => {}
+    }
+}
+"#,
+            r#"
+struct S<T> { field: T }
+
+impl<T: Clone> S<T> {
+    fn get(&self) -> T { self.field.clone() }
+}
+"#,
+        ];
+        for fixture in fixtures {
+            let file = SourceFile::parse(fixture, Edition::CURRENT).tree();
+            let splice = |range: TextRange, with: &str| {
+                let range = std::ops::Range::<usize>::from(range);
+                format!("{}{with}{}", &fixture[..range.start], &fixture[range.end..])
+            };
+            for element in file.syntax().descendants_with_tokens() {
+                let range = element.text_range();
+                match element {
+                    NodeOrToken::Node(node)
+                        if ast::Item::can_cast(node.kind())
+                            || ast::Stmt::can_cast(node.kind())
+                            || ast::MatchArm::can_cast(node.kind())
+                            || ast::UseTree::can_cast(node.kind()) =>
+                    {
+                        check_applies(fixture, &splice(range, ""));
+                        check_applies(fixture, &splice(range, &format!("{node}{node}")));
+                    }
+                    NodeOrToken::Token(token) if token.kind() == SyntaxKind::IDENT => {
+                        check_applies(fixture, &splice(range, "renamed"));
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    fn check_applies(from: &str, to: &str) {
+        let from_node = SourceFile::parse(from, Edition::CURRENT).tree().syntax().clone();
+        let to_node = SourceFile::parse(to, Edition::CURRENT).tree().syntax().clone();
+
+        let mut text = from.to_owned();
+        let mut text_edit = TextEdit::builder();
+        super::diff(&from_node, &to_node).into_text_edit(&mut text_edit);
+        text_edit.finish().apply(&mut text);
+        assert_eq!(text, to, "diff did not turn `from` to `to`");
+    }
+
     fn check_diff(from: &str, to: &str, expected_diff: Expect) {
         let from_node = SourceFile::parse(from, Edition::CURRENT).tree().syntax().clone();
         let to_node = SourceFile::parse(to, Edition::CURRENT).tree().syntax().clone();
@@ -550,10 +611,6 @@ fn check_diff(from: &str, to: &str, expected_diff: Expect) {
         );
         expected_diff.assert_eq(&actual);
 
-        let mut from = from.to_owned();
-        let mut text_edit = TextEdit::builder();
-        diff.into_text_edit(&mut text_edit);
-        text_edit.finish().apply(&mut from);
-        assert_eq!(&*from, to, "diff did not turn `from` to `to`");
+        check_applies(from, to);
     }
 }
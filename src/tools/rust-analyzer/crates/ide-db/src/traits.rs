@@ -114,12 +114,12 @@ fn assoc_item_of_trait(
 #[cfg(test)]
 mod tests {
     use expect_test::{Expect, expect};
-    use hir::{EditionedFileId, FilePosition, Semantics};
+    use hir::{EditionedFileId, FilePosition, HirDisplay, Semantics, sym};
     use span::Edition;
     use syntax::ast::{self, AstNode};
     use test_fixture::ChangeFixture;
 
-    use crate::RootDatabase;
+    use crate::{RootDatabase, famous_defs::FamousDefs};
 
     /// Creates analysis from a multi-file fixture, returns positions marked with $0.
     pub(crate) fn position(
@@ -166,6 +166,42 @@ fn check_missing_assoc(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect:
         expect.assert_eq(&actual);
     }
 
+    #[test]
+    fn iterator_impl_and_item() {
+        let (db, position) = position(
+            r#"
+//- minicore: iterator
+struct Counter;
+impl Iterator for Counter {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+struct NotAnIterator$0;
+"#,
+        );
+        hir::attach_db(&db, || {
+            let sema = Semantics::new(&db);
+            let file = sema.parse(position.file_id);
+            let structs = file
+                .syntax()
+                .descendants()
+                .filter_map(ast::Struct::cast)
+                .map(|it| sema.to_def(&it).unwrap())
+                .collect::<Vec<_>>();
+            let [counter, not_an_iterator] = structs[..] else { panic!("expected two structs") };
+            let krate = counter.module(&db).krate(&db);
+            let iterator = FamousDefs(&sema, krate).core_iter_Iterator().unwrap();
+            let item = iterator.type_alias(&db, &sym::Item).unwrap();
+
+            let counter = counter.ty(&db);
+            assert!(counter.impls_trait(&db, iterator, &[]));
+            let counter_item = counter.normalize_trait_assoc_type(&db, &[], item).unwrap();
+            assert_eq!(counter_item.display(&db, krate.to_display_target(&db)).to_string(), "u32");
+
+            assert!(!not_an_iterator.ty(&db).impls_trait(&db, iterator, &[]));
+        });
+    }
+
     #[test]
     fn resolve_trait() {
         check_trait(
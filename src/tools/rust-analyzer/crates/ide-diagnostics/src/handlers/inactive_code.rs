@@ -209,6 +209,25 @@ fn cfg_true_false() {
   #[cfg(any(not(true)), false)] fn inactive2() {}
 //^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ weak: code is inactive due to #[cfg] directives: true is enabled
 
+"#,
+        );
+    }
+
+    #[test]
+    fn crate_cfg_options() {
+        check(
+            r#"
+//- /lib.rs cfg:feature=yes
+  #[cfg(feature = "nope")] fn f() {}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ weak: code is inactive due to #[cfg] directives: feature = "nope" is disabled
+
+  #[cfg(feature = "yes")] fn g() {}
+"#,
+        );
+        check(
+            r#"
+//- /lib.rs cfg:feature=nope
+  #[cfg(feature = "nope")] fn f() {}
 "#,
         );
     }
@@ -829,6 +829,35 @@ fn f() {
         );
     }
 
+    #[test]
+    fn no_diagnostic_for_foreign_non_exhaustive_variants() {
+        check_diagnostics(
+            r#"
+//- /main.rs crate:main deps:dep
+#[non_exhaustive]
+struct Local { a: u32, b: u32 }
+
+fn f(s: dep::S, e: dep::E) {
+    let _ = dep::S { a: 0 };
+    let dep::S { a: _ } = s;
+    match e {
+        dep::E::V { a: _ } => {}
+    }
+    let _ = Local { a: 0 };
+          //^^^^^ 💡 error: missing structure fields:
+          //    | - b
+}
+//- /dep.rs crate:dep
+#[non_exhaustive]
+pub struct S { pub a: u32, pub b: u32 }
+pub enum E {
+    #[non_exhaustive]
+    V { a: u32, b: u32 },
+}
+"#,
+        );
+    }
+
     #[test]
     fn import_extern_crate_clash_with_inner_item() {
         // This is more of a resolver test, but doesn't really work with the hir_def testsuite.
@@ -1,7 +1,7 @@
 use hir::db::ExpandDatabase;
 use ide_db::{assists::Assist, base_db::AnchoredPathBuf, source_change::FileSystemEdit};
 use itertools::Itertools;
-use syntax::AstNode;
+use syntax::{AstNode, ast::HasName};
 
 use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, fix};
 
@@ -35,6 +35,10 @@ pub(crate) fn unresolved_module(
 fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedModule) -> Option<Vec<Assist>> {
     let root = ctx.sema.db.parse_or_expand(d.decl.file_id);
     let unresolved_module = d.decl.value.to_node(&root);
+    let initial_contents = match unresolved_module.name() {
+        Some(name) => format!("//! The `{}` module.\n", name.text()),
+        None => String::new(),
+    };
     Some(
         d.candidates
             .iter()
@@ -47,7 +51,7 @@ fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedModule) -> Option<Vec<
                             anchor: d.decl.file_id.original_file(ctx.sema.db).file_id(ctx.sema.db),
                             path: candidate.clone(),
                         },
-                        initial_contents: "".to_owned(),
+                        initial_contents: initial_contents.clone(),
                     }
                     .into(),
                     unresolved_module.syntax().text_range(),
@@ -289,6 +289,24 @@ fn caller2() {
         );
     }
 
+    #[test]
+    fn test_call_hierarchy_outgoing_through_closure() {
+        check_hierarchy(
+            false,
+            r#"
+//- /lib.rs
+fn callee() {}
+fn cal$0ler() {
+    let f = || callee();
+    f();
+}
+"#,
+            expect![["caller Function FileId(0) 15..64 18..24"]],
+            expect![[]],
+            expect!["callee Function FileId(0) 0..14 3..9 : FileId(0):44..50"],
+        );
+    }
+
     #[test]
     fn test_call_hierarchy_in_tests_mod() {
         check_hierarchy(
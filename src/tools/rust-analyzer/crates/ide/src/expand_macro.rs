@@ -15,6 +15,10 @@ pub struct ExpandedMacro {
     pub expansion: String,
 }
 
+/// How deep macro calls produced by expansions are expanded in turn. Calls nested deeper than
+/// this are shown unexpanded.
+const MAX_EXPANSION_DEPTH: usize = 64;
+
 // Feature: Expand Macro Recursively
 //
 // Shows the full macro expansion of the macro at the current caret position.
@@ -102,7 +106,14 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
             {
                 break (
                     def.name(db).display(db, file_id.edition(db)).to_string(),
-                    expand_macro_recur(&sema, &item, &mut error, &mut span_map, TextSize::new(0))?,
+                    expand_macro_recur(
+                        &sema,
+                        &item,
+                        &mut error,
+                        &mut span_map,
+                        TextSize::new(0),
+                        0,
+                    )?,
                     SyntaxKind::MACRO_ITEMS,
                 );
             }
@@ -119,6 +130,7 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
                         &mut error,
                         &mut span_map,
                         TextSize::new(0),
+                        0,
                     )?,
                     syntax_kind,
                 );
@@ -144,7 +156,15 @@ fn expand_macro_recur(
     error: &mut String,
     result_span_map: &mut SpanMap<SyntaxContext>,
     offset_in_original_node: TextSize,
+    depth: usize,
 ) -> Option<SyntaxNode> {
+    if depth > MAX_EXPANSION_DEPTH {
+        format_to!(
+            error,
+            "\nnot expanding macro calls nested more than {MAX_EXPANSION_DEPTH} levels deep"
+        );
+        return None;
+    }
     let ExpandResult { value: expanded, err } = match macro_call {
         item @ ast::Item::MacroCall(macro_call) => sema
             .expand_attr_macro(item)
@@ -164,7 +184,14 @@ fn expand_macro_recur(
         expanded.text_range().len(),
         &expansion_span_map,
     );
-    Some(expand(sema, expanded, error, result_span_map, u32::from(offset_in_original_node) as i32))
+    Some(expand(
+        sema,
+        expanded,
+        error,
+        result_span_map,
+        u32::from(offset_in_original_node) as i32,
+        depth,
+    ))
 }
 
 fn expand(
@@ -173,6 +200,7 @@ fn expand(
     error: &mut String,
     result_span_map: &mut SpanMap<SyntaxContext>,
     mut offset_in_original_node: i32,
+    depth: usize,
 ) -> SyntaxNode {
     let children = expanded.descendants().filter_map(ast::Item::cast);
     let mut replacements = Vec::new();
@@ -187,6 +215,7 @@ fn expand(
                 (offset_in_original_node + (u32::from(child.syntax().text_range().start()) as i32))
                     as u32,
             ),
+            depth + 1,
         ) {
             offset_in_original_node = offset_in_original_node
                 + (u32::from(new_node.text_range().len()) as i32)
@@ -381,6 +410,24 @@ fn b(){}"#]],
         );
     }
 
+    #[test]
+    fn macro_expand_infinite_recursion() {
+        check(
+            r#"
+macro_rules! foo {
+    () => { foo!(); }
+}
+f$0oo!();
+"#,
+            expect![[r#"
+                foo!
+                Expansion had errors:
+                not expanding macro calls nested more than 64 levels deep
+
+                foo!();"#]],
+        );
+    }
+
     #[test]
     fn macro_expand_multiple_lines() {
         check(
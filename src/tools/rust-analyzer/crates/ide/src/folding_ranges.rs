@@ -618,6 +618,34 @@ fn f2() {}
         )
     }
 
+    #[test]
+    fn fold_unbalanced_regions() {
+        check(
+            r#"
+// endregion
+<fold region>// region: outer
+fn f() <fold block>{
+    <fold region>// region: inner
+    let x = 1;
+    // endregion</fold>
+}</fold>
+// endregion</fold>
+// region: unclosed
+"#,
+        )
+    }
+
+    #[test]
+    fn single_line_blocks_are_not_folded() {
+        check(
+            r#"
+fn foo() {}
+struct S { a: i32 }
+impl S { fn bar(&self) { let _ = [1, 2]; } }
+"#,
+        )
+    }
+
     #[test]
     fn fold_consecutive_const() {
         check(
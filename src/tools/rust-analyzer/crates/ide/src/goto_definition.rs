@@ -236,18 +236,26 @@ fn try_lookup_include_path(
     }
     let path = token.value.value().ok()?;
 
+    let krate = sema.file_to_module_def(file_id).map(|it| it.krate(sema.db));
     let file_id = sema.db.resolve_path(AnchoredPath { anchor: file_id, path: &path })?;
     let size = sema.db.file_text(file_id).text(sema.db).len().try_into().ok()?;
-    Some(NavigationTarget {
+    let nav = NavigationTarget {
         file_id,
         full_range: TextRange::new(0.into(), size),
         name: hir::Symbol::intern(&path),
         alias: None,
+        crate_name: None,
+        is_workspace_member: true,
         focus_range: None,
         kind: None,
         container_name: None,
         description: None,
         docs: None,
+    };
+    // The included file belongs to the crate doing the including.
+    Some(match krate {
+        Some(krate) => nav.with_crate_origin(sema.db, krate),
+        None => nav,
     })
 }
 
@@ -1078,6 +1086,45 @@ fn bar() {
         );
     }
 
+    #[test]
+    fn goto_def_for_macro_defined_struct() {
+        check(
+            r#"
+//- /lib.rs
+macro_rules! define_struct {
+    ($name:ident) => {
+        pub struct $name { field: u32 }
+    };
+}
+
+define_struct!(Foo);
+             //^^^
+
+fn bar(foo: Fo$0o) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_def_for_synthetic_macro_defined_struct() {
+        check(
+            r#"
+//- /lib.rs
+macro_rules! define_struct {
+    () => {
+        pub struct Foo;
+                 //^^^
+    };
+}
+
+  define_struct!();
+//^^^^^^^^^^^^^^
+
+fn bar(foo: Fo$0o) {}
+"#,
+        );
+    }
+
     #[test]
     fn goto_definition_works_for_macro_inside_pattern() {
         check(
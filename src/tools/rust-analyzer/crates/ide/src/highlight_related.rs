@@ -987,6 +987,57 @@ fn foo() {
         );
     }
 
+    #[test]
+    fn test_hl_local_mut_borrow() {
+        check(
+            r#"
+struct S { field: u32 }
+fn foo() {
+    let mut bar$0 = S { field: 0 };
+         // ^^^ write
+    let r = &mut bar;
+              // ^^^ write
+    let f = &mut bar.field;
+              // ^^^ write
+    let s = &bar;
+          // ^^^ read
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_hl_shadowed_local() {
+        check(
+            r#"
+fn foo() {
+    let bar = 1;
+    let bar$0 = bar + 1;
+     // ^^^
+    bar;
+ // ^^^ read
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_hl_field_not_local() {
+        check(
+            r#"
+struct S { field$0: u32 }
+        // ^^^^^
+fn foo(s: &mut S) {
+    let field = 0;
+    s.field = field;
+   // ^^^^^ write
+    S { field };
+     // ^^^^^ read,field_shorthand
+}
+"#,
+        );
+    }
+
     #[test]
     fn test_multi_macro_usage() {
         check(
@@ -1642,7 +1693,7 @@ struct Struct { field: u32 }
 fn function(field: u32) {
           //^^^^^
     Struct { field$0 }
-           //^^^^^ read
+           //^^^^^ read,field_shorthand
 }
 "#,
         );
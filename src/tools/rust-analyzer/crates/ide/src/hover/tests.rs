@@ -11226,3 +11226,108 @@ fn foo(&self)
         "#]],
     );
 }
+
+#[test]
+fn hover_struct_defined_by_macro() {
+    check(
+        r#"
+macro_rules! define_struct {
+    ($name:ident) => {
+        pub struct $name { field: u32 }
+    };
+}
+
+define_struct!(Foo);
+
+fn f(foo: Fo$0o) {}
+"#,
+        expect![[r#"
+            *Foo*
+
+            ```rust
+            ra_test_fixture
+            ```
+
+            ```rust
+            pub struct Foo {
+                field: u32,
+            }
+            ```
+        "#]],
+    );
+    check_actions(
+        r#"
+macro_rules! define_struct {
+    ($name:ident) => {
+        pub struct $name { field: u32 }
+    };
+}
+
+define_struct!(Foo);
+
+fn f() {
+    let fo$0o = Foo { field: 0 };
+}
+"#,
+        expect![[r#"
+            [
+                GoToType(
+                    [
+                        HoverGotoTypeData {
+                            mod_path: "ra_test_fixture::Foo",
+                            nav: NavigationTarget {
+                                file_id: FileId(
+                                    0,
+                                ),
+                                full_range: 102..122,
+                                focus_range: 117..120,
+                                name: "Foo",
+                                kind: Struct,
+                                description: "pub struct Foo",
+                            },
+                        },
+                    ],
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn hover_synthetic_struct_defined_by_macro() {
+    check_actions(
+        r#"
+macro_rules! define_struct {
+    () => {
+        pub struct Foo;
+    };
+}
+
+define_struct!();
+
+fn f() {
+    let fo$0o = Foo;
+}
+"#,
+        expect![[r#"
+            [
+                GoToType(
+                    [
+                        HoverGotoTypeData {
+                            mod_path: "ra_test_fixture::Foo",
+                            nav: NavigationTarget {
+                                file_id: FileId(
+                                    0,
+                                ),
+                                full_range: 75..89,
+                                name: "Foo",
+                                kind: Struct,
+                                description: "pub struct Foo",
+                            },
+                        },
+                    ],
+                ),
+            ]
+        "#]],
+    );
+}
@@ -823,10 +823,7 @@ fn hint_iterator<'db>(
     }
 
     if ty.impls_trait(db, iter_trait, &[]) {
-        let assoc_type_item = iter_trait.items(db).into_iter().find_map(|item| match item {
-            hir::AssocItem::TypeAlias(alias) if alias.name(db) == sym::Item => Some(alias),
-            _ => None,
-        })?;
+        let assoc_type_item = iter_trait.type_alias(db, &sym::Item)?;
         if let Some(ty) = ty.normalize_trait_assoc_type(db, &[], assoc_type_item) {
             return Some((iter_trait, assoc_type_item, ty));
         }
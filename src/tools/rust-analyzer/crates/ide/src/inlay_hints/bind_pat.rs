@@ -978,6 +978,32 @@ fn main() {
         );
     }
 
+    #[test]
+    fn hint_truncation_with_unknown_argument() {
+        check_with_config(
+            InlayHintsConfig { max_length: Some(8), ..TEST_CONFIG },
+            r#"
+struct Smol<T>(T);
+
+fn main() {
+    let a = Smol(unresolved());
+      //^ Smol<{unknown}>
+    let b = Smol(Smol(unresolved()));
+      //^ Smol<Smol<…>>
+}"#,
+        );
+        check_with_config(
+            TEST_CONFIG,
+            r#"
+struct Smol<T>(T);
+
+fn main() {
+    let b = Smol(Smol(unresolved()));
+      //^ Smol<Smol<{unknown}>>
+}"#,
+        );
+    }
+
     #[test]
     fn edit_for_let_stmt() {
         check_edit(
@@ -21,13 +21,51 @@ pub(super) fn hints(
         return None;
     }
 
-    if matches!(expr, ast::Expr::RecordExpr(_)) {
+    // Chaining can be defined as an expression whose next sibling tokens are newline and dot
+    // Ignoring extra whitespace and comments
+    if !ends_chain_line(expr) {
         return None;
     }
 
-    let descended = sema.descend_node_into_attributes(expr.clone()).pop();
-    let desc_expr = descended.as_ref().unwrap_or(expr);
+    // The type shown after a line of the chain, if any.
+    let hint_ty = |expr: &ast::Expr| {
+        if matches!(expr, ast::Expr::RecordExpr(_)) {
+            return None;
+        }
+        let descended = sema.descend_node_into_attributes(expr.clone()).pop();
+        let ty = sema.type_of_expr(descended.as_ref().unwrap_or(expr))?.original;
+        if ty.is_unknown() {
+            return None;
+        }
+        if matches!(expr, ast::Expr::PathExpr(_))
+            && let Some(hir::Adt::Struct(st)) = ty.as_adt()
+            && st.fields(sema.db).is_empty()
+        {
+            return None;
+        }
+        Some(ty)
+    };
+    let ty = hint_ty(expr)?;
+    // Repeating the type of the previous line, as in builder chains, is just noise.
+    if previous_chain_lines(expr).find_map(|prev| hint_ty(&prev)).is_some_and(|prev| prev == ty) {
+        return None;
+    }
+    let label = label_of_ty(famous_defs, config, &ty, display_target)?;
+    acc.push(InlayHint {
+        range: expr.syntax().text_range(),
+        kind: InlayKind::Chaining,
+        label,
+        text_edit: None,
+        position: InlayHintPosition::After,
+        pad_left: true,
+        pad_right: false,
+        resolve_parent: Some(expr.syntax().text_range()),
+    });
+    Some(())
+}
 
+/// Whether `expr` is followed by a line break and then a `.`, ignoring comments.
+fn ends_chain_line(expr: &ast::Expr) -> bool {
     let mut tokens = expr
         .syntax()
         .siblings_with_tokens(Direction::Next)
@@ -37,40 +75,22 @@ pub(super) fn hints(
             SyntaxKind::COMMENT => false,
             _ => true,
         });
-
-    // Chaining can be defined as an expression whose next sibling tokens are newline and dot
-    // Ignoring extra whitespace and comments
-    let next = tokens.next()?.kind();
-    if next == SyntaxKind::WHITESPACE {
-        let mut next_next = tokens.next()?.kind();
-        while next_next == SyntaxKind::WHITESPACE {
-            next_next = tokens.next()?.kind();
-        }
-        if next_next == T![.] {
-            let ty = sema.type_of_expr(desc_expr)?.original;
-            if ty.is_unknown() {
-                return None;
-            }
-            if matches!(expr, ast::Expr::PathExpr(_))
-                && let Some(hir::Adt::Struct(st)) = ty.as_adt()
-                && st.fields(sema.db).is_empty()
-            {
-                return None;
-            }
-            let label = label_of_ty(famous_defs, config, &ty, display_target)?;
-            acc.push(InlayHint {
-                range: expr.syntax().text_range(),
-                kind: InlayKind::Chaining,
-                label,
-                text_edit: None,
-                position: InlayHintPosition::After,
-                pad_left: true,
-                pad_right: false,
-                resolve_parent: Some(expr.syntax().text_range()),
-            });
-        }
+    if tokens.next().map(|it| it.kind()) != Some(SyntaxKind::WHITESPACE) {
+        return false;
     }
-    Some(())
+    tokens.find(|it| it.kind() != SyntaxKind::WHITESPACE).is_some_and(|it| it.kind() == T![.])
+}
+
+/// The receivers further down the chain that end a line, closest first.
+fn previous_chain_lines(expr: &ast::Expr) -> impl Iterator<Item = ast::Expr> {
+    let receiver = |expr: &ast::Expr| match expr {
+        ast::Expr::MethodCallExpr(it) => it.receiver(),
+        ast::Expr::FieldExpr(it) => it.expr(),
+        ast::Expr::AwaitExpr(it) => it.expr(),
+        ast::Expr::TryExpr(it) => it.expr(),
+        _ => None,
+    };
+    std::iter::successors(receiver(expr), receiver).filter(ends_chain_line)
 }
 
 #[cfg(test)]
@@ -172,6 +192,51 @@ fn main() {
         );
     }
 
+    #[test]
+    fn chaining_hints_skip_repeated_types() {
+        check_expect(
+            InlayHintsConfig { type_hints: false, chaining_hints: true, ..DISABLED_CONFIG },
+            r#"
+struct Builder(u8);
+impl Builder {
+    fn new() -> Builder { Builder(0) }
+    fn a(self) -> Builder { self }
+    fn build(self) -> u8 { self.0 }
+}
+
+fn main() {
+    let x = Builder::new()
+        .a()
+        .a()
+        .build();
+}
+"#,
+            expect![[r#"
+                [
+                    (
+                        172..186,
+                        [
+                            InlayHintLabelPart {
+                                text: "Builder",
+                                linked_location: Some(
+                                    Computed(
+                                        FileRangeWrapper {
+                                            file_id: FileId(
+                                                0,
+                                            ),
+                                            range: 7..14,
+                                        },
+                                    ),
+                                ),
+                                tooltip: "",
+                            },
+                        ],
+                    ),
+                ]
+            "#]],
+        );
+    }
+
     #[test]
     fn chaining_hints_without_newlines() {
         check_chains(
@@ -625,25 +690,6 @@ fn main() {
                             },
                         ],
                     ),
-                    (
-                        145..185,
-                        [
-                            InlayHintLabelPart {
-                                text: "Struct",
-                                linked_location: Some(
-                                    Computed(
-                                        FileRangeWrapper {
-                                            file_id: FileId(
-                                                0,
-                                            ),
-                                            range: 7..13,
-                                        },
-                                    ),
-                                ),
-                                tooltip: "",
-                            },
-                        ],
-                    ),
                     (
                         145..168,
                         [
@@ -85,6 +85,11 @@ fn remove_newline(
     token: &SyntaxToken,
     offset: TextSize,
 ) {
+    if let Some(string) = ast::AnyString::cast(token.clone()) {
+        join_string_literal_lines(edit, &string, offset);
+        return;
+    }
+
     if token.kind() != WHITESPACE || token.text().bytes().filter(|&b| b == b'\n').count() != 1 {
         let n_spaces_after_line_break = {
             let suff = &token.text()[TextRange::new(
@@ -94,24 +99,8 @@ fn remove_newline(
             suff.bytes().take_while(|&b| b == b' ').count()
         };
 
-        let mut no_space = false;
-        if let Some(string) = ast::String::cast(token.clone()) {
-            if let Some(range) = string.open_quote_text_range() {
-                cov_mark::hit!(join_string_literal_open_quote);
-                no_space |= range.end() == offset;
-            }
-            if let Some(range) = string.close_quote_text_range() {
-                cov_mark::hit!(join_string_literal_close_quote);
-                no_space |= range.start()
-                    == offset
-                        + TextSize::of('\n')
-                        + TextSize::try_from(n_spaces_after_line_break).unwrap();
-            }
-        }
-
         let range = TextRange::at(offset, ((n_spaces_after_line_break + 1) as u32).into());
-        let replace_with = if no_space { "" } else { " " };
-        edit.replace(range, replace_with.to_owned());
+        edit.replace(range, " ".to_owned());
         return;
     }
 
@@ -202,6 +191,32 @@ fn remove_newline(
     edit.replace(token.text_range(), compute_ws(prev.kind(), next.kind()).to_owned());
 }
 
+/// Joins two lines of a string literal without changing its value: the line break becomes a `\n`
+/// escape, and a line continuation is removed together with the whitespace it skips. Raw strings
+/// can't express a line break on a single line, so they are left alone.
+fn join_string_literal_lines(
+    edit: &mut TextEditBuilder,
+    string: &ast::AnyString,
+    offset: TextSize,
+) {
+    if string.is_raw() {
+        cov_mark::hit!(join_raw_string_literal);
+        return;
+    }
+    let text = string.text();
+    let pos = usize::from(offset - string.syntax().text_range().start());
+    let backslashes = text[..pos].bytes().rev().take_while(|&b| b == b'\\').count();
+    if backslashes % 2 == 1 {
+        cov_mark::hit!(join_string_literal_continuation);
+        let rest = &text[pos..];
+        let skipped = rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+        let start = offset - TextSize::of('\\');
+        edit.delete(TextRange::new(start, offset + TextSize::from(skipped as u32)));
+        return;
+    }
+    edit.replace(TextRange::at(offset, TextSize::of('\n')), "\\n".to_owned());
+}
+
 fn join_single_expr_block(edit: &mut TextEditBuilder, token: &SyntaxToken) -> Option<()> {
     let block_expr = ast::BlockExpr::cast(token.parent_ancestors().nth(1)?)?;
     if !block_expr.is_standalone() {
@@ -899,74 +914,183 @@ fn foo() {
 
     #[test]
     fn join_string_literal() {
-        {
-            cov_mark::check!(join_string_literal_open_quote);
-            check_join_lines(
-                r#"
+        check_join_lines(
+            r#"
 fn main() {
     $0"
 hello
 ";
 }
 "#,
-                r#"
+            r#"
 fn main() {
-    $0"hello
+    $0"\nhello
 ";
 }
 "#,
-            );
-        }
-
-        {
-            cov_mark::check!(join_string_literal_close_quote);
-            check_join_lines(
-                r#"
+        );
+        check_join_lines(
+            r#"
 fn main() {
     $0"hello
 ";
 }
 "#,
-                r#"
+            r#"
 fn main() {
-    $0"hello";
+    $0"hello\n";
 }
 "#,
-            );
-            check_join_lines(
-                r#"
+        );
+        check_join_lines(
+            r#"
+fn main() {
+    "
+$0hello
+    world
+";
+}
+"#,
+            r#"
+fn main() {
+    "
+$0hello\n    world
+";
+}
+"#,
+        );
+        check_join_lines(
+            r#"
+fn main() {
+    $0b"hello
+world";
+}
+"#,
+            r#"
+fn main() {
+    $0b"hello\nworld";
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn join_string_literal_keeps_escaped_backslash() {
+        check_join_lines(
+            r#"
+fn main() {
+    $0"C:\\
+Users";
+}
+"#,
+            r#"
+fn main() {
+    $0"C:\\\nUsers";
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn join_string_literal_continuation() {
+        cov_mark::check!(join_string_literal_continuation);
+        check_join_lines(
+            r#"
+fn main() {
+    $0"hello \
+       world";
+}
+"#,
+            r#"
+fn main() {
+    $0"hello world";
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn join_raw_string_literal() {
+        cov_mark::check!(join_raw_string_literal);
+        check_join_lines(
+            r#"
 fn main() {
     $0r"hello
     ";
 }
 "#,
-                r#"
+            r#"
 fn main() {
-    $0r"hello";
+    $0r"hello
+    ";
 }
 "#,
-            );
-        }
+        );
+    }
 
-        check_join_lines(
+    #[test]
+    fn join_string_literal_selection() {
+        check_join_lines_sel(
             r#"
 fn main() {
-    "
-$0hello
-world
-";
+    $0"one
+two
+three"$0;
 }
 "#,
             r#"
 fn main() {
-    "
-$0hello world
-";
+    "one\ntwo\nthree";
 }
 "#,
         );
     }
 
+    #[test]
+    fn test_join_lines_use_tree_list_selection() {
+        check_join_lines_sel(
+            r"
+use foo::{$0
+    bar,
+    baz::{self, qux},
+$0};
+",
+            r"
+use foo::{bar, baz::{self, qux}};
+",
+        );
+        check_join_lines_sel(
+            r"
+use foo::{$0
+    bar,
+    baz,
+$0};
+",
+            r"
+use foo::{bar, baz};
+",
+        );
+    }
+
+    #[test]
+    fn join_lines_keeps_braces_of_if_branches() {
+        check_join_lines(
+            r"
+fn foo(c: bool) -> i32 {
+    if c { 1 } else {$0
+        2
+    }
+}
+",
+            r"
+fn foo(c: bool) -> i32 {
+    if c { 1 } else {$0 2
+    }
+}
+",
+        );
+    }
+
     #[test]
     fn join_last_line_empty() {
         check_join_lines(
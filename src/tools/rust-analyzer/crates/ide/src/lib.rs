@@ -41,6 +41,7 @@
 mod moniker;
 mod move_item;
 mod parent_module;
+mod qualified_name;
 mod references;
 mod rename;
 mod runnables;
@@ -512,9 +513,10 @@ pub fn symbol_search(&self, query: Query, limit: usize) -> Cancellable<Vec<Navig
         Cancelled::catch(|| {
             let symbols = symbol_index::world_symbols(&self.db, query);
             hir::attach_db(&self.db, || {
+                let sema = Semantics::new(&self.db);
                 symbols
                     .into_iter()
-                    .filter_map(|s| s.try_to_nav(&Semantics::new(&self.db)))
+                    .filter_map(|s| s.try_to_nav(&sema))
                     .take(limit)
                     .map(UpmappingResult::call_site)
                     .collect::<Vec<_>>()
@@ -583,6 +585,12 @@ pub fn moniker(
         self.with_db(|db| moniker::moniker(db, position))
     }
 
+    /// Returns the fully qualified name of the item under the cursor, like
+    /// `krate::module::Type::method`.
+    pub fn qualified_name_at(&self, position: FilePosition) -> Cancellable<Option<String>> {
+        self.with_db(|db| qualified_name::qualified_name_at(db, position))
+    }
+
     /// Returns URL(s) for the documentation of the symbol under the cursor.
     /// # Arguments
     /// * `position` - Position in the file.
@@ -23,6 +23,8 @@
     ast::{self, HasName},
 };
 
+use crate::qualified_name;
+
 /// `NavigationTarget` represents an element in the editor's UI which you can
 /// click on to navigate to a particular piece of code.
 ///
@@ -56,6 +58,14 @@ pub struct NavigationTarget {
     /// In addition to a `name` field, a `NavigationTarget` may also be aliased
     /// In such cases we want a `NavigationTarget` to be accessible by its alias
     pub alias: Option<Symbol>,
+    /// The name of the crate the target belongs to, if known.
+    pub crate_name: Option<Symbol>,
+    /// Whether the target is part of the workspace, as opposed to a dependency or the sysroot.
+    ///
+    /// Clients can use this to mark targets whose sources shouldn't be edited. Targets whose crate
+    /// isn't known are assumed to be part of the workspace, as they are built from the file the
+    /// user is looking at.
+    pub is_workspace_member: bool,
 }
 
 impl fmt::Debug for NavigationTarget {
@@ -110,6 +120,8 @@ fn upmap_from_ra_fixture(
             )?,
             docs: self.docs.upmap_from_ra_fixture(analysis, virtual_file_id, real_file_id)?,
             alias: self.alias.upmap_from_ra_fixture(analysis, virtual_file_id, real_file_id)?,
+            crate_name: self.crate_name,
+            is_workspace_member: self.is_workspace_member,
         })
     }
 }
@@ -142,6 +154,13 @@ pub fn focus_or_full_range(&self) -> TextRange {
         self.focus_range.unwrap_or(self.full_range)
     }
 
+    /// Fills in the crate the target's definition belongs to.
+    pub(crate) fn with_crate_origin(mut self, db: &RootDatabase, krate: Crate) -> NavigationTarget {
+        self.crate_name = krate.display_name(db).map(|it| it.crate_name().symbol().clone());
+        self.is_workspace_member = krate.origin(db).is_local();
+        self
+    }
+
     pub(crate) fn from_module_to_decl(
         db: &RootDatabase,
         module: hir::Module,
@@ -162,6 +181,7 @@ pub(crate) fn from_module_to_decl(
                         res.description = Some(
                             module.display(db, module.krate(db).to_display_target(db)).to_string(),
                         );
+                        res.container_name = qualified_name::module_container_name(db, module);
                         res
                     },
                 )
@@ -221,6 +241,8 @@ pub(crate) fn from_syntax(
             description: None,
             docs: None,
             alias: None,
+            crate_name: None,
+            is_workspace_member: true,
         }
     }
 }
@@ -232,6 +254,24 @@ fn try_to_nav(
     ) -> Option<UpmappingResult<NavigationTarget>> {
         let db = sema.db;
         let display_target = self.def.krate(db).to_display_target(db);
+        // Rendered once and shared between the call site and the definition site.
+        let description = match self.def {
+            hir::ModuleDef::Module(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::Function(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::Adt(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::Variant(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::Const(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::Static(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::Trait(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::TypeAlias(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::Macro(it) => Some(it.display(db, display_target).to_string()),
+            hir::ModuleDef::BuiltinType(_) => None,
+        };
+        let name = self
+            .is_alias
+            .then(|| self.def.name(db))
+            .flatten()
+            .map_or_else(|| self.name.clone(), |it| it.symbol().clone());
         Some(
             orig_range_with_focus_r(
                 db,
@@ -242,46 +282,18 @@ fn try_to_nav(
             .map(|(FileRange { file_id, range: full_range }, focus_range)| {
                 NavigationTarget {
                     file_id,
-                    name: self
-                        .is_alias
-                        .then(|| self.def.name(db))
-                        .flatten()
-                        .map_or_else(|| self.name.clone(), |it| it.symbol().clone()),
+                    name: name.clone(),
                     alias: self.is_alias.then(|| self.name.clone()),
+                    crate_name: None,
+                    is_workspace_member: true,
                     kind: Some(self.def.into()),
                     full_range,
                     focus_range,
                     container_name: self.container_name.clone(),
-                    description: match self.def {
-                        hir::ModuleDef::Module(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::Function(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::Adt(it) => Some(it.display(db, display_target).to_string()),
-                        hir::ModuleDef::Variant(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::Const(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::Static(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::Trait(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::TypeAlias(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::Macro(it) => {
-                            Some(it.display(db, display_target).to_string())
-                        }
-                        hir::ModuleDef::BuiltinType(_) => None,
-                    },
+                    description: description.clone(),
                     docs: None,
                 }
+                .with_crate_origin(db, self.def.krate(db))
             }),
         )
     }
@@ -292,7 +304,7 @@ fn try_to_nav(
         &self,
         sema: &Semantics<'_, RootDatabase>,
     ) -> Option<UpmappingResult<NavigationTarget>> {
-        match self {
+        let res = match self {
             Definition::Local(it) => Some(it.to_nav(sema.db)),
             Definition::Label(it) => it.try_to_nav(sema),
             Definition::Module(it) => Some(it.to_nav(sema.db)),
@@ -318,6 +330,10 @@ fn try_to_nav(
             | Definition::BuiltinAttr(_) => None,
             // FIXME: The focus range should be set to the helper declaration
             Definition::DeriveHelper(it) => it.derive().try_to_nav(sema),
+        };
+        match self.krate(sema.db) {
+            Some(krate) => res.map(|res| res.map(|nav| nav.with_crate_origin(sema.db, krate))),
+            None => res,
         }
     }
 }
@@ -351,12 +367,7 @@ fn container_name(self, db: &RootDatabase) -> Option<Symbol> {
 }
 
 fn container_name(db: &RootDatabase, t: impl HasContainer) -> Option<Symbol> {
-    match t.container(db) {
-        hir::ItemContainer::Trait(it) => Some(it.name(db).symbol().clone()),
-        // FIXME: Handle owners of blocks correctly here
-        hir::ItemContainer::Module(it) => it.name(db).map(|name| name.symbol().clone()),
-        _ => None,
-    }
+    qualified_name::container_name(db, t.container(db))
 }
 
 impl ToNavFromAst for hir::Function {
@@ -392,6 +403,9 @@ fn container_name(self, db: &RootDatabase) -> Option<Symbol> {
 }
 impl ToNavFromAst for hir::Variant {
     const KIND: SymbolKind = SymbolKind::Variant;
+    fn container_name(self, db: &RootDatabase) -> Option<Symbol> {
+        Some(self.parent_enum(db).name(db).symbol().clone())
+    }
 }
 impl ToNavFromAst for hir::Union {
     const KIND: SymbolKind = SymbolKind::Union;
@@ -445,6 +459,7 @@ fn to_nav(&self, db: &RootDatabase) -> UpmappingResult<NavigationTarget> {
         let InFile { file_id, value } = self.definition_source(db);
 
         let name = self.name(db).map(|it| it.symbol().clone()).unwrap_or_else(|| sym::underscore);
+        let container_name = qualified_name::module_container_name(db, *self);
         let (syntax, focus) = match &value {
             ModuleSource::SourceFile(node) => (node.syntax(), None),
             ModuleSource::Module(node) => (node.syntax(), node.name()),
@@ -453,13 +468,15 @@ fn to_nav(&self, db: &RootDatabase) -> UpmappingResult<NavigationTarget> {
 
         orig_range_with_focus(db, file_id, syntax, focus).map(
             |(FileRange { file_id, range: full_range }, focus_range)| {
-                NavigationTarget::from_syntax(
+                let mut res = NavigationTarget::from_syntax(
                     file_id,
                     name.clone(),
                     focus_range,
                     full_range,
                     SymbolKind::Module,
-                )
+                );
+                res.container_name = container_name.clone();
+                res.with_crate_origin(db, self.krate(db))
             },
         )
     }
@@ -479,6 +496,8 @@ fn try_to_nav(
         let db = sema.db;
         let InFile { file_id, value } = self.source(db)?;
         let derive_path = self.as_builtin_derive_path(db);
+        let container_name =
+            qualified_name::container_name(db, hir::ItemContainer::Module(self.module(db)));
 
         let (file_id, focus, syntax) = match &derive_path {
             Some(attr) => (attr.file_id.into(), None, attr.value.syntax()),
@@ -487,13 +506,15 @@ fn try_to_nav(
 
         Some(orig_range_with_focus(db, file_id, syntax, focus).map(
             |(FileRange { file_id, range: full_range }, focus_range)| {
-                NavigationTarget::from_syntax(
+                let mut res = NavigationTarget::from_syntax(
                     file_id,
                     sym::kw_impl,
                     focus_range,
                     full_range,
                     SymbolKind::Impl,
-                )
+                );
+                res.container_name = container_name.clone();
+                res
             },
         ))
     }
@@ -539,6 +560,7 @@ fn try_to_nav(
         let db = sema.db;
         let src = self.source(db)?;
         let krate = self.parent_def(db).module(db).krate(db);
+        let container_name = Some(self.parent_def(db).name(db).symbol().clone());
 
         let field_source = match &src.value {
             FieldSource::Named(it) => {
@@ -547,19 +569,22 @@ fn try_to_nav(
                         res.docs = self.docs(db).map(Documentation::into_owned);
                         res.description =
                             Some(self.display(db, krate.to_display_target(db)).to_string());
+                        res.container_name = container_name.clone();
                         res
                     },
                 )
             }
             FieldSource::Pos(it) => orig_range(db, src.file_id, it.syntax()).map(
                 |(FileRange { file_id, range: full_range }, focus_range)| {
-                    NavigationTarget::from_syntax(
+                    let mut res = NavigationTarget::from_syntax(
                         file_id,
                         Symbol::integer(self.index()),
                         focus_range,
                         full_range,
                         SymbolKind::Field,
-                    )
+                    );
+                    res.container_name = container_name.clone();
+                    res
                 },
             ),
         };
@@ -586,6 +611,8 @@ fn try_to_nav(
             )
             .map(|mut res| {
                 res.docs = self.docs(db).map(Documentation::into_owned);
+                res.container_name =
+                    qualified_name::container_name(db, hir::ItemContainer::Module(self.module(db)));
                 res
             }),
         )
@@ -655,6 +682,8 @@ fn to_nav(&self, db: &RootDatabase) -> UpmappingResult<NavigationTarget> {
                     file_id,
                     name,
                     alias: None,
+                    crate_name: None,
+                    is_workspace_member: true,
                     kind: Some(kind),
                     full_range,
                     focus_range,
@@ -662,6 +691,7 @@ fn to_nav(&self, db: &RootDatabase) -> UpmappingResult<NavigationTarget> {
                     description: None,
                     docs: None,
                 }
+                .with_crate_origin(db, local.module(db).krate(db))
             },
         )
     }
@@ -679,20 +709,26 @@ fn try_to_nav(
         sema: &Semantics<'_, RootDatabase>,
     ) -> Option<UpmappingResult<NavigationTarget>> {
         let db = sema.db;
+        let krate = self.module(db).krate(db);
         let InFile { file_id, value } = self.source(db)?;
         let name = self.name(db).symbol().clone();
 
         Some(orig_range_with_focus(db, file_id, value.syntax(), value.lifetime()).map(
-            |(FileRange { file_id, range: full_range }, focus_range)| NavigationTarget {
-                file_id,
-                name: name.clone(),
-                alias: None,
-                kind: Some(SymbolKind::Label),
-                full_range,
-                focus_range,
-                container_name: None,
-                description: None,
-                docs: None,
+            |(FileRange { file_id, range: full_range }, focus_range)| {
+                NavigationTarget {
+                    file_id,
+                    name: name.clone(),
+                    alias: None,
+                    crate_name: None,
+                    is_workspace_member: true,
+                    kind: Some(SymbolKind::Label),
+                    full_range,
+                    focus_range,
+                    container_name: None,
+                    description: None,
+                    docs: None,
+                }
+                .with_crate_origin(db, krate)
             },
         ))
     }
@@ -704,6 +740,7 @@ fn try_to_nav(
         sema: &Semantics<'_, RootDatabase>,
     ) -> Option<UpmappingResult<NavigationTarget>> {
         let db = sema.db;
+        let krate = self.module(db).krate(db);
         let InFile { file_id, value } = self.merge().source(db)?;
         let name = self.name(db).symbol().clone();
 
@@ -723,16 +760,21 @@ fn try_to_nav(
         let focus = value.as_ref().either(|it| it.name(), |it| it.name());
 
         Some(orig_range_with_focus(db, file_id, syntax, focus).map(
-            |(FileRange { file_id, range: full_range }, focus_range)| NavigationTarget {
-                file_id,
-                name: name.clone(),
-                alias: None,
-                kind: Some(SymbolKind::TypeParam),
-                full_range,
-                focus_range,
-                container_name: None,
-                description: None,
-                docs: None,
+            |(FileRange { file_id, range: full_range }, focus_range)| {
+                NavigationTarget {
+                    file_id,
+                    name: name.clone(),
+                    alias: None,
+                    crate_name: None,
+                    is_workspace_member: true,
+                    kind: Some(SymbolKind::TypeParam),
+                    full_range,
+                    focus_range,
+                    container_name: None,
+                    description: None,
+                    docs: None,
+                }
+                .with_crate_origin(db, krate)
             },
         ))
     }
@@ -753,20 +795,26 @@ fn try_to_nav(
         sema: &Semantics<'_, RootDatabase>,
     ) -> Option<UpmappingResult<NavigationTarget>> {
         let db = sema.db;
+        let krate = self.module(db).krate(db);
         let InFile { file_id, value } = self.source(db)?;
         let name = self.name(db).symbol().clone();
 
         Some(orig_range(db, file_id, value.syntax()).map(
-            |(FileRange { file_id, range: full_range }, focus_range)| NavigationTarget {
-                file_id,
-                name: name.clone(),
-                alias: None,
-                kind: Some(SymbolKind::LifetimeParam),
-                full_range,
-                focus_range,
-                container_name: None,
-                description: None,
-                docs: None,
+            |(FileRange { file_id, range: full_range }, focus_range)| {
+                NavigationTarget {
+                    file_id,
+                    name: name.clone(),
+                    alias: None,
+                    crate_name: None,
+                    is_workspace_member: true,
+                    kind: Some(SymbolKind::LifetimeParam),
+                    full_range,
+                    focus_range,
+                    container_name: None,
+                    description: None,
+                    docs: None,
+                }
+                .with_crate_origin(db, krate)
             },
         ))
     }
@@ -778,6 +826,7 @@ fn try_to_nav(
         sema: &Semantics<'_, RootDatabase>,
     ) -> Option<UpmappingResult<NavigationTarget>> {
         let db = sema.db;
+        let krate = self.module(db).krate(db);
         let InFile { file_id, value } = self.merge().source(db)?;
         let name = self.name(db).symbol().clone();
 
@@ -790,16 +839,21 @@ fn try_to_nav(
         };
 
         Some(orig_range_with_focus(db, file_id, value.syntax(), value.name()).map(
-            |(FileRange { file_id, range: full_range }, focus_range)| NavigationTarget {
-                file_id,
-                name: name.clone(),
-                alias: None,
-                kind: Some(SymbolKind::ConstParam),
-                full_range,
-                focus_range,
-                container_name: None,
-                description: None,
-                docs: None,
+            |(FileRange { file_id, range: full_range }, focus_range)| {
+                NavigationTarget {
+                    file_id,
+                    name: name.clone(),
+                    alias: None,
+                    crate_name: None,
+                    is_workspace_member: true,
+                    kind: Some(SymbolKind::ConstParam),
+                    full_range,
+                    focus_range,
+                    container_name: None,
+                    description: None,
+                    docs: None,
+                }
+                .with_crate_origin(db, krate)
             },
         ))
     }
@@ -811,20 +865,27 @@ fn try_to_nav(
         sema: &Semantics<'_, RootDatabase>,
     ) -> Option<UpmappingResult<NavigationTarget>> {
         let db = sema.db;
+        let krate = self.parent(db).module(db).krate(db);
         let InFile { file_id, value } = &self.source(db)?;
         let file_id = *file_id;
         Some(orig_range_with_focus(db, file_id, value.syntax(), value.name()).map(
-            |(FileRange { file_id, range: full_range }, focus_range)| NavigationTarget {
-                file_id,
-                name:
-                    self.name(db).map_or_else(|| sym::underscore.clone(), |it| it.symbol().clone()),
-                alias: None,
-                kind: Some(SymbolKind::Local),
-                full_range,
-                focus_range,
-                container_name: None,
-                description: None,
-                docs: None,
+            |(FileRange { file_id, range: full_range }, focus_range)| {
+                NavigationTarget {
+                    file_id,
+                    name: self
+                        .name(db)
+                        .map_or_else(|| sym::underscore.clone(), |it| it.symbol().clone()),
+                    alias: None,
+                    crate_name: None,
+                    is_workspace_member: true,
+                    kind: Some(SymbolKind::Local),
+                    full_range,
+                    focus_range,
+                    container_name: None,
+                    description: None,
+                    docs: None,
+                }
+                .with_crate_origin(db, krate)
             },
         ))
     }
@@ -1057,8 +1118,10 @@ fn orig_range_r(
 #[cfg(test)]
 mod tests {
     use expect_test::expect;
+    use ide_db::{MiniCore, items_locator::AssocSearchMode};
+    use itertools::Itertools;
 
-    use crate::{Query, fixture};
+    use crate::{GotoDefinitionConfig, Query, fixture};
 
     #[test]
     fn test_nav_for_symbol() {
@@ -1098,6 +1161,94 @@ fn foo() { enum FooInner { } }
         .assert_debug_eq(&navs);
     }
 
+    #[test]
+    fn test_nav_for_assoc_item_symbol() {
+        let (analysis, _) = fixture::file(
+            r#"
+struct Foo;
+impl Foo {
+    fn deserialize(&self) {}
+}
+"#,
+        );
+
+        let navs = analysis.symbol_search(Query::new("deserialize".to_owned()), !0).unwrap();
+        expect![[r#"
+            [
+                NavigationTarget {
+                    file_id: FileId(
+                        0,
+                    ),
+                    full_range: 27..51,
+                    focus_range: 30..41,
+                    name: "deserialize",
+                    kind: Function,
+                    container_name: "Foo",
+                    description: "fn deserialize(&self)",
+                },
+            ]
+        "#]]
+        .assert_debug_eq(&navs);
+
+        let mut query = Query::new("deserialize".to_owned());
+        query.assoc_search_mode(AssocSearchMode::Exclude);
+        let navs = analysis.symbol_search(query, !0).unwrap();
+        assert!(navs.is_empty());
+    }
+
+    #[test]
+    fn test_nav_crate_origin() {
+        let (analysis, _) = fixture::file(
+            r#"
+//- /main.rs crate:main deps:dep
+pub struct InMain;
+//- /dep/lib.rs crate:dep library
+pub struct InDep;
+"#,
+        );
+
+        let mut query = Query::new("In".to_owned());
+        query.libs();
+        let navs = analysis.symbol_search(query, !0).unwrap();
+        let origins = navs
+            .iter()
+            .map(|nav| {
+                (
+                    nav.name.to_string(),
+                    nav.crate_name.as_ref().map(|it| it.to_string()),
+                    nav.is_workspace_member,
+                )
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            origins,
+            [
+                ("InDep".to_owned(), Some("dep".to_owned()), false),
+                ("InMain".to_owned(), Some("main".to_owned()), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nav_from_syntax_is_workspace_member() {
+        let (analysis, position) = fixture::position(
+            r#"
+fn main() {
+    loop {
+        break$0;
+    }
+}
+"#,
+        );
+
+        let config = GotoDefinitionConfig { minicore: MiniCore::default() };
+        let navs = analysis.goto_definition(position, &config).unwrap().unwrap().info;
+        let [nav] = &*navs else { panic!("expected a single target, got {navs:?}") };
+        assert_eq!(nav.crate_name, None);
+        assert!(nav.is_workspace_member);
+    }
+
     #[test]
     fn test_world_symbols_are_case_sensitive() {
         let (analysis, _) = fixture::file(
@@ -1111,6 +1262,30 @@ fn foo() {}
         assert_eq!(navs.len(), 2)
     }
 
+    #[test]
+    fn test_world_symbols_across_files() {
+        let mut fixture = String::from("//- /lib.rs\nmod a;\nmod b;\n");
+        for (file, prefix) in [("lib", ""), ("a", "//- /a.rs\n"), ("b", "//- /b.rs\n")] {
+            fixture.push_str(prefix);
+            for i in 0..34 {
+                fixture.push_str(&format!("fn sym_{file}_{i}() {{}}\n"));
+            }
+        }
+
+        // Index the files on the thread doing the search, so that the probe sees every parse.
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let navs = pool.install(|| {
+            let (analysis, _) = fixture::file(&fixture);
+            cov_mark::check_count!(parse_file, 3);
+            analysis.symbol_search(Query::new("sym_".to_owned()), !0).unwrap()
+        });
+        assert_eq!(navs.len(), 102);
+        assert_eq!(navs.iter().map(|nav| nav.file_id).unique().count(), 3);
+        for nav in &navs {
+            assert_eq!(nav.description.as_deref(), Some(&*format!("fn {}()", nav.name)));
+        }
+    }
+
     #[test]
     fn test_ensure_hidden_symbols_are_not_returned() {
         let (analysis, _) = fixture::file(
@@ -0,0 +1,371 @@
+//! Computes fully qualified names like `krate::module::Type::method` by walking
+//! the ownership chain of a definition.
+//!
+//! The same chain is used to fill `NavigationTarget::container_name`.
+
+use hir::{
+    Crate, Function, HasContainer, HirDisplay, Impl, InFile, ItemContainer, Module, ModuleSource,
+    Semantics, Symbol, VariantDef,
+};
+use ide_db::{
+    FilePosition, RootDatabase,
+    defs::{Definition, IdentClass},
+    helpers::pick_best_token,
+};
+use span::Edition;
+use syntax::{AstNode, AstPtr, SyntaxKind::*, T, ast};
+
+// Feature: Copy Qualified Name
+//
+// Copies the fully qualified name of the item under the cursor, such as
+// `krate::module::Type::method`, to the clipboard.
+//
+// | Editor  | Action Name |
+// |---------|-------------|
+// | VS Code | **rust-analyzer: Copy Qualified Name** |
+pub(crate) fn qualified_name_at(
+    db: &RootDatabase,
+    FilePosition { file_id, offset }: FilePosition,
+) -> Option<String> {
+    let sema = &Semantics::new(db);
+    let file = sema.parse_guess_edition(file_id).syntax().clone();
+    let original_token = pick_best_token(file.token_at_offset(offset), |kind| match kind {
+        IDENT | INT_NUMBER | LIFETIME_IDENT | T![self] | T![super] | T![crate] | T![Self] => 2,
+        kind if kind.is_trivia() => 0,
+        _ => 1,
+    })?;
+    sema.descend_into_macros_exact(original_token)
+        .into_iter()
+        .filter_map(|token| IdentClass::classify_token(sema, &token))
+        .flat_map(IdentClass::definitions_no_ops)
+        .find_map(|def| qualified_name(db, def))
+}
+
+/// Renders the fully qualified name of `def`, or `None` for definitions that
+/// are not items, like locals and generic parameters.
+pub(crate) fn qualified_name(db: &RootDatabase, def: Definition) -> Option<String> {
+    let edition = def.krate(db).map_or(Edition::CURRENT, |krate| krate.edition(db));
+    Some(def_segments(db, def, edition)?.join("::"))
+}
+
+/// Returns the name of the closest named container of an item: the trait,
+/// the impl's self type, the module, or the function owning the block the
+/// item is declared in. Items at the crate root have no container name.
+pub(crate) fn container_name(db: &RootDatabase, container: ItemContainer) -> Option<Symbol> {
+    match container {
+        ItemContainer::Trait(it) => Some(it.name(db).symbol().clone()),
+        ItemContainer::Impl(it) => match (it.trait_(db), it.self_ty(db).as_adt()) {
+            (None, Some(adt)) => Some(adt.name(db).symbol().clone()),
+            _ => {
+                let edition = it.module(db).krate(db).edition(db);
+                Some(Symbol::intern(&impl_segment(db, it, edition)))
+            }
+        },
+        ItemContainer::Module(it) => module_name(db, it),
+        ItemContainer::ExternBlock(it) => module_name(db, it.module(db)),
+        ItemContainer::Crate(_) => None,
+    }
+}
+
+/// Like [`container_name`], but for modules themselves.
+pub(crate) fn module_container_name(db: &RootDatabase, module: Module) -> Option<Symbol> {
+    module.parent(db).and_then(|parent| module_name(db, parent))
+}
+
+fn def_segments(db: &RootDatabase, def: Definition, edition: Edition) -> Option<Vec<String>> {
+    let mut segments = match def {
+        Definition::Module(it) => return Some(module_segments(db, it, edition)),
+        Definition::Crate(it) => return Some(vec![crate_name(db, it)]),
+        Definition::SelfType(it) => {
+            let mut segments = module_segments(db, it.module(db), edition);
+            segments.push(impl_segment(db, it, edition));
+            return Some(segments);
+        }
+        Definition::BuiltinType(it) => {
+            return Some(vec![it.name().display(db, edition).to_string()]);
+        }
+        Definition::Field(it) => {
+            let parent = match it.parent_def(db) {
+                VariantDef::Struct(it) => Definition::Adt(it.into()),
+                VariantDef::Union(it) => Definition::Adt(it.into()),
+                VariantDef::Variant(it) => Definition::Variant(it),
+            };
+            def_segments(db, parent, edition)?
+        }
+        Definition::Variant(it) => {
+            def_segments(db, Definition::Adt(it.parent_enum(db).into()), edition)?
+        }
+        Definition::Macro(it) => module_segments(db, it.module(db), edition),
+        Definition::Adt(it) => module_segments(db, it.module(db), edition),
+        Definition::Function(it) => container_segments(db, it.container(db), edition)?,
+        Definition::Const(it) => container_segments(db, it.container(db), edition)?,
+        Definition::Static(it) => container_segments(db, it.container(db), edition)?,
+        Definition::TypeAlias(it) => container_segments(db, it.container(db), edition)?,
+        Definition::Trait(it) => container_segments(db, it.container(db), edition)?,
+        Definition::ExternCrateDecl(it) => container_segments(db, it.container(db), edition)?,
+        _ => return None,
+    };
+    segments.push(def.name(db)?.display(db, edition).to_string());
+    Some(segments)
+}
+
+fn container_segments(
+    db: &RootDatabase,
+    container: ItemContainer,
+    edition: Edition,
+) -> Option<Vec<String>> {
+    let segments = match container {
+        ItemContainer::Trait(it) => def_segments(db, Definition::Trait(it), edition)?,
+        ItemContainer::Impl(it) => def_segments(db, Definition::SelfType(it), edition)?,
+        ItemContainer::Module(it) => module_segments(db, it, edition),
+        ItemContainer::ExternBlock(it) => module_segments(db, it.module(db), edition),
+        ItemContainer::Crate(it) => vec![crate_name(db, it)],
+    };
+    Some(segments)
+}
+
+fn module_segments(db: &RootDatabase, module: Module, edition: Edition) -> Vec<String> {
+    let Some(parent) = module.parent(db) else {
+        return vec![crate_name(db, module.krate(db))];
+    };
+    match module.name(db) {
+        Some(name) => {
+            let mut segments = module_segments(db, parent, edition);
+            segments.push(name.display(db, edition).to_string());
+            segments
+        }
+        // Blocks are unnamed, so they are replaced by the function owning them,
+        // or skipped entirely when there is none (e.g. in a `const` body).
+        None => block_owner(db, module)
+            .and_then(|owner| def_segments(db, Definition::Function(owner), edition))
+            .unwrap_or_else(|| module_segments(db, parent, edition)),
+    }
+}
+
+fn module_name(db: &RootDatabase, module: Module) -> Option<Symbol> {
+    if let Some(name) = module.name(db) {
+        return Some(name.symbol().clone());
+    }
+    let parent = module.parent(db)?;
+    match block_owner(db, module) {
+        Some(owner) => Some(owner.name(db).symbol().clone()),
+        None => module_name(db, parent),
+    }
+}
+
+/// Renders an impl as its self type, or as `<Type as Trait>` for trait impls.
+///
+/// Generic arguments of the self type are omitted, as in `Vec::new`.
+fn impl_segment(db: &RootDatabase, impl_: Impl, edition: Edition) -> String {
+    let self_ty = impl_.self_ty(db);
+    let self_ty = match self_ty.as_adt() {
+        Some(adt) => adt.name(db).display(db, edition).to_string(),
+        None => {
+            let display_target = impl_.module(db).krate(db).to_display_target(db);
+            self_ty.display(db, display_target).to_string()
+        }
+    };
+    match impl_.trait_(db) {
+        Some(trait_) => format!("<{self_ty} as {}>", trait_.name(db).display(db, edition)),
+        None => self_ty,
+    }
+}
+
+/// Finds the function whose body contains the given block module.
+fn block_owner(db: &RootDatabase, module: Module) -> Option<Function> {
+    let InFile { file_id, value: ModuleSource::BlockExpr(block) } = module.definition_source(db)
+    else {
+        return None;
+    };
+    let sema = Semantics::new(db);
+    let root = sema.parse_or_expand(file_id);
+    let block = AstPtr::new(&block).to_node(&root);
+    let fn_ = block.syntax().ancestors().find_map(ast::Fn::cast)?;
+    sema.to_def(&fn_)
+}
+
+fn crate_name(db: &RootDatabase, krate: Crate) -> String {
+    krate.display_name(db).map_or_else(|| "crate".to_owned(), |it| it.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{Expect, expect};
+
+    use crate::fixture;
+
+    fn check(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+        let (analysis, position) = fixture::position(ra_fixture);
+        let name = analysis.qualified_name_at(position).unwrap();
+        expect.assert_eq(name.as_deref().unwrap_or("<none>"));
+    }
+
+    #[test]
+    fn module_items() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+mod bar {
+    pub mod baz {
+        pub struct S$0;
+    }
+}
+"#,
+            expect!["foo::bar::baz::S"],
+        );
+        check(
+            r#"
+//- /lib.rs crate:foo
+mod bar$0 {}
+"#,
+            expect!["foo::bar"],
+        );
+    }
+
+    #[test]
+    fn inherent_impl_items() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+mod bar {
+    struct S;
+    impl S {
+        fn method$0(&self) {}
+    }
+}
+"#,
+            expect!["foo::bar::S::method"],
+        );
+    }
+
+    #[test]
+    fn trait_impl_items() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+struct S<T>(T);
+trait Trait { const C: u32; }
+impl<T> Trait for S<T> {
+    const C$0: u32 = 0;
+}
+"#,
+            expect!["foo::<S as Trait>::C"],
+        );
+    }
+
+    #[test]
+    fn trait_items() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+mod bar {
+    trait Trait {
+        type Assoc$0;
+    }
+}
+"#,
+            expect!["foo::bar::Trait::Assoc"],
+        );
+    }
+
+    #[test]
+    fn fields_and_variants() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+enum E { V { field$0: u8 } }
+"#,
+            expect!["foo::E::V::field"],
+        );
+        check(
+            r#"
+//- /lib.rs crate:foo
+enum E { V$0 }
+"#,
+            expect!["foo::E::V"],
+        );
+    }
+
+    #[test]
+    fn items_in_nested_functions() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+struct S;
+impl S {
+    fn outer() {
+        {
+            fn inner() {
+                struct Local$0;
+            }
+        }
+    }
+}
+"#,
+            expect!["foo::S::outer::inner::Local"],
+        );
+    }
+
+    #[test]
+    fn items_in_const_blocks() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+mod bar {
+    const _: () = {
+        struct Hidden$0;
+    };
+}
+"#,
+            expect!["foo::bar::Hidden"],
+        );
+    }
+
+    #[test]
+    fn items_in_macro_expansions() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+macro_rules! define {
+    ($name:ident) => { pub struct $name; };
+}
+mod bar {
+    define!(Generated);
+}
+fn f(_: bar::Generated$0) {}
+"#,
+            expect!["foo::bar::Generated"],
+        );
+    }
+
+    #[test]
+    fn usages_resolve_to_the_definition() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+mod bar {
+    pub struct S;
+    impl S {
+        pub fn new() -> S { S }
+    }
+}
+fn main() {
+    bar::S::new$0();
+}
+"#,
+            expect!["foo::bar::S::new"],
+        );
+    }
+
+    #[test]
+    fn locals_have_no_qualified_name() {
+        check(
+            r#"
+fn main() {
+    let x$0 = 92;
+}
+"#,
+            expect!["<none>"],
+        );
+    }
+}
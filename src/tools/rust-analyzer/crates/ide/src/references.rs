@@ -517,7 +517,7 @@ fn test() {
 }
 "#,
             expect![[r#"
-                f Field FileId(0) 11..17 11..12
+                f Field FileId(0) 11..17 11..12 S
 
                 FileId(0) 61..62 read test
                 FileId(0) 76..77 write test
@@ -666,7 +666,7 @@ fn main() {
 }
 "#,
             expect![[r#"
-                A Variant FileId(0) 15..27 15..16
+                A Variant FileId(0) 15..27 15..16 Foo
 
                 FileId(0) 95..96
             "#]],
@@ -688,7 +688,7 @@ fn main() {
 }
 "#,
             expect![[r#"
-                A Variant FileId(0) 15..21 15..16
+                A Variant FileId(0) 15..21 15..16 Foo
 
                 FileId(0) 89..90
             "#]],
@@ -849,7 +849,7 @@ fn main(s: Foo) {
 }
 "#,
             expect![[r#"
-                spam Field FileId(0) 17..30 21..25
+                spam Field FileId(0) 17..30 21..25 Foo
 
                 FileId(0) 67..71 read
             "#]],
@@ -866,13 +866,30 @@ fn f$0(&self) {  }
 }
 "#,
             expect![[r#"
-                f Function FileId(0) 27..43 30..31
+                f Function FileId(0) 27..43 30..31 Foo
 
                 (no references)
             "#]],
         );
     }
 
+    #[test]
+    fn test_find_all_refs_item_in_fn_body() {
+        check(
+            r#"
+fn outer() {
+    struct Inn$0er;
+    let _: Inner;
+}
+"#,
+            expect![[r#"
+                Inner Struct FileId(0) 17..30 24..29 outer
+
+                FileId(0) 42..47
+            "#]],
+        );
+    }
+
     #[test]
     fn test_find_all_refs_enum_var_name() {
         check(
@@ -884,7 +901,7 @@ enum Foo {
 }
 "#,
             expect![[r#"
-                B Variant FileId(0) 22..23 22..23
+                B Variant FileId(0) 22..23 22..23 Foo
 
                 (no references)
             "#]],
@@ -902,7 +919,7 @@ enum Foo {
 }
 "#,
             expect![[r#"
-                field Field FileId(0) 26..35 26..31
+                field Field FileId(0) 26..35 26..31 B
 
                 (no references)
             "#]],
@@ -952,7 +969,7 @@ fn stuff() -> Self {
 }
 "#,
             expect![[r#"
-                Assoc TypeAlias FileId(0) 92..108 97..102
+                Assoc TypeAlias FileId(0) 92..108 97..102 <() as TestTrait>
 
                 FileId(0) 31..36
             "#]],
@@ -1186,7 +1203,7 @@ fn foo() {
 }
 "#,
             expect![[r#"
-                f Field FileId(0) 15..21 15..16
+                f Field FileId(0) 15..21 15..16 S
 
                 FileId(0) 55..56 read
                 FileId(0) 68..69 write
@@ -1228,7 +1245,7 @@ fn main() {
 }
 "#,
             expect![[r#"
-                new Function FileId(0) 54..81 61..64
+                new Function FileId(0) 54..81 61..64 Foo
 
                 FileId(0) 126..129
             "#]],
@@ -1273,9 +1290,30 @@ fn f(s: S) {
 }
 "#,
             expect![[r#"
-                field Field FileId(0) 15..24 15..20
+                field Field FileId(0) 15..24 15..20 S
+
+                FileId(0) 68..73 read field_shorthand
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_find_all_refs_field_categories() {
+        check(
+            r#"
+struct S { x$0: i32 }
+fn f(mut s: S, x: i32) {
+    s.x = 1;
+    let _ = s.x;
+    let _ = S { x };
+}
+"#,
+            expect![[r#"
+                x Field FileId(0) 11..17 11..12 S
 
-                FileId(0) 68..73 read
+                FileId(0) 51..52 write
+                FileId(0) 72..73 read
+                FileId(0) 91..92 read field_shorthand
             "#]],
         );
     }
@@ -1297,9 +1335,9 @@ fn f(e: En) {
 }
 "#,
             expect![[r#"
-                field Field FileId(0) 32..41 32..37
+                field Field FileId(0) 32..41 32..37 Variant
 
-                FileId(0) 102..107 read
+                FileId(0) 102..107 read field_shorthand
             "#]],
         );
     }
@@ -1321,7 +1359,7 @@ fn f() -> m::En {
 }
 "#,
             expect![[r#"
-                field Field FileId(0) 56..65 56..61
+                field Field FileId(0) 56..65 56..61 Variant
 
                 FileId(0) 125..130 read
             "#]],
@@ -1794,7 +1832,7 @@ fn foo(self) {
 
 "#,
             expect![[r#"
-                Bar Variant FileId(0) 11..16 11..14
+                Bar Variant FileId(0) 11..16 11..14 Foo
 
                 FileId(0) 89..92
             "#]],
@@ -1932,7 +1970,7 @@ macro_rules! foo$0 {
 lib::foo!();
 "#,
             expect![[r#"
-                foo Macro FileId(1) 0..61 29..32
+                foo Macro FileId(1) 0..61 29..32 qux
 
                 FileId(0) 46..49 import
                 FileId(2) 0..3
@@ -2229,7 +2267,7 @@ fn f<T: Trait>() {
 }
 "#,
             expect![[r#"
-                CONST Const FileId(0) 65..88 71..76
+                CONST Const FileId(0) 65..88 71..76 <() as Trait>
 
                 FileId(0) 183..188
             "#]],
@@ -2255,7 +2293,7 @@ fn f<T: Trait>() {
 }
 "#,
             expect![[r#"
-                TypeAlias TypeAlias FileId(0) 61..81 66..75
+                TypeAlias TypeAlias FileId(0) 61..81 66..75 <() as Trait>
 
                 FileId(0) 23..32
                 FileId(0) 117..126
@@ -2284,7 +2322,7 @@ fn f<T: Trait>() {
 }
 "#,
             expect![[r#"
-                function Function FileId(0) 62..78 65..73
+                function Function FileId(0) 62..78 65..73 <() as Trait>
 
                 FileId(0) 166..174
             "#]],
@@ -2314,7 +2352,7 @@ fn f<T: Trait>() {
 }
 "#,
             expect![[r#"
-                CONST Const FileId(0) 65..88 71..76
+                CONST Const FileId(0) 65..88 71..76 <() as Trait>
 
                 FileId(0) 183..188
             "#]],
@@ -2369,7 +2407,7 @@ fn f<T: Trait>() {
 }
 "#,
             expect![[r#"
-                function Function FileId(0) 62..78 65..73
+                function Function FileId(0) 62..78 65..73 <() as Trait>
 
                 FileId(0) 166..174
             "#]],
@@ -2419,7 +2457,7 @@ fn method(&self) -> u8 {
 fn method() {}
 "#,
             expect![[r#"
-                method Field FileId(0) 60..70 60..66
+                method Field FileId(0) 60..70 60..66 Bar
 
                 FileId(0) 136..142 read
             "#]],
@@ -2442,7 +2480,7 @@ fn method$0(&self) -> u8 {
 fn method() {}
 "#,
             expect![[r#"
-                method Function FileId(0) 98..148 101..107
+                method Function FileId(0) 98..148 101..107 <Bar as Foo>
 
                 (no references)
             "#]],
@@ -2465,7 +2503,7 @@ fn method(&self) -> u8 {
 fn method() {}
 "#,
             expect![[r#"
-                method Field FileId(0) 60..70 60..66
+                method Field FileId(0) 60..70 60..66 Bar
 
                 FileId(0) 136..142 read
             "#]],
@@ -2814,7 +2852,7 @@ fn baz() {
 }
         "#,
             expect![[r#"
-                new Function FileId(0) 27..38 30..33
+                new Function FileId(0) 27..38 30..33 Foo
 
                 FileId(0) 62..65
                 FileId(0) 91..94
@@ -2864,7 +2902,7 @@ impl Trait for Other {
 pub(in super::super) type Baz = Itself<crate::Foo>;
         "#,
             expect![[r#"
-                new Function FileId(0) 42..53 45..48
+                new Function FileId(0) 42..53 45..48 Foo
 
                 FileId(0) 83..86
                 FileId(1) 40..43
@@ -2903,7 +2941,7 @@ impl super::Foo {
 fn foo() { <super::Foo as super::Trait>::Assoc::new(); }
                 "#,
             expect![[r#"
-                new Function FileId(0) 40..51 43..46
+                new Function FileId(0) 40..51 43..46 Foo
 
                 FileId(0) 73..76
                 FileId(0) 195..198
@@ -2929,7 +2967,7 @@ impl Foo {
 }
             "#,
             expect![[r#"
-                new Function FileId(0) 27..38 30..33
+                new Function FileId(0) 27..38 30..33 Foo
 
                 FileId(0) 68..71
                 FileId(0) 123..126
@@ -2960,7 +2998,7 @@ fn bar() {
 }
             "#,
             expect![[r#"
-                new Function FileId(0) 27..38 30..33
+                new Function FileId(0) 27..38 30..33 Foo
 
                 FileId(0) 188..191
                 FileId(0) 233..236
@@ -3000,7 +3038,7 @@ fn bar() {
 }
                 "#,
             expect![[r#"
-                new Function FileId(0) 27..38 30..33
+                new Function FileId(0) 27..38 30..33 Foo
 
                 (no references)
             "#]],
@@ -3026,7 +3064,7 @@ fn bar() {
 }
                 "#,
             expect![[r#"
-                new Function FileId(0) 27..38 30..33
+                new Function FileId(0) 27..38 30..33 Foo
 
                 FileId(0) 131..134
             "#]],
@@ -75,9 +75,14 @@ pub(crate) fn prepare_rename(
     let source_file = sema.parse_guess_edition(position.file_id);
     let syntax = source_file.syntax();
 
+    // Non-local items can still be renamed inside a use tree, by aliasing them.
+    let in_use_tree = aliasable_use_tree(syntax, position.offset).is_some();
     let res = find_definitions(&sema, syntax, position, &Name::new_symbol_root(sym::underscore))?
         .filter(|(_, _, def, _, _)| def.range_for_rename(&sema).is_some())
-        .map(|(frange, kind, _, _, _)| {
+        .map(|(frange, kind, def, _, _)| {
+            if !in_use_tree && def.krate(db).is_some_and(|krate| !krate.origin(db).is_local()) {
+                bail!("Cannot rename a non-local definition");
+            }
             always!(
                 frange.range.contains_inclusive(position.offset)
                     && frange.file_id == position.file_id
@@ -220,15 +225,7 @@ fn alias_fallback(
     FilePosition { file_id, offset }: FilePosition,
     new_name: &str,
 ) -> Option<SourceChange> {
-    let use_tree = syntax
-        .token_at_offset(offset)
-        .flat_map(|syntax| syntax.parent_ancestors())
-        .find_map(ast::UseTree::cast)?;
-
-    let last_path_segment = use_tree.path()?.segments().last()?.name_ref()?;
-    if !last_path_segment.syntax().text_range().contains_inclusive(offset) {
-        return None;
-    };
+    let use_tree = aliasable_use_tree(syntax, offset)?;
 
     let mut builder = SourceChangeBuilder::new(file_id);
 
@@ -246,6 +243,21 @@ fn alias_fallback(
     Some(builder.finish())
 }
 
+/// Returns the use tree whose last path segment is at `offset`, which can be renamed by giving
+/// it an alias instead of renaming the item it imports.
+fn aliasable_use_tree(syntax: &SyntaxNode, offset: TextSize) -> Option<ast::UseTree> {
+    let use_tree = syntax
+        .token_at_offset(offset)
+        .flat_map(|syntax| syntax.parent_ancestors())
+        .find_map(ast::UseTree::cast)?;
+
+    let last_path_segment = use_tree.path()?.segments().last()?.name_ref()?;
+    if !last_path_segment.syntax().text_range().contains_inclusive(offset) {
+        return None;
+    };
+    Some(use_tree)
+}
+
 fn find_definitions(
     sema: &Semantics<'_, RootDatabase>,
     syntax: &SyntaxNode,
@@ -1025,6 +1037,28 @@ fn foo(self) -> Self$0 {
         );
     }
 
+    #[test]
+    fn test_prepare_rename_literal() {
+        check_prepare(
+            r"fn foo() { let x = 92$0; }",
+            expect![[r#"No references found at position"#]],
+        );
+    }
+
+    #[test]
+    fn test_prepare_rename_non_local_definition() {
+        check_prepare(
+            r#"
+//- /lib.rs crate:lib new_source_root:library
+pub struct S;
+//- /main.rs crate:main deps:lib new_source_root:local
+use lib::S;
+fn main() { let _: S$0; }
+"#,
+            expect![[r#"Cannot rename a non-local definition"#]],
+        );
+    }
+
     #[test]
     fn test_rename_to_underscore() {
         check("_", r#"fn main() { let i$0 = 1; }"#, r#"fn main() { let _ = 1; }"#);
@@ -2712,6 +2746,29 @@ fn foo(Foo { i: bar }: Foo) -> i32 {
         )
     }
 
+    #[test]
+    fn test_rename_field_in_pat_shorthand() {
+        check(
+            "baz",
+            r#"
+struct Foo { i$0: i32 }
+
+fn foo(foo: Foo) -> i32 {
+    let Foo { i } = foo;
+    i
+}
+"#,
+            r#"
+struct Foo { baz: i32 }
+
+fn foo(foo: Foo) -> i32 {
+    let Foo { baz: i } = foo;
+    i
+}
+"#,
+        );
+    }
+
     #[test]
     fn test_struct_field_complex_ident_pat() {
         cov_mark::check!(rename_record_pat_field_name_split);
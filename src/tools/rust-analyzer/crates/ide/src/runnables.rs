@@ -959,7 +959,7 @@ fn foo() {}
             expect![[r#"
                 [
                     "(Bin, NavigationTarget { file_id: FileId(0), full_range: 1..13, focus_range: 4..8, name: \"main\", kind: Function })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 44..98, name: \"foo\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 44..98, name: \"foo\", container_name: \"Data\" })",
                 ]
             "#]],
         );
@@ -984,7 +984,7 @@ fn foo() {}
             expect![[r#"
                 [
                     "(Bin, NavigationTarget { file_id: FileId(0), full_range: 1..13, focus_range: 4..8, name: \"main\", kind: Function })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 52..106, name: \"foo\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 52..106, name: \"foo\", container_name: \"Data\" })",
                 ]
             "#]],
         );
@@ -1009,7 +1009,7 @@ fn foo() {}
             expect![[r#"
                 [
                     "(Bin, NavigationTarget { file_id: FileId(0), full_range: 1..13, focus_range: 4..8, name: \"main\", kind: Function })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 70..124, name: \"foo\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 70..124, name: \"foo\", container_name: \"Data\" })",
                 ]
             "#]],
         );
@@ -1034,7 +1034,7 @@ fn foo() {}
             expect![[r#"
                 [
                     "(Bin, NavigationTarget { file_id: FileId(0), full_range: 1..13, focus_range: 4..8, name: \"main\", kind: Function })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 79..133, name: \"foo\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 79..133, name: \"foo\", container_name: \"Data\" })",
                 ]
             "#]],
         );
@@ -1059,7 +1059,7 @@ fn foo() {}
             expect![[r#"
                 [
                     "(Bin, NavigationTarget { file_id: FileId(0), full_range: 1..13, focus_range: 4..8, name: \"main\", kind: Function })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 100..154, name: \"foo\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 100..154, name: \"foo\", container_name: \"Data\" })",
                 ]
             "#]],
         );
@@ -1113,11 +1113,11 @@ mod nested_tests_4 {}
 "#,
             expect![[r#"
                 [
-                    "(TestMod, NavigationTarget { file_id: FileId(0), full_range: 22..323, focus_range: 26..40, name: \"nested_tests_0\", kind: Module, description: \"mod nested_tests_0\" })",
-                    "(TestMod, NavigationTarget { file_id: FileId(0), full_range: 51..192, focus_range: 55..69, name: \"nested_tests_1\", kind: Module, description: \"mod nested_tests_1\" })",
+                    "(TestMod, NavigationTarget { file_id: FileId(0), full_range: 22..323, focus_range: 26..40, name: \"nested_tests_0\", kind: Module, container_name: \"root_tests\", description: \"mod nested_tests_0\" })",
+                    "(TestMod, NavigationTarget { file_id: FileId(0), full_range: 51..192, focus_range: 55..69, name: \"nested_tests_1\", kind: Module, container_name: \"nested_tests_0\", description: \"mod nested_tests_1\" })",
                     "(Test, NavigationTarget { file_id: FileId(0), full_range: 84..126, focus_range: 107..121, name: \"nested_test_11\", kind: Function })",
                     "(Test, NavigationTarget { file_id: FileId(0), full_range: 140..182, focus_range: 163..177, name: \"nested_test_12\", kind: Function })",
-                    "(TestMod, NavigationTarget { file_id: FileId(0), full_range: 202..286, focus_range: 206..220, name: \"nested_tests_2\", kind: Module, description: \"mod nested_tests_2\" })",
+                    "(TestMod, NavigationTarget { file_id: FileId(0), full_range: 202..286, focus_range: 206..220, name: \"nested_tests_2\", kind: Module, container_name: \"nested_tests_0\", description: \"mod nested_tests_2\" })",
                     "(Test, NavigationTarget { file_id: FileId(0), full_range: 235..276, focus_range: 258..271, name: \"nested_test_2\", kind: Function })",
                 ]
             "#]],
@@ -1195,7 +1195,7 @@ fn foo() {}
         "#,
             expect![[r#"
                 [
-                    "(DocTest, NavigationTarget { file_id: FileId(1), full_range: 27..81, name: \"foo\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(1), full_range: 27..81, name: \"foo\", container_name: \"Foo\" })",
                 ]
             "#]],
         );
@@ -1547,7 +1547,7 @@ fn foo() {}
             expect![[r#"
                 [
                     "(Bin, NavigationTarget { file_id: FileId(0), full_range: 1..13, focus_range: 4..8, name: \"main\", kind: Function })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 121..156, name: \"foo\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 121..156, name: \"foo\", container_name: \"Data\" })",
                 ]
             "#]],
         );
@@ -1580,9 +1580,9 @@ fn t() {}
             expect![[r#"
                 [
                     "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 20..103, focus_range: 47..56, name: \"impl\", kind: Impl })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 63..101, name: \"t\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 63..101, name: \"t\", container_name: \"Foo\" })",
                     "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 105..188, focus_range: 126..146, name: \"impl\", kind: Impl })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 153..186, name: \"t\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 153..186, name: \"t\", container_name: \"Foo\" })",
                 ]
             "#]],
         );
@@ -1681,10 +1681,10 @@ impl<T> r#trait for r#struct<T> {}
                     "(Test, NavigationTarget { file_id: FileId(0), full_range: 17..41, focus_range: 32..36, name: \"r#fn\", kind: Function })",
                     "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 47..84, name: \"r#for\", container_name: \"mod\" })",
                     "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 90..146, name: \"r#struct\", container_name: \"mod\" })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 152..266, focus_range: 189..205, name: \"impl\", kind: Impl })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 216..260, name: \"r#fn\" })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 323..367, name: \"r#fn\" })",
-                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 401..459, focus_range: 445..456, name: \"impl\", kind: Impl })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 152..266, focus_range: 189..205, name: \"impl\", kind: Impl, container_name: \"mod\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 216..260, name: \"r#fn\", container_name: \"struct\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 323..367, name: \"r#fn\", container_name: \"struct\" })",
+                    "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 401..459, focus_range: 445..456, name: \"impl\", kind: Impl, container_name: \"mod\" })",
                 ]
             "#]],
         )
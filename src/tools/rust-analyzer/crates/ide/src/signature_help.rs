@@ -15,7 +15,7 @@
 };
 use itertools::Itertools;
 use span::Edition;
-use stdx::format_to;
+use stdx::{format_to, truncate_with_ellipsis};
 use syntax::{
     AstNode, Direction, NodeOrToken, SyntaxElementChildren, SyntaxNode, SyntaxToken, T, TextRange,
     TextSize, ToSmolStr, algo,
@@ -156,6 +156,9 @@ pub(crate) fn signature_help(
     None
 }
 
+/// Longest return type we show for calls, longer ones are cut off with `…`.
+const MAX_RET_TYPE_LEN: usize = 80;
+
 fn signature_help_for_call(
     sema: &Semantics<'_, RootDatabase>,
     arg_list: ast::ArgList,
@@ -183,7 +186,10 @@ fn signature_help_for_call(
                 .params(db)
                 .iter()
                 .filter(|param| match param {
-                    GenericParam::TypeParam(type_param) => !type_param.is_implicit(db),
+                    GenericParam::TypeParam(type_param) => {
+                        !type_param.is_implicit(db)
+                            && !type_param.is_inlined_in_compact_signature(db)
+                    }
                     GenericParam::ConstParam(_) | GenericParam::LifetimeParam(_) => true,
                 })
                 .map(|param| param.display(db, display_target))
@@ -268,12 +274,11 @@ fn signature_help_for_call(
             // In that case, fall back to render definitions of the respective parameters.
             // This is overly conservative: we do not substitute known type vars
             // (see FIXME in tests::impl_trait) and falling back on any unknowns.
-            match (p.ty().contains_unknown(), fn_params.as_deref()) {
-                (true, Some(fn_params)) => {
-                    format_to!(buf, "{}", fn_params[idx].ty().display(db, display_target))
-                }
-                _ => format_to!(buf, "{}", p.ty().display(db, display_target)),
-            }
+            let ty = match (p.ty().contains_unknown(), fn_params.as_deref()) {
+                (true, Some(fn_params)) => fn_params[idx].ty(),
+                _ => p.ty(),
+            };
+            format_to!(buf, "{}", ty.display(db, display_target).with_compact_signature(true));
             res.push_call_param(&buf);
         }
     }
@@ -281,7 +286,11 @@ fn signature_help_for_call(
 
     let mut render = |ret_type: hir::Type<'_>| {
         if !ret_type.is_unit() {
-            format_to!(res.signature, " -> {}", ret_type.display(db, display_target));
+            let ret_type =
+                ret_type.display(db, display_target).with_compact_signature(true).to_string();
+            // Parameters are kept whole so their ranges stay valid, only the return type is capped.
+            let ret_type = truncate_with_ellipsis(&ret_type, MAX_RET_TYPE_LEN);
+            format_to!(res.signature, " -> {}", ret_type);
         }
     };
     match callable.kind() {
@@ -857,7 +866,9 @@ fn foo(x: u32, y: u32) -> u32
     fn test_fn_signature_two_args_first_generics() {
         check(
             r#"
-//- minicore: sized, fn
+//- minicore: sized, fn, copy
+trait Display {}
+trait Debug {}
 fn foo<T, U: Copy + Display>(x: T, y: U) -> u32
     where T: Copy + Display, U: Debug
 { x + y }
@@ -865,8 +876,8 @@ fn foo<T, U: Copy + Display>(x: T, y: U) -> u32
 fn bar() { foo($03, ); }
 "#,
             expect![[r#"
-                fn foo<T, U>(x: i32, y: U) -> u32
-                             ^^^^^^  ----
+                fn foo(x: i32, y: impl Copy + Display + Debug) -> u32
+                       ^^^^^^  ------------------------------
             "#]],
         );
     }
@@ -1824,8 +1835,8 @@ fn sup() {
 }
 "#,
             expect![[r#"
-                fn test<V>(&mut self, val: V)
-                                      ^^^^^^
+                fn test(&mut self, val: impl Sized)
+                                   ^^^^^^^^^^^^^^^
             "#]],
         );
     }
@@ -2660,8 +2671,8 @@ fn main() {
 }
             "#,
             expect![[r#"
-                async fn conn_mut<F: FnOnce() -> T, T>(f: F) -> Result<T, i32>
-                                                       ^^^^
+                async fn conn_mut<T>(f: impl FnOnce() -> T) -> Result<T, i32>
+                                     ^^^^^^^^^^^^^^^^^^^^^
             "#]],
         );
     }
@@ -1,5 +1,6 @@
-use ide_db::RootDatabase;
-use ide_db::base_db::{BuiltCrateData, ExtraCrateData};
+use ide_db::base_db::{BuiltCrateData, ExtraCrateData, RootQueryDb, SourceDatabase, SourceRootId};
+use ide_db::symbol_index::{LibraryRoots, LocalRoots};
+use ide_db::{FxHashSet, RootDatabase};
 use itertools::Itertools;
 use span::FileId;
 use stdx::format_to;
@@ -28,6 +29,37 @@ pub(crate) fn status(db: &RootDatabase, file_id: Option<FileId>) -> String {
     // format_to!(buf, "{} ast id maps\n", collect_query_count(AstIdMapQuery.in_db(db)));
     // format_to!(buf, "{} block def maps\n", collect_query_count(BlockDefMapQuery.in_db(db)));
 
+    format_to!(buf, "Crates: {}\n", db.all_crates().len());
+    let count_files = |roots: &FxHashSet<SourceRootId>| {
+        roots.iter().map(|&root| db.source_root(root).source_root(db).iter().count()).sum::<usize>()
+    };
+    let local_roots = LocalRoots::get(db).roots(db);
+    format_to!(
+        buf,
+        "Local source roots: {} ({} files)\n",
+        local_roots.len(),
+        count_files(local_roots)
+    );
+    let library_roots = LibraryRoots::get(db).roots(db);
+    format_to!(
+        buf,
+        "Library source roots: {} ({} files)\n",
+        library_roots.len(),
+        count_files(library_roots)
+    );
+
+    let overflows = hir::trait_solving_overflows();
+    if overflows.count > 0 {
+        format_to!(
+            buf,
+            "\nTrait solving ran out of fuel {} times, most recently on:\n",
+            overflows.count
+        );
+        for goal in &overflows.recent {
+            format_to!(buf, "    {goal}\n");
+        }
+    }
+
     if let Some(file_id) = file_id {
         format_to!(buf, "\nCrates for file {}:\n", file_id.index());
         let crates = crate::parent_module::crates_for(db, file_id);
@@ -76,3 +108,29 @@ pub(crate) fn status(db: &RootDatabase, file_id: Option<FileId>) -> String {
 
     buf.trim().to_owned()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    #[test]
+    fn reports_crates_and_source_roots() {
+        let (analysis, file_id) = fixture::file(
+            r#"
+//- /main.rs crate:main deps:dep
+mod foo;
+//- /foo.rs
+//- /dep.rs crate:dep new_source_root:library
+"#,
+        );
+        let status = analysis.status(None).unwrap();
+        let summary = status.lines().take(3).collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            ["Crates: 2", "Local source roots: 1 (2 files)", "Library source roots: 1 (1 files)"]
+        );
+
+        let status = analysis.status(Some(file_id)).unwrap();
+        assert!(status.contains("Crate: main("), "{status}");
+    }
+}
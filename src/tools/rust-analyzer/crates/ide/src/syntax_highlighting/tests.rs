@@ -5,7 +5,7 @@
 use span::Edition;
 use test_utils::{AssertLinear, bench, bench_fixture, skip_slow_tests};
 
-use crate::{FileRange, HighlightConfig, HlTag, TextRange, fixture};
+use crate::{FileRange, HighlightConfig, HlTag, TextRange, TextSize, fixture};
 
 const HL_CONFIG: HighlightConfig<'_> = HighlightConfig {
     strings: true,
@@ -1217,6 +1217,84 @@ struct Foo {
     assert_eq!(&highlights[0].highlight.to_string(), "field.declaration.public");
 }
 
+#[test]
+fn test_injection_and_format_string_ranges() {
+    let (analysis, file_id) = fixture::file(
+        r#"
+//- minicore: fmt
+/// ```
+/// let doc = 1;
+/// ```
+fn main() {
+    let name = 92;
+    format_args!("{name} {:?}", name);
+}
+"#,
+    );
+    let text = analysis.file_text(file_id).unwrap();
+    let highlights = analysis.highlight(HL_CONFIG, file_id).unwrap();
+    // Highlights of the `len` bytes found `skip` bytes after the start of `needle`.
+    let highlights_at = |needle: &str, skip: u32, len: u32| {
+        let start = TextSize::from(text.find(needle).unwrap() as u32 + skip);
+        let range = TextRange::at(start, len.into());
+        highlights
+            .iter()
+            .filter(|it| it.range == range)
+            .map(|it| it.highlight.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(highlights_at("let doc", 0, 3), ["keyword.injected"]);
+    assert_eq!(highlights_at("\"{name}", 1, 1), ["format_specifier"]);
+    assert_eq!(highlights_at("\"{name}", 6, 1), ["format_specifier"]);
+    let name = highlights_at("\"{name}", 2, 4);
+    assert!(name.len() == 1 && name[0].starts_with("variable"), "{name:?}");
+    assert_eq!(highlights_at("{:?}", 0, 1), ["format_specifier"]);
+}
+
+#[test]
+fn test_modifier_ranges() {
+    let (analysis, file_id) = fixture::file(
+        r#"
+struct String;
+unsafe fn unsafe_fn() {}
+fn take(_: String) {}
+fn main() {
+    let mut count = 0;
+    count += 1;
+    let s = String;
+    take(s);
+    let ptr = &count as *const i32;
+    unsafe {
+        unsafe_fn();
+        let _ = *ptr;
+    }
+}
+"#,
+    );
+    let text = analysis.file_text(file_id).unwrap();
+    let highlights = analysis.highlight(HL_CONFIG, file_id).unwrap();
+    // Checks the tag and modifiers of the `len` bytes found `skip` bytes after `needle`'s start.
+    let check = |needle: &str, skip: u32, len: u32, tag: &str, mods: &[&str]| {
+        let start = TextSize::from(text.find(needle).unwrap() as u32 + skip);
+        let range = TextRange::at(start, len.into());
+        let highlight = highlights.iter().find(|it| it.range == range).unwrap().highlight;
+        assert_eq!(highlight.tag.to_string(), tag, "{needle}");
+        let actual_mods = highlight.mods.iter().map(|it| it.to_string()).collect::<Vec<_>>();
+        for mod_ in mods {
+            assert!(actual_mods.iter().any(|it| it == mod_), "{needle}: {actual_mods:?}");
+        }
+    };
+
+    check("count = 0", 0, 5, "variable", &["declaration", "mutable"]);
+    check("count += 1", 0, 5, "variable", &["mutable"]);
+    check("&count as", 1, 5, "variable", &["mutable"]);
+    check("s);", 0, 1, "variable", &["consuming"]);
+    check("unsafe {", 0, 6, "keyword", &["unsafe"]);
+    check("unsafe_fn();", 0, 9, "function", &["unsafe"]);
+    check("*ptr", 0, 1, "operator", &["unsafe"]);
+}
+
 #[test]
 fn ranges_sorted() {
     let (analysis, file_id) = fixture::file(
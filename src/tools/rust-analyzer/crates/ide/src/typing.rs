@@ -672,6 +672,27 @@ fn main() {
         )
     }
 
+    #[test]
+    fn dot_and_eq_noop_in_string_literal() {
+        type_char_noop(
+            '.',
+            r#"
+fn main() {
+    let s = "xs.foo()
+    $0";
+}
+            "#,
+        );
+        type_char_noop(
+            '=',
+            r#"
+fn main() {
+    let s = "a $0 b"
+}
+            "#,
+        );
+    }
+
     #[test]
     fn indents_new_chain_call_with_semi() {
         type_char(
@@ -162,7 +162,7 @@ pub fn load_workspace_into_db(
             .collect()
     };
 
-    let project_folders = ProjectFolders::new(std::slice::from_ref(&ws), &[], None);
+    let project_folders = ProjectFolders::new(std::slice::from_ref(&ws), &[], &[], None);
     loader.set_config(vfs::loader::Config {
         load: project_folders.load,
         watch: vec![],
@@ -196,6 +196,7 @@ impl ProjectFolders {
     pub fn new(
         workspaces: &[ProjectWorkspace],
         global_excludes: &[AbsPathBuf],
+        global_exclude_globs: &[vfs::Glob],
         user_config_dir_path: Option<&AbsPath>,
     ) -> ProjectFolders {
         let mut res = ProjectFolders::default();
@@ -283,6 +284,11 @@ pub fn new(
                         dirs.exclude.push(excl.clone());
                     }
                 }
+                // Vendored JavaScript packages are never part of a Rust project.
+                let default_globs =
+                    dirs.include.iter().map(|incl| vfs::Glob::new(incl.clone(), "**/node_modules"));
+                dirs.exclude_globs = default_globs.collect();
+                dirs.exclude_globs.extend(global_exclude_globs.iter().cloned());
 
                 vfs::loader::Entry::Directories(dirs)
             };
@@ -192,7 +192,7 @@ pub(crate) fn reparser(
         MATCH_ARM_LIST => items::match_arm_list,
         USE_TREE_LIST => items::use_tree_list,
         EXTERN_ITEM_LIST => items::extern_item_list,
-        TOKEN_TREE if first_child? == T!['{'] => items::token_tree,
+        TOKEN_TREE if matches!(first_child?, T!['{'] | T!['('] | T!['[']) => items::token_tree,
         ASSOC_ITEM_LIST => match parent? {
             IMPL | TRAIT => items::assoc_item_list,
             _ => return None,
@@ -387,6 +387,59 @@ fn error_block(p: &mut Parser<'_>, message: &str) {
     m.complete(p, ERROR);
 }
 
+/// Skips the rest of the current delimited group, or up to one of `stop` outside
+/// of nested delimiters, as an error, for input nested too deeply to parse
+/// without overflowing the stack.
+fn too_deeply_nested(p: &mut Parser<'_>, m: Marker, message: &str, stop: TokenSet) {
+    p.error(message);
+    if p.at(EOF) || p.at_ts(stop) || p.at_ts(TokenSet::new(&[T![')'], T![']'], T!['}']])) {
+        m.abandon(p);
+        return;
+    }
+    // Curly braces get a node of their own to keep them paired up in the tree.
+    let mut open_delims: Vec<Option<Marker>> = Vec::new();
+    while !p.at(EOF) {
+        if open_delims.is_empty() && p.at_ts(stop) {
+            break;
+        }
+        match p.current() {
+            T!['('] | T!['['] => {
+                open_delims.push(None);
+                p.bump_any();
+            }
+            T!['{'] => {
+                open_delims.push(Some(p.start()));
+                p.bump_any();
+            }
+            T![')'] | T![']'] => match open_delims.last() {
+                None => break,
+                Some(None) => {
+                    open_delims.pop();
+                    p.bump_any();
+                }
+                // A stray closing delimiter inside of curly braces.
+                Some(Some(_)) => p.bump_any(),
+            },
+            T!['}'] => {
+                // Unclosed parentheses and brackets end at the closing curly brace.
+                while let Some(None) = open_delims.last() {
+                    open_delims.pop();
+                }
+                let Some(Some(curly)) = open_delims.pop() else { break };
+                p.bump_any();
+                curly.complete(p, ERROR);
+            }
+            _ => p.bump_any(),
+        }
+    }
+    while let Some(open) = open_delims.pop() {
+        if let Some(curly) = open {
+            curly.complete(p, ERROR);
+        }
+    }
+    m.complete(p, ERROR);
+}
+
 // test_err top_level_let
 // let ref foo: fn() = 1 + 3;
 fn error_let_stmt(p: &mut Parser<'_>, message: &str) {
@@ -145,6 +145,8 @@ pub(super) fn let_stmt(p: &mut Parser<'_>, with_semi: Semicolon) {
         // fn f() { let Some(x) = opt else { return }; }
         let m = p.start();
         p.bump(T![else]);
+        // test_err let_else_missing_block
+        // fn f() { let Some(x) = opt else; }
         block_expr(p);
         m.complete(p, LET_ELSE);
     }
@@ -248,6 +250,22 @@ fn expr_bp(
     m: Option<Marker>,
     r: Restrictions,
     bp: u8,
+) -> Option<(CompletedMarker, BlockLike)> {
+    if !p.enter_nested() {
+        let m = m.unwrap_or_else(|| p.start());
+        too_deeply_nested(p, m, "expression is too deeply nested", TokenSet::EMPTY);
+        return None;
+    }
+    let res = expr_bp_unchecked(p, m, r, bp);
+    p.exit_nested();
+    res
+}
+
+fn expr_bp_unchecked(
+    p: &mut Parser<'_>,
+    m: Option<Marker>,
+    r: Restrictions,
+    bp: u8,
 ) -> Option<(CompletedMarker, BlockLike)> {
     let m = m.unwrap_or_else(|| {
         let m = p.start();
@@ -119,93 +119,38 @@ pub(super) fn opt_item(p: &mut Parser<'_>, m: Marker, is_in_extern: bool) -> Res
     let mut has_mods = false;
     let mut has_extern = false;
 
-    // modifiers
-    if p.at(T![const]) && p.nth(1) != T!['{'] {
-        p.eat(T![const]);
-        has_mods = true;
-    }
-
-    // test_err async_without_semicolon
-    // fn foo() { let _ = async {} }
-    if p.at(T![async])
-        && (!matches!(p.nth(1), T!['{'] | T![gen] | T![move] | T![|])
-            || matches!((p.nth(1), p.nth(2)), (T![gen], T![fn])))
-    {
-        p.eat(T![async]);
-        has_mods = true;
-    }
-
-    // test_err gen_fn 2021
-    // gen fn gen_fn() {}
-    // async gen fn async_gen_fn() {}
-    if p.at(T![gen]) && p.nth(1) == T![fn] {
-        p.eat(T![gen]);
-        has_mods = true;
-    }
-
-    // test_err unsafe_block_in_mod
-    // fn foo(){} unsafe { } fn bar(){}
-    if p.at(T![unsafe]) && p.nth(1) != T!['{'] {
-        p.eat(T![unsafe]);
-        has_mods = true;
-    }
+    // test fn_qualifiers_any_order
+    // async unsafe extern "C" fn a() {}
+    // unsafe extern "C" async fn b() {}
+    // extern "C" async unsafe fn c() {}
+    // const unsafe fn d() {}
+    // unsafe const fn e() {}
 
-    // test safe_outside_of_extern
-    // fn foo() { safe = true; }
-    if is_in_extern && p.at_contextual_kw(T![safe]) {
-        p.eat_contextual_kw(T![safe]);
-        has_mods = true;
-    }
-
-    if p.at(T![extern]) {
-        has_extern = true;
+    // modifiers
+    let mut seen: Vec<SyntaxKind> = Vec::new();
+    while let Some(kind) = current_qualifier(p, is_in_extern) {
         has_mods = true;
-        abi(p);
+        if kind == T![extern] {
+            has_extern = true;
+        }
+        if seen.contains(&kind) {
+            // test_err duplicate_fn_qualifier
+            // unsafe async unsafe fn foo() {}
+            // extern "C" extern "C" fn bar() {}
+            let m = p.start();
+            p.error(format!("duplicate `{}` qualifier", kind.text()));
+            bump_qualifier(p, kind);
+            m.complete(p, ERROR);
+        } else {
+            seen.push(kind);
+            bump_qualifier(p, kind);
+        }
     }
     if p.at_contextual_kw(T![auto]) && p.nth(1) == T![trait] {
         p.bump_remap(T![auto]);
         has_mods = true;
     }
 
-    // test default_item
-    // default impl T for Foo {}
-    if p.at_contextual_kw(T![default]) {
-        match p.nth(1) {
-            T![fn] | T![type] | T![const] | T![impl] => {
-                p.bump_remap(T![default]);
-                has_mods = true;
-            }
-            // test default_unsafe_item
-            // default unsafe impl T for Foo {
-            //     default unsafe fn foo() {}
-            // }
-            T![unsafe] if matches!(p.nth(2), T![impl] | T![fn]) => {
-                p.bump_remap(T![default]);
-                p.bump(T![unsafe]);
-                has_mods = true;
-            }
-            // test default_async_fn
-            // impl T for Foo {
-            //     default async fn foo() {}
-            // }
-            T![async]
-                if p.nth_at(2, T![fn]) || (p.nth_at(2, T![unsafe]) && p.nth_at(3, T![fn])) =>
-            {
-                p.bump_remap(T![default]);
-                p.bump(T![async]);
-
-                // test default_async_unsafe_fn
-                // impl T for Foo {
-                //     default async unsafe fn foo() {}
-                // }
-                p.eat(T![unsafe]);
-
-                has_mods = true;
-            }
-            _ => (),
-        }
-    }
-
     // items
     match p.current() {
         T![fn] => fn_(p, m),
@@ -240,6 +185,79 @@ pub(super) fn opt_item(p: &mut Parser<'_>, m: Marker, is_in_extern: bool) -> Res
     Ok(())
 }
 
+/// Returns the qualifier of a function-like item at the current position.
+///
+/// Qualifiers are accepted in any order, invalid orders and combinations are
+/// reported by the validator.
+fn current_qualifier(p: &Parser<'_>, is_in_extern: bool) -> Option<SyntaxKind> {
+    let kind = match p.current() {
+        // `const X` is a const item and `const {}` a const block.
+        T![const] if !matches!(p.nth(1), T!['{'] | IDENT | T![_] | T![mut]) => T![const],
+
+        // test_err async_without_semicolon
+        // fn foo() { let _ = async {} }
+        T![async]
+            if !matches!(p.nth(1), T!['{'] | T![gen] | T![move] | T![|])
+                || matches!((p.nth(1), p.nth(2)), (T![gen], T![fn])) =>
+        {
+            T![async]
+        }
+
+        // test_err gen_fn 2021
+        // gen fn gen_fn() {}
+        // async gen fn async_gen_fn() {}
+        T![gen] if matches!(p.nth(1), T![fn] | T![unsafe] | T![extern] | T![const]) => T![gen],
+
+        // test_err unsafe_block_in_mod
+        // fn foo(){} unsafe { } fn bar(){}
+        T![unsafe] if p.nth(1) != T!['{'] => T![unsafe],
+
+        T![extern] => T![extern],
+
+        // test safe_outside_of_extern
+        // fn foo() { safe = true; }
+        IDENT if is_in_extern && p.at_contextual_kw(T![safe]) => T![safe],
+
+        // test default_item
+        // default impl T for Foo {}
+
+        // test default_unsafe_item
+        // default unsafe impl T for Foo {
+        //     default unsafe fn foo() {}
+        // }
+
+        // test default_async_fn
+        // impl T for Foo {
+        //     default async fn foo() {}
+        // }
+
+        // test default_async_unsafe_fn
+        // impl T for Foo {
+        //     default async unsafe fn foo() {}
+        // }
+        IDENT
+            if p.at_contextual_kw(T![default])
+                && matches!(
+                    p.nth(1),
+                    T![fn] | T![type] | T![const] | T![impl] | T![unsafe] | T![async] | T![extern]
+                ) =>
+        {
+            T![default]
+        }
+
+        _ => return None,
+    };
+    Some(kind)
+}
+
+fn bump_qualifier(p: &mut Parser<'_>, kind: SyntaxKind) {
+    match kind {
+        T![extern] => abi(p),
+        T![safe] | T![default] => p.bump_remap(kind),
+        _ => p.bump(kind),
+    }
+}
+
 fn opt_item_without_modifiers(p: &mut Parser<'_>, m: Marker) -> Result<(), Marker> {
     let la = p.nth(1);
     match p.current() {
@@ -41,6 +41,21 @@ pub(super) fn type_no_bounds(p: &mut Parser<'_>) {
 }
 
 fn type_with_bounds_cond(p: &mut Parser<'_>, allow_bounds: bool) {
+    if !p.enter_nested() {
+        let m = p.start();
+        too_deeply_nested(
+            p,
+            m,
+            "type is too deeply nested",
+            TYPE_RECOVERY_SET.union(TokenSet::new(&[T!['{'], T![;], T![=], T![where]])),
+        );
+        return;
+    }
+    type_with_bounds_cond_unchecked(p, allow_bounds);
+    p.exit_nested();
+}
+
+fn type_with_bounds_cond_unchecked(p: &mut Parser<'_>, allow_bounds: bool) {
     match p.current() {
         T!['('] => paren_or_tuple_type(p),
         T![!] => never_type(p),
@@ -48,6 +48,10 @@
 
 pub use edition::Edition;
 
+/// How deeply expressions and types may be nested before the parser gives up on
+/// them, to keep pathological input from overflowing the stack.
+pub const DEFAULT_NESTING_LIMIT: u32 = 512;
+
 pub use crate::{
     input::Input,
     lexed_str::LexedStr,
@@ -92,6 +96,13 @@ pub enum TopEntryPoint {
 
 impl TopEntryPoint {
     pub fn parse(&self, input: &Input) -> Output {
+        self.parse_with_nesting_limit(input, DEFAULT_NESTING_LIMIT)
+    }
+
+    /// Like [`TopEntryPoint::parse`], but with a custom limit on how deeply
+    /// expressions and types may nest. Anything nested deeper is reported as an
+    /// error and skipped.
+    pub fn parse_with_nesting_limit(&self, input: &Input, nesting_limit: u32) -> Output {
         let _p = tracing::info_span!("TopEntryPoint::parse", ?self).entered();
         let entry_point: fn(&'_ mut parser::Parser<'_>) = match self {
             TopEntryPoint::SourceFile => grammar::entry::top::source_file,
@@ -102,7 +113,7 @@ pub fn parse(&self, input: &Input) -> Output {
             TopEntryPoint::Expr => grammar::entry::top::expr,
             TopEntryPoint::MetaItem => grammar::entry::top::meta_item,
         };
-        let mut p = parser::Parser::new(input);
+        let mut p = parser::Parser::new(input, 0, nesting_limit);
         entry_point(&mut p);
         let events = p.finish();
         let res = event::process(events);
@@ -167,7 +178,7 @@ pub fn parse(&self, input: &Input) -> Output {
             PrefixEntryPoint::Item => grammar::entry::prefix::item,
             PrefixEntryPoint::MetaItem => grammar::entry::prefix::meta_item,
         };
-        let mut p = parser::Parser::new(input);
+        let mut p = parser::Parser::new(input, 0, DEFAULT_NESTING_LIMIT);
         entry_point(&mut p);
         let events = p.finish();
         event::process(events)
@@ -178,7 +189,7 @@ pub fn parse(&self, input: &Input) -> Output {
 pub struct Reparser(fn(&mut parser::Parser<'_>));
 
 impl Reparser {
-    /// If the node is a braced block, return the corresponding `Reparser`.
+    /// If the node is a braced block or a token tree, return the corresponding `Reparser`.
     pub fn for_node(
         node: SyntaxKind,
         first_child: Option<SyntaxKind>,
@@ -190,10 +201,13 @@ pub fn for_node(
     /// Re-parse given tokens using this `Reparser`.
     ///
     /// Tokens must start with `{`, end with `}` and form a valid brace
-    /// sequence.
-    pub fn parse(self, tokens: &Input) -> Output {
+    /// sequence, or, for token trees, be delimited by any matching pair.
+    ///
+    /// `depth` is how many expressions and types the block is nested in, so
+    /// that the nesting limit is hit at the same place as in a full parse.
+    pub fn parse(self, tokens: &Input, depth: u32) -> Output {
         let Reparser(r) = self;
-        let mut p = parser::Parser::new(tokens);
+        let mut p = parser::Parser::new(tokens, depth, DEFAULT_NESTING_LIMIT);
         r(&mut p);
         let events = p.finish();
         event::process(events)
@@ -26,13 +26,16 @@ pub(crate) struct Parser<'t> {
     pos: usize,
     events: Vec<Event>,
     steps: Cell<u32>,
+    /// How many expressions and types we are currently nested in.
+    depth: u32,
+    nesting_limit: u32,
 }
 
 const PARSER_STEP_LIMIT: usize = if cfg!(debug_assertions) { 150_000 } else { 15_000_000 };
 
 impl<'t> Parser<'t> {
-    pub(super) fn new(inp: &'t Input) -> Parser<'t> {
-        Parser { inp, pos: 0, events: Vec::new(), steps: Cell::new(0) }
+    pub(super) fn new(inp: &'t Input, depth: u32, nesting_limit: u32) -> Parser<'t> {
+        Parser { inp, pos: 0, events: Vec::new(), steps: Cell::new(0), depth, nesting_limit }
     }
 
     pub(crate) fn finish(self) -> Vec<Event> {
@@ -58,6 +61,22 @@ pub(crate) fn nth(&self, n: usize) -> SyntaxKind {
         self.inp.kind(self.pos + n)
     }
 
+    /// Enters a nested expression or type. Returns `false` without entering if
+    /// that would nest deeper than the nesting limit, in which case the caller
+    /// must not recurse any further.
+    pub(crate) fn enter_nested(&mut self) -> bool {
+        if self.depth >= self.nesting_limit {
+            return false;
+        }
+        self.depth += 1;
+        true
+    }
+
+    /// Leaves a nested expression or type entered with [`Parser::enter_nested`].
+    pub(crate) fn exit_nested(&mut self) {
+        self.depth -= 1;
+    }
+
     /// Checks if the current token is `kind`.
     pub(crate) fn at(&self, kind: SyntaxKind) -> bool {
         self.nth_at(0, kind)
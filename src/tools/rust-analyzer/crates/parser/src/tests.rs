@@ -61,7 +61,7 @@ fn lex(text: &str, edition: Edition) -> String {
 fn parse_ok() {
     for case in TestCase::list("parser/ok") {
         let _guard = stdx::panic_context::enter(format!("{:?}", case.rs));
-        let (actual, errors) = parse(TopEntryPoint::SourceFile, &case.text, Edition::CURRENT);
+        let (actual, errors) = parse_rast(&case.text, Edition::CURRENT);
         assert!(!errors, "errors in an OK file {}:\n{actual}", case.rs.display());
         expect_file![case.rast].assert_eq(&actual);
     }
@@ -71,13 +71,30 @@ fn parse_ok() {
 fn parse_err() {
     for case in TestCase::list("parser/err") {
         let _guard = stdx::panic_context::enter(format!("{:?}", case.rs));
-        let (actual, errors) = parse(TopEntryPoint::SourceFile, &case.text, Edition::CURRENT);
+        let (actual, errors) = parse_rast(&case.text, Edition::CURRENT);
         assert!(errors, "no errors in an ERR file {}:\n{actual}", case.rs.display());
         expect_file![case.rast].assert_eq(&actual)
     }
 }
 
 fn parse(entry: TopEntryPoint, text: &str, edition: Edition) -> (String, bool) {
+    dump_parse(entry, text, edition, false)
+}
+
+/// Parses a `.rs` file for comparison with its `.rast` expectation file. Set `RA_STABLE_DUMP=1`
+/// together with `UPDATE_EXPECT=1` to rewrite them in the format of `syntax::debug_dump`, which
+/// includes the range of every node and token.
+fn parse_rast(text: &str, edition: Edition) -> (String, bool) {
+    let with_ranges = std::env::var("RA_STABLE_DUMP").is_ok();
+    dump_parse(TopEntryPoint::SourceFile, text, edition, with_ranges)
+}
+
+fn dump_parse(
+    entry: TopEntryPoint,
+    text: &str,
+    edition: Edition,
+    with_ranges: bool,
+) -> (String, bool) {
     let lexed = LexedStr::new(edition, text);
     let input = lexed.to_input(edition);
     let output = entry.parse(&input);
@@ -87,16 +104,24 @@ fn parse(entry: TopEntryPoint, text: &str, edition: Edition) -> (String, bool) {
     let mut indent = String::new();
     let mut depth = 0;
     let mut len = 0;
+    // Where to insert the range of each open node once we know where it ends, and its start.
+    let mut open_nodes = Vec::new();
     lexed.intersperse_trivia(&output, &mut |step| match step {
         crate::StrStep::Token { kind, text } => {
             assert!(depth > 0);
+            if with_ranges {
+                writeln!(buf, "{indent}{kind:?}@{len}..{} {text:?}", len + text.len()).unwrap();
+            } else {
+                writeln!(buf, "{indent}{kind:?} {text:?}").unwrap();
+            }
             len += text.len();
-            writeln!(buf, "{indent}{kind:?} {text:?}").unwrap();
         }
         crate::StrStep::Enter { kind } => {
             assert!(depth > 0 || len == 0);
             depth += 1;
-            writeln!(buf, "{indent}{kind:?}").unwrap();
+            write!(buf, "{indent}{kind:?}").unwrap();
+            open_nodes.push((buf.len(), len));
+            buf.push('\n');
             indent.push_str("  ");
         }
         crate::StrStep::Exit => {
@@ -104,6 +129,10 @@ fn parse(entry: TopEntryPoint, text: &str, edition: Edition) -> (String, bool) {
             depth -= 1;
             indent.pop();
             indent.pop();
+            let (pos, start) = open_nodes.pop().unwrap();
+            if with_ranges {
+                buf.insert_str(pos, &format!("@{start}..{len}"));
+            }
         }
         crate::StrStep::Error { msg, pos } => {
             assert!(depth > 0);
@@ -175,7 +204,7 @@ fn run_and_expect_errors(path: &str) {
 fn run_and_expect_no_errors_with_edition(path: &str, edition: Edition) {
     let path = PathBuf::from(path);
     let text = std::fs::read_to_string(&path).unwrap();
-    let (actual, errors) = parse(TopEntryPoint::SourceFile, &text, edition);
+    let (actual, errors) = parse_rast(&text, edition);
     assert!(!errors, "errors in an OK file {}:\n{actual}", path.display());
     let mut p = PathBuf::from("..");
     p.push(path);
@@ -187,7 +216,7 @@ fn run_and_expect_no_errors_with_edition(path: &str, edition: Edition) {
 fn run_and_expect_errors_with_edition(path: &str, edition: Edition) {
     let path = PathBuf::from(path);
     let text = std::fs::read_to_string(&path).unwrap();
-    let (actual, errors) = parse(TopEntryPoint::SourceFile, &text, edition);
+    let (actual, errors) = parse_rast(&text, edition);
     assert!(errors, "no errors in an ERR file {}:\n{actual}", path.display());
     let mut p = PathBuf::from("..");
     p.push(path);
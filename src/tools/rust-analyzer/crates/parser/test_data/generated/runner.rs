@@ -253,6 +253,10 @@ fn fn_pointer_unnamed_arg() {
         run_and_expect_no_errors("test_data/parser/inline/ok/fn_pointer_unnamed_arg.rs");
     }
     #[test]
+    fn fn_qualifiers_any_order() {
+        run_and_expect_no_errors("test_data/parser/inline/ok/fn_qualifiers_any_order.rs");
+    }
+    #[test]
     fn for_binder_bound() {
         run_and_expect_no_errors("test_data/parser/inline/ok/for_binder_bound.rs");
     }
@@ -773,6 +777,10 @@ fn crate_visibility_empty_recover() {
         run_and_expect_errors("test_data/parser/inline/err/crate_visibility_empty_recover.rs");
     }
     #[test]
+    fn duplicate_fn_qualifier() {
+        run_and_expect_errors("test_data/parser/inline/err/duplicate_fn_qualifier.rs");
+    }
+    #[test]
     fn empty_param_slot() {
         run_and_expect_errors("test_data/parser/inline/err/empty_param_slot.rs");
     }
@@ -816,6 +824,10 @@ fn invalid_question_for_type_trait_bound() {
         );
     }
     #[test]
+    fn let_else_missing_block() {
+        run_and_expect_errors("test_data/parser/inline/err/let_else_missing_block.rs");
+    }
+    #[test]
     fn let_else_right_curly_brace() {
         run_and_expect_errors("test_data/parser/inline/err/let_else_right_curly_brace.rs");
     }
@@ -0,0 +1,2 @@
+unsafe async unsafe fn foo() {}
+extern "C" extern "C" fn bar() {}
@@ -0,0 +1 @@
+fn f() { let Some(x) = opt else; }
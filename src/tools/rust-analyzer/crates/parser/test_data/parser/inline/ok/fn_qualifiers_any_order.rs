@@ -0,0 +1,5 @@
+async unsafe extern "C" fn a() {}
+unsafe extern "C" async fn b() {}
+extern "C" async unsafe fn c() {}
+const unsafe fn d() {}
+unsafe const fn e() {}
@@ -94,7 +94,9 @@ pub enum MaxSubstitutionLength {
         /// List of files to ignore
         ///
         /// These paths (file/directories) will be ignored by rust-analyzer. They are relative to
-        /// the workspace root, and globs are not supported. You may also need to add the folders to
+        /// the workspace root. Paths containing `*` are treated as globs, where `*` matches within
+        /// a single path component and `**` matches any number of them, e.g. `**/generated`.
+        /// `node_modules` directories are always ignored. You may also need to add the folders to
         /// Code's `files.watcherExclude`.
         files_exclude | files_excludeDirs: Vec<Utf8PathBuf> = vec![],
 
@@ -786,6 +788,12 @@ pub enum MaxSubstitutionLength {
         cargo_cfgs: Vec<String> = {
             vec!["debug_assertions".into(), "miri".into()]
         },
+        /// Map of crate names to cfg options to enable or disable for just that crate.
+        ///
+        /// Entries use the same format as `#rust-analyzer.cargo.cfgs#`, for example
+        /// `{ "my_crate": ["feature=serde", "!debug_assertions"] }`.
+        /// The `"*"` key applies to all crates, on top of `#rust-analyzer.cargo.cfgs#`.
+        cargo_crateCfgs: FxHashMap<String, Vec<String>> = FxHashMap::default(),
         /// Extra arguments that are passed to every cargo invocation.
         cargo_extraArgs: Vec<String> = vec![],
         /// Extra environment variables that will be set when running cargo, rustc
@@ -921,6 +929,9 @@ pub enum MaxSubstitutionLength {
         /// they will end up being interpreted as options to
         /// [`rustc`’s built-in test harness (“libtest”)](https://doc.rust-lang.org/rustc/tests/index.html#cli-arguments).
         runnables_extraTestBinaryArgs: Vec<String> = vec!["--nocapture".to_owned()],
+        /// Extra environment variables that will be set when running tests, benchmarks, or
+        /// doc-tests. Useful when tests read fixtures relative to a path given in the environment.
+        runnables_extraTestEnv: FxHashMap<String, String> = FxHashMap::default(),
 
         /// Path to the Cargo.toml of the rust compiler workspace, for usage in rustc_private
         /// projects, or "discover" to try to automatically find it if the `rustc-dev` component
@@ -953,6 +964,9 @@ pub enum MaxSubstitutionLength {
         /// though Cargo might be the eventual consumer.
         vfs_extraIncludes: Vec<String> = vec![],
 
+        /// Exclude associated items (methods, associated constants and types) from
+        /// workspace symbol search.
+        workspace_symbol_search_excludeAssocItems: bool = false,
         /// Exclude all imports from workspace symbol search.
         ///
         /// In addition to regular imports (which are always excluded),
@@ -1537,6 +1551,7 @@ pub fn runnable(&self) -> bool {
 pub struct FilesConfig {
     pub watcher: FilesWatcher,
     pub exclude: Vec<AbsPathBuf>,
+    pub exclude_globs: Vec<vfs::Glob>,
 }
 
 #[derive(Debug, Clone)]
@@ -1572,6 +1587,8 @@ pub struct RunnablesConfig {
     pub cargo_extra_args: Vec<String>,
     /// Additional arguments for the binary being run, if it is a test or benchmark.
     pub extra_test_binary_args: Vec<String>,
+    /// Additional environment variables for tests and benchmarks.
+    pub extra_test_env: FxHashMap<String, String>,
 }
 
 /// Configuration for workspace symbol search requests.
@@ -1579,6 +1596,8 @@ pub struct RunnablesConfig {
 pub struct WorkspaceSymbolConfig {
     /// Should imports be excluded.
     pub search_exclude_imports: bool,
+    /// Should associated items be excluded.
+    pub search_exclude_assoc_items: bool,
     /// In what scope should the symbol be searched in.
     pub search_scope: WorkspaceSymbolSearchScope,
     /// What kind of symbol is being searched for.
@@ -2224,11 +2243,28 @@ pub fn files(&self) -> FilesConfig {
                 _ => FilesWatcher::Server,
             },
             exclude: self.excluded().collect(),
+            exclude_globs: self.excluded_globs().collect(),
         }
     }
 
     pub fn excluded(&self) -> impl Iterator<Item = AbsPathBuf> + use<'_> {
-        self.files_exclude().iter().map(|it| self.root_path.join(it))
+        self.files_exclude()
+            .iter()
+            .filter(|it| !vfs::Glob::is_glob(it.as_str()))
+            .map(|it| self.root_path.join(it))
+    }
+
+    pub fn excluded_globs(&self) -> impl Iterator<Item = vfs::Glob> + use<'_> {
+        self.files_exclude()
+            .iter()
+            .filter(|it| vfs::Glob::is_glob(it.as_str()))
+            .map(|it| vfs::Glob::new(self.root_path.clone(), it.as_str()))
+    }
+
+    /// Whether `path` is excluded from the workspace by `files.exclude`.
+    pub fn is_excluded(&self, path: &AbsPath) -> bool {
+        self.excluded().any(|excluded| path.starts_with(&excluded))
+            || self.excluded_globs().any(|glob| glob.matches(path))
     }
 
     pub fn notifications(&self) -> NotificationsConfig {
@@ -2284,38 +2320,7 @@ pub fn cargo(&self, source_root: Option<SourceRootId>) -> CargoConfig {
             sysroot_src,
             rustc_source,
             extra_includes,
-            cfg_overrides: project_model::CfgOverrides {
-                global: {
-                    let (enabled, disabled): (Vec<_>, Vec<_>) =
-                        self.cargo_cfgs(source_root).iter().partition_map(|s| {
-                            s.strip_prefix("!").map_or(Either::Left(s), Either::Right)
-                        });
-                    CfgDiff::new(
-                        enabled
-                            .into_iter()
-                            // parse any cfg setting formatted as key=value or just key (without value)
-                            .map(|s| match s.split_once("=") {
-                                Some((key, val)) => CfgAtom::KeyValue {
-                                    key: Symbol::intern(key),
-                                    value: Symbol::intern(val),
-                                },
-                                None => CfgAtom::Flag(Symbol::intern(s)),
-                            })
-                            .collect(),
-                        disabled
-                            .into_iter()
-                            .map(|s| match s.split_once("=") {
-                                Some((key, val)) => CfgAtom::KeyValue {
-                                    key: Symbol::intern(key),
-                                    value: Symbol::intern(val),
-                                },
-                                None => CfgAtom::Flag(Symbol::intern(s)),
-                            })
-                            .collect(),
-                    )
-                },
-                selective: Default::default(),
-            },
+            cfg_overrides: self.cfg_overrides(source_root),
             wrap_rustc_in_build_scripts: *self.cargo_buildScripts_useRustcWrapper(source_root),
             invocation_strategy: match self.cargo_buildScripts_invocationStrategy(source_root) {
                 InvocationStrategy::Once => project_model::InvocationStrategy::Once,
@@ -2330,6 +2335,22 @@ pub fn cargo(&self, source_root: Option<SourceRootId>) -> CargoConfig {
         }
     }
 
+    fn cfg_overrides(&self, source_root: Option<SourceRootId>) -> project_model::CfgOverrides {
+        let crate_cfgs = self.cargo_crateCfgs(source_root);
+        project_model::CfgOverrides {
+            global: parse_cfg_diff(
+                self.cargo_cfgs(source_root)
+                    .iter()
+                    .chain(crate_cfgs.get("*").into_iter().flatten()),
+            ),
+            selective: crate_cfgs
+                .iter()
+                .filter(|(krate, _)| *krate != "*")
+                .map(|(krate, cfgs)| (krate.clone(), parse_cfg_diff(cfgs)))
+                .collect(),
+        }
+    }
+
     pub fn cfg_set_test(&self, source_root: Option<SourceRootId>) -> bool {
         *self.cfg_setTest(source_root)
     }
@@ -2499,6 +2520,7 @@ pub fn runnables(&self, source_root: Option<SourceRootId>) -> RunnablesConfig {
             override_cargo: self.runnables_command(source_root).clone(),
             cargo_extra_args: self.runnables_extraArgs(source_root).clone(),
             extra_test_binary_args: self.runnables_extraTestBinaryArgs(source_root).clone(),
+            extra_test_env: self.runnables_extraTestEnv(source_root).clone(),
         }
     }
 
@@ -2558,6 +2580,8 @@ pub fn document_symbol(&self, source_root: Option<SourceRootId>) -> DocumentSymb
     pub fn workspace_symbol(&self, source_root: Option<SourceRootId>) -> WorkspaceSymbolConfig {
         WorkspaceSymbolConfig {
             search_exclude_imports: *self.workspace_symbol_search_excludeImports(source_root),
+            search_exclude_assoc_items: *self
+                .workspace_symbol_search_excludeAssocItems(source_root),
             search_scope: match self.workspace_symbol_search_scope(source_root) {
                 WorkspaceSymbolSearchScopeDef::Workspace => WorkspaceSymbolSearchScope::Workspace,
                 WorkspaceSymbolSearchScopeDef::WorkspaceAndDependencies => {
@@ -3553,6 +3577,9 @@ macro_rules! set {
         "FxHashMap<String, Option<String>>" => set! {
             "type": "object",
         },
+        "FxHashMap<String, Vec<String>>" => set! {
+            "type": "object",
+        },
         "Option<usize>" => set! {
             "type": ["null", "integer"],
             "minimum": 0,
@@ -3997,6 +4024,29 @@ fn doc_comment_to_string(doc: &[&str]) -> String {
         .fold(String::new(), |mut acc, it| format_to_acc!(acc, "{it}\n"))
 }
 
+/// Parses cfg settings formatted as `key`, `key=value` or `!key` into a diff, later entries take
+/// precedence over earlier ones for the same cfg.
+fn parse_cfg_diff<'a>(cfgs: impl IntoIterator<Item = &'a String>) -> CfgDiff {
+    let mut atoms = FxHashMap::default();
+    for cfg in cfgs {
+        let (enable, cfg) = match cfg.strip_prefix('!') {
+            Some(cfg) => (false, cfg),
+            None => (true, cfg.as_str()),
+        };
+        let atom = match cfg.split_once('=') {
+            Some((key, value)) => {
+                CfgAtom::KeyValue { key: Symbol::intern(key), value: Symbol::intern(value) }
+            }
+            None => CfgAtom::Flag(Symbol::intern(cfg)),
+        };
+        atoms.insert(atom, enable);
+    }
+    let (enable, disable) = atoms.into_iter().partition_map(|(atom, enable)| {
+        if enable { Either::Left(atom) } else { Either::Right(atom) }
+    });
+    CfgDiff::new(enable, disable)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{borrow::Cow, fs};
@@ -4121,6 +4171,44 @@ fn proc_macro_srv_rel() {
         );
     }
 
+    #[test]
+    fn cargo_crate_cfgs() {
+        let mut config =
+            Config::new(AbsPathBuf::assert(project_root()), Default::default(), vec![], None);
+
+        let mut change = ConfigChange::default();
+        change.change_client_config(serde_json::json!({
+            "cargo" : {
+                "crateCfgs" : {
+                    "*": ["!miri", "tokio_unstable"],
+                    "foo": ["feature=x", "!test", "feature=x"],
+                }
+            }
+        }));
+
+        (config, _, _) = config.apply_change(change);
+        let flag = |name| CfgAtom::Flag(Symbol::intern(name));
+        let cfg_overrides = config.cargo(None).cfg_overrides;
+        assert_eq!(
+            cfg_overrides.global,
+            CfgDiff::new(
+                vec![flag("debug_assertions"), flag("tokio_unstable")],
+                vec![flag("miri")]
+            )
+        );
+        assert_eq!(cfg_overrides.selective.len(), 1);
+        assert_eq!(
+            cfg_overrides.selective["foo"],
+            CfgDiff::new(
+                vec![CfgAtom::KeyValue {
+                    key: Symbol::intern("feature"),
+                    value: Symbol::intern("x")
+                }],
+                vec![flag("test")]
+            )
+        );
+    }
+
     #[test]
     fn cargo_target_dir_unset() {
         let mut config =
@@ -168,3 +168,26 @@ fn check_on_save_patching_enable() {
         json!({ "checkOnSave": true, "check": { "enable": true, "overrideCommand": "foo" }})
     );
 }
+
+#[test]
+fn completion_callable_patching() {
+    let cases = [
+        (true, true, Some("fill_arguments")),
+        (false, true, Some("add_parentheses")),
+        // Argument snippets without parentheses keep the default.
+        (true, false, None),
+        (false, false, Some("none")),
+    ];
+    for (add_call_argument_snippets, add_call_parenthesis, expected) in cases {
+        let mut json = json!({ "completion": {
+            "addCallArgumentSnippets": add_call_argument_snippets,
+            "addCallParenthesis": add_call_parenthesis,
+        }});
+        patch_json_for_outdated_configs(&mut json);
+        assert_eq!(
+            json.pointer("/completion/callable/snippets").and_then(Value::as_str),
+            expected,
+            "arguments: {add_call_argument_snippets}, parentheses: {add_call_parenthesis}"
+        );
+    }
+}
@@ -4,7 +4,7 @@
 use std::mem;
 
 use cargo_metadata::PackageId;
-use ide::FileId;
+use ide::{Cancellable, FileId};
 use ide_db::{FxHashMap, base_db::DbPanicContext};
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
@@ -47,6 +47,9 @@ pub(crate) struct DiagnosticCollection {
         FxHashMap<FileId, (DiagnosticsGeneration, Vec<lsp_types::Diagnostic>)>,
     pub(crate) check: Vec<WorkspaceFlycheckDiagnostic>,
     pub(crate) check_fixes: CheckFixes,
+    /// Per flycheck, the error reported on the workspace manifest when the last check failed
+    /// without producing diagnostics. Cleared by the next successful check.
+    check_failures: FxHashMap<usize, (FileId, lsp_types::Diagnostic)>,
     changes: FxHashSet<FileId>,
     /// Counter for supplying a new generation number for diagnostics.
     /// This is used to keep track of when to clear the diagnostics for a given file as we compute
@@ -71,10 +74,12 @@ pub(crate) fn clear_check(&mut self, flycheck_id: usize) {
         if let Some(fixes) = Arc::make_mut(&mut self.check_fixes).get_mut(flycheck_id) {
             fixes.clear();
         }
+        self.clear_check_failure(flycheck_id);
     }
 
     pub(crate) fn clear_check_all(&mut self) {
         Arc::make_mut(&mut self.check_fixes).clear();
+        self.changes.extend(self.check_failures.drain().map(|(_, (file_id, _))| file_id));
         self.changes.extend(
             self.check
                 .iter_mut()
@@ -144,6 +149,26 @@ pub(crate) fn clear_check_older_than_for_package(
         }
     }
 
+    pub(crate) fn set_check_failure(
+        &mut self,
+        flycheck_id: usize,
+        file_id: FileId,
+        diagnostic: lsp_types::Diagnostic,
+    ) {
+        if let Some((old_file_id, _)) =
+            self.check_failures.insert(flycheck_id, (file_id, diagnostic))
+        {
+            self.changes.insert(old_file_id);
+        }
+        self.changes.insert(file_id);
+    }
+
+    pub(crate) fn clear_check_failure(&mut self, flycheck_id: usize) {
+        if let Some((file_id, _)) = self.check_failures.remove(&flycheck_id) {
+            self.changes.insert(file_id);
+        }
+    }
+
     pub(crate) fn clear_native_for(&mut self, file_id: FileId) {
         self.native_syntax.remove(&file_id);
         self.native_semantic.remove(&file_id);
@@ -244,7 +269,11 @@ pub(crate) fn diagnostics_for(
             .flat_map(|it| it.per_package.values())
             .filter_map(move |it| it.per_file.get(&file_id))
             .flatten();
-        native_syntax.chain(native_semantic).chain(check)
+        let check_failures = self
+            .check_failures
+            .values()
+            .filter_map(move |(it, diagnostic)| (*it == file_id).then_some(diagnostic));
+        native_syntax.chain(native_semantic).chain(check).chain(check_failures)
     }
 
     pub(crate) fn take_changes(&mut self) -> Option<FxHashSet<FileId>> {
@@ -284,38 +313,43 @@ pub(crate) fn fetch_native_diagnostics(
     // the diagnostics produced may point to different files not requested by the concrete request,
     // put those into here and filter later
     let mut odd_ones = Vec::new();
+    // Once the analysis gets cancelled every following file would fail as well, so we stop
+    // early to hand the thread back to more urgent work.
     let mut diagnostics = subscriptions[slice]
         .iter()
         .copied()
-        .filter_map(|file_id| {
-            let line_index = snapshot.file_line_index(file_id).ok()?;
-            let source_root = snapshot.analysis.source_root_id(file_id).ok()?;
-
-            let config = &snapshot.config.diagnostics(Some(source_root));
-            let diagnostics = match kind {
-                NativeDiagnosticsFetchKind::Syntax => {
-                    snapshot.analysis.syntax_diagnostics(config, file_id).ok()?
-                }
+        .map_while(|file_id| {
+            let mut fetch = || -> Cancellable<_> {
+                let line_index = snapshot.file_line_index(file_id)?;
+                let source_root = snapshot.analysis.source_root_id(file_id)?;
+
+                let config = &snapshot.config.diagnostics(Some(source_root));
+                let diagnostics = match kind {
+                    NativeDiagnosticsFetchKind::Syntax => {
+                        snapshot.analysis.syntax_diagnostics(config, file_id)?
+                    }
 
-                NativeDiagnosticsFetchKind::Semantic if config.enabled => snapshot
-                    .analysis
-                    .semantic_diagnostics(config, ide::AssistResolveStrategy::None, file_id)
-                    .ok()?,
-                NativeDiagnosticsFetchKind::Semantic => return None,
+                    NativeDiagnosticsFetchKind::Semantic if config.enabled => snapshot
+                        .analysis
+                        .semantic_diagnostics(config, ide::AssistResolveStrategy::None, file_id)?,
+                    NativeDiagnosticsFetchKind::Semantic => return Ok(None),
+                };
+                let diagnostics = diagnostics
+                    .into_iter()
+                    .filter_map(|d| {
+                        if d.range.file_id == file_id {
+                            Some(convert_diagnostic(&line_index, d))
+                        } else {
+                            odd_ones.push(d);
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Ok(Some((file_id, diagnostics)))
             };
-            let diagnostics = diagnostics
-                .into_iter()
-                .filter_map(|d| {
-                    if d.range.file_id == file_id {
-                        Some(convert_diagnostic(&line_index, d))
-                    } else {
-                        odd_ones.push(d);
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            Some((file_id, diagnostics))
+            fetch().ok()
         })
+        .flatten()
         .collect::<Vec<_>>();
 
     // Add back any diagnostics that point to files we are subscribed to
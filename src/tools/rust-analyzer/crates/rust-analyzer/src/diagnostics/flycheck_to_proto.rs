@@ -195,6 +195,7 @@ fn map_rust_child_diagnostic(
     let mut edit_map: FxHashMap<lsp_types::Url, Vec<lsp_types::TextEdit>> = FxHashMap::default();
     let mut suggested_replacements = Vec::new();
     let mut is_preferred = true;
+    let mut is_applicable = true;
     for &span in &spans {
         if let Some(suggested_replacement) = &span.suggested_replacement {
             if !suggested_replacement.is_empty() {
@@ -207,12 +208,12 @@ fn map_rust_child_diagnostic(
             // We accept both "MaybeIncorrect" and "MachineApplicable". "MaybeIncorrect" means that
             // the suggestion is *complete* (contains no placeholders where code needs to be
             // inserted), but might not be what the user wants, or might need minor adjustments.
-            if matches!(
+            // A suggestion with several parts is only useful if all of them are applied.
+            is_applicable &= matches!(
                 span.suggestion_applicability,
                 None | Some(Applicability::MaybeIncorrect | Applicability::MachineApplicable)
-            ) {
-                edit_map.entry(location.uri).or_default().push(edit);
-            }
+            );
+            edit_map.entry(location.uri).or_default().push(edit);
             is_preferred &=
                 matches!(span.suggestion_applicability, Some(Applicability::MachineApplicable));
         }
@@ -228,7 +229,7 @@ fn map_rust_child_diagnostic(
         message.push_str(&suggestions);
     }
 
-    let suggested_fix = if edit_map.is_empty() {
+    let suggested_fix = if edit_map.is_empty() || !is_applicable {
         None
     } else {
         Some(Box::new(Fix {
@@ -545,6 +546,152 @@ fn check_with_config(config: DiagnosticsMapConfig, diagnostics_json: &str, expec
         expect.assert_debug_eq(&actual)
     }
 
+    const RUSTC_UNUSED_IMPORT_IN_NESTED_USE: &str = r##"{
+        "message": "unused import: `collections::HashMap`",
+        "code": {
+            "code": "unused_imports",
+            "explanation": null
+        },
+        "level": "warning",
+        "spans": [
+            {
+                "file_name": "lib.rs",
+                "byte_start": 10,
+                "byte_end": 30,
+                "line_start": 1,
+                "line_end": 1,
+                "column_start": 11,
+                "column_end": 31,
+                "is_primary": true,
+                "text": [
+                    {
+                        "text": "use std::{collections::HashMap, fmt::Debug};",
+                        "highlight_start": 11,
+                        "highlight_end": 31
+                    }
+                ],
+                "label": null,
+                "suggested_replacement": null,
+                "suggestion_applicability": null,
+                "expansion": null
+            }
+        ],
+        "children": [
+            {
+                "message": "`#[warn(unused_imports)]` (part of `#[warn(unused)]`) on by default",
+                "code": null,
+                "level": "note",
+                "spans": [],
+                "children": [],
+                "rendered": null
+            },
+            {
+                "message": "remove the unused import",
+                "code": null,
+                "level": "help",
+                "spans": [
+                    {
+                        "file_name": "lib.rs",
+                        "byte_start": 10,
+                        "byte_end": 32,
+                        "line_start": 1,
+                        "line_end": 1,
+                        "column_start": 11,
+                        "column_end": 33,
+                        "is_primary": true,
+                        "text": [
+                            {
+                                "text": "use std::{collections::HashMap, fmt::Debug};",
+                                "highlight_start": 11,
+                                "highlight_end": 33
+                            }
+                        ],
+                        "label": null,
+                        "suggested_replacement": "",
+                        "suggestion_applicability": "MachineApplicable",
+                        "expansion": null
+                    },
+                    {
+                        "file_name": "lib.rs",
+                        "byte_start": 9,
+                        "byte_end": 10,
+                        "line_start": 1,
+                        "line_end": 1,
+                        "column_start": 10,
+                        "column_end": 11,
+                        "is_primary": true,
+                        "text": [
+                            {
+                                "text": "use std::{collections::HashMap, fmt::Debug};",
+                                "highlight_start": 10,
+                                "highlight_end": 11
+                            }
+                        ],
+                        "label": null,
+                        "suggested_replacement": "",
+                        "suggestion_applicability": "MachineApplicable",
+                        "expansion": null
+                    },
+                    {
+                        "file_name": "lib.rs",
+                        "byte_start": 42,
+                        "byte_end": 43,
+                        "line_start": 1,
+                        "line_end": 1,
+                        "column_start": 43,
+                        "column_end": 44,
+                        "is_primary": true,
+                        "text": [
+                            {
+                                "text": "use std::{collections::HashMap, fmt::Debug};",
+                                "highlight_start": 43,
+                                "highlight_end": 44
+                            }
+                        ],
+                        "label": null,
+                        "suggested_replacement": "",
+                        "suggestion_applicability": "MachineApplicable",
+                        "expansion": null
+                    }
+                ],
+                "children": [],
+                "rendered": null
+            }
+        ],
+        "rendered": "warning: unused import: `collections::HashMap`\n --> lib.rs:1:11\n  |\n1 | use std::{collections::HashMap, fmt::Debug};\n  |           ^^^^^^^^^^^^^^^^^^^^\n  |\n  = note: `#[warn(unused_imports)]` (part of `#[warn(unused)]`) on by default\n\n"
+    }"##;
+
+    #[test]
+    fn rustc_unused_import_multipart_fix() {
+        check(
+            RUSTC_UNUSED_IMPORT_IN_NESTED_USE,
+            expect_file!["./test_data/rustc_unused_import_multipart_fix.txt"],
+        );
+    }
+
+    #[test]
+    fn rustc_unused_import_maybe_incorrect_fix() {
+        check(
+            &RUSTC_UNUSED_IMPORT_IN_NESTED_USE.replace(
+                r#""suggestion_applicability": "MachineApplicable""#,
+                r#""suggestion_applicability": "MaybeIncorrect""#,
+            ),
+            expect_file!["./test_data/rustc_unused_import_maybe_incorrect_fix.txt"],
+        );
+    }
+
+    #[test]
+    fn rustc_unused_import_partially_applicable_fix() {
+        check(
+            &RUSTC_UNUSED_IMPORT_IN_NESTED_USE.replacen(
+                r#""suggestion_applicability": "MachineApplicable""#,
+                r#""suggestion_applicability": "HasPlaceholders""#,
+                1,
+            ),
+            expect_file!["./test_data/rustc_unused_import_partially_applicable_fix.txt"],
+        );
+    }
+
     #[test]
     fn rustc_incompatible_type_for_trait() {
         check(
@@ -51,11 +51,19 @@ pub(crate) struct CargoOptions {
 }
 
 #[derive(Clone, Debug)]
-pub(crate) enum Target {
-    Bin(String),
-    Example(String),
-    Benchmark(String),
-    Test(String),
+pub(crate) struct Target {
+    pub(crate) kind: TargetKind,
+    pub(crate) name: String,
+    /// Features listed in the target's `required-features`, without which Cargo skips it.
+    pub(crate) required_features: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum TargetKind {
+    Bin,
+    Example,
+    Benchmark,
+    Test,
 }
 
 impl CargoOptions {
@@ -249,6 +257,10 @@ pub(crate) enum FlycheckMessage {
     /// Request clearing all outdated diagnostics.
     ClearDiagnostics { id: usize, kind: ClearDiagnosticsKind },
 
+    /// Request reporting a failed check that produced no diagnostics, like a toolchain or
+    /// manifest error, on the manifest of the checked workspace.
+    CommandFailed { id: usize, manifest_path: AbsPathBuf, message: String },
+
     /// Request check progress notification to client
     Progress {
         /// Flycheck instance ID
@@ -277,6 +289,11 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             FlycheckMessage::ClearDiagnostics { id, kind } => {
                 f.debug_struct("ClearDiagnostics").field("id", id).field("kind", kind).finish()
             }
+            FlycheckMessage::CommandFailed { id, manifest_path, message: _ } => f
+                .debug_struct("CommandFailed")
+                .field("id", id)
+                .field("manifest_path", manifest_path)
+                .finish(),
             FlycheckMessage::Progress { id, progress } => {
                 f.debug_struct("Progress").field("id", id).field("progress", progress).finish()
             }
@@ -516,6 +533,16 @@ fn run(mut self, inbox: Receiver<StateChange>) {
                                 }
                             }
                         }
+                        if let Err(error) = &res {
+                            self.send(FlycheckMessage::CommandFailed {
+                                id: self.id,
+                                manifest_path: self
+                                    .manifest_path
+                                    .clone()
+                                    .unwrap_or_else(|| self.root.join("Cargo.toml")),
+                                message: error.to_string(),
+                            });
+                        }
                     } else if res.is_ok() {
                         // We clear diagnostics for packages on
                         // `[CargoCheckMessage::CompilerArtifact]` but there seem to be setups where
@@ -670,12 +697,17 @@ fn check_command(
                 };
 
                 if let Some(tgt) = target {
-                    match tgt {
-                        Target::Bin(tgt) => cmd.arg("--bin").arg(tgt),
-                        Target::Example(tgt) => cmd.arg("--example").arg(tgt),
-                        Target::Test(tgt) => cmd.arg("--test").arg(tgt),
-                        Target::Benchmark(tgt) => cmd.arg("--bench").arg(tgt),
+                    let flag = match tgt.kind {
+                        TargetKind::Bin => "--bin",
+                        TargetKind::Example => "--example",
+                        TargetKind::Test => "--test",
+                        TargetKind::Benchmark => "--bench",
                     };
+                    cmd.arg(flag).arg(tgt.name);
+                    if !options.all_features && !tgt.required_features.is_empty() {
+                        cmd.arg("--features");
+                        cmd.arg(tgt.required_features.join(" "));
+                    }
                 }
 
                 cmd.arg(if *ansi_color_output {
@@ -794,3 +826,91 @@ enum JsonMessage {
     Cargo(cargo_metadata::Message),
     Rustc(Diagnostic),
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use ide::FileId;
+
+    use crate::diagnostics::DiagnosticCollection;
+
+    use super::*;
+
+    fn recv(receiver: &Receiver<FlycheckMessage>) -> FlycheckMessage {
+        receiver.recv_timeout(Duration::from_secs(30)).expect("flycheck did not respond")
+    }
+
+    #[test]
+    fn failed_check_is_reported_until_next_successful_run() {
+        let root = std::env::temp_dir().join(format!("ra-flycheck-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let root = AbsPathBuf::assert_utf8(root);
+
+        // Fails without printing any JSON until the `ok` marker exists, like a broken toolchain.
+        let config = FlycheckConfig::CustomCommand {
+            command: "sh".to_owned(),
+            args: vec![
+                "-c".to_owned(),
+                "test -e ok || { echo 'error: toolchain is broken' >&2; exit 1; }".to_owned(),
+            ],
+            extra_env: Default::default(),
+            invocation_strategy: InvocationStrategy::PerWorkspace,
+        };
+        let (sender, receiver) = unbounded();
+        let flycheck = FlycheckHandle::spawn(0, 0, sender, config, None, root.clone(), None, None);
+        let manifest = FileId::from_raw(0);
+        let mut diagnostics = DiagnosticCollection::default();
+
+        flycheck.restart_workspace(None);
+        assert!(matches!(
+            recv(&receiver),
+            FlycheckMessage::Progress { progress: Progress::DidStart, .. }
+        ));
+        assert!(matches!(
+            recv(&receiver),
+            FlycheckMessage::ClearDiagnostics {
+                kind: ClearDiagnosticsKind::All(ClearScope::Workspace),
+                ..
+            }
+        ));
+        let FlycheckMessage::CommandFailed { id, manifest_path, message } = recv(&receiver) else {
+            panic!("expected the failed command to be reported");
+        };
+        assert_eq!(manifest_path, root.join("Cargo.toml"));
+        assert!(message.contains("error: toolchain is broken"), "{message}");
+        diagnostics.set_check_failure(
+            id,
+            manifest,
+            lsp_types::Diagnostic { message, ..Default::default() },
+        );
+        assert!(matches!(
+            recv(&receiver),
+            FlycheckMessage::Progress { progress: Progress::DidFinish(Err(_)), .. }
+        ));
+        assert_eq!(diagnostics.take_changes(), Some(FxHashSet::from_iter([manifest])));
+        assert_eq!(diagnostics.diagnostics_for(manifest).count(), 1);
+
+        std::fs::write(root.join("ok"), "").unwrap();
+        flycheck.restart_workspace(None);
+        assert!(matches!(
+            recv(&receiver),
+            FlycheckMessage::Progress { progress: Progress::DidStart, .. }
+        ));
+        assert!(matches!(
+            recv(&receiver),
+            FlycheckMessage::ClearDiagnostics {
+                kind: ClearDiagnosticsKind::All(ClearScope::Workspace),
+                ..
+            }
+        ));
+        let FlycheckMessage::Progress { id, progress: Progress::DidFinish(Ok(())) } =
+            recv(&receiver)
+        else {
+            panic!("expected the check to succeed");
+        };
+        diagnostics.clear_check_failure(id);
+        assert_eq!(diagnostics.take_changes(), Some(FxHashSet::from_iter([manifest])));
+        assert_eq!(diagnostics.diagnostics_for(manifest).count(), 0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
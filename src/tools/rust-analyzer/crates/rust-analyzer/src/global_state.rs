@@ -27,7 +27,7 @@
 use proc_macro_api::ProcMacroClient;
 use project_model::{ManifestPath, ProjectWorkspace, ProjectWorkspaceKind, WorkspaceBuildScripts};
 use rustc_hash::{FxHashMap, FxHashSet};
-use stdx::thread;
+use stdx::thread::{self, PoolMetrics};
 use tracing::{Level, span, trace};
 use triomphe::Arc;
 use vfs::{AbsPathBuf, AnchoredPathBuf, ChangeKind, Vfs, VfsPath};
@@ -38,7 +38,7 @@
     discover,
     flycheck::{FlycheckHandle, FlycheckMessage},
     line_index::{LineEndings, LineIndex},
-    lsp::{from_proto, to_proto::url_from_abs_path},
+    lsp::{from_proto, to_proto::url_from_abs_path, utils::StartupProgress},
     lsp_ext,
     main_loop::Task,
     mem_docs::MemDocs,
@@ -85,10 +85,23 @@ pub(crate) struct Handle<H, C> {
 pub(crate) struct GlobalState {
     sender: Sender<lsp_server::Message>,
     req_queue: ReqQueue,
+    /// Requests cancelled by the client whose handlers are still running on a worker thread.
+    ///
+    /// The client already got a `RequestCanceled` response for these, so the late handler result
+    /// has to be dropped, and the id must not be reused until that happened.
+    cancelled_requests: FxHashSet<lsp_server::RequestId>,
+    /// The most recent request per supersedable method and document, see
+    /// [`GlobalState::supersede_request`].
+    latest_requests: FxHashMap<(&'static str, Url), lsp_server::RequestId>,
 
     pub(crate) task_pool: Handle<TaskPool<Task>, Receiver<Task>>,
     pub(crate) fmt_pool: Handle<TaskPool<Task>, Receiver<Task>>,
     pub(crate) cancellation_pool: thread::Pool,
+    /// The number of events the main loop has handled so far.
+    pub(crate) loop_turns: u64,
+    /// A message taken from the client's inbox while batching `didChange` notifications that
+    /// has to be handled next.
+    pub(crate) lookahead_message: Option<lsp_server::Message>,
 
     pub(crate) config: Arc<Config>,
     pub(crate) config_errors: Option<ConfigErrors>,
@@ -140,6 +153,9 @@ pub(crate) struct GlobalState {
     // used to track how long VFS loading takes. this can't be on `vfs::loader::Handle`,
     // as that handle's lifetime is the same as `GlobalState` itself.
     pub(crate) vfs_span: Option<tracing::span::EnteredSpan>,
+    /// Whether the start and end of the initial indexing was announced to clients
+    /// that don't support work done progress.
+    pub(crate) startup_progress: StartupProgress,
     pub(crate) wants_to_switch: Option<Cause>,
 
     /// `workspaces` field stores the data we actually use, while the `OpQueue`
@@ -218,6 +234,8 @@ pub(crate) struct GlobalStateSnapshot {
     // FIXME: Can we derive this from somewhere else?
     pub(crate) proc_macros_loaded: bool,
     pub(crate) flycheck: Arc<[FlycheckHandle]>,
+    pub(crate) task_pool_metrics: PoolMetrics,
+    pub(crate) loop_turns: u64,
     minicore: MiniCoreRustAnalyzerInternalOnly,
 }
 
@@ -261,9 +279,13 @@ pub(crate) fn new(sender: Sender<lsp_server::Message>, config: Config) -> Global
         let mut this = GlobalState {
             sender,
             req_queue: ReqQueue::default(),
+            cancelled_requests: FxHashSet::default(),
+            latest_requests: FxHashMap::default(),
             task_pool,
             fmt_pool,
             cancellation_pool,
+            loop_turns: 0,
+            lookahead_message: None,
             loader,
             config: Arc::new(config.clone()),
             analysis_host,
@@ -306,6 +328,7 @@ pub(crate) fn new(sender: Sender<lsp_server::Message>, config: Config) -> Global
             vfs_progress_config_version: 0,
             vfs_span: None,
             vfs_done: true,
+            startup_progress: StartupProgress::Pending,
             wants_to_switch: None,
 
             workspaces: Arc::from(Vec::new()),
@@ -584,6 +607,8 @@ pub(crate) fn snapshot(&self) -> GlobalStateSnapshot {
             proc_macros_loaded: !self.config.expand_proc_macros()
                 || self.fetch_proc_macros_queue.last_op_result().copied().unwrap_or(false),
             flycheck: self.flycheck.clone(),
+            task_pool_metrics: self.task_pool.handle.metrics(),
+            loop_turns: self.loop_turns,
         }
     }
 
@@ -613,17 +638,43 @@ pub(crate) fn send_notification<N: lsp_types::notification::Notification>(
         self.send(not.into());
     }
 
+    /// Registers an incoming request, returning `false` if it must not be handled.
+    ///
+    /// Responses may be sent in any order, but each request gets exactly one of them. A client
+    /// reusing the id of a request that is still in flight (including one it cancelled whose
+    /// handler hasn't finished yet) would break that, so such a request is answered with
+    /// `InvalidRequest` right away and the original request is left untouched.
     pub(crate) fn register_request(
         &mut self,
         request: &lsp_server::Request,
         request_received: Instant,
-    ) {
+    ) -> bool {
+        if !self.req_queue.incoming.is_completed(&request.id)
+            || self.cancelled_requests.contains(&request.id)
+        {
+            self.send(
+                lsp_server::Response::new_err(
+                    request.id.clone(),
+                    lsp_server::ErrorCode::InvalidRequest as i32,
+                    format!("request id {} is already in flight", request.id),
+                )
+                .into(),
+            );
+            return false;
+        }
         self.req_queue
             .incoming
             .register(request.id.clone(), (request.method.clone(), request_received));
+        true
     }
 
     pub(crate) fn respond(&mut self, response: lsp_server::Response) {
+        self.forget_latest_request(&response.id);
+        // The client has already been answered for cancelled requests, whatever the handler's
+        // outcome was, so its result is dropped and the id can be reused from now on.
+        if self.cancelled_requests.remove(&response.id) {
+            return;
+        }
         if let Some((method, start)) = self.req_queue.incoming.complete(&response.id) {
             if let Some(err) = &response.error
                 && err.message.starts_with("server panicked")
@@ -638,11 +689,51 @@ pub(crate) fn respond(&mut self, response: lsp_server::Response) {
     }
 
     pub(crate) fn cancel(&mut self, request_id: lsp_server::RequestId) {
-        if let Some(response) = self.req_queue.incoming.cancel(request_id) {
+        if let Some(response) = self.req_queue.incoming.cancel(request_id.clone()) {
+            self.forget_latest_request(&request_id);
+            self.cancelled_requests.insert(request_id);
             self.send(response.into());
         }
     }
 
+    /// Cancels the still pending request of the same kind for the same document, if any.
+    ///
+    /// Completion and hover results are only useful for the latest cursor position, so while the
+    /// user is typing there is no point in computing the stale ones queued up before.
+    pub(crate) fn supersede_request(&mut self, request: &lsp_server::Request) {
+        use lsp_types::request::{Completion, HoverRequest, Request};
+
+        let Some(method) =
+            [Completion::METHOD, HoverRequest::METHOD].into_iter().find(|&it| it == request.method)
+        else {
+            return;
+        };
+        let Some(uri) =
+            request.params["textDocument"]["uri"].as_str().and_then(|uri| Url::parse(uri).ok())
+        else {
+            return;
+        };
+        if let Some(previous) = self.latest_requests.insert((method, uri), request.id.clone())
+            && previous != request.id
+            && !self.req_queue.incoming.is_completed(&previous)
+        {
+            tracing::debug!(method, %previous, superseded_by = %request.id, "cancelling superseded request");
+            self.cancel(previous);
+        }
+    }
+
+    /// Drops the [`GlobalState::supersede_request`] bookkeeping for a request that got answered,
+    /// only requests that are still pending can be superseded.
+    fn forget_latest_request(&mut self, request_id: &lsp_server::RequestId) {
+        self.latest_requests.retain(|_, latest| latest != request_id);
+    }
+
+    /// Called when the handler of a request gave up without a response, returns `true` if the
+    /// request was cancelled in the meantime and must not be retried.
+    pub(crate) fn finish_cancelled(&mut self, request_id: &lsp_server::RequestId) -> bool {
+        self.cancelled_requests.remove(request_id)
+    }
+
     pub(crate) fn is_completed(&self, request: &lsp_server::Request) -> bool {
         self.req_queue.incoming.is_completed(&request.id)
     }
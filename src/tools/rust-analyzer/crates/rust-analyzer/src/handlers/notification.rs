@@ -18,7 +18,7 @@
 
 use crate::{
     config::{Config, ConfigChange},
-    flycheck::{InvocationStrategy, Target},
+    flycheck::{InvocationStrategy, Target, TargetKind},
     global_state::{FetchWorkspaceRequest, GlobalState},
     lsp::{from_proto, utils::apply_document_changes},
     lsp_ext::{self, RunFlycheckParams},
@@ -77,7 +77,7 @@ pub(crate) fn handle_did_open_text_document(
         }
 
         if let Some(abs_path) = path.as_path()
-            && state.config.excluded().any(|excluded| abs_path.starts_with(&excluded))
+            && state.config.is_excluded(abs_path)
         {
             tracing::trace!("opened excluded file {abs_path}");
             state.vfs.write().0.insert_excluded_file(path);
@@ -329,21 +329,24 @@ fn run_flycheck(state: &mut GlobalState, vfs_path: VfsPath) -> bool {
                 InvocationStrategy::PerWorkspace => {
                     Box::new(move || {
                         let target = TargetSpec::for_file(&world, file_id)?.and_then(|it| {
-                            let tgt_kind = it.target_kind();
-                            let (tgt_name, root, package) = match it {
-                                TargetSpec::Cargo(c) => (c.target, c.workspace_root, c.package_id),
-                                _ => return None,
+                            let TargetSpec::Cargo(c) = it else {
+                                return None;
                             };
 
-                            let tgt = match tgt_kind {
-                                project_model::TargetKind::Bin => Target::Bin(tgt_name),
-                                project_model::TargetKind::Example => Target::Example(tgt_name),
-                                project_model::TargetKind::Test => Target::Test(tgt_name),
-                                project_model::TargetKind::Bench => Target::Benchmark(tgt_name),
-                                _ => return Some((None, root, package)),
+                            let kind = match c.target_kind {
+                                project_model::TargetKind::Bin => TargetKind::Bin,
+                                project_model::TargetKind::Example => TargetKind::Example,
+                                project_model::TargetKind::Test => TargetKind::Test,
+                                project_model::TargetKind::Bench => TargetKind::Benchmark,
+                                _ => return Some((None, c.workspace_root, c.package_id)),
+                            };
+                            let tgt = Target {
+                                kind,
+                                name: c.target,
+                                required_features: c.required_features,
                             };
 
-                            Some((Some(tgt), root, package))
+                            Some((Some(tgt), c.workspace_root, c.package_id))
                         });
                         tracing::debug!(?target, "flycheck target");
                         // we have a specific non-library target, attempt to only check that target, nothing
@@ -12,7 +12,7 @@
     InlayFieldsToResolve, Query, RangeInfo, ReferenceCategory, Runnable, RunnableKind,
     SingleResolve, SourceChange, TextEdit,
 };
-use ide_db::{FxHashMap, SymbolKind};
+use ide_db::{FxHashMap, SymbolKind, items_locator::AssocSearchMode};
 use itertools::Itertools;
 use lsp_server::ErrorCode;
 use lsp_types::{
@@ -117,6 +117,12 @@ pub(crate) fn handle_analyzer_status(
             .unwrap_or_else(|_| "Analysis retrieval was cancelled".to_owned()),
     );
 
+    buf.push_str("\nTask pool:\n");
+    for lane in snap.task_pool_metrics.lanes() {
+        format_to!(buf, "{:?}: {} queued, max wait {:?}\n", lane.lane, lane.depth, lane.max_wait);
+    }
+    format_to!(buf, "Main loop turns: {}\n", snap.loop_turns);
+
     buf.push_str("\nVersion: \n");
     format_to!(buf, "{}", crate::version());
 
@@ -675,36 +681,93 @@ pub(crate) fn handle_workspace_symbol(
     let _p = tracing::info_span!("handle_workspace_symbol").entered();
 
     let config = snap.config.workspace_symbol(None);
-    let (all_symbols, libs) = decide_search_kind_and_scope(&params, &config);
+    let parsed = parse_symbol_query(&params.query);
+    let (all_symbols, libs) = decide_search_kind_and_scope(&params, &parsed, &config);
 
-    let query = {
-        let query: String = params.query.chars().filter(|&c| c != '#' && c != '*').collect();
-        let mut q = Query::new(query);
-        if !all_symbols {
+    let make_query = |only_types: bool| {
+        let mut q = Query::new(parsed.name.clone());
+        if only_types {
             q.only_types();
         }
         if libs {
             q.libs();
         }
+        if parsed.exact {
+            q.exact();
+            q.case_sensitive();
+        }
         if config.search_exclude_imports {
             q.exclude_imports();
         }
+        if config.search_exclude_assoc_items {
+            q.assoc_search_mode(AssocSearchMode::Exclude);
+        }
+        q.kinds(parsed.kinds.iter().copied());
         q
     };
-    let mut res = exec_query(&snap, query, config.search_limit)?;
+    let mut res = exec_query(&snap, make_query(!all_symbols), config.search_limit)?;
     if res.is_empty() && !all_symbols {
-        res = exec_query(&snap, Query::new(params.query), config.search_limit)?;
+        res = exec_query(&snap, make_query(false), config.search_limit)?;
     }
 
     return Ok(Some(lsp_types::WorkspaceSymbolResponse::Nested(res)));
 
+    struct SymbolQuery {
+        name: String,
+        /// A `#` anywhere in the query searches all symbols instead of only types.
+        all_symbols: bool,
+        /// A trailing `#` requires the name to match exactly.
+        exact: bool,
+        /// A `*` anywhere in the query includes dependencies.
+        libs: bool,
+        /// An item keyword prefix like `fn foo` filters by kind.
+        kinds: Vec<SymbolKind>,
+    }
+
+    fn parse_symbol_query(query: &str) -> SymbolQuery {
+        let query = query.trim();
+        let (kinds, rest) = match query.split_once(char::is_whitespace) {
+            Some((keyword, rest)) => match keyword_symbol_kinds(keyword) {
+                Some(kinds) => (kinds.to_vec(), rest.trim_start()),
+                None => (Vec::new(), query),
+            },
+            None => (Vec::new(), query),
+        };
+        SymbolQuery {
+            name: rest.chars().filter(|&c| c != '#' && c != '*').collect(),
+            all_symbols: rest.contains('#'),
+            exact: rest.trim_end_matches('*').ends_with('#'),
+            libs: rest.contains('*'),
+            kinds,
+        }
+    }
+
+    fn keyword_symbol_kinds(keyword: &str) -> Option<&'static [SymbolKind]> {
+        let kinds: &[_] = match keyword {
+            "fn" => &[SymbolKind::Function, SymbolKind::Method],
+            "struct" => &[SymbolKind::Struct],
+            "enum" => &[SymbolKind::Enum],
+            "union" => &[SymbolKind::Union],
+            "trait" => &[SymbolKind::Trait],
+            "type" => &[SymbolKind::TypeAlias],
+            "const" => &[SymbolKind::Const],
+            "static" => &[SymbolKind::Static],
+            "mod" => &[SymbolKind::Module],
+            "macro" => &[SymbolKind::Macro, SymbolKind::ProcMacro],
+            _ => return None,
+        };
+        Some(kinds)
+    }
+
     fn decide_search_kind_and_scope(
         params: &WorkspaceSymbolParams,
+        parsed: &SymbolQuery,
         config: &WorkspaceSymbolConfig,
     ) -> (bool, bool) {
-        // Support old-style parsing of markers in the query.
-        let mut all_symbols = params.query.contains('#');
-        let mut libs = params.query.contains('*');
+        // Support old-style parsing of markers in the query. A kind filter
+        // already narrows the search, so it doesn't need to be limited to types.
+        let mut all_symbols = parsed.all_symbols || !parsed.kinds.is_empty();
+        let mut libs = parsed.libs;
 
         // If no explicit marker was set, check request params. If that's also empty
         // use global config.
@@ -740,7 +803,8 @@ fn exec_query(
     ) -> anyhow::Result<Vec<lsp_types::WorkspaceSymbol>> {
         let mut res = Vec::new();
         for nav in snap.analysis.symbol_search(query, limit)? {
-            let container_name = nav.container_name.as_ref().map(|v| v.to_string());
+            let container_name =
+                nav.container_name.as_ref().or(nav.crate_name.as_ref()).map(|v| v.to_string());
 
             let info = lsp_types::WorkspaceSymbol {
                 name: match &nav.alias {
@@ -1038,6 +1102,15 @@ pub(crate) fn handle_runnables(
                     cargo_args.push("--all-targets".to_owned());
                 }
                 cargo_args.extend(config.cargo_extra_args.iter().cloned());
+                let mut environment: FxHashMap<_, _> = spec
+                    .sysroot_root
+                    .as_ref()
+                    .map(|root| ("RUSTC_TOOLCHAIN".to_owned(), root.to_string()))
+                    .into_iter()
+                    .collect();
+                if cmd == "test" {
+                    environment.extend(config.extra_test_env.clone());
+                }
                 res.push(lsp_ext::Runnable {
                     label: format!(
                         "cargo {cmd} -p {}{all_targets}",
@@ -1052,12 +1125,7 @@ pub(crate) fn handle_runnables(
                         override_cargo: config.override_cargo.clone(),
                         cargo_args,
                         executable_args: Vec::new(),
-                        environment: spec
-                            .sysroot_root
-                            .as_ref()
-                            .map(|root| ("RUSTC_TOOLCHAIN".to_owned(), root.to_string()))
-                            .into_iter()
-                            .collect(),
+                        environment,
                     }),
                 })
             }
@@ -1382,7 +1450,7 @@ pub(crate) fn handle_rename(
 pub(crate) fn handle_references(
     snap: GlobalStateSnapshot,
     params: lsp_types::ReferenceParams,
-) -> anyhow::Result<Option<Vec<Location>>> {
+) -> anyhow::Result<Option<Vec<lsp_ext::ReferenceLocation>>> {
     let _p = tracing::info_span!("handle_references").entered();
     let position = try_default!(from_proto::file_position(&snap, params.text_document_position)?);
 
@@ -1402,9 +1470,12 @@ pub(crate) fn handle_references(
         .into_iter()
         .flat_map(|refs| {
             let decl = if include_declaration {
-                refs.declaration.map(|decl| FileRange {
-                    file_id: decl.nav.file_id,
-                    range: decl.nav.focus_or_full_range(),
+                refs.declaration.map(|decl| {
+                    let frange = FileRange {
+                        file_id: decl.nav.file_id,
+                        range: decl.nav.focus_or_full_range(),
+                    };
+                    (frange, ReferenceCategory::empty())
                 })
             } else {
                 None
@@ -1417,12 +1488,12 @@ pub(crate) fn handle_references(
                             (!exclude_imports || !category.contains(ReferenceCategory::IMPORT))
                                 && (!exclude_tests || !category.contains(ReferenceCategory::TEST))
                         })
-                        .map(move |(range, _)| FileRange { file_id, range })
+                        .map(move |(range, category)| (FileRange { file_id, range }, category))
                 })
                 .chain(decl)
         })
-        .unique()
-        .filter_map(|frange| to_proto::location(&snap, frange).ok())
+        .unique_by(|&(frange, _)| frange)
+        .filter_map(|(frange, category)| to_proto::reference_location(&snap, frange, category).ok())
         .collect();
 
     Ok(Some(locations))
@@ -1494,14 +1565,17 @@ pub(crate) fn handle_code_action(
         };
         let code_action = to_proto::code_action(&snap, &client_commands, assist, resolve_data)?;
 
-        // Check if the client supports the necessary `ResourceOperation`s.
+        // Hide actions the client can't apply because it lacks the necessary `ResourceOperation`s.
         let changes = code_action.edit.as_ref().and_then(|it| it.document_changes.as_ref());
-        if let Some(changes) = changes {
-            for change in changes {
-                if let lsp_ext::SnippetDocumentChangeOperation::Op(res_op) = change {
-                    resource_ops_supported(&snap.config, resolve_resource_op(res_op))?
-                }
-            }
+        let resource_ops_unsupported = changes.into_iter().flatten().any(|change| {
+            matches!(
+                change,
+                lsp_ext::SnippetDocumentChangeOperation::Op(res_op)
+                    if resource_ops_supported(&snap.config, resolve_resource_op(res_op)).is_err()
+            )
+        });
+        if resource_ops_unsupported {
+            continue;
         }
 
         res.push(code_action)
@@ -1703,26 +1777,25 @@ pub(crate) fn handle_code_lens_resolve(
 pub(crate) fn handle_document_highlight(
     snap: GlobalStateSnapshot,
     params: lsp_types::DocumentHighlightParams,
-) -> anyhow::Result<Option<Vec<lsp_types::DocumentHighlight>>> {
+) -> anyhow::Result<Option<Vec<lsp_ext::DocumentHighlight>>> {
     let _p = tracing::info_span!("handle_document_highlight").entered();
     let position =
         try_default!(from_proto::file_position(&snap, params.text_document_position_params)?);
     let line_index = snap.file_line_index(position.file_id)?;
     let source_root = snap.analysis.source_root_id(position.file_id)?;
 
-    let refs = match snap
+    let mut refs = match snap
         .analysis
         .highlight_related(snap.config.highlight_related(Some(source_root)), position)?
     {
         None => return Ok(None),
         Some(refs) => refs,
     };
+    // Highlights are collected into a hash set, keep the response stable.
+    refs.sort_by_key(|it| it.range.start());
     let res = refs
         .into_iter()
-        .map(|ide::HighlightedRange { range, category }| lsp_types::DocumentHighlight {
-            range: to_proto::range(&line_index, range),
-            kind: to_proto::document_highlight_kind(category),
-        })
+        .map(|highlight| to_proto::document_highlight(&line_index, highlight))
         .collect();
     Ok(Some(res))
 }
@@ -2077,6 +2150,15 @@ pub(crate) fn handle_open_docs(
     }
 }
 
+pub(crate) fn handle_qualified_name(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::TextDocumentPositionParams,
+) -> anyhow::Result<Option<String>> {
+    let _p = tracing::info_span!("handle_qualified_name").entered();
+    let position = try_default!(from_proto::file_position(&snap, params)?);
+    Ok(snap.analysis.qualified_name_at(position)?)
+}
+
 pub(crate) fn handle_open_cargo_toml(
     snap: GlobalStateSnapshot,
     params: lsp_ext::OpenCargoTomlParams,
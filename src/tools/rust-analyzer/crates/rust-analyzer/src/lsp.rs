@@ -47,6 +47,7 @@ fn hash_completion_relevance(hasher: &mut TentHash, relevance: &CompletionReleva
             u8::from(relevance.is_name_already_imported),
             u8::from(relevance.requires_import),
             u8::from(relevance.is_private_editable),
+            u8::from(relevance.is_deprecated),
         ]);
 
         match relevance.type_match {
@@ -85,6 +86,7 @@ fn hash_completion_relevance(hasher: &mut TentHash, relevance: &CompletionReleva
         u8::from(item.is_snippet),
         u8::from(item.deprecated),
         u8::from(item.trigger_call_info),
+        u8::from(item.requires_unsafe),
     ]);
 
     hasher.update(item.label.primary.len().to_ne_bytes());
@@ -699,6 +699,40 @@ pub struct CommandLink {
     pub tooltip: Option<String>,
 }
 
+pub enum References {}
+
+impl Request for References {
+    type Params = lsp_types::ReferenceParams;
+    type Result = Option<Vec<ReferenceLocation>>;
+    const METHOD: &'static str = lsp_types::request::References::METHOD;
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceLocation {
+    #[serde(flatten)]
+    pub location: lsp_types::Location,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub field_shorthand: bool,
+}
+
+pub enum DocumentHighlightRequest {}
+
+impl Request for DocumentHighlightRequest {
+    type Params = lsp_types::DocumentHighlightParams;
+    type Result = Option<Vec<DocumentHighlight>>;
+    const METHOD: &'static str = lsp_types::request::DocumentHighlightRequest::METHOD;
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentHighlight {
+    #[serde(flatten)]
+    pub highlight: lsp_types::DocumentHighlight,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub field_shorthand: bool,
+}
+
 pub enum ExternalDocs {}
 
 impl Request for ExternalDocs {
@@ -727,6 +761,14 @@ pub struct ExternalDocsPair {
     pub local: Option<lsp_types::Url>,
 }
 
+pub enum QualifiedName {}
+
+impl Request for QualifiedName {
+    type Params = lsp_types::TextDocumentPositionParams;
+    type Result = Option<String>;
+    const METHOD: &'static str = "rust-analyzer/qualifiedName";
+}
+
 pub enum OpenCargoToml {}
 
 impl Request for OpenCargoToml {
@@ -10,11 +10,11 @@
 use ide::{
     Annotation, AnnotationKind, Assist, AssistKind, Cancellable, CompletionFieldsToResolve,
     CompletionItem, CompletionItemKind, CompletionRelevance, Documentation, FileId, FileRange,
-    FileSystemEdit, Fold, FoldKind, Highlight, HlMod, HlOperator, HlPunct, HlRange, HlTag, Indel,
-    InlayFieldsToResolve, InlayHint, InlayHintLabel, InlayHintLabelPart, InlayKind, LazyProperty,
-    Markup, NavigationTarget, ReferenceCategory, RenameError, Runnable, Severity, SignatureHelp,
-    SnippetEdit, SourceChange, StructureNodeKind, SymbolKind, TextEdit, TextRange, TextSize,
-    UpdateTest,
+    FileSystemEdit, Fold, FoldKind, Highlight, HighlightedRange, HlMod, HlOperator, HlPunct,
+    HlRange, HlTag, Indel, InlayFieldsToResolve, InlayHint, InlayHintLabel, InlayHintLabelPart,
+    InlayKind, LazyProperty, Markup, NavigationTarget, ReferenceCategory, RenameError, Runnable,
+    Severity, SignatureHelp, SnippetEdit, SourceChange, StructureNodeKind, SymbolKind, TextEdit,
+    TextRange, TextSize, UpdateTest,
 };
 use ide_db::{
     FxHasher, MiniCore, assists, rust_doc::format_docs, source_change::ChangeAnnotationId,
@@ -109,6 +109,19 @@ pub(crate) fn document_highlight_kind(
     None
 }
 
+pub(crate) fn document_highlight(
+    line_index: &LineIndex,
+    HighlightedRange { range, category }: HighlightedRange,
+) -> lsp_ext::DocumentHighlight {
+    lsp_ext::DocumentHighlight {
+        highlight: lsp_types::DocumentHighlight {
+            range: self::range(line_index, range),
+            kind: document_highlight_kind(category),
+        },
+        field_shorthand: category.contains(ReferenceCategory::FIELD_SHORTHAND),
+    }
+}
+
 pub(crate) fn diagnostic_severity(severity: Severity) -> lsp_types::DiagnosticSeverity {
     match severity {
         Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
@@ -388,18 +401,24 @@ fn completion_item(
         ..Default::default()
     };
 
+    // Calls to unsafe functions are flagged right after the label so that clients can tell them
+    // apart without having to parse the signature in the description.
+    let label_detail = match (&item.label.detail_left, item.requires_unsafe) {
+        (Some(detail), true) => Some(format!("{detail} (unsafe)")),
+        (None, true) => Some(" (unsafe)".to_owned()),
+        (detail, false) => detail.clone(),
+    };
     if config.completion_label_details_support() {
-        let has_label_details =
-            item.label.detail_left.is_some() || item.label.detail_right.is_some();
+        let has_label_details = label_detail.is_some() || item.label.detail_right.is_some();
         if fields_to_resolve.resolve_label_details {
             something_to_resolve |= has_label_details;
         } else if has_label_details {
             lsp_item.label_details = Some(lsp_types::CompletionItemLabelDetails {
-                detail: item.label.detail_left.clone(),
+                detail: label_detail,
                 description: item.label.detail_right.clone(),
             });
         }
-    } else if let Some(label_detail) = &item.label.detail_left {
+    } else if let Some(label_detail) = &label_detail {
         lsp_item.label.push_str(label_detail.as_str());
     }
 
@@ -1021,6 +1040,16 @@ pub(crate) fn location(
     Ok(loc)
 }
 
+pub(crate) fn reference_location(
+    snap: &GlobalStateSnapshot,
+    frange: FileRange,
+    category: ReferenceCategory,
+) -> Cancellable<lsp_ext::ReferenceLocation> {
+    let location = location(snap, frange)?;
+    let field_shorthand = category.contains(ReferenceCategory::FIELD_SHORTHAND);
+    Ok(lsp_ext::ReferenceLocation { location, field_shorthand })
+}
+
 /// Prefer using `location_link`, if the client has the cap.
 pub(crate) fn location_from_nav(
     snap: &GlobalStateSnapshot,
@@ -1554,6 +1583,10 @@ pub(crate) fn runnable(
     let target_spec = TargetSpec::for_file(snap, runnable.nav.file_id)?;
     let source_root = snap.analysis.source_root_id(runnable.nav.file_id).ok();
     let config = snap.config.runnables(source_root);
+    let test_env = match runnable.kind {
+        ide::RunnableKind::Bin => Default::default(),
+        _ => config.extra_test_env,
+    };
 
     match target_spec {
         Some(TargetSpec::Cargo(spec)) => {
@@ -1590,6 +1623,7 @@ pub(crate) fn runnable(
                         .sysroot_root
                         .map(|root| ("RUSTC_TOOLCHAIN".to_owned(), root.to_string()))
                         .into_iter()
+                        .chain(test_env)
                         .collect(),
                 }),
             }))
@@ -1604,7 +1638,7 @@ pub(crate) fn runnable(
                         program: json_shell_runnable_args.program,
                         args: json_shell_runnable_args.args,
                         cwd: json_shell_runnable_args.cwd,
-                        environment: Default::default(),
+                        environment: test_env,
                     };
                     Ok(Some(lsp_ext::Runnable {
                         label,
@@ -1636,7 +1670,7 @@ pub(crate) fn runnable(
                     cargo_args,
                     cwd: path.as_path().unwrap().to_path_buf().into(),
                     executable_args,
-                    environment: Default::default(),
+                    environment: test_env,
                 }),
             }))
         }
@@ -2059,6 +2093,113 @@ fn bar(_: usize) {}
         assert!(!docs.contains("use crate::bar"));
     }
 
+    #[test]
+    fn completion_of_unsafe_fn_is_marked_in_label() {
+        let text = r#"
+unsafe fn unsafe_fn() {}
+fn safe_fn() {}
+
+fn main() {
+    unsafe_f$0
+}
+"#;
+
+        let (offset, text) = extract_offset(text);
+        let (analysis, file_id) = Analysis::from_single_file(text.clone());
+        let line_index = LineIndex {
+            index: Arc::new(ide::LineIndex::new(&text)),
+            endings: LineEndings::Unix,
+            encoding: PositionEncoding::Utf8,
+        };
+        let tdpp = lsp_types::TextDocumentPositionParams::new(
+            lsp_types::TextDocumentIdentifier::new("file:///main.rs".parse().unwrap()),
+            position(&line_index, offset),
+        );
+
+        let render = |label_details_support| {
+            let caps = lsp_types::ClientCapabilities {
+                text_document: Some(lsp_types::TextDocumentClientCapabilities {
+                    completion: Some(lsp_types::CompletionClientCapabilities {
+                        completion_item: Some(lsp_types::CompletionItemCapability {
+                            label_details_support: Some(label_details_support),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            let config = Config::new(
+                paths::AbsPathBuf::assert(test_utils::project_root()),
+                caps,
+                vec![],
+                None,
+            );
+            let completion_config = config.completion(None, MiniCore::default());
+            let items = analysis
+                .completions(&completion_config, FilePosition { file_id, offset }, None)
+                .unwrap()
+                .unwrap();
+            completion_items(
+                &config,
+                &CompletionFieldsToResolve::empty(),
+                &line_index,
+                None,
+                tdpp.clone(),
+                None,
+                items,
+            )
+            .into_iter()
+            .filter(|it| it.kind == Some(lsp_types::CompletionItemKind::FUNCTION))
+            .map(|it| {
+                let detail = it.label_details.and_then(|it| it.detail);
+                format!("{} {detail:?}", it.label)
+            })
+            .collect::<Vec<_>>()
+        };
+
+        expect![[r#"
+            [
+                "main None",
+                "safe_fn None",
+                "unsafe_fn Some(\" (unsafe)\")",
+            ]
+        "#]]
+        .assert_debug_eq(&render(true));
+        expect![[r#"
+            [
+                "main None",
+                "safe_fn None",
+                "unsafe_fn (unsafe) None",
+            ]
+        "#]]
+        .assert_debug_eq(&render(false));
+    }
+
+    #[test]
+    fn document_highlight_marks_field_shorthand() {
+        let text = "struct S { field: i32 }\nfn f(field: i32) -> S { S { field } }\n";
+        let line_index = LineIndex {
+            index: Arc::new(ide::LineIndex::new(text)),
+            endings: LineEndings::Unix,
+            encoding: PositionEncoding::Utf8,
+        };
+        let range = TextRange::at(TextSize::from(52), TextSize::from(5));
+        let render = |category| {
+            serde_json::to_string(&document_highlight(
+                &line_index,
+                HighlightedRange { range, category },
+            ))
+            .unwrap()
+        };
+
+        expect![[r#"{"range":{"start":{"line":1,"character":28},"end":{"line":1,"character":33}},"kind":2,"fieldShorthand":true}"#]].assert_eq(&render(
+            ReferenceCategory::READ | ReferenceCategory::FIELD_SHORTHAND,
+        ));
+        expect![[r#"{"range":{"start":{"line":1,"character":28},"end":{"line":1,"character":33}},"kind":2}"#]].assert_eq(&render(ReferenceCategory::READ));
+    }
+
     #[track_caller]
     fn check_rendered_snippets(edit: TextEdit, snippets: SnippetEdit, expect: Expect) {
         check_rendered_snippets_in_source(
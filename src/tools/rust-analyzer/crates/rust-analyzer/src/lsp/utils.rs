@@ -36,6 +36,15 @@ pub(crate) fn fraction(done: usize, total: usize) -> f64 {
     }
 }
 
+/// Tracks the initial indexing for clients without work done progress support,
+/// which only get told when it starts and when it is done.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum StartupProgress {
+    Pending,
+    Running,
+    Done,
+}
+
 impl GlobalState {
     pub(crate) fn show_message(
         &mut self,
@@ -122,6 +131,7 @@ pub(crate) fn report_progress(
         cancel_token: Option<String>,
     ) {
         if !self.config.work_done_progress() {
+            self.report_startup_progress(title, state);
             return;
         }
         let percentage = fraction.map(|f| {
@@ -163,6 +173,31 @@ pub(crate) fn report_progress(
             value: lsp_types::ProgressParamsValue::WorkDone(work_done_progress),
         });
     }
+
+    /// Falls back to `window/showMessage` for the initial indexing. Everything
+    /// else would be too noisy without a progress UI.
+    fn report_startup_progress(&mut self, title: &str, state: Progress) {
+        if title != "Indexing" {
+            return;
+        }
+        let message = match (self.startup_progress, state) {
+            (StartupProgress::Pending, Progress::Begin) => {
+                self.startup_progress = StartupProgress::Running;
+                "rust-analyzer: indexing the workspace"
+            }
+            (StartupProgress::Running, Progress::End) => {
+                self.startup_progress = StartupProgress::Done;
+                "rust-analyzer: indexing finished"
+            }
+            _ => return,
+        };
+        self.send_notification::<lsp_types::notification::ShowMessage>(
+            lsp_types::ShowMessageParams {
+                typ: lsp_types::MessageType::INFO,
+                message: message.to_owned(),
+            },
+        );
+    }
 }
 
 pub(crate) fn apply_document_changes(
@@ -330,6 +365,51 @@ macro_rules! c {
         assert_eq!(text, "ațc\ncb");
     }
 
+    #[test]
+    fn test_apply_document_changes_at_line_boundaries() {
+        let change = |(sl, sc): (u32, u32), (el, ec): (u32, u32), text: &str| {
+            TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(sl, sc), Position::new(el, ec))),
+                range_length: None,
+                text: text.to_owned(),
+            }
+        };
+        let encoding = PositionEncoding::Wide(WideEncoding::Utf16);
+
+        // Ranges ending exactly at the start of a line, including the one past the last newline.
+        let text = apply_document_changes(encoding, "ab\ncd\n", vec![change((0, 1), (1, 0), "X")]);
+        assert_eq!(text, "aXcd\n");
+        let text = apply_document_changes(encoding, "ab\ncd\n", vec![change((1, 0), (2, 0), "")]);
+        assert_eq!(text, "ab\n");
+        let text = apply_document_changes(
+            encoding,
+            "ab\ncd\n",
+            vec![change((2, 0), (2, 0), "ef"), change((0, 2), (1, 0), " ")],
+        );
+        assert_eq!(text, "ab cd\nef");
+
+        // CRLF in inserted text counts as a single line break for the following changes.
+        let text = apply_document_changes(
+            encoding,
+            "a\nb",
+            vec![change((0, 1), (0, 1), "\r\nx"), change((1, 0), (1, 1), "y")],
+        );
+        assert_eq!(text, "a\r\ny\nb");
+
+        // A full text change resets the document, only the changes after it are applied.
+        let full = TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "full\n".to_owned(),
+        };
+        let text = apply_document_changes(
+            encoding,
+            "ab\ncd\n",
+            vec![change((0, 0), (0, 1), "z"), full, change((1, 0), (1, 0), "tail")],
+        );
+        assert_eq!(text, "full\ntail");
+    }
+
     #[test]
     fn empty_completion_disjoint_tests() {
         let empty_completion = CompletionItem::new_simple("label".to_owned(), "detail".to_owned());
@@ -38,6 +38,9 @@
     test_runner::{CargoTestMessage, CargoTestOutput, TestState},
 };
 
+/// How long to wait for a worker thread to exit before giving up on it.
+const WORKER_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub fn main_loop(config: Config, connection: Connection) -> anyhow::Result<()> {
     tracing::info!("initial config: {:#?}", config);
 
@@ -73,6 +76,8 @@ pub fn main_loop(config: Config, connection: Connection) -> anyhow::Result<()> {
 
 enum Event {
     Lsp(lsp_server::Message),
+    /// Consecutive `textDocument/didChange` notifications, handled in a single loop turn.
+    DidChangeTextDocuments(Vec<Notification>),
     Task(Task),
     DeferredTask(DeferredTask),
     Vfs(vfs::loader::Message),
@@ -86,6 +91,7 @@ impl fmt::Display for Event {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Event::Lsp(_) => write!(f, "Event::Lsp"),
+            Event::DidChangeTextDocuments(_) => write!(f, "Event::DidChangeTextDocuments"),
             Event::Task(_) => write!(f, "Event::Task"),
             Event::Vfs(_) => write!(f, "Event::Vfs"),
             Event::Flycheck(_) => write!(f, "Event::Flycheck"),
@@ -163,6 +169,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 
         match self {
             Event::Lsp(it) => fmt::Debug::fmt(it, f),
+            Event::DidChangeTextDocuments(nots) => {
+                f.debug_struct("DidChangeTextDocuments").field("len", &nots.len()).finish()
+            }
             Event::Task(it) => fmt::Debug::fmt(it, f),
             Event::DeferredTask(it) => fmt::Debug::fmt(it, f),
             Event::Vfs(it) => fmt::Debug::fmt(it, f),
@@ -212,9 +221,23 @@ fn run(mut self, inbox: Receiver<lsp_server::Message>) -> anyhow::Result<()> {
             ) {
                 return Ok(());
             }
+            let event = match event {
+                Event::Lsp(msg) => self.batch_did_change(msg, &inbox),
+                event => event,
+            };
             self.handle_event(event);
         }
 
+        // `self` owns the senders of all other channels, so a disconnected channel
+        // means the VFS loader thread exited, most likely by panicking.
+        let additional_info = match self.loader.handle.stop(WORKER_STOP_TIMEOUT) {
+            Ok(()) => None,
+            Err(err) => Some(format!("VfsLoader: {err}")),
+        };
+        self.show_and_log_error(
+            "rust-analyzer's file loader stopped unexpectedly, shutting down".to_owned(),
+            additional_info,
+        );
         Err(anyhow::anyhow!("A receiver has been dropped, something panicked!"))
     }
 
@@ -266,6 +289,10 @@ fn next_event(
         &mut self,
         inbox: &Receiver<lsp_server::Message>,
     ) -> Result<Option<Event>, crossbeam_channel::RecvError> {
+        if let Some(msg) = self.lookahead_message.take() {
+            return Ok(Some(Event::Lsp(msg)));
+        }
+
         // Make sure we reply to formatting requests ASAP so the editor doesn't block
         if let Ok(task) = self.fmt_pool.receiver.try_recv() {
             return Ok(Some(Event::Task(task)));
@@ -303,8 +330,36 @@ fn next_event(
         .map(Some)
     }
 
+    /// Collects the `didChange` notifications queued right behind `msg`, so that the changes are
+    /// processed once instead of once per keystroke.
+    fn batch_did_change(
+        &mut self,
+        msg: lsp_server::Message,
+        inbox: &Receiver<lsp_server::Message>,
+    ) -> Event {
+        let is_did_change = notification_is::<lsp_types::notification::DidChangeTextDocument>;
+        let mut nots = Vec::new();
+        let mut msg = msg;
+        loop {
+            match msg {
+                lsp_server::Message::Notification(not) if is_did_change(&not) => nots.push(not),
+                msg if nots.is_empty() => return Event::Lsp(msg),
+                msg => {
+                    self.lookahead_message = Some(msg);
+                    break;
+                }
+            }
+            match inbox.try_recv() {
+                Ok(next) => msg = next,
+                Err(_) => break,
+            }
+        }
+        Event::DidChangeTextDocuments(nots)
+    }
+
     fn handle_event(&mut self, event: Event) {
         let loop_start = Instant::now();
+        self.loop_turns += 1;
         let _p = tracing::info_span!("GlobalState::handle_event", event = %event).entered();
 
         let event_dbg_msg = format!("{event:?}");
@@ -323,6 +378,11 @@ fn handle_event(&mut self, event: Event) {
                 lsp_server::Message::Notification(not) => self.on_notification(not),
                 lsp_server::Message::Response(resp) => self.complete_request(resp),
             },
+            Event::DidChangeTextDocuments(nots) => {
+                for not in nots {
+                    self.on_notification(not);
+                }
+            }
             Event::DeferredTask(task) => {
                 let _p = tracing::info_span!("GlobalState::handle_event/queued_task").entered();
                 self.handle_deferred_task(task);
@@ -645,9 +705,10 @@ fn update_diagnostics(&mut self) {
                 break;
             }
             // Diagnostics are triggered by the user typing
-            // so we run them on a latency sensitive thread.
+            // so we run them on a latency sensitive thread, but queue them
+            // behind the requests the user is actively waiting for.
             let snapshot = self.snapshot();
-            self.task_pool.handle.spawn_with_sender(ThreadIntent::LatencySensitive, {
+            self.task_pool.handle.spawn_background_with_sender(ThreadIntent::LatencySensitive, {
                 let subscriptions = subscriptions.clone();
                 // Do not fetch semantic diagnostics (and populate query results) if we haven't even
                 // loaded the initial workspace yet.
@@ -776,6 +837,7 @@ fn update_status_or_notify(&mut self) {
     fn handle_task(&mut self, prime_caches_progress: &mut Vec<PrimeCachesProgress>, task: Task) {
         match task {
             Task::Response(response) => self.respond(response),
+            Task::Retry(req) if self.finish_cancelled(&req.id) => (),
             // Only retry requests that haven't been cancelled. Otherwise we do unnecessary work.
             Task::Retry(req) if !self.is_completed(&req) => self.on_request(req),
             Task::Retry(_) => (),
@@ -1167,6 +1229,19 @@ fn handle_flycheck_msg(&mut self, message: FlycheckMessage, cargo_finished: &mut
                 id,
                 kind: ClearDiagnosticsKind::OlderThan(generation, ClearScope::Package(package_id)),
             } => self.diagnostics.clear_check_older_than_for_package(id, package_id, generation),
+            FlycheckMessage::CommandFailed { id, manifest_path, message } => {
+                let file_id = self.vfs.read().0.file_id(&VfsPath::from(manifest_path));
+                if let Some((file_id, vfs::FileExcluded::No)) = file_id {
+                    let diagnostic = lsp_types::Diagnostic {
+                        range: lsp_types::Range::default(),
+                        severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+                        source: Some(self.config.flycheck(None).to_string()),
+                        message,
+                        ..Default::default()
+                    };
+                    self.diagnostics.set_check_failure(id, file_id, diagnostic);
+                }
+            }
             FlycheckMessage::Progress { id, progress } => {
                 let (state, message) = match progress {
                     flycheck::Progress::DidStart => (Progress::Begin, None),
@@ -1182,6 +1257,9 @@ fn handle_flycheck_msg(&mut self, message: FlycheckMessage, cargo_finished: &mut
                         return;
                     }
                     flycheck::Progress::DidFinish(result) => {
+                        if result.is_ok() {
+                            self.diagnostics.clear_check_failure(id);
+                        }
                         self.last_flycheck_error =
                             result.err().map(|err| format!("cargo check failed to start: {err}"));
                         *cargo_finished = true;
@@ -1211,8 +1289,10 @@ fn handle_flycheck_msg(&mut self, message: FlycheckMessage, cargo_finished: &mut
     fn on_new_request(&mut self, request_received: Instant, req: Request) {
         let _p =
             span!(Level::INFO, "GlobalState::on_new_request", req.method = ?req.method).entered();
-        self.register_request(&req, request_received);
-        self.on_request(req);
+        if self.register_request(&req, request_received) {
+            self.supersede_request(&req);
+            self.on_request(req);
+        }
     }
 
     /// Handles a request.
@@ -1284,7 +1364,7 @@ fn on_request(&mut self, req: Request) {
             })
             .on::<RETRY, lsp_request::DocumentSymbolRequest>(handlers::handle_document_symbol)
             .on::<RETRY, lsp_request::FoldingRangeRequest>(handlers::handle_folding_range)
-            .on::<NO_RETRY, lsp_request::SignatureHelpRequest>(handlers::handle_signature_help)
+            .on_latency_sensitive::<NO_RETRY, lsp_request::SignatureHelpRequest>(handlers::handle_signature_help)
             .on::<RETRY, lsp_request::WillRenameFiles>(handlers::handle_will_rename_files)
             .on::<NO_RETRY, lsp_request::GotoDefinition>(handlers::handle_goto_definition)
             .on::<NO_RETRY, lsp_request::GotoDeclaration>(handlers::handle_goto_declaration)
@@ -1296,8 +1376,8 @@ fn on_request(&mut self, req: Request) {
             .on_identity::<NO_RETRY, lsp_request::CodeLensResolve, _>(handlers::handle_code_lens_resolve)
             .on::<NO_RETRY, lsp_request::PrepareRenameRequest>(handlers::handle_prepare_rename)
             .on::<NO_RETRY, lsp_request::Rename>(handlers::handle_rename)
-            .on::<NO_RETRY, lsp_request::References>(handlers::handle_references)
-            .on::<NO_RETRY, lsp_request::DocumentHighlightRequest>(handlers::handle_document_highlight)
+            .on::<NO_RETRY, lsp_ext::References>(handlers::handle_references)
+            .on_latency_sensitive::<NO_RETRY, lsp_ext::DocumentHighlightRequest>(handlers::handle_document_highlight)
             .on::<NO_RETRY, lsp_request::CallHierarchyPrepare>(handlers::handle_call_hierarchy_prepare)
             .on::<NO_RETRY, lsp_request::CallHierarchyIncomingCalls>(handlers::handle_call_hierarchy_incoming)
             .on::<NO_RETRY, lsp_request::CallHierarchyOutgoingCalls>(handlers::handle_call_hierarchy_outgoing)
@@ -1322,8 +1402,9 @@ fn on_request(&mut self, req: Request) {
             .on::<NO_RETRY, lsp_ext::RelatedTests>(handlers::handle_related_tests)
             .on::<NO_RETRY, lsp_ext::CodeActionRequest>(handlers::handle_code_action)
             .on_identity::<RETRY, lsp_ext::CodeActionResolveRequest, _>(handlers::handle_code_action_resolve)
-            .on::<NO_RETRY, lsp_ext::HoverRequest>(handlers::handle_hover)
+            .on_latency_sensitive::<NO_RETRY, lsp_ext::HoverRequest>(handlers::handle_hover)
             .on::<NO_RETRY, lsp_ext::ExternalDocs>(handlers::handle_open_docs)
+            .on::<NO_RETRY, lsp_ext::QualifiedName>(handlers::handle_qualified_name)
             .on::<NO_RETRY, lsp_ext::OpenCargoToml>(handlers::handle_open_cargo_toml)
             .on::<NO_RETRY, lsp_ext::MoveItem>(handlers::handle_move_item)
             //
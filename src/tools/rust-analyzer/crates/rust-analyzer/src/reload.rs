@@ -659,6 +659,7 @@ pub(crate) fn switch_workspaces(&mut self, cause: Cause) {
         let project_folders = ProjectFolders::new(
             &self.workspaces,
             &files_config.exclude,
+            &files_config.exclude_globs,
             Config::user_config_dir_path().as_deref(),
         );
 
@@ -191,7 +191,9 @@ pub(crate) fn runnable_args(
                 );
                 feats.extend(target_required_features);
 
-                feats.dedup();
+                // A feature can be both required by the target and enabled in the config.
+                let mut seen = FxHashSet::default();
+                feats.retain(|feat| seen.insert(feat.clone()));
                 for feature in feats {
                     cargo_args.push("--features".to_owned());
                     cargo_args.push(feature);
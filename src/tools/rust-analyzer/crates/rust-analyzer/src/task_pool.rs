@@ -4,7 +4,7 @@
 use std::panic::UnwindSafe;
 
 use crossbeam_channel::Sender;
-use stdx::thread::{Pool, ThreadIntent};
+use stdx::thread::{Lane, Pool, PoolMetrics, ThreadIntent};
 
 use crate::main_loop::DeferredTask;
 
@@ -40,9 +40,26 @@ pub(crate) fn spawn_with_sender<F>(&mut self, intent: ThreadIntent, task: F)
         })
     }
 
+    /// Like [`TaskPool::spawn_with_sender`], but queues the task behind
+    /// requests the user is waiting for.
+    pub(crate) fn spawn_background_with_sender<F>(&mut self, intent: ThreadIntent, task: F)
+    where
+        F: FnOnce(Sender<T>) + Send + UnwindSafe + 'static,
+        T: Send + 'static,
+    {
+        self.pool.spawn_in(Lane::Background, intent, {
+            let sender = self.sender.clone();
+            move || task(sender)
+        })
+    }
+
     pub(crate) fn len(&self) -> usize {
         self.pool.len()
     }
+
+    pub(crate) fn metrics(&self) -> PoolMetrics {
+        self.pool.metrics()
+    }
 }
 
 /// `DeferredTaskQueue` holds deferred tasks.
@@ -36,6 +36,7 @@
     fmt::Write,
     marker::PhantomData,
     mem,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
@@ -56,10 +57,58 @@ pub fn init(spec: &str) -> tracing::subscriber::DefaultGuard {
     tracing::subscriber::set_default(subscriber)
 }
 
+/// Runs `f`, collecting the aggregated span trees of all entries closed while it runs instead
+/// of printing them.
+///
+/// Useful in benchmarks that want to assert on how often some query ran:
+///
+/// ```ignore
+/// let (_, report) = hprof::collect("*", || analysis.completions(&config, position, None));
+/// assert!(report.count("find_path") <= 100);
+/// ```
+pub fn collect<R>(spec: &str, f: impl FnOnce() -> R) -> (R, Report) {
+    let sink = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = Registry::default().with(SpanTree::with_sink(spec, Some(sink.clone())));
+    let res = tracing::subscriber::with_default(subscriber, f);
+    let roots = mem::take(&mut *sink.lock().unwrap());
+    (res, Report { roots })
+}
+
+/// Aggregated span trees recorded by [`collect`].
+#[derive(Debug)]
+pub struct Report {
+    roots: Vec<Node>,
+}
+
+impl Report {
+    /// How many times a span called `name` was entered, at any depth.
+    pub fn count(&self, name: &str) -> u32 {
+        self.nodes().filter(|node| node.name == name).map(|node| node.count).sum()
+    }
+
+    /// Total time spent in spans called `name`, at any depth.
+    ///
+    /// Time spent in a span nested in another span of the same name is counted twice.
+    pub fn total_duration(&self, name: &str) -> Duration {
+        self.nodes().filter(|node| node.name == name).map(|node| node.duration).sum()
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = &Node> {
+        let mut stack: Vec<&Node> = self.roots.iter().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(&node.children);
+            Some(node)
+        })
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct SpanTree<S> {
     aggregate: bool,
     write_filter: WriteFilter,
+    /// Where finished entries go when they are collected rather than printed.
+    sink: Option<Arc<Mutex<Vec<Node>>>>,
     _inner: PhantomData<fn(S)>,
 }
 
@@ -68,6 +117,10 @@ impl<S> SpanTree<S>
     S: Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
 {
     pub(crate) fn new(spec: &str) -> impl Layer<S> {
+        Self::with_sink(spec, None)
+    }
+
+    fn with_sink(spec: &str, sink: Option<Arc<Mutex<Vec<Node>>>>) -> impl Layer<S> {
         let (write_filter, allowed_names) = WriteFilter::from_spec(spec);
 
         // this filter the first pass for `tracing`: these are all the "profiling" spans, but things like
@@ -86,7 +139,8 @@ pub(crate) fn new(spec: &str) -> impl Layer<S> {
                 && !metadata.target().starts_with("chalk")
         });
 
-        Self { aggregate: true, write_filter, _inner: PhantomData }.with_filter(profile_filter)
+        Self { aggregate: true, write_filter, sink, _inner: PhantomData }
+            .with_filter(profile_filter)
     }
 }
 
@@ -106,11 +160,13 @@ fn new(attrs: &Attributes<'_>) -> Self {
     }
 
     fn into_node(self, name: &'static str) -> Node {
+        let duration = self.start.elapsed();
         Node {
             name,
             fields: self.fields,
             count: 1,
-            duration: self.start.elapsed(),
+            duration,
+            max_duration: duration,
             children: self.children,
         }
     }
@@ -152,18 +208,24 @@ fn on_close(&self, id: Id, ctx: Context<'_, S>) {
                 if self.aggregate {
                     node.aggregate()
                 }
-                node.print(&self.write_filter)
+                match &self.sink {
+                    Some(sink) => sink.lock().unwrap().push(node),
+                    None => node.print(&self.write_filter),
+                }
             }
         }
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Debug)]
 struct Node {
     name: &'static str,
     fields: String,
     count: u32,
+    /// Total duration of all merged spans.
     duration: Duration,
+    /// Duration of the slowest of the merged spans.
+    max_duration: Duration,
     children: Vec<Node>,
 }
 
@@ -186,7 +248,8 @@ fn go(&self, level: usize, filter: &WriteFilter) {
             }
 
             if self.count > 1 {
-                let _ = write!(out, " ({} calls)", self.count);
+                let _ =
+                    write!(out, " ({} calls, max {}ms)", self.count, self.max_duration.as_millis());
             }
 
             eprintln!("{out}");
@@ -208,6 +271,8 @@ fn aggregate(&mut self) {
             if self.children[idx].name == self.children[i].name {
                 let child = mem::take(&mut self.children[i]);
                 self.children[idx].duration += child.duration;
+                self.children[idx].max_duration =
+                    self.children[idx].max_duration.max(child.max_duration);
                 self.children[idx].count += child.count;
                 self.children[idx].children.extend(child.children);
             } else {
@@ -264,3 +329,77 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{n:5}ms")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &'static str, millis: u64, children: Vec<Node>) -> Node {
+        let duration = Duration::from_millis(millis);
+        Node { name, count: 1, duration, max_duration: duration, children, ..Node::default() }
+    }
+
+    #[test]
+    fn aggregate_merges_siblings_with_the_same_name() {
+        let mut root = node(
+            "handle_completion",
+            100,
+            vec![
+                node("find_path", 10, vec![node("find_path_inner", 1, vec![])]),
+                node("resolve", 5, vec![]),
+                node("find_path", 30, vec![node("find_path_inner", 2, vec![])]),
+                node("find_path", 20, vec![]),
+            ],
+        );
+        root.aggregate();
+
+        let names = root.children.iter().map(|it| it.name).collect::<Vec<_>>();
+        assert_eq!(names, ["find_path", "resolve"]);
+
+        let find_path = &root.children[0];
+        assert_eq!(find_path.count, 3);
+        assert_eq!(find_path.duration, Duration::from_millis(60));
+        assert_eq!(find_path.max_duration, Duration::from_millis(30));
+
+        assert_eq!(find_path.children.len(), 1);
+        let inner = &find_path.children[0];
+        assert_eq!(inner.count, 2);
+        assert_eq!(inner.duration, Duration::from_millis(3));
+        assert_eq!(inner.max_duration, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn report_counts_spans_at_any_depth() {
+        let mut root = node(
+            "handle_completion",
+            100,
+            vec![
+                node("find_path", 10, vec![]),
+                node(
+                    "render",
+                    50,
+                    vec![node("find_path", 5, vec![]), node("find_path", 5, vec![])],
+                ),
+            ],
+        );
+        root.aggregate();
+        let report = Report { roots: vec![root, node("find_path", 1, vec![])] };
+
+        assert_eq!(report.count("find_path"), 4);
+        assert_eq!(report.total_duration("find_path"), Duration::from_millis(21));
+        assert_eq!(report.count("handle_completion"), 1);
+        assert_eq!(report.count("missing"), 0);
+    }
+
+    #[test]
+    fn collect_records_spans() {
+        let ((), report) = collect("*", || {
+            let _entry = tracing::info_span!("entry").entered();
+            for _ in 0..3 {
+                let _span = tracing::info_span!("leaf").entered();
+            }
+        });
+        assert_eq!(report.count("entry"), 1);
+        assert_eq!(report.count("leaf"), 3);
+    }
+}
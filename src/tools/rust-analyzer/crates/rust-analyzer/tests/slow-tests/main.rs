@@ -19,24 +19,46 @@
 mod support;
 mod testdir;
 
-use std::{collections::HashMap, path::PathBuf, time::Instant};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use lsp_types::{
-    CodeActionContext, CodeActionParams, CompletionParams, DidOpenTextDocumentParams,
-    DocumentFormattingParams, DocumentRangeFormattingParams, FileRename, FormattingOptions,
-    GotoDefinitionParams, HoverParams, InlayHint, InlayHintLabel, InlayHintParams,
-    PartialResultParams, Position, Range, RenameFilesParams, TextDocumentItem,
-    TextDocumentPositionParams, WorkDoneProgressParams,
-    notification::DidOpenTextDocument,
+    CancelParams, CodeActionContext, CodeActionParams, CompletionParams, Diagnostic,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentFormattingParams, DocumentHighlightParams,
+    DocumentRangeFormattingParams, DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse,
+    FileChangeType, FileEvent, FileRename, FoldingRangeParams, FoldingRangeProviderCapability,
+    FormattingOptions, GotoDefinitionParams, GotoDefinitionResponse, HoverParams, InlayHint,
+    InlayHintLabel, InlayHintParams, NumberOrString, PartialResultParams, Position, Range,
+    RenameFilesParams, SelectionRange, SelectionRangeParams, TextDocumentContentChangeEvent,
+    TextDocumentItem, TextDocumentPositionParams, VersionedTextDocumentIdentifier,
+    WorkDoneProgressParams, WorkspaceSymbolParams,
+    notification::{
+        Cancel, DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument,
+        DidOpenTextDocument,
+    },
     request::{
-        CodeActionRequest, Completion, Formatting, GotoTypeDefinition, HoverRequest,
-        InlayHintRequest, InlayHintResolveRequest, RangeFormatting, WillRenameFiles,
-        WorkspaceSymbolRequest,
+        CodeActionRequest, Completion, DocumentHighlightRequest, DocumentSymbolRequest,
+        FoldingRangeRequest, Formatting, GotoDefinition, GotoImplementation, GotoTypeDefinition,
+        HoverRequest, InlayHintRequest, InlayHintResolveRequest, RangeFormatting,
+        SelectionRangeRequest, WillRenameFiles, WorkspaceSymbolRequest,
+    },
+};
+use rust_analyzer::{
+    config::Config,
+    lsp::ext::{
+        AnalyzerStatus, AnalyzerStatusParams, CodeAction, CodeActionResolveRequest, ExpandMacro,
+        ExpandMacroParams, OnEnter, OpenCargoToml, OpenCargoTomlParams, ParentModule, Runnables,
+        RunnablesParams, Ssr, SsrParams,
     },
+    server_capabilities,
 };
-use rust_analyzer::lsp::ext::{OnEnter, Runnables, RunnablesParams};
 use serde_json::json;
-use stdx::format_to_acc;
+use stdx::{format_to, format_to_acc};
+use vfs::AbsPathBuf;
 
 use test_utils::skip_slow_tests;
 use testdir::TestDir;
@@ -120,6 +142,46 @@ fn f() {
     );
 }
 
+#[test]
+fn applies_changed_configuration() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+struct Foo;
+fn f() {
+    let x = Foo;
+}
+"#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let hint_count = || {
+        let res = server.send_request::<InlayHintRequest>(InlayHintParams {
+            range: Range::new(Position::new(0, 0), Position::new(3, 1)),
+            text_document: server.doc_id("src/lib.rs"),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        serde_json::from_value::<Option<Vec<InlayHint>>>(res).unwrap().unwrap_or_default().len()
+    };
+    assert_eq!(hint_count(), 1);
+
+    server.change_client_config(json!({ "inlayHints": { "typeHints": { "enable": false } } }));
+    assert_eq!(hint_count(), 0);
+
+    server.change_client_config(json!({ "inlayHints": { "typeHints": { "enable": true } } }));
+    assert_eq!(hint_count(), 1);
+}
+
 #[test]
 fn completes_items_from_standard_library_in_cargo_script() {
     // this test requires nightly so CI can't run it
@@ -233,6 +295,13 @@ fn test_runnables_project() {
 name = "foo"
 version = "0.0.0"
 
+[features]
+extra = []
+
+[[example]]
+name = "demo"
+required-features = ["extra"]
+
 //- /foo/src/lib.rs
 pub fn foo() {}
 
@@ -240,6 +309,9 @@ pub fn foo() {}
 #[test]
 fn test_eggs() {}
 
+//- /foo/examples/demo.rs
+fn main() {}
+
 //- /bar/Cargo.toml
 [package]
 name = "bar"
@@ -276,7 +348,7 @@ fn main() {}
                 "end": { "character": 12, "line": 1 },
                 "start": { "character": 3, "line": 1 }
               },
-              "targetUri": "file:///[..]/tests/spam.rs"
+              "targetUri": "${root}/foo/tests/spam.rs"
             }
           },
           {
@@ -299,7 +371,7 @@ fn main() {}
             "kind": "cargo",
             "label": "test-mod ",
             "location": {
-              "targetUri": "file:///[..]/tests/spam.rs",
+              "targetUri": "${root}/foo/tests/spam.rs",
               "targetRange": {
                 "start": {
                   "line": 0,
@@ -346,6 +418,46 @@ fn main() {}
           }
         ]),
     );
+
+    server.request::<Runnables>(
+        RunnablesParams { text_document: server.doc_id("foo/examples/demo.rs"), position: None },
+        json!([
+          {
+            "args": {
+              "cargoArgs": ["run", "--package", "foo", "--example", "demo", "--features", "extra"],
+              "executableArgs": [],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "run demo",
+            "location": "{...}"
+          },
+          {
+            "args": {
+              "cargoArgs": ["check", "--package", "foo", "--all-targets"],
+              "executableArgs": [],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "cargo check -p foo --all-targets"
+          },
+          {
+            "args": {
+              "cargoArgs": ["test", "--package", "foo", "--all-targets"],
+              "executableArgs": [],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "cargo test -p foo --all-targets"
+          }
+        ]),
+    );
 }
 
 // Each package in these workspaces should be run from its own root
@@ -521,6 +633,75 @@ fn otherpkg() {}
     );
 }
 
+#[test]
+fn test_runnables_ignored_test_and_extra_env() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /foo/Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /foo/src/lib.rs
+#[test]
+#[ignore]
+fn slow() {}
+"#,
+    )
+    .root("foo")
+    .with_config(serde_json::json!({
+        "runnables": { "extraTestEnv": { "FIXTURES": "tests/fixtures" } }
+    }))
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    server.request::<Runnables>(
+        RunnablesParams { text_document: server.doc_id("foo/src/lib.rs"), position: None },
+        json!([
+            {
+                "label": "test slow",
+                "kind": "cargo",
+                "location": "{...}",
+                "args": {
+                    "overrideCargo": null,
+                    "workspaceRoot": server.path().join("foo"),
+                    "cwd": server.path().join("foo"),
+                    "cargoArgs": ["test", "--package", "foo", "--lib"],
+                    "executableArgs": ["slow", "--exact", "--nocapture", "--ignored"],
+                    "environment": { "FIXTURES": "tests/fixtures" }
+                },
+            },
+            {
+                "label": "cargo check -p foo --all-targets",
+                "kind": "cargo",
+                "args": {
+                    "overrideCargo": null,
+                    "workspaceRoot": server.path().join("foo"),
+                    "cwd": server.path().join("foo"),
+                    "cargoArgs": ["check", "--package", "foo", "--all-targets"],
+                    "executableArgs": []
+                },
+            },
+            {
+                "label": "cargo test -p foo --all-targets",
+                "kind": "cargo",
+                "args": {
+                    "overrideCargo": null,
+                    "workspaceRoot": server.path().join("foo"),
+                    "cwd": server.path().join("foo"),
+                    "cargoArgs": ["test", "--package", "foo", "--all-targets"],
+                    "executableArgs": [],
+                    "environment": { "FIXTURES": "tests/fixtures" }
+                },
+            }
+        ]),
+    );
+}
+
 #[test]
 fn test_format_document() {
     if skip_slow_tests() {
@@ -731,7 +912,7 @@ fn main() {
 }
 
 #[test]
-fn test_missing_module_code_action() {
+fn test_ssr_across_files() {
     if skip_slow_tests() {
         return;
     }
@@ -746,113 +927,402 @@ fn test_missing_module_code_action() {
 //- /src/lib.rs
 mod bar;
 
-fn main() {}
+pub fn foo(a: i32, b: i32) -> i32 { a - b }
+fn baz() -> i32 { foo(1, 2) }
+
+//- /src/bar.rs
+use crate::foo;
+
+fn qux() -> i32 { foo(3, 4 + 5) }
 "#,
     )
     .wait_until_workspace_is_loaded();
 
-    server.request::<CodeActionRequest>(
-        CodeActionParams {
-            text_document: server.doc_id("src/lib.rs"),
-            range: Range::new(Position::new(0, 4), Position::new(0, 7)),
-            context: CodeActionContext::default(),
-            partial_result_params: PartialResultParams::default(),
-            work_done_progress_params: WorkDoneProgressParams::default(),
+    server.request::<Ssr>(
+        SsrParams {
+            query: "foo($a, $b) ==>> foo($b, $a)".to_owned(),
+            parse_only: false,
+            position: TextDocumentPositionParams::new(
+                server.doc_id("src/lib.rs"),
+                Position::new(0, 0),
+            ),
+            selections: Vec::new(),
         },
-        json!([
-            {
-                "title": "Create module at `bar.rs`",
-                "kind": "quickfix",
-                "edit": {
-                "documentChanges": [
-                    {
-                    "kind": "create",
-                    "uri": "file://[..]/src/bar.rs"
-                    }
-                ]
-                }
-            },
-            {
-                "title": "Create module at `bar/mod.rs`",
-                "kind": "quickfix",
-                "edit": {
-                "documentChanges": [
-                    {
-                    "kind": "create",
-                    "uri": "file://[..]src/bar/mod.rs"
-                    }
-                ]
+        json!({
+            "documentChanges": [
+                {
+                    "textDocument": { "uri": "file://[..]/src/lib.rs", "version": null },
+                    "edits": [{ "range": "{...}", "newText": "foo(2, 1)" }]
+                },
+                {
+                    "textDocument": { "uri": "file://[..]/src/bar.rs", "version": null },
+                    "edits": [{ "range": "{...}", "newText": "foo(4 + 5, 3)" }]
                 }
-            }
-        ]),
-    );
-
-    server.request::<CodeActionRequest>(
-        CodeActionParams {
-            text_document: server.doc_id("src/lib.rs"),
-            range: Range::new(Position::new(2, 8), Position::new(2, 8)),
-            context: CodeActionContext::default(),
-            partial_result_params: PartialResultParams::default(),
-            work_done_progress_params: WorkDoneProgressParams::default(),
-        },
-        json!([]),
+            ]
+        }),
     );
 }
 
 #[test]
-fn test_missing_module_code_action_in_json_project() {
+fn test_selection_range() {
     if skip_slow_tests() {
         return;
     }
 
-    let tmp_dir = TestDir::new();
+    let server = project(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
 
-    let path = tmp_dir.path();
+//- /src/lib.rs
+fn f(x: Option<S>) -> u32 {
+    match x {
+        Some(s) => s.foo().bar(1),
+        None => 0,
+    }
+}
+"#,
+    )
+    .wait_until_workspace_is_loaded();
 
-    let project = json!({
-        "roots": [path],
-        "crates": [ {
-            "root_module": path.join("src/lib.rs"),
-            "deps": [],
-            "edition": "2015",
-            "cfg": [ "cfg_atom_1", "feature=\"cfg_1\""],
-        } ]
+    let res = server.send_request::<SelectionRangeRequest>(SelectionRangeParams {
+        text_document: server.doc_id("src/lib.rs"),
+        positions: vec![Position::new(2, 22), Position::new(0, 5)],
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
     });
+    let res = serde_json::from_value::<Vec<SelectionRange>>(res).unwrap();
+    assert_eq!(res.len(), 2);
+
+    let chains = res
+        .iter()
+        .map(|it| {
+            std::iter::successors(Some(it), |it| it.parent.as_deref())
+                .map(|it| it.range)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    for (chain, position) in chains.iter().zip([Position::new(2, 22), Position::new(0, 5)]) {
+        assert_eq!(chain[0], Range::new(position, position));
+        for (inner, outer) in chain.iter().zip(chain.iter().skip(1)) {
+            assert!(inner != outer, "{chain:?}");
+            assert!(outer.start <= inner.start && inner.end <= outer.end, "{chain:?}");
+        }
+    }
+    let method_call = Range::new(Position::new(2, 19), Position::new(2, 33));
+    assert!(chains[0].contains(&method_call), "{:?}", chains[0]);
+    assert!(!chains[1].contains(&method_call), "{:?}", chains[1]);
+}
 
-    let code = format!(
+#[test]
+fn test_hierarchical_document_symbols() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
         r#"
-//- /.rust-project.json
-{project}
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
 
 //- /src/lib.rs
-mod bar;
-
-fn main() {{}}
+mod m {
+    pub struct S;
+    impl S {
+        pub fn a(&self) {}
+        pub fn b(&self) {}
+    }
+}
 "#,
-    );
+    )
+    .wait_until_workspace_is_loaded();
 
-    let server =
-        Project::with_fixture(&code).tmp_dir(tmp_dir).server().wait_until_workspace_is_loaded();
+    let res = server.send_request::<DocumentSymbolRequest>(DocumentSymbolParams {
+        text_document: server.doc_id("src/lib.rs"),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+    let Ok(DocumentSymbolResponse::Nested(symbols)) = serde_json::from_value(res) else {
+        panic!("expected nested document symbols");
+    };
 
-    server.request::<CodeActionRequest>(
-        CodeActionParams {
-            text_document: server.doc_id("src/lib.rs"),
-            range: Range::new(Position::new(0, 4), Position::new(0, 7)),
-            context: CodeActionContext::default(),
-            partial_result_params: PartialResultParams::default(),
-            work_done_progress_params: WorkDoneProgressParams::default(),
-        },
-        json!([
-            {
-                "title": "Create module at `bar.rs`",
-                "kind": "quickfix",
-                "edit": {
-                "documentChanges": [
-                    {
-                    "kind": "create",
-                    "uri": "file://[..]/src/bar.rs"
-                    }
-                ]
+    fn render(symbols: &[DocumentSymbol], depth: usize, acc: &mut String) {
+        for symbol in symbols {
+            let detail = symbol.detail.as_deref().unwrap_or_default();
+            format_to!(acc, "{:depth$}{} {detail}\n", "", symbol.name, depth = depth * 2);
+            render(symbol.children.as_deref().unwrap_or_default(), depth + 1, acc);
+        }
+    }
+    let mut actual = String::new();
+    render(&symbols, 0, &mut actual);
+    assert_eq!(actual, "m \n  S \n  impl S \n    a fn(&self)\n    b fn(&self)\n",);
+
+    let impl_ = &symbols[0].children.as_ref().unwrap()[1];
+    assert_eq!(impl_.range, Range::new(Position::new(2, 4), Position::new(5, 5)));
+    assert_eq!(impl_.selection_range, Range::new(Position::new(2, 9), Position::new(2, 10)));
+}
+
+#[test]
+fn flycheck_diagnostics_are_cleared_after_fix() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub fn f() -> u32 { 92 }
+"#,
+    )
+    .with_config(json!({ "checkOnSave": true }))
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let has_check_error = |diagnostics: &[Diagnostic]| {
+        diagnostics.iter().any(|it| it.source.as_deref() == Some("rustc"))
+    };
+
+    server.write_file_and_save("src/lib.rs", "pub fn f() -> u32 { \"92\" }\n".to_owned());
+    server.wait_for_diagnostics("src/lib.rs", has_check_error);
+
+    server.write_file_and_save("src/lib.rs", "pub fn f() -> u32 { 92 }\n".to_owned());
+    server.wait_for_diagnostics("src/lib.rs", |diagnostics| !has_check_error(diagnostics));
+}
+
+#[test]
+fn test_flat_document_symbols() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+mod bar;
+
+//- /src/bar.rs
+pub struct S;
+pub fn f() {}
+"#,
+    )
+    .with_flat_document_symbols()
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let symbol = |name: &str, kind: u32| {
+        json!({
+            "name": name,
+            "kind": kind,
+            "tags": [],
+            "deprecated": false,
+            "location": { "uri": "${root}/src/bar.rs", "range": "${any}" }
+        })
+    };
+    server.request::<DocumentSymbolRequest>(
+        DocumentSymbolParams {
+            text_document: server.doc_id("src/bar.rs"),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        },
+        json!([symbol("S", 23), symbol("f", 12)]),
+    );
+}
+
+#[test]
+fn test_goto_implementation() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub trait Shape {}
+pub struct Circle;
+impl Shape for Circle {}
+pub struct Square;
+impl Shape for Square {}
+impl Circle {}
+"#,
+    )
+    .wait_until_workspace_is_loaded();
+
+    let impls_at = |position: Position| {
+        let res = server.send_request::<GotoImplementation>(GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                server.doc_id("src/lib.rs"),
+                position,
+            ),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        });
+        let Ok(GotoDefinitionResponse::Link(links)) = serde_json::from_value(res) else {
+            panic!("expected location links");
+        };
+        let mut ranges = links.iter().map(|it| it.target_selection_range).collect::<Vec<_>>();
+        ranges.sort_by_key(|it| it.start);
+        ranges
+    };
+    let range = |line, start, end| Range::new(Position::new(line, start), Position::new(line, end));
+
+    assert_eq!(impls_at(Position::new(0, 11)), [range(2, 15, 21), range(4, 15, 21)]);
+    assert_eq!(impls_at(Position::new(1, 12)), [range(2, 15, 21), range(5, 5, 11)]);
+}
+
+#[test]
+fn test_parent_module() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+mod foo;
+#[path = "foo.rs"]
+mod bar;
+
+//- /src/foo.rs
+pub fn f() {}
+"#,
+    )
+    .wait_until_workspace_is_loaded();
+
+    let parent_module_of = |path: &str| {
+        let res = server.send_request::<ParentModule>(TextDocumentPositionParams::new(
+            server.doc_id(path),
+            Position::new(0, 0),
+        ));
+        let Ok(GotoDefinitionResponse::Link(links)) = serde_json::from_value(res) else {
+            panic!("expected location links");
+        };
+        links
+    };
+    let range = |line, start, end| Range::new(Position::new(line, start), Position::new(line, end));
+
+    // A file included by several `mod` items has all of them as parents.
+    let mut links = parent_module_of("src/foo.rs");
+    links.sort_by_key(|it| it.target_selection_range.start);
+    assert!(links.iter().all(|it| it.target_uri == server.doc_id("src/lib.rs").uri));
+    assert_eq!(
+        links.iter().map(|it| it.target_selection_range).collect::<Vec<_>>(),
+        [range(0, 4, 7), range(2, 4, 7)]
+    );
+
+    // The parent of a crate root is the package manifest.
+    let links = parent_module_of("src/lib.rs");
+    assert_eq!(links.len(), 1);
+    assert!(links[0].target_uri.path().ends_with("/Cargo.toml"));
+}
+
+#[test]
+fn test_open_cargo_toml() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
+        r#"
+//- /Cargo.toml
+[workspace]
+members = ["foo"]
+
+//- /foo/Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /foo/src/lib.rs
+mod bar;
+
+//- /foo/src/bar.rs
+pub fn f() {}
+"#,
+    )
+    .wait_until_workspace_is_loaded();
+
+    let res = server.send_request::<OpenCargoToml>(OpenCargoTomlParams {
+        text_document: server.doc_id("foo/src/bar.rs"),
+    });
+    let Ok(GotoDefinitionResponse::Scalar(location)) = serde_json::from_value(res) else {
+        panic!("expected a single location");
+    };
+    assert!(location.uri.path().ends_with("/foo/Cargo.toml"));
+    assert_eq!(location.range, Range::default());
+}
+
+#[test]
+fn test_missing_module_code_action() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+mod bar;
+
+fn main() {}
+"#,
+    )
+    .wait_until_workspace_is_loaded();
+
+    server.request::<CodeActionRequest>(
+        CodeActionParams {
+            text_document: server.doc_id("src/lib.rs"),
+            range: Range::new(Position::new(0, 4), Position::new(0, 7)),
+            context: CodeActionContext::default(),
+            partial_result_params: PartialResultParams::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        },
+        json!([
+            {
+                "title": "Create module at `bar.rs`",
+                "kind": "quickfix",
+                "edit": {
+                "documentChanges": [
+                    {
+                    "kind": "create",
+                    "uri": "file://[..]/src/bar.rs"
+                    },
+                    {
+                    "textDocument": { "uri": "file://[..]/src/bar.rs", "version": null },
+                    "edits": [{
+                        "range": "{...}",
+                        "newText": "//! The `bar` module.\n",
+                        "insertTextFormat": 1
+                    }]
+                    }
+                ]
                 }
             },
             {
@@ -863,6 +1333,14 @@ fn main() {{}}
                     {
                     "kind": "create",
                     "uri": "file://[..]src/bar/mod.rs"
+                    },
+                    {
+                    "textDocument": { "uri": "file://[..]src/bar/mod.rs", "version": null },
+                    "edits": [{
+                        "range": "{...}",
+                        "newText": "//! The `bar` module.\n",
+                        "insertTextFormat": 1
+                    }]
                     }
                 ]
                 }
@@ -883,17 +1361,412 @@ fn main() {{}}
 }
 
 #[test]
-fn diagnostics_dont_block_typing() {
-    if skip_slow_tests() || std::env::var("CI").is_ok() {
-        // FIXME: This test is failing too frequently (therefore we disable it on CI).
+fn test_code_action_resolve() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+mod bar;
+
+fn main() {}
+"#,
+    )
+    .with_code_action_resolve()
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    // With resolve support, the edits are left out of the initial response...
+    let res = server.send_request::<CodeActionRequest>(CodeActionParams {
+        text_document: server.doc_id("src/lib.rs"),
+        range: Range::new(Position::new(0, 4), Position::new(0, 7)),
+        context: CodeActionContext::default(),
+        partial_result_params: PartialResultParams::default(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    });
+    let actions: Vec<CodeAction> = serde_json::from_value(res).unwrap();
+    assert_eq!(actions.len(), 2);
+    assert!(actions.iter().all(|it| it.edit.is_none() && it.data.is_some()));
+    let action = actions.into_iter().find(|it| it.title == "Create module at `bar.rs`").unwrap();
+
+    // ...and computed only for the action that gets resolved.
+    server.request::<CodeActionResolveRequest>(
+        action,
+        json!({
+            "title": "Create module at `bar.rs`",
+            "kind": "quickfix",
+            "edit": {
+                "documentChanges": [
+                    {
+                        "kind": "create",
+                        "uri": "file://[..]/src/bar.rs"
+                    },
+                    {
+                        "textDocument": { "uri": "file://[..]/src/bar.rs", "version": null },
+                        "edits": [{
+                            "range": "{...}",
+                            "newText": "//! The `bar` module.\n",
+                            "insertTextFormat": 1
+                        }]
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_code_action_without_resolve_support_has_eager_edits() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+mod bar;
+
+fn main() {}
+"#,
+    )
+    .wait_until_workspace_is_loaded();
+
+    let res = server.send_request::<CodeActionRequest>(CodeActionParams {
+        text_document: server.doc_id("src/lib.rs"),
+        range: Range::new(Position::new(0, 4), Position::new(0, 7)),
+        context: CodeActionContext::default(),
+        partial_result_params: PartialResultParams::default(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    });
+    let actions: Vec<CodeAction> = serde_json::from_value(res).unwrap();
+    assert_eq!(actions.len(), 2);
+    assert!(actions.iter().all(|it| it.edit.is_some() && it.data.is_none()));
+}
+
+#[test]
+fn test_missing_module_code_action_in_json_project() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let tmp_dir = TestDir::new();
+
+    let path = tmp_dir.path();
+
+    let project = json!({
+        "roots": [path],
+        "crates": [ {
+            "root_module": path.join("src/lib.rs"),
+            "deps": [],
+            "edition": "2015",
+            "cfg": [ "cfg_atom_1", "feature=\"cfg_1\""],
+        } ]
+    });
+
+    let code = format!(
+        r#"
+//- /.rust-project.json
+{project}
+
+//- /src/lib.rs
+mod bar;
+
+fn main() {{}}
+"#,
+    );
+
+    let server =
+        Project::with_fixture(&code).tmp_dir(tmp_dir).server().wait_until_workspace_is_loaded();
+
+    server.request::<CodeActionRequest>(
+        CodeActionParams {
+            text_document: server.doc_id("src/lib.rs"),
+            range: Range::new(Position::new(0, 4), Position::new(0, 7)),
+            context: CodeActionContext::default(),
+            partial_result_params: PartialResultParams::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        },
+        json!([
+            {
+                "title": "Create module at `bar.rs`",
+                "kind": "quickfix",
+                "edit": {
+                "documentChanges": [
+                    {
+                    "kind": "create",
+                    "uri": "file://[..]/src/bar.rs"
+                    },
+                    {
+                    "textDocument": { "uri": "file://[..]/src/bar.rs", "version": null },
+                    "edits": [{
+                        "range": "{...}",
+                        "newText": "//! The `bar` module.\n",
+                        "insertTextFormat": 1
+                    }]
+                    }
+                ]
+                }
+            },
+            {
+                "title": "Create module at `bar/mod.rs`",
+                "kind": "quickfix",
+                "edit": {
+                "documentChanges": [
+                    {
+                    "kind": "create",
+                    "uri": "file://[..]src/bar/mod.rs"
+                    },
+                    {
+                    "textDocument": { "uri": "file://[..]src/bar/mod.rs", "version": null },
+                    "edits": [{
+                        "range": "{...}",
+                        "newText": "//! The `bar` module.\n",
+                        "insertTextFormat": 1
+                    }]
+                    }
+                ]
+                }
+            }
+        ]),
+    );
+
+    server.request::<CodeActionRequest>(
+        CodeActionParams {
+            text_document: server.doc_id("src/lib.rs"),
+            range: Range::new(Position::new(2, 8), Position::new(2, 8)),
+            context: CodeActionContext::default(),
+            partial_result_params: PartialResultParams::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        },
+        json!([]),
+    );
+
+    // Open the file so the server publishes its diagnostics, which tell us when the new module
+    // file has been loaded below.
+    let has_unresolved_module = |diagnostics: &[lsp_types::Diagnostic]| {
+        diagnostics.iter().any(|it| it.message.contains("unresolved module"))
+    };
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: server.doc_id("src/lib.rs").uri,
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "mod bar;\n\nfn main() {}\n".to_owned(),
+        },
+    });
+    server.wait_for_diagnostics("src/lib.rs", has_unresolved_module);
+
+    // Apply the first fix the way a client would, then tell the server about the new file.
+    std::fs::write(server.path().join("src/bar.rs"), "//! The `bar` module.\n").unwrap();
+    server.notification::<DidChangeWatchedFiles>(DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(server.doc_id("src/bar.rs").uri, FileChangeType::CREATED)],
+    });
+    server.wait_for_diagnostics("src/lib.rs", |diagnostics| !has_unresolved_module(diagnostics));
+
+    let actions = server.send_request::<CodeActionRequest>(CodeActionParams {
+        text_document: server.doc_id("src/lib.rs"),
+        range: Range::new(Position::new(0, 4), Position::new(0, 7)),
+        context: CodeActionContext::default(),
+        partial_result_params: PartialResultParams::default(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    });
+    assert!(
+        !actions.to_string().contains("Create module"),
+        "module is still unresolved after creating its file: {actions}"
+    );
+}
+
+#[test]
+fn diagnostics_dont_block_typing() {
+    if skip_slow_tests() || std::env::var("CI").is_ok() {
+        // FIXME: This test is failing too frequently (therefore we disable it on CI).
+        return;
+    }
+
+    let librs: String = (0..10).fold(String::new(), |mut acc, i| format_to_acc!(acc, "mod m{i};"));
+    let libs: String = (0..10).fold(String::new(), |mut acc, i| {
+        format_to_acc!(acc, "//- /src/m{i}.rs\nfn foo() {{}}\n\n")
+    });
+    let server = Project::with_fixture(&format!(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+{librs}
+
+{libs}
+
+fn main() {{}}
+"#
+    ))
+    .with_config(serde_json::json!({
+        "cargo": { "sysroot": "discover" },
+    }))
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    for i in 0..10 {
+        server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: server.doc_id(&format!("src/m{i}.rs")).uri,
+                language_id: "rust".to_owned(),
+                version: 0,
+                text: "/// Docs\nfn foo() {}".to_owned(),
+            },
+        });
+    }
+    let start = Instant::now();
+    server.request::<OnEnter>(
+        TextDocumentPositionParams {
+            text_document: server.doc_id("src/m0.rs"),
+            position: Position { line: 0, character: 5 },
+        },
+        json!([{
+            "insertTextFormat": 2,
+            "newText": "\n/// $0",
+            "range": {
+            "end": { "character": 5, "line": 0 },
+            "start": { "character": 5, "line": 0 }
+            }
+        }]),
+    );
+    let elapsed = start.elapsed();
+    assert!(elapsed.as_millis() < 2000, "typing enter took {elapsed:?}");
+}
+
+#[test]
+fn request_ordering_under_concurrent_cancels_and_edits() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let tmp_dir = TestDir::new();
+    let path = tmp_dir.path();
+    let project = json!({
+        "roots": [path],
+        "crates": [ {
+            "root_module": path.join("src/lib.rs"),
+            "deps": [],
+            "edition": "2021",
+        } ]
+    });
+    let server = Project::with_fixture(&format!(
+        r#"
+//- /.rust-project.json
+{project}
+
+//- /src/lib.rs
+fn f0() {{}}
+"#
+    ))
+    .tmp_dir(tmp_dir)
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let uri = server.doc_id("src/lib.rs").uri;
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: uri.clone(),
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "fn f0() {}".to_owned(),
+        },
+    });
+    let hover = || HoverParams {
+        text_document_position_params: TextDocumentPositionParams::new(
+            server.doc_id("src/lib.rs"),
+            Position::new(0, 3),
+        ),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+
+    // (request id, document version at the time the request was sent)
+    let mut sent = Vec::new();
+    let mut id = 1000;
+    let duplicate_id = 1040;
+    for version in 1..=30 {
+        server.notification::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(uri.clone(), version),
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: format!("fn f{version}() {{}}"),
+            }],
+        });
+        for _ in 0..3 {
+            server.send_request_no_wait::<HoverRequest>(id, hover());
+            sent.push((id, version));
+            if id == duplicate_id {
+                // A misbehaving client reusing an id that is likely still in flight.
+                server.send_request_no_wait::<HoverRequest>(id, hover());
+                sent.push((id, version));
+            }
+            id += 1;
+        }
+        if version % 2 == 0 {
+            server.notification::<Cancel>(CancelParams { id: NumberOrString::Number(id - 2) });
+        }
+    }
+
+    let last_id = id - 1;
+    let responses = server.recv_responses(sent.len());
+    let mut by_id = HashMap::<_, Vec<_>>::new();
+    for response in &responses {
+        by_id.entry(response.id.clone()).or_default().push(response);
+    }
+    for &(id, version) in &sent {
+        let responses = &by_id[&lsp_server::RequestId::from(id)];
+        let expected = if id == duplicate_id { 2 } else { 1 };
+        assert_eq!(responses.len(), expected, "wrong number of responses for request {id}");
+        for response in responses {
+            match &response.error {
+                None => {
+                    let result = response.result.as_ref().unwrap().to_string();
+                    assert!(
+                        result.contains(&format!("fn f{version}()")),
+                        "request {id} sent at version {version} saw another version: {result}"
+                    );
+                }
+                // Cancelled explicitly, superseded by a later hover, or outdated by an edit.
+                Some(err)
+                    if err.code == lsp_server::ErrorCode::RequestCanceled as i32
+                        || err.code == lsp_server::ErrorCode::ContentModified as i32 => {}
+                Some(err) if err.code == lsp_server::ErrorCode::InvalidRequest as i32 => {
+                    assert_eq!(id, duplicate_id, "request {id} was rejected as a duplicate")
+                }
+                Some(err) => panic!("unexpected error for request {id}: {err:?}"),
+            }
+        }
+    }
+    // Nothing came after the last hover, so it has to be answered.
+    let last = by_id[&lsp_server::RequestId::from(last_id)][0];
+    assert!(last.error.is_none(), "request {last_id} failed: {:?}", last.error);
+    // No stray responses may follow, the next response has to be the one for this request.
+    server.request::<HoverRequest>(hover(), json!({ "contents": "{...}", "range": "{...}" }));
+}
+
+#[test]
+fn superseded_completion_requests_are_cancelled() {
+    if skip_slow_tests() {
         return;
     }
 
-    let librs: String = (0..10).fold(String::new(), |mut acc, i| format_to_acc!(acc, "mod m{i};"));
-    let libs: String = (0..10).fold(String::new(), |mut acc, i| {
-        format_to_acc!(acc, "//- /src/m{i}.rs\nfn foo() {{}}\n\n")
-    });
-    let server = Project::with_fixture(&format!(
+    let server = Project::with_fixture(
         r#"
 //- /Cargo.toml
 [package]
@@ -901,46 +1774,143 @@ fn diagnostics_dont_block_typing() {
 version = "0.0.0"
 
 //- /src/lib.rs
-{librs}
-
-{libs}
-
-fn main() {{}}
-"#
-    ))
-    .with_config(serde_json::json!({
-        "cargo": { "sysroot": "discover" },
-    }))
+struct Foo { field: u32 }
+fn f(foo: Foo) { foo. }
+"#,
+    )
     .server()
     .wait_until_workspace_is_loaded();
 
-    for i in 0..10 {
+    let completion = || CompletionParams {
+        text_document_position: TextDocumentPositionParams::new(
+            server.doc_id("src/lib.rs"),
+            Position::new(1, 21),
+        ),
+        context: None,
+        partial_result_params: PartialResultParams::default(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+
+    let check = |cancelled: i32, latest: i32| {
+        let responses = server.recv_responses(2);
+        let response = |id: i32| {
+            let id = lsp_server::RequestId::from(id);
+            responses.iter().find(|it| it.id == id).unwrap_or_else(|| panic!("no response {id}"))
+        };
+        let err = response(cancelled).error.as_ref();
+        assert_eq!(
+            err.map(|err| err.code),
+            Some(lsp_server::ErrorCode::RequestCanceled as i32),
+            "{cancelled} was not cancelled"
+        );
+        let response = response(latest);
+        assert!(response.error.is_none(), "{latest} failed: {:?}", response.error);
+        assert!(response.result.as_ref().unwrap().to_string().contains("field"));
+    };
+
+    // An explicitly cancelled request.
+    server.send_request_no_wait::<Completion>(1, completion());
+    server.notification::<Cancel>(CancelParams { id: NumberOrString::Number(1) });
+    server.send_request_no_wait::<Completion>(2, completion());
+    check(1, 2);
+
+    // A request superseded by a later one for the same document.
+    server.send_request_no_wait::<Completion>(3, completion());
+    server.send_request_no_wait::<Completion>(4, completion());
+    check(3, 4);
+}
+
+#[test]
+fn completion_is_not_starved_by_diagnostics() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    const N_FILES: usize = 100;
+    let tmp_dir = TestDir::new();
+    let path = tmp_dir.path();
+    let project = json!({
+        "roots": [path],
+        "crates": [ {
+            "root_module": path.join("src/lib.rs"),
+            "deps": [],
+            "edition": "2021",
+        } ]
+    });
+    let module = |i: usize| {
+        format!(
+            "pub struct S{i} {{ pub field: u32 }}\n\
+             pub fn f{i}(s: S{i}) -> u32 {{ let y = (s.field, s.field); y.0 + y.1 + missing{i}() }}\n"
+        )
+    };
+    let mut fixture = format!("//- /.rust-project.json\n{project}\n\n//- /src/lib.rs\n");
+    fixture.push_str("fn complete(s: m0::S0) { s. }\n");
+    (0..N_FILES).for_each(|i| format_to!(fixture, "pub mod m{i};\n"));
+    (0..N_FILES).for_each(|i| format_to!(fixture, "//- /src/m{i}.rs\n{}", module(i)));
+    let server = Project::with_fixture(&fixture)
+        .tmp_dir(tmp_dir)
+        .with_config(json!({ "numThreads": 2 }))
+        .server()
+        .wait_until_workspace_is_loaded();
+
+    // Every opened file schedules diagnostics for all open files on the background lane.
+    for i in 0..N_FILES {
+        let rel_path = format!("src/m{i}.rs");
         server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
             text_document: TextDocumentItem {
-                uri: server.doc_id(&format!("src/m{i}.rs")).uri,
+                uri: server.doc_id(&rel_path).uri,
                 language_id: "rust".to_owned(),
                 version: 0,
-                text: "/// Docs\nfn foo() {}".to_owned(),
+                text: module(i),
             },
         });
     }
-    let start = Instant::now();
-    server.request::<OnEnter>(
-        TextDocumentPositionParams {
-            text_document: server.doc_id("src/m0.rs"),
-            position: Position { line: 0, character: 5 },
+
+    let status = || {
+        let status =
+            server.send_request::<AnalyzerStatus>(AnalyzerStatusParams { text_document: None });
+        let status = status.as_str().unwrap().to_owned();
+        let queued = ["Interactive", "Default", "Background"]
+            .map(|lane| {
+                let prefix = format!("{lane}: ");
+                let line = status.lines().find_map(|line| line.strip_prefix(&*prefix)).unwrap();
+                line.split_once(" queued").unwrap().0.parse::<usize>().unwrap()
+            })
+            .iter()
+            .sum::<usize>();
+        let turns = status
+            .lines()
+            .find_map(|line| line.strip_prefix("Main loop turns: "))
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+        (queued, turns)
+    };
+    let (queued, turns_before) = status();
+    assert!(queued > 10, "diagnostics did not pile up, only {queued} tasks were queued");
+
+    server.send_request_no_wait::<Completion>(
+        1,
+        CompletionParams {
+            text_document_position: TextDocumentPositionParams::new(
+                server.doc_id("src/lib.rs"),
+                Position::new(0, 27),
+            ),
+            context: None,
+            partial_result_params: PartialResultParams::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
         },
-        json!([{
-            "insertTextFormat": 2,
-            "newText": "\n/// $0",
-            "range": {
-            "end": { "character": 5, "line": 0 },
-            "start": { "character": 5, "line": 0 }
-            }
-        }]),
     );
-    let elapsed = start.elapsed();
-    assert!(elapsed.as_millis() < 2000, "typing enter took {elapsed:?}");
+    let response = server.recv_responses(1).remove(0);
+    assert!(response.result.unwrap().to_string().contains("field"));
+    let (_, turns_after) = status();
+
+    // Counting loop turns rather than measuring time keeps this independent of the
+    // machine's speed: had the completion queued behind the diagnostics, every one of
+    // them would have reported back to the main loop first. It may only wait for the
+    // tasks already running and the few background tasks the pool lets through.
+    let turns = turns_after - turns_before;
+    assert!(turns <= 20, "completion took {turns} loop turns with {queued} queued tasks");
 }
 
 #[test]
@@ -1410,54 +2380,391 @@ fn main() {}
 }
 
 #[test]
-fn test_exclude_config_works() {
+fn test_exclude_config_works() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /foo/Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /foo/src/lib.rs
+pub fn foo() {}
+
+//- /bar/Cargo.toml
+[package]
+name = "bar"
+version = "0.0.0"
+
+[dependencies]
+foo = { path = "../foo" }
+
+//- /bar/src/lib.rs
+"#,
+    )
+    .root("foo")
+    .root("bar")
+    .root("baz")
+    .with_config(json!({
+       "files": {
+           "exclude": ["foo"]
+        }
+    }))
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    server.request::<WorkspaceSymbolRequest>(Default::default(), json!([]));
+
+    let server = Project::with_fixture(
+        r#"
+//- /foo/Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /foo/src/lib.rs
+pub fn foo() {}
+
+//- /bar/Cargo.toml
+[package]
+name = "bar"
+version = "0.0.0"
+
+//- /bar/src/lib.rs
+pub fn bar() {}
+
+//- /baz/Cargo.toml
+[package]
+name = "baz"
+version = "0.0.0"
+
+//- /baz/src/lib.rs
+"#,
+    )
+    .root("foo")
+    .root("bar")
+    .root("baz")
+    .with_config(json!({
+       "files": {
+           "exclude": ["foo", "bar"]
+        }
+    }))
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    server.request::<WorkspaceSymbolRequest>(Default::default(), json!([]));
+}
+
+#[test]
+fn test_files_under_target_are_excluded_but_still_get_overlays() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub struct InSrc;
+
+//- /target/debug/build/generated.rs
+pub struct InTarget;
+"#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let query =
+        |query: &str| WorkspaceSymbolParams { query: query.to_owned(), ..Default::default() };
+    server.request::<WorkspaceSymbolRequest>(
+        query("InSrc"),
+        json!([{
+            "name": "InSrc",
+            "kind": 23,
+            "location": { "uri": "file://[..]/src/lib.rs", "range": "{...}" }
+        }]),
+    );
+    server.request::<WorkspaceSymbolRequest>(query("InTarget"), json!([]));
+
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: server.doc_id("target/debug/build/generated.rs").uri,
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "pub struct InTarget\n".to_owned(),
+        },
+    });
+    server.wait_for_diagnostics("target/debug/build/generated.rs", |diagnostics| {
+        diagnostics.iter().any(|it| it.message.contains("expected"))
+    });
+}
+
+#[test]
+fn test_qualified_name() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+mod bar {
+    pub struct S;
+    impl S {
+        pub fn new() -> S { S }
+    }
+}
+"#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    server.request::<rust_analyzer::lsp::ext::QualifiedName>(
+        TextDocumentPositionParams::new(server.doc_id("src/lib.rs"), Position::new(3, 15)),
+        json!("foo::bar::S::new"),
+    );
+}
+
+#[test]
+fn test_workspace_symbol_query_syntax() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let tmp_dir = TestDir::new();
+    let path = tmp_dir.path();
+
+    let project = json!({
+        "roots": [path],
+        "crates": [
+            {
+                "root_module": path.join("src/lib.rs"),
+                "deps": [{ "crate": 1, "name": "dep" }],
+                "edition": "2021",
+                "cfg": [],
+            },
+            {
+                "root_module": path.join("dep/lib.rs"),
+                "deps": [],
+                "edition": "2021",
+                "cfg": [],
+                "is_workspace_member": false,
+            },
+        ]
+    });
+
+    let code = format!(
+        r#"
+//- /.rust-project.json
+{project}
+
+//- /src/lib.rs
+pub struct Foo;
+pub struct FooBar;
+pub fn foo() {{}}
+
+//- /dep/lib.rs
+pub struct Foo;
+"#,
+    );
+
+    let server =
+        Project::with_fixture(&code).tmp_dir(tmp_dir).server().wait_until_workspace_is_loaded();
+
+    let query =
+        |query: &str| WorkspaceSymbolParams { query: query.to_owned(), ..Default::default() };
+    let symbol = |name: &str, kind: u32, file: &str| {
+        json!({
+            "name": name,
+            "kind": kind,
+            "location": { "uri": format!("file://[..]/{file}"), "range": "{...}" }
+        })
+    };
+
+    server
+        .request::<WorkspaceSymbolRequest>(query("Foo#"), json!([symbol("Foo", 23, "src/lib.rs")]));
+    server.request::<WorkspaceSymbolRequest>(
+        query("Foo#*"),
+        json!([symbol("Foo", 23, "src/lib.rs"), symbol("Foo", 23, "dep/lib.rs")]),
+    );
+    server.request::<WorkspaceSymbolRequest>(
+        query("fn foo"),
+        json!([symbol("foo", 12, "src/lib.rs")]),
+    );
+}
+
+#[test]
+fn test_document_highlight() {
     if skip_slow_tests() {
         return;
     }
 
     let server = Project::with_fixture(
         r#"
-//- /foo/Cargo.toml
+//- /Cargo.toml
 [package]
 name = "foo"
 version = "0.0.0"
 
-//- /foo/src/lib.rs
-pub fn foo() {}
+//- /src/lib.rs
+fn f() {
+    let mut x = 1;
+    let r = &mut x;
+    x;
+}
+"#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
 
-//- /bar/Cargo.toml
+    server.request::<DocumentHighlightRequest>(
+        DocumentHighlightParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                server.doc_id("src/lib.rs"),
+                Position::new(3, 4),
+            ),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        },
+        json!([
+            {
+                "range": {
+                    "start": { "line": 1, "character": 12 },
+                    "end": { "line": 1, "character": 13 }
+                },
+                "kind": 3
+            },
+            {
+                "range": {
+                    "start": { "line": 2, "character": 17 },
+                    "end": { "line": 2, "character": 18 }
+                },
+                "kind": 3
+            },
+            {
+                "range": {
+                    "start": { "line": 3, "character": 4 },
+                    "end": { "line": 3, "character": 5 }
+                },
+                "kind": 2
+            }
+        ]),
+    );
+}
+
+#[test]
+fn test_deleted_files_leave_the_index() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
 [package]
-name = "bar"
+name = "foo"
 version = "0.0.0"
 
-[dependencies]
-foo = { path = "../foo" }
+//- /src/lib.rs
+mod foo;
+mod bar;
 
-//- /bar/src/lib.rs
+//- /src/foo.rs
+pub struct Foo;
+
+//- /src/bar.rs
+pub struct Bar;
 "#,
     )
-    .root("foo")
-    .root("bar")
-    .root("baz")
-    .with_config(json!({
-       "files": {
-           "exclude": ["foo"]
-        }
-    }))
     .server()
     .wait_until_workspace_is_loaded();
 
-    server.request::<WorkspaceSymbolRequest>(Default::default(), json!([]));
+    // `bar.rs` is open in the editor, so its contents are owned by the client.
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: server.doc_id("src/bar.rs").uri,
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "pub struct Bar;\n".to_owned(),
+        },
+    });
+
+    let symbols = |query: &str| {
+        server.send_request::<WorkspaceSymbolRequest>(WorkspaceSymbolParams {
+            query: query.to_owned(),
+            ..Default::default()
+        })
+    };
+    // The loader picks up deletions on its own thread, so poll until it caught up.
+    let wait_until_gone = |query: &str| {
+        for _ in 0..50 {
+            let res = symbols(query);
+            if res.is_null() || res == json!([]) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        panic!("`{query}` is still in the index");
+    };
+
+    std::fs::remove_file(server.path().join("src/foo.rs")).unwrap();
+    std::fs::remove_file(server.path().join("src/bar.rs")).unwrap();
+    server.notification::<DidChangeWatchedFiles>(DidChangeWatchedFilesParams {
+        changes: vec![
+            FileEvent::new(server.doc_id("src/foo.rs").uri, FileChangeType::DELETED),
+            FileEvent::new(server.doc_id("src/bar.rs").uri, FileChangeType::DELETED),
+        ],
+    });
+    wait_until_gone("Foo#");
+
+    // The overlay wins over the deletion on disk until the document gets closed.
+    server.request::<WorkspaceSymbolRequest>(
+        WorkspaceSymbolParams { query: "Bar#".to_owned(), ..Default::default() },
+        json!([{
+            "name": "Bar",
+            "kind": 23,
+            "location": { "uri": "file://[..]/src/bar.rs", "range": "{...}" }
+        }]),
+    );
+
+    server.notification::<DidCloseTextDocument>(DidCloseTextDocumentParams {
+        text_document: server.doc_id("src/bar.rs"),
+    });
+    wait_until_gone("Bar#");
+}
+
+#[test]
+fn test_reload_on_manifest_change() {
+    if skip_slow_tests() {
+        return;
+    }
 
     let server = Project::with_fixture(
         r#"
+//- /Cargo.toml
+[workspace]
+members = ["foo", "bar"]
+
 //- /foo/Cargo.toml
 [package]
 name = "foo"
 version = "0.0.0"
 
 //- /foo/src/lib.rs
-pub fn foo() {}
+pub fn hello() {}
 
 //- /bar/Cargo.toml
 [package]
@@ -1465,28 +2772,97 @@ pub fn foo() {}
 version = "0.0.0"
 
 //- /bar/src/lib.rs
-pub fn bar() {}
+pub fn call() {
+    foo::hello();
+}
+"#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
 
-//- /baz/Cargo.toml
+    std::fs::write(
+        server.path().join("bar/Cargo.toml"),
+        r#"
 [package]
-name = "baz"
+name = "bar"
 version = "0.0.0"
 
-//- /baz/src/lib.rs
+[dependencies]
+foo = { path = "../foo" }
 "#,
     )
-    .root("foo")
-    .root("bar")
-    .root("baz")
-    .with_config(json!({
-       "files": {
-           "exclude": ["foo", "bar"]
-        }
-    }))
-    .server()
-    .wait_until_workspace_is_loaded();
+    .unwrap();
+    server.notification::<DidChangeWatchedFiles>(DidChangeWatchedFilesParams {
+        changes: vec![FileEvent::new(server.doc_id("bar/Cargo.toml").uri, FileChangeType::CHANGED)],
+    });
+    server.wait_until_workspace_is_reloaded();
 
-    server.request::<WorkspaceSymbolRequest>(Default::default(), json!([]));
+    server.request::<GotoDefinition>(
+        GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                server.doc_id("bar/src/lib.rs"),
+                Position::new(1, 10),
+            ),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+        json!([{
+            "originSelectionRange": "{...}",
+            "targetRange": "{...}",
+            "targetSelectionRange": "{...}",
+            "targetUri": "file:///[..]foo/src/lib.rs"
+        }]),
+    );
+}
+
+#[test]
+fn test_crate_cfgs_enable_cfgd_out_code() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let fixture = r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+[features]
+x = []
+
+//- /src/lib.rs
+#[cfg(feature = "x")]
+fn with_x() {}
+
+fn f() {
+    with_x();
+}
+"#;
+    let goto_with_x = |server: &support::Server| {
+        server.send_request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                server.doc_id("src/lib.rs"),
+                Position::new(4, 4),
+            ),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    };
+
+    let server = Project::with_fixture(fixture).server().wait_until_workspace_is_loaded();
+    let res = goto_with_x(&server);
+    assert!(res.as_array().is_none_or(|it| it.is_empty()), "{res}");
+
+    let server = Project::with_fixture(fixture)
+        .with_config(json!({
+            "cargo": {
+                "crateCfgs": { "foo": ["feature=x"] }
+            }
+        }))
+        .server()
+        .wait_until_workspace_is_loaded();
+    let res = goto_with_x(&server);
+    assert!(res.as_array().is_some_and(|it| it.len() == 1), "{res}");
 }
 
 #[test]
@@ -1527,3 +2903,159 @@ fn test() {
     assert_eq!(arr.len(), 2);
     expect![[r#"{"goal":"Goal { param_env: ParamEnv { clauses: [] }, predicate: Binder { value: TraitPredicate(usize: Trait, polarity:Positive), bound_vars: [] } }","result":"Err(NoSolution)","depth":0,"candidates":[]}"#]].assert_eq(&arr[0].to_string());
 }
+
+#[test]
+fn test_progress_is_reported_while_loading() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub fn foo() {}
+"#,
+    )
+    .with_work_done_progress()
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    for token in ["rustAnalyzer/Fetching", "rustAnalyzer/Roots Scanned"] {
+        let kinds = server.progress_kinds(token);
+        assert_eq!(kinds.first().map(String::as_str), Some("begin"), "{token}: {kinds:?}");
+        assert_eq!(kinds.last().map(String::as_str), Some("end"), "{token}: {kinds:?}");
+    }
+}
+
+#[test]
+fn test_indexing_is_announced_without_progress_support() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let tmp_dir = TestDir::new();
+    let path = tmp_dir.path();
+    let project = json!({
+        "roots": [path],
+        "crates": [ {
+            "root_module": path.join("src/lib.rs"),
+            "deps": [],
+            "edition": "2021",
+            "cfg": [],
+        } ]
+    });
+    let code = format!(
+        r#"
+//- /.rust-project.json
+{project}
+
+//- /src/lib.rs
+pub fn foo() {{}}
+"#,
+    );
+
+    let server =
+        Project::with_fixture(&code).tmp_dir(tmp_dir).server().wait_until_workspace_is_loaded();
+
+    server.wait_for_shown_message("rust-analyzer: indexing finished");
+    assert_eq!(
+        server.shown_messages(),
+        ["rust-analyzer: indexing the workspace", "rust-analyzer: indexing finished"]
+    );
+    assert!(server.progress_kinds("rustAnalyzer/Indexing").is_empty());
+}
+
+#[test]
+fn test_folding_ranges() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+use std::fmt;
+use std::io;
+
+// one
+// two
+fn f() {}
+
+// region: helpers
+fn g() {
+    f();
+}
+// endregion: helpers
+"#,
+    )
+    .wait_until_workspace_is_loaded();
+
+    let config = Config::new(
+        AbsPathBuf::assert(server.path().to_path_buf()),
+        Default::default(),
+        vec![],
+        None,
+    );
+    assert!(matches!(
+        server_capabilities(&config).folding_range_provider,
+        Some(FoldingRangeProviderCapability::Simple(true))
+    ));
+
+    server.request::<FoldingRangeRequest>(
+        FoldingRangeParams {
+            text_document: server.doc_id("src/lib.rs"),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+        json!([
+            { "startLine": 0, "startCharacter": 0, "endLine": 1, "endCharacter": 12, "kind": "imports" },
+            { "startLine": 3, "startCharacter": 0, "endLine": 4, "endCharacter": 6, "kind": "comment" },
+            { "startLine": 7, "startCharacter": 0, "endLine": 11, "endCharacter": 21, "kind": "region" },
+            { "startLine": 8, "startCharacter": 7, "endLine": 10, "endCharacter": 1 }
+        ]),
+    );
+}
+
+#[test]
+fn test_expand_macro_recursively() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = project(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+macro_rules! inner {
+    ($name:ident) => { fn $name() {} };
+}
+macro_rules! outer {
+    ($name:ident) => { inner!($name); };
+}
+outer!(expanded);
+"#,
+    )
+    .wait_until_workspace_is_loaded();
+
+    server.request::<ExpandMacro>(
+        ExpandMacroParams {
+            text_document: server.doc_id("src/lib.rs"),
+            position: Position::new(6, 2),
+        },
+        json!({ "name": "outer!", "expansion": "fn expanded()[..]{}[..]" }),
+    );
+}
@@ -7,7 +7,7 @@
 
 use crossbeam_channel::{Receiver, after, select};
 use itertools::Itertools;
-use lsp_server::{Connection, Message, Notification, Request};
+use lsp_server::{Connection, Message, Notification, Request, Response};
 use lsp_types::{TextDocumentIdentifier, Url, notification::Exit, request::Shutdown};
 use parking_lot::{Mutex, MutexGuard};
 use paths::{Utf8Path, Utf8PathBuf};
@@ -30,6 +30,9 @@ pub(crate) struct Project<'a> {
     roots: Vec<Utf8PathBuf>,
     config: serde_json::Value,
     root_dir_contains_symlink: bool,
+    work_done_progress: bool,
+    hierarchical_document_symbols: bool,
+    code_action_resolve: bool,
 }
 
 impl Project<'_> {
@@ -54,6 +57,9 @@ pub(crate) fn with_fixture(fixture: &str) -> Project<'_> {
                 }
             }),
             root_dir_contains_symlink: false,
+            work_done_progress: false,
+            hierarchical_document_symbols: true,
+            code_action_resolve: false,
         }
     }
 
@@ -72,18 +78,23 @@ pub(crate) fn with_root_dir_contains_symlink(mut self) -> Self {
         self
     }
 
+    pub(crate) fn with_work_done_progress(mut self) -> Self {
+        self.work_done_progress = true;
+        self
+    }
+
+    pub(crate) fn with_flat_document_symbols(mut self) -> Self {
+        self.hierarchical_document_symbols = false;
+        self
+    }
+
+    pub(crate) fn with_code_action_resolve(mut self) -> Self {
+        self.code_action_resolve = true;
+        self
+    }
+
     pub(crate) fn with_config(mut self, config: serde_json::Value) -> Self {
-        fn merge(dst: &mut serde_json::Value, src: serde_json::Value) {
-            match (dst, src) {
-                (Value::Object(dst), Value::Object(src)) => {
-                    for (k, v) in src {
-                        merge(dst.entry(k).or_insert(v.clone()), v)
-                    }
-                }
-                (dst, src) => *dst = src,
-            }
-        }
-        merge(&mut self.config, config);
+        merge_config(&mut self.config, config);
         self
     }
 
@@ -234,12 +245,23 @@ pub(crate) fn server_with_lock(self, config_lock: bool) -> Server {
                         code_action_literal_support: Some(
                             lsp_types::CodeActionLiteralSupport::default(),
                         ),
+                        resolve_support: self.code_action_resolve.then(|| {
+                            lsp_types::CodeActionCapabilityResolveSupport {
+                                properties: vec!["edit".to_owned()],
+                            }
+                        }),
                         ..Default::default()
                     }),
                     hover: Some(lsp_types::HoverClientCapabilities {
                         content_format: Some(vec![lsp_types::MarkupKind::Markdown]),
                         ..Default::default()
                     }),
+                    document_symbol: Some(lsp_types::DocumentSymbolClientCapabilities {
+                        hierarchical_document_symbol_support: Some(
+                            self.hierarchical_document_symbols,
+                        ),
+                        ..Default::default()
+                    }),
                     inlay_hint: Some(lsp_types::InlayHintClientCapabilities {
                         resolve_support: Some(lsp_types::InlayHintResolveClientCapabilities {
                             properties: vec![
@@ -255,7 +277,7 @@ pub(crate) fn server_with_lock(self, config_lock: bool) -> Server {
                     ..Default::default()
                 }),
                 window: Some(lsp_types::WindowClientCapabilities {
-                    work_done_progress: Some(false),
+                    work_done_progress: Some(self.work_done_progress),
                     ..Default::default()
                 }),
                 experimental: Some(json!({
@@ -268,7 +290,7 @@ pub(crate) fn server_with_lock(self, config_lock: bool) -> Server {
         );
         let mut change = ConfigChange::default();
 
-        change.change_client_config(self.config);
+        change.change_client_config(self.config.clone());
 
         let error_sink: ConfigErrors;
         (config, error_sink, _) = config.apply_change(change);
@@ -276,7 +298,18 @@ pub(crate) fn server_with_lock(self, config_lock: bool) -> Server {
 
         config.rediscover_workspaces();
 
-        Server::new(config_dir_guard, tmp_dir.keep(), config)
+        Server::new(config_dir_guard, tmp_dir.keep(), config, self.config)
+    }
+}
+
+fn merge_config(dst: &mut Value, src: Value) {
+    match (dst, src) {
+        (Value::Object(dst), Value::Object(src)) => {
+            for (k, v) in src {
+                merge_config(dst.entry(k).or_insert(v.clone()), v)
+            }
+        }
+        (dst, src) => *dst = src,
     }
 }
 
@@ -292,6 +325,8 @@ pub(crate) struct Server {
     /// XXX: remove the tempdir last
     dir: TestDir,
     _config_dir_guard: Option<(MutexGuard<'static, ()>, TestDir)>,
+    /// The settings the client reports when asked for `workspace/configuration`.
+    client_config: RefCell<Value>,
 }
 
 impl Server {
@@ -299,6 +334,7 @@ fn new(
         config_dir_guard: Option<(MutexGuard<'static, ()>, TestDir)>,
         dir: TestDir,
         config: Config,
+        client_config: Value,
     ) -> Server {
         let (connection, client) = Connection::memory();
 
@@ -313,6 +349,7 @@ fn new(
             client,
             _thread,
             _config_dir_guard: config_dir_guard,
+            client_config: RefCell::new(client_config),
         }
     }
 
@@ -321,6 +358,19 @@ pub(crate) fn doc_id(&self, rel_path: &str) -> TextDocumentIdentifier {
         TextDocumentIdentifier { uri: Url::from_file_path(path).unwrap() }
     }
 
+    /// The URI of the project root as the server reports it, which `${root}` expands to in
+    /// expected responses.
+    fn root_uri(&self) -> String {
+        let uri = Url::from_file_path(self.dir.path()).unwrap().to_string();
+        // The server lowercases Windows drive letters, e.g. `file:///c:/...`.
+        match uri.strip_prefix("file:///") {
+            Some(rest) if rest.as_bytes().get(1) == Some(&b':') => {
+                format!("file:///{}", rest[..1].to_ascii_lowercase() + &rest[1..])
+            }
+            _ => uri,
+        }
+    }
+
     pub(crate) fn notification<N>(&self, params: N::Params)
     where
         N: lsp_types::notification::Notification,
@@ -336,14 +386,17 @@ pub(crate) fn request<R>(&self, params: R::Params, expected_resp: Value)
         R: lsp_types::request::Request,
         R::Params: Serialize,
     {
+        let mut expected_resp = expected_resp;
+        expand_root(&mut expected_resp, &self.root_uri());
         let actual = self.send_request::<R>(params);
-        if let Some((expected_part, actual_part)) = find_mismatch(&expected_resp, &actual) {
+        if let Some(mismatch) = find_mismatch(&expected_resp, &actual) {
             panic!(
-                "JSON mismatch\nExpected:\n{}\nWas:\n{}\nExpected part:\n{}\nActual part:\n{}\n",
+                "JSON mismatch at `{}`\nExpected:\n{}\nWas:\n{}\nExpected part:\n{}\nActual part:\n{}\n",
+                mismatch.pointer,
                 to_string_pretty(&expected_resp).unwrap(),
                 to_string_pretty(&actual).unwrap(),
-                to_string_pretty(expected_part).unwrap(),
-                to_string_pretty(actual_part).unwrap(),
+                to_string_pretty(mismatch.expected).unwrap(),
+                to_string_pretty(mismatch.actual).unwrap(),
             );
         }
     }
@@ -367,6 +420,9 @@ fn send_request_(&self, r: Request) -> Value {
         while let Some(msg) = self.recv().unwrap_or_else(|Timeout| panic!("timeout: {r:?}")) {
             match msg {
                 Message::Request(req) => {
+                    if req.method == "window/workDoneProgress/create" {
+                        continue;
+                    }
                     if req.method == "client/registerCapability" {
                         let params = req.params.to_string();
                         if ["workspace/didChangeWatchedFiles", "textDocument/didSave"]
@@ -390,23 +446,143 @@ fn send_request_(&self, r: Request) -> Value {
         }
         panic!("no response for {r:?}");
     }
+    /// Merges `config` into the client settings and notifies the server about it, answering the
+    /// `workspace/configuration` request it sends back in response.
+    pub(crate) fn change_client_config(&self, config: Value) {
+        merge_config(&mut self.client_config.borrow_mut(), config);
+        self.notification::<lsp_types::notification::DidChangeConfiguration>(
+            lsp_types::DidChangeConfigurationParams { settings: Value::Null },
+        );
+        loop {
+            match self.recv() {
+                Ok(Some(Message::Request(req))) if req.method == "workspace/configuration" => {
+                    let settings = json!([self.client_config.borrow().clone()]);
+                    self.client.sender.send(Response::new_ok(req.id, settings).into()).unwrap();
+                    return;
+                }
+                Ok(Some(Message::Request(req)))
+                    if req.method == "client/registerCapability"
+                        || req.method == "window/workDoneProgress/create" => {}
+                Ok(Some(Message::Request(req))) => panic!("unexpected request: {req:?}"),
+                Ok(Some(_)) => (),
+                Ok(None) => panic!("server hung up before asking for the configuration"),
+                Err(Timeout) => panic!("timeout while waiting for a configuration request"),
+            }
+        }
+    }
+
+    /// Sends a request with the given id without waiting for the response, see
+    /// [`Server::recv_responses`].
+    pub(crate) fn send_request_no_wait<R>(&self, id: i32, params: R::Params)
+    where
+        R: lsp_types::request::Request,
+        R::Params: Serialize,
+    {
+        let r = Request::new(id.into(), R::METHOD.to_owned(), params);
+        self.client.sender.send(r.into()).unwrap();
+    }
+
+    /// Waits for `n` responses and returns them in the order they arrived.
+    #[track_caller]
+    pub(crate) fn recv_responses(&self, n: usize) -> Vec<Response> {
+        let mut responses = Vec::with_capacity(n);
+        while responses.len() < n {
+            match self.recv() {
+                Ok(Some(Message::Response(res))) => responses.push(res),
+                Ok(Some(Message::Request(req)))
+                    if req.method == "client/registerCapability"
+                        || req.method == "window/workDoneProgress/create" => {}
+                Ok(Some(Message::Request(req))) => panic!("unexpected request: {req:?}"),
+                Ok(Some(Message::Notification(_))) => (),
+                Ok(None) => panic!("server hung up after {} responses", responses.len()),
+                Err(Timeout) => panic!("timeout, got {} of {n} responses", responses.len()),
+            }
+        }
+        responses
+    }
+
     pub(crate) fn wait_until_workspace_is_loaded(self) -> Server {
-        self.wait_for_message_cond(1, &|msg: &Message| match msg {
-            Message::Notification(n) if n.method == "experimental/serverStatus" => {
-                let status = n
+        self.wait_for_message_cond(1, &is_quiescent_status)
+            .unwrap_or_else(|Timeout| panic!("timeout while waiting for ws to load"));
+        self
+    }
+
+    /// Waits for the server to settle again after a change that makes it reload the workspace,
+    /// like an edit to a `Cargo.toml`.
+    pub(crate) fn wait_until_workspace_is_reloaded(&self) {
+        let loaded = self.messages.borrow().iter().filter(|msg| is_quiescent_status(msg)).count();
+        self.wait_for_message_cond(loaded + 1, &is_quiescent_status)
+            .unwrap_or_else(|Timeout| panic!("timeout while waiting for ws to reload"));
+    }
+
+    /// Returns the kinds (`begin`, `report` or `end`) of the `$/progress` notifications
+    /// received so far for `token`, in order.
+    pub(crate) fn progress_kinds(&self, token: &str) -> Vec<String> {
+        self.messages
+            .borrow()
+            .iter()
+            .filter_map(|msg| match msg {
+                Message::Notification(n)
+                    if n.method == "$/progress" && n.params["token"] == token =>
+                {
+                    n.params["value"]["kind"].as_str().map(ToOwned::to_owned)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the texts of the `window/showMessage` notifications received so far, in order.
+    pub(crate) fn shown_messages(&self) -> Vec<String> {
+        self.messages
+            .borrow()
+            .iter()
+            .filter_map(|msg| match msg {
+                Message::Notification(n) if n.method == "window/showMessage" => {
+                    n.params["message"].as_str().map(ToOwned::to_owned)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Waits until the server has shown `text` in a `window/showMessage` notification.
+    pub(crate) fn wait_for_shown_message(&self, text: &str) {
+        let matches = |msg: &Message| match msg {
+            Message::Notification(n) if n.method == "window/showMessage" => {
+                n.params["message"] == text
+            }
+            _ => false,
+        };
+        self.wait_for_message_cond(1, &matches)
+            .unwrap_or_else(|Timeout| panic!("timeout while waiting for message {text:?}"));
+    }
+
+    /// Waits for the next `textDocument/publishDiagnostics` notification for `rel_path` whose
+    /// diagnostics satisfy `cond`.
+    pub(crate) fn wait_for_diagnostics(
+        &self,
+        rel_path: &str,
+        cond: impl Fn(&[lsp_types::Diagnostic]) -> bool,
+    ) {
+        let uri = self.doc_id(rel_path).uri;
+        let matches = |msg: &Message| match msg {
+            Message::Notification(n) if n.method == "textDocument/publishDiagnostics" => {
+                let params = n
                     .clone()
-                    .extract::<lsp::ext::ServerStatusParams>("experimental/serverStatus")
+                    .extract::<lsp_types::PublishDiagnosticsParams>(
+                        "textDocument/publishDiagnostics",
+                    )
                     .unwrap();
-                if status.health != lsp::ext::Health::Ok {
-                    panic!("server errored/warned while loading workspace: {:?}", status.message);
-                }
-                status.quiescent
+                params.uri == uri && cond(&params.diagnostics)
             }
             _ => false,
-        })
-        .unwrap_or_else(|Timeout| panic!("timeout while waiting for ws to load"));
-        self
+        };
+        let seen = self.messages.borrow().iter().filter(|msg| matches(msg)).count();
+        self.wait_for_message_cond(seen + 1, &matches)
+            .unwrap_or_else(|Timeout| panic!("timeout while waiting for diagnostics"));
     }
+
     fn wait_for_message_cond(
         &self,
         n: usize,
@@ -461,6 +637,22 @@ fn drop(&mut self) {
 
 struct Timeout;
 
+fn is_quiescent_status(msg: &Message) -> bool {
+    match msg {
+        Message::Notification(n) if n.method == "experimental/serverStatus" => {
+            let status = n
+                .clone()
+                .extract::<lsp::ext::ServerStatusParams>("experimental/serverStatus")
+                .unwrap();
+            if status.health != lsp::ext::Health::Ok {
+                panic!("server errored/warned while loading workspace: {:?}", status.message);
+            }
+            status.quiescent
+        }
+        _ => false,
+    }
+}
+
 fn recv_timeout(receiver: &Receiver<Message>) -> Result<Option<Message>, Timeout> {
     let timeout =
         if cfg!(target_os = "macos") { Duration::from_secs(300) } else { Duration::from_secs(120) };
@@ -472,24 +664,52 @@ fn recv_timeout(receiver: &Receiver<Message>) -> Result<Option<Message>, Timeout
 
 // Comparison functionality borrowed from cargo:
 
+/// The first place where the actual JSON differs from the expected one.
+struct Mismatch<'a> {
+    /// A JSON pointer (RFC 6901) into the expected value.
+    pointer: String,
+    expected: &'a Value,
+    actual: &'a Value,
+}
+
+/// Replaces `${root}` in the strings of `expected` with the URI of the project root, without a
+/// trailing slash.
+fn expand_root(expected: &mut Value, root: &str) {
+    match expected {
+        Value::String(s) => *s = s.replace("${root}", root),
+        Value::Array(values) => values.iter_mut().for_each(|it| expand_root(it, root)),
+        Value::Object(map) => map.values_mut().for_each(|it| expand_root(it, root)),
+        _ => (),
+    }
+}
+
 /// Compares JSON object for approximate equality.
 /// You can use `[..]` wildcard in strings (useful for OS dependent things such
-/// as paths). You can use a `"{...}"` string literal as a wildcard for
+/// as paths). You can use a `"{...}"` or `"${any}"` string literal as a wildcard for
 /// arbitrary nested JSON. Arrays are sorted before comparison.
-fn find_mismatch<'a>(expected: &'a Value, actual: &'a Value) -> Option<(&'a Value, &'a Value)> {
+fn find_mismatch<'a>(expected: &'a Value, actual: &'a Value) -> Option<Mismatch<'a>> {
+    let mismatch = || Some(Mismatch { pointer: String::new(), expected, actual });
+    let nested = |key: &str, mismatch: Option<Mismatch<'a>>| {
+        mismatch.map(|it| Mismatch {
+            pointer: format!("/{}{}", key.replace('~', "~0").replace('/', "~1"), it.pointer),
+            ..it
+        })
+    };
     match (expected, actual) {
+        // magic string literals "{...}" and "${any}" act as wildcards for any sub-JSON
+        (Value::String(l), _) if l == "{...}" || l == "${any}" => None,
         (Value::Number(l), Value::Number(r)) if l == r => None,
         (Value::Bool(l), Value::Bool(r)) if l == r => None,
         (Value::String(l), Value::String(r)) if lines_match(l, r) => None,
         (Value::Array(l), Value::Array(r)) => {
             if l.len() != r.len() {
-                return Some((expected, actual));
+                return mismatch();
             }
 
-            let mut l = l.iter().collect::<Vec<_>>();
+            let mut l = l.iter().enumerate().collect::<Vec<_>>();
             let mut r = r.iter().collect::<Vec<_>>();
 
-            l.retain(|l| match r.iter().position(|r| find_mismatch(l, r).is_none()) {
+            l.retain(|(_, l)| match r.iter().position(|r| find_mismatch(l, r).is_none()) {
                 Some(i) => {
                     r.remove(i);
                     false
@@ -497,35 +717,26 @@ fn find_mismatch<'a>(expected: &'a Value, actual: &'a Value) -> Option<(&'a Valu
                 None => true,
             });
 
-            if !l.is_empty() {
+            if let Some(&(idx, l)) = l.first() {
                 assert!(!r.is_empty());
-                Some((l[0], r[0]))
+                nested(&idx.to_string(), find_mismatch(l, r[0]))
             } else {
                 assert_eq!(r.len(), 0);
                 None
             }
         }
         (Value::Object(l), Value::Object(r)) => {
-            fn sorted_values(obj: &serde_json::Map<String, Value>) -> Vec<&Value> {
-                let mut entries = obj.iter().collect::<Vec<_>>();
-                entries.sort_by_key(|it| it.0);
-                entries.into_iter().map(|(_k, v)| v).collect::<Vec<_>>()
-            }
-
             let same_keys = l.len() == r.len() && l.keys().all(|k| r.contains_key(k));
             if !same_keys {
-                return Some((expected, actual));
+                return mismatch();
             }
 
-            let l = sorted_values(l);
-            let r = sorted_values(r);
-
-            l.into_iter().zip(r).find_map(|(l, r)| find_mismatch(l, r))
+            let mut keys = l.keys().collect::<Vec<_>>();
+            keys.sort();
+            keys.into_iter().find_map(|k| nested(k, find_mismatch(&l[k], &r[k])))
         }
         (Value::Null, Value::Null) => None,
-        // magic string literal "{...}" acts as wildcard for any sub-JSON
-        (Value::String(l), _) if l == "{...}" => None,
-        _ => Some((expected, actual)),
+        _ => mismatch(),
     }
 }
 
@@ -552,6 +763,27 @@ fn lines_match(expected: &str, actual: &str) -> bool {
     actual.is_empty() || expected.ends_with("[..]")
 }
 
+#[test]
+fn find_mismatch_reports_pointer() {
+    let expected = json!({ "a": [{ "b/c": 1 }], "range": "${any}", "uri": "${root}/src/lib.rs" });
+    let mut expected_root = expected.clone();
+    expand_root(&mut expected_root, "file:///tmp/root");
+
+    let actual = json!({
+        "a": [{ "b/c": 1 }],
+        "range": { "start": 0 },
+        "uri": "file:///tmp/root/src/lib.rs",
+    });
+    assert!(find_mismatch(&expected_root, &actual).is_none());
+    assert_eq!(find_mismatch(&expected, &actual).unwrap().pointer, "/uri");
+
+    let actual =
+        json!({ "a": [{ "b/c": 2 }], "range": null, "uri": "file:///tmp/root/src/lib.rs" });
+    let mismatch = find_mismatch(&expected_root, &actual).unwrap();
+    assert_eq!(mismatch.pointer, "/a/0/b~1c");
+    assert_eq!(mismatch.actual, &json!(2));
+}
+
 #[test]
 fn lines_match_works() {
     assert!(lines_match("a b", "a b"));
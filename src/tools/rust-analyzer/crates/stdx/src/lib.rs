@@ -222,6 +222,18 @@ pub fn trim_indent(mut text: &str) -> String {
         .collect()
 }
 
+/// Truncates `text` to at most `max_len` characters, marking the cut with `…`.
+#[must_use]
+pub fn truncate_with_ellipsis(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_owned();
+    }
+    let mut res = text.chars().take(max_len.saturating_sub(1)).collect::<String>();
+    res.truncate(res.trim_end().len());
+    res.push('…');
+    res
+}
+
 pub fn equal_range_by<T, F>(slice: &[T], mut key: F) -> ops::Range<usize>
 where
     F: FnMut(&T) -> Ordering,
@@ -352,6 +364,12 @@ fn main() {
         );
     }
 
+    #[test]
+    fn test_truncate_with_ellipsis() {
+        assert_eq!(truncate_with_ellipsis("fn(u32) -> u8", 20), "fn(u32) -> u8");
+        assert_eq!(truncate_with_ellipsis("fn(u32, Vec<String>) -> u8", 12), "fn(u32, Vec…");
+    }
+
     #[test]
     fn test_replace() {
         #[track_caller]
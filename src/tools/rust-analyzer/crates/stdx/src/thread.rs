@@ -15,13 +15,17 @@
 //! Thus, [`ThreadIntent`] has no default value
 //! and every entry point to creating a thread requires a [`ThreadIntent`] upfront.
 
-use std::fmt;
+use std::{
+    any::Any,
+    fmt,
+    time::{Duration, Instant},
+};
 
 mod intent;
 mod pool;
 
 pub use intent::ThreadIntent;
-pub use pool::Pool;
+pub use pool::{Lane, LaneMetrics, Pool, PoolMetrics};
 
 /// # Panics
 ///
@@ -87,6 +91,57 @@ impl<T> JoinHandle<T> {
     pub fn join(mut self) -> T {
         self.inner.take().unwrap().join()
     }
+
+    /// Waits at most `timeout` for the thread to finish.
+    ///
+    /// Unlike [`JoinHandle::join`], a panic in the thread is returned as an
+    /// error instead of being propagated. A thread that doesn't finish in time
+    /// is detached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no thread to join.
+    pub fn join_timeout(mut self, timeout: Duration) -> Result<T, JoinError> {
+        let handle = self.inner.take().unwrap().detach();
+        let deadline = Instant::now() + timeout;
+        while !handle.is_finished() {
+            if Instant::now() >= deadline {
+                return Err(JoinError::TimedOut);
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        handle.join().map_err(|payload| JoinError::Panicked(panic_message(&*payload)))
+    }
+}
+
+/// The error returned by [`JoinHandle::join_timeout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoinError {
+    /// The thread panicked with the given message.
+    Panicked(String),
+    /// The thread didn't finish in time.
+    TimedOut,
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinError::Panicked(message) => write!(f, "thread panicked: {message}"),
+            JoinError::TimedOut => f.write_str("thread did not stop in time"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_owned()
+    }
 }
 
 impl<T> Drop for JoinHandle<T> {
@@ -106,3 +161,31 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.pad("JoinHandle { .. }")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Builder, JoinError, ThreadIntent};
+
+    #[test]
+    fn join_timeout_reports_panics() {
+        let handle = Builder::new(ThreadIntent::Worker, "Panicking")
+            .spawn(|| panic!("worker failed"))
+            .unwrap();
+        assert_eq!(
+            handle.join_timeout(Duration::from_secs(10)),
+            Err(JoinError::Panicked("worker failed".to_owned()))
+        );
+    }
+
+    #[test]
+    fn join_timeout_gives_up_on_wedged_threads() {
+        let (sender, receiver) = crossbeam_channel::bounded::<()>(0);
+        let handle = Builder::new(ThreadIntent::Worker, "Wedged")
+            .spawn(move || _ = receiver.recv())
+            .unwrap();
+        assert_eq!(handle.join_timeout(Duration::from_millis(50)), Err(JoinError::TimedOut));
+        drop(sender);
+    }
+}
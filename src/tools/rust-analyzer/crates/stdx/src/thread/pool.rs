@@ -8,15 +8,16 @@
 //! the threading utilities in [`crate::thread`].
 
 use std::{
+    collections::VecDeque,
     marker::PhantomData,
     panic::{self, UnwindSafe},
     sync::{
-        Arc,
+        Arc, Condvar, Mutex,
         atomic::{AtomicUsize, Ordering},
     },
+    time::{Duration, Instant},
 };
 
-use crossbeam_channel::{Receiver, Sender};
 use crossbeam_utils::sync::WaitGroup;
 
 use crate::thread::{Builder, JoinHandle, ThreadIntent};
@@ -25,15 +26,42 @@ pub struct Pool {
     // `_handles` is never read: the field is present
     // only for its `Drop` impl.
 
-    // The worker threads exit once the channel closes;
-    // make sure to keep `job_sender` above `handles`
-    // so that the channel is actually closed
-    // before we join the worker threads!
-    job_sender: Sender<Job>,
+    // The worker threads exit once the queue is closed and drained,
+    // which happens when the pool is dropped.
+    queue: Arc<JobQueue>,
     _handles: Box<[JoinHandle]>,
     extant_tasks: Arc<AtomicUsize>,
 }
 
+/// The queue a job waits in until a worker thread picks it up.
+///
+/// Lanes are served in priority order, but a lane that has been passed over
+/// [`MAX_SKIPPED`] times in a row is served next, so no lane starves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    /// Requests the user is actively waiting for, like completion.
+    Interactive,
+    /// Everything else.
+    Default,
+    /// Recomputation not directly requested by the user, like diagnostics.
+    Background,
+}
+
+impl Lane {
+    pub const ALL: [Lane; 3] = [Lane::Interactive, Lane::Default, Lane::Background];
+
+    fn for_intent(intent: ThreadIntent) -> Lane {
+        match intent {
+            ThreadIntent::LatencySensitive => Lane::Interactive,
+            ThreadIntent::Worker => Lane::Default,
+        }
+    }
+}
+
+/// How many times in a row a non-empty lane can be passed over for a
+/// higher-priority one.
+const MAX_SKIPPED: usize = 8;
+
 struct Job {
     requested_intent: ThreadIntent,
     f: Box<dyn FnOnce() + Send + UnwindSafe + 'static>,
@@ -48,7 +76,7 @@ pub fn new(threads: usize) -> Self {
         const STACK_SIZE: usize = 8 * 1024 * 1024;
         const INITIAL_INTENT: ThreadIntent = ThreadIntent::Worker;
 
-        let (job_sender, job_receiver) = crossbeam_channel::unbounded();
+        let queue = Arc::new(JobQueue::default());
         let extant_tasks = Arc::new(AtomicUsize::new(0));
 
         let mut handles = Vec::with_capacity(threads);
@@ -58,10 +86,10 @@ pub fn new(threads: usize) -> Self {
                 .allow_leak(true)
                 .spawn({
                     let extant_tasks = Arc::clone(&extant_tasks);
-                    let job_receiver: Receiver<Job> = job_receiver.clone();
+                    let queue = Arc::clone(&queue);
                     move || {
                         let mut current_intent = INITIAL_INTENT;
-                        for job in job_receiver {
+                        while let Some(job) = queue.pop() {
                             if job.requested_intent != current_intent {
                                 job.requested_intent.apply_to_current_thread();
                                 current_intent = job.requested_intent;
@@ -78,10 +106,19 @@ pub fn new(threads: usize) -> Self {
             handles.push(handle);
         }
 
-        Self { _handles: handles.into_boxed_slice(), extant_tasks, job_sender }
+        Self { _handles: handles.into_boxed_slice(), extant_tasks, queue }
     }
 
+    /// Spawns a job in the lane matching its intent: latency sensitive jobs are
+    /// [`Lane::Interactive`], others [`Lane::Default`].
     pub fn spawn<F>(&self, intent: ThreadIntent, f: F)
+    where
+        F: FnOnce() + Send + UnwindSafe + 'static,
+    {
+        self.spawn_in(Lane::for_intent(intent), intent, f);
+    }
+
+    pub fn spawn_in<F>(&self, lane: Lane, intent: ThreadIntent, f: F)
     where
         F: FnOnce() + Send + UnwindSafe + 'static,
     {
@@ -93,7 +130,7 @@ pub fn spawn<F>(&self, intent: ThreadIntent, f: F)
         });
 
         let job = Job { requested_intent: intent, f };
-        self.job_sender.send(job).unwrap();
+        self.queue.push(lane, job);
     }
 
     pub fn scoped<'pool, 'scope, F, R>(&'pool self, f: F) -> R
@@ -116,6 +153,132 @@ pub fn len(&self) -> usize {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns a handle to the queue metrics of this pool.
+    #[must_use]
+    pub fn metrics(&self) -> PoolMetrics {
+        PoolMetrics { queue: Arc::clone(&self.queue) }
+    }
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+/// A cheaply cloneable handle for inspecting the queue of a [`Pool`].
+#[derive(Clone)]
+pub struct PoolMetrics {
+    queue: Arc<JobQueue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaneMetrics {
+    pub lane: Lane,
+    /// The number of jobs currently waiting in the lane.
+    pub depth: usize,
+    /// The longest time a job waited in the lane before being picked up since the
+    /// previous call to [`PoolMetrics::lanes`].
+    pub max_wait: Duration,
+}
+
+impl PoolMetrics {
+    /// Returns the current metrics of every lane and starts a new max wait period.
+    #[must_use]
+    pub fn lanes(&self) -> [LaneMetrics; 3] {
+        let mut state = self.queue.state.lock().unwrap();
+        Lane::ALL.map(|lane| LaneMetrics {
+            lane,
+            depth: state.lanes[lane as usize].len(),
+            max_wait: state.take_max_wait(lane),
+        })
+    }
+}
+
+#[derive(Default)]
+struct JobQueue {
+    state: Mutex<QueueState>,
+    available: Condvar,
+}
+
+impl JobQueue {
+    fn push(&self, lane: Lane, job: Job) {
+        self.state.lock().unwrap().push(lane, job);
+        self.available.notify_one();
+    }
+
+    /// Blocks until a job is available, returns `None` once the queue is closed and empty.
+    fn pop(&self) -> Option<Job> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some((_, job)) = state.pop() {
+                return Some(job);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.available.wait(state).unwrap();
+        }
+    }
+
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.available.notify_all();
+    }
+}
+
+struct QueueState<T = Job> {
+    lanes: [VecDeque<(Instant, T)>; 3],
+    skipped: [usize; 3],
+    max_wait: [Duration; 3],
+    closed: bool,
+}
+
+impl<T> Default for QueueState<T> {
+    fn default() -> Self {
+        QueueState {
+            lanes: Default::default(),
+            skipped: [0; 3],
+            max_wait: [Duration::ZERO; 3],
+            closed: false,
+        }
+    }
+}
+
+impl<T> QueueState<T> {
+    fn push(&mut self, lane: Lane, item: T) {
+        self.lanes[lane as usize].push_back((Instant::now(), item));
+    }
+
+    fn pop(&mut self) -> Option<(Lane, T)> {
+        let mut chosen = None;
+        for (idx, lane) in self.lanes.iter().enumerate() {
+            if lane.is_empty() {
+                continue;
+            }
+            if chosen.is_none() {
+                chosen = Some(idx);
+            } else if self.skipped[idx] >= MAX_SKIPPED {
+                chosen = Some(idx);
+                break;
+            }
+        }
+        let chosen = chosen?;
+
+        for (idx, lane) in self.lanes.iter().enumerate() {
+            self.skipped[idx] =
+                if idx == chosen || lane.is_empty() { 0 } else { self.skipped[idx] + 1 };
+        }
+        let (queued_at, item) = self.lanes[chosen].pop_front()?;
+        let max_wait = &mut self.max_wait[chosen];
+        *max_wait = (*max_wait).max(queued_at.elapsed());
+        Some((Lane::ALL[chosen], item))
+    }
+
+    fn take_max_wait(&mut self, lane: Lane) -> Duration {
+        std::mem::take(&mut self.max_wait[lane as usize])
+    }
 }
 
 pub struct Scope<'pool, 'scope> {
@@ -147,6 +310,127 @@ pub fn spawn<F>(&self, intent: ThreadIntent, f: F)
                 >(f)
             },
         };
-        self.pool.job_sender.send(job).unwrap();
+        self.pool.queue.push(Lane::for_intent(intent), job);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Barrier, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{Lane, MAX_SKIPPED, Pool, QueueState};
+    use crate::thread::ThreadIntent;
+
+    fn drain(queue: &mut QueueState<usize>) -> Vec<(Lane, usize)> {
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+
+    #[test]
+    fn lanes_are_served_in_priority_order() {
+        let mut queue = QueueState::default();
+        queue.push(Lane::Background, 0);
+        queue.push(Lane::Default, 1);
+        queue.push(Lane::Interactive, 2);
+        queue.push(Lane::Interactive, 3);
+
+        assert_eq!(
+            drain(&mut queue),
+            [
+                (Lane::Interactive, 2),
+                (Lane::Interactive, 3),
+                (Lane::Default, 1),
+                (Lane::Background, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_lanes_do_not_starve() {
+        let mut queue = QueueState::default();
+        queue.push(Lane::Background, 0);
+        queue.push(Lane::Default, 1);
+        for job in 0..3 * MAX_SKIPPED {
+            queue.push(Lane::Interactive, 100 + job);
+        }
+
+        let order = drain(&mut queue);
+        let position = |lane| order.iter().position(|&(it, _)| it == lane).unwrap();
+        assert_eq!(position(Lane::Default), MAX_SKIPPED);
+        assert_eq!(position(Lane::Background), MAX_SKIPPED + 1);
+        assert_eq!(order.len(), 3 * MAX_SKIPPED + 2);
+    }
+
+    #[test]
+    fn max_wait_is_reset_when_read() {
+        let mut queue = QueueState::default();
+        queue.push(Lane::Default, 0);
+        thread::sleep(Duration::from_millis(1));
+        queue.pop();
+
+        assert!(queue.take_max_wait(Lane::Default) > Duration::ZERO);
+        assert_eq!(queue.take_max_wait(Lane::Default), Duration::ZERO);
+    }
+
+    #[test]
+    fn interactive_job_overtakes_flood_of_background_jobs() {
+        const FLOOD: usize = 50;
+
+        let pool = Pool::new(1);
+        let barrier = Arc::new(Barrier::new(2));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // Keep the only worker busy until the queue has been flooded.
+        pool.spawn_in(Lane::Default, ThreadIntent::Worker, {
+            let barrier = Arc::clone(&barrier);
+            move || _ = barrier.wait()
+        });
+        for job in 0..FLOOD {
+            let order = Arc::clone(&order);
+            pool.spawn_in(Lane::Background, ThreadIntent::Worker, move || {
+                order.lock().unwrap().push(job)
+            });
+        }
+        pool.spawn(ThreadIntent::LatencySensitive, {
+            let order = Arc::clone(&order);
+            move || order.lock().unwrap().push(usize::MAX)
+        });
+        barrier.wait();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while order.lock().unwrap().len() < FLOOD + 1 {
+            assert!(Instant::now() < deadline, "pool did not finish its jobs");
+            thread::sleep(Duration::from_millis(1));
+        }
+        let order = order.lock().unwrap();
+        assert_eq!(order[0], usize::MAX);
+        assert_eq!(order[1..], (0..FLOOD).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn queued_jobs_run_after_the_pool_is_dropped() {
+        let pool = Pool::new(1);
+        let barrier = Arc::new(Barrier::new(2));
+        let done = Arc::new(Mutex::new(0));
+
+        pool.spawn(ThreadIntent::Worker, {
+            let barrier = Arc::clone(&barrier);
+            move || _ = barrier.wait()
+        });
+        for _ in 0..10 {
+            let done = Arc::clone(&done);
+            pool.spawn(ThreadIntent::Worker, move || *done.lock().unwrap() += 1);
+        }
+        drop(pool);
+        barrier.wait();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while *done.lock().unwrap() < 10 {
+            assert!(Instant::now() < deadline, "queued jobs were dropped");
+            thread::sleep(Duration::from_millis(1));
+        }
     }
 }
@@ -18,16 +18,16 @@
 
 use crate::{
     SyntaxKind,
-    syntax_node::{SyntaxNode, SyntaxNodeChildren, SyntaxToken},
+    syntax_node::{SyntaxNode, SyntaxToken},
 };
 
 pub use self::{
     expr_ext::{ArrayExprKind, BlockModifier, CallableExpr, ElseBranch, LiteralKind},
     generated::{nodes::*, tokens::*},
     node_ext::{
-        AttrKind, FieldKind, Macro, NameLike, NameOrNameRef, PathSegmentKind, SelfParamKind,
-        SlicePatComponents, StructKind, TokenTreeChildren, TypeBoundKind, TypeOrConstParam,
-        VisibilityKind,
+        AttrKind, FieldKind, Macro, MetaKind, NameLike, NameOrNameRef, PathSegmentKind,
+        SelfParamKind, SlicePatComponents, StructKind, TokenTreeChildren, TypeBoundKind,
+        TypeOrConstParam, VisibilityKind,
     },
     operators::{ArithOp, BinaryOp, CmpOp, LogicOp, Ordering, RangeOp, UnaryOp},
     token_ext::{
@@ -96,20 +96,48 @@ fn text(&self) -> &str {
 /// An iterator over `SyntaxNode` children of a particular AST type.
 #[derive(Debug, Clone)]
 pub struct AstChildren<N> {
-    inner: SyntaxNodeChildren,
+    /// The next children to look at from either end, `None` once they have met.
+    front: Option<SyntaxNode>,
+    back: Option<SyntaxNode>,
     ph: PhantomData<N>,
 }
 
 impl<N> AstChildren<N> {
     fn new(parent: &SyntaxNode) -> Self {
-        AstChildren { inner: parent.children(), ph: PhantomData }
+        AstChildren { front: parent.first_child(), back: parent.last_child(), ph: PhantomData }
     }
 }
 
 impl<N: AstNode> Iterator for AstChildren<N> {
     type Item = N;
     fn next(&mut self) -> Option<N> {
-        self.inner.find_map(N::cast)
+        while let Some(node) = self.front.take() {
+            if self.back.as_ref() == Some(&node) {
+                self.back = None;
+            } else {
+                self.front = node.next_sibling();
+            }
+            if let Some(it) = N::cast(node) {
+                return Some(it);
+            }
+        }
+        None
+    }
+}
+
+impl<N: AstNode> DoubleEndedIterator for AstChildren<N> {
+    fn next_back(&mut self) -> Option<N> {
+        while let Some(node) = self.back.take() {
+            if self.front.as_ref() == Some(&node) {
+                self.front = None;
+            } else {
+                self.back = node.prev_sibling();
+            }
+            if let Some(it) = N::cast(node) {
+                return Some(it);
+            }
+        }
+        None
     }
 }
 
@@ -166,6 +194,11 @@ pub(super) fn child<N: AstNode>(parent: &SyntaxNode) -> Option<N> {
         parent.children().find_map(N::cast)
     }
 
+    #[inline]
+    pub(super) fn last_child<N: AstNode>(parent: &SyntaxNode) -> Option<N> {
+        AstChildren::new(parent).next_back()
+    }
+
     #[inline]
     pub(super) fn children<N: AstNode>(parent: &SyntaxNode) -> AstChildren<N> {
         AstChildren::new(parent)
@@ -177,6 +210,36 @@ pub(super) fn token(parent: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken
     }
 }
 
+#[test]
+fn test_ast_children_double_ended() {
+    let file = SourceFile::parse(
+        r#"
+fn f() {}
+struct S;
+fn g() {}
+enum E {}
+fn h() {}
+        "#,
+        parser::Edition::CURRENT,
+    )
+    .tree();
+    let names = |fns: &mut dyn Iterator<Item = Fn>| {
+        fns.map(|it| it.name().unwrap().to_string()).collect::<Vec<_>>()
+    };
+    let children = || support::children::<Fn>(file.syntax());
+    assert_eq!(names(&mut children()), ["f", "g", "h"]);
+    assert_eq!(names(&mut children().rev()), ["h", "g", "f"]);
+
+    // Both ends stop once they meet.
+    let mut fns = children();
+    assert_eq!(fns.next().unwrap().name().unwrap().text(), "f");
+    assert_eq!(fns.next_back().unwrap().name().unwrap().text(), "h");
+    assert_eq!(names(&mut fns), ["g"]);
+    assert!(fns.next_back().is_none());
+
+    assert_eq!(support::last_child::<Struct>(file.syntax()).unwrap().syntax().text(), "struct S;");
+}
+
 #[test]
 fn assert_ast_is_dyn_compatible() {
     fn _f(_: &dyn AstNode, _: &dyn HasName) {}
@@ -210,7 +273,7 @@ mod foo {}
     .ok()
     .unwrap();
     let module = file.syntax().descendants().find_map(Module::cast).unwrap();
-    assert_eq!(" doc", module.doc_comments().doc_comment_text().unwrap());
+    assert_eq!("doc", module.doc_comments().doc_comment_text().unwrap());
 }
 
 #[test]
@@ -241,7 +304,7 @@ fn test_doc_comment_of_statics() {
     .ok()
     .unwrap();
     let st = file.syntax().descendants().find_map(Static::cast).unwrap();
-    assert_eq!(" Number of levels", st.doc_comments().doc_comment_text().unwrap());
+    assert_eq!("Number of levels", st.doc_comments().doc_comment_text().unwrap());
 }
 
 #[test]
@@ -262,7 +325,7 @@ mod foo {}
     .unwrap();
     let module = file.syntax().descendants().find_map(Module::cast).unwrap();
     assert_eq!(
-        " doc1\n ```\n fn foo() {\n     // ...\n }\n ```",
+        "doc1\n```\nfn foo() {\n    // ...\n}\n```",
         module.doc_comments().doc_comment_text().unwrap()
     );
 }
@@ -282,7 +345,7 @@ mod foo {}
     .ok()
     .unwrap();
     let module = file.syntax().descendants().find_map(Module::cast).unwrap();
-    assert_eq!(" this\n is\n mod\n foo", module.doc_comments().doc_comment_text().unwrap());
+    assert_eq!("this\nis\nmod\nfoo", module.doc_comments().doc_comment_text().unwrap());
 }
 
 #[test]
@@ -297,7 +360,7 @@ mod foo {}
     .ok()
     .unwrap();
     let module = file.syntax().descendants().find_map(Module::cast).unwrap();
-    assert_eq!(" this is mod foo", module.doc_comments().doc_comment_text().unwrap());
+    assert_eq!("this is mod foo", module.doc_comments().doc_comment_text().unwrap());
 }
 
 #[test]
@@ -312,7 +375,7 @@ mod foo {}
     .ok()
     .unwrap();
     let module = file.syntax().descendants().find_map(Module::cast).unwrap();
-    assert_eq!(" this is mod foo ", module.doc_comments().doc_comment_text().unwrap());
+    assert_eq!("this is mod foo ", module.doc_comments().doc_comment_text().unwrap());
 }
 
 #[test]
@@ -331,10 +394,7 @@ mod foo {}
     .ok()
     .unwrap();
     let module = file.syntax().descendants().find_map(Module::cast).unwrap();
-    assert_eq!(
-        "\n        this\n        is\n        mod foo\n        ",
-        module.doc_comments().doc_comment_text().unwrap()
-    );
+    assert_eq!("this\nis\nmod foo", module.doc_comments().doc_comment_text().unwrap());
 }
 
 #[test]
@@ -346,7 +406,7 @@ fn test_comments_preserve_trailing_whitespace() {
     .unwrap();
     let def = file.syntax().descendants().find_map(Struct::cast).unwrap();
     assert_eq!(
-        " Representation of a Realm.   \n In the specification these are called Realm Records.",
+        "Representation of a Realm.   \nIn the specification these are called Realm Records.",
         def.doc_comments().doc_comment_text().unwrap()
     );
 }
@@ -364,7 +424,195 @@ mod foo {}
     .ok()
     .unwrap();
     let module = file.syntax().descendants().find_map(Module::cast).unwrap();
-    assert_eq!(" doc comment", module.doc_comments().doc_comment_text().unwrap());
+    assert_eq!("doc comment", module.doc_comments().doc_comment_text().unwrap());
+}
+
+#[test]
+fn test_doc_comment_block_with_gutter() {
+    let file = SourceFile::parse(
+        r#"
+        /**
+         * Foo
+         *
+         *     indented
+         */
+        mod foo {}
+        "#,
+        parser::Edition::CURRENT,
+    )
+    .ok()
+    .unwrap();
+    let module = file.syntax().descendants().find_map(Module::cast).unwrap();
+    assert_eq!("Foo\n\n    indented", module.doc_comments().doc_comment_text().unwrap());
+}
+
+#[test]
+fn test_doc_comment_empty_line() {
+    let file = SourceFile::parse(
+        r#"
+        /// foo
+        ///
+        ///bar
+        mod foo {}
+        "#,
+        parser::Edition::CURRENT,
+    )
+    .ok()
+    .unwrap();
+    let module = file.syntax().descendants().find_map(Module::cast).unwrap();
+    assert_eq!("foo\n\nbar", module.doc_comments().doc_comment_text().unwrap());
+}
+
+#[test]
+fn test_empty_block_comment_is_not_doc() {
+    let file = SourceFile::parse(
+        r#"
+        /**/
+        mod foo {}
+        "#,
+        parser::Edition::CURRENT,
+    )
+    .ok()
+    .unwrap();
+    let comment = file
+        .syntax()
+        .descendants_with_tokens()
+        .find_map(|it| it.into_token().and_then(Comment::cast));
+    let comment = comment.unwrap();
+    assert!(!comment.kind().is_doc());
+    assert!(comment.doc_content().is_none());
+    let module = file.syntax().descendants().find_map(Module::cast).unwrap();
+    assert!(module.doc_comments().doc_comment_text().is_none());
+}
+
+#[test]
+fn test_meta_kinds() {
+    let file = SourceFile::parse(
+        r##"
+#[inline]
+#[rustc::lint]
+#[path = r#"foo.rs"#]
+#[deprecated(since = "1.0", note = "...")]
+#[cfg(feature = "x")]
+#[unsafe(no_mangle)]
+#[doc = concat!("a", "b")]
+fn foo() {}
+        "##,
+        parser::Edition::CURRENT,
+    )
+    .ok()
+    .unwrap();
+    let func = file.syntax().descendants().find_map(Fn::cast).unwrap();
+    let kinds = func
+        .attrs()
+        .map(|attr| match attr.meta_kind() {
+            Some(MetaKind::Path(path)) => format!("path {path}"),
+            Some(MetaKind::KeyValue { path, value }) => format!("key-value {path} {value}"),
+            Some(MetaKind::Call { path, token_tree }) => format!("call {path} {token_tree}"),
+            None => "none".to_owned(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            "path inline",
+            "path rustc::lint",
+            r##"key-value path r#"foo.rs"#"##,
+            r#"call deprecated (since = "1.0", note = "...")"#,
+            r#"call cfg (feature = "x")"#,
+            "path no_mangle",
+            "none",
+        ]
+    );
+    let attr = func.attrs().nth(2).unwrap();
+    assert!(attr.as_simple_atom().is_none());
+    assert_eq!(attr.meta_kind().unwrap().path().to_string(), "path");
+}
+
+#[test]
+fn test_semicolon_tokens() {
+    let file = SourceFile::parse(
+        r#"
+mod foo; // comment
+use bar; /* comment */
+fn f() {
+    let x = 1; // comment
+    x + 1; // comment
+    let y = 92 92;
+}
+        "#,
+        parser::Edition::CURRENT,
+    )
+    .tree();
+    let module = file.syntax().descendants().find_map(Module::cast).unwrap();
+    assert!(module.semicolon_token().is_some());
+    let use_ = file.syntax().descendants().find_map(Use::cast).unwrap();
+    assert!(use_.semicolon_token().is_some());
+    let expr_stmt = file.syntax().descendants().find_map(ExprStmt::cast).unwrap();
+    assert!(expr_stmt.semicolon_token().is_some());
+    let mut lets = file.syntax().descendants().filter_map(LetStmt::cast);
+    assert!(lets.next().unwrap().semicolon_token().is_some());
+    // The parser recovers from the missing semicolon by starting a new statement.
+    let recovered = lets.next().unwrap();
+    assert_eq!(recovered.initializer().unwrap().syntax().text(), "92");
+    assert!(recovered.semicolon_token().is_none());
+    let next = file.syntax().descendants().filter_map(ExprStmt::cast).last().unwrap();
+    assert_eq!(next.syntax().text(), "92;");
+    assert!(next.semicolon_token().is_some());
+}
+
+#[test]
+fn test_impl_header() {
+    let file = SourceFile::parse(
+        r#"
+impl<T> Foo<T> {}
+impl<T> !Send for Foo<T> {}
+impl<T> Trait for Vec<T> where Vec<T>: Clone, T: Copy {}
+impl Trait for {}
+        "#,
+        parser::Edition::CURRENT,
+    )
+    .tree();
+    let impls = file.syntax().descendants().filter_map(Impl::cast).collect::<Vec<_>>();
+    let header = |impl_: &Impl| {
+        (
+            impl_.trait_().map(|it| it.syntax().to_string()),
+            impl_.self_ty().map(|it| it.syntax().to_string()),
+            impl_.is_negative(),
+        )
+    };
+
+    assert_eq!(header(&impls[0]), (None, Some("Foo<T>".to_owned()), false));
+    assert!(impls[0].trait_path().is_none());
+
+    assert_eq!(header(&impls[1]), (Some("Send".to_owned()), Some("Foo<T>".to_owned()), true));
+    assert_eq!(impls[1].trait_path().unwrap().to_string(), "Send");
+
+    assert_eq!(header(&impls[2]), (Some("Trait".to_owned()), Some("Vec<T>".to_owned()), false));
+
+    assert_eq!(header(&impls[3]), (Some("Trait".to_owned()), None, false));
+}
+
+#[test]
+fn test_await_expr_receivers() {
+    let file = SourceFile::parse(
+        r#"
+async fn f() {
+    x.await?;
+    x.0.await;
+    x.await.await;
+}
+        "#,
+        parser::Edition::CURRENT,
+    )
+    .tree();
+    let receivers = file
+        .syntax()
+        .descendants()
+        .filter_map(AwaitExpr::cast)
+        .map(|it| it.expr().unwrap().syntax().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(receivers, ["x", "x.0", "x.await", "x"]);
 }
 
 #[test]
@@ -68,13 +68,41 @@ pub fn from_token(token: &SyntaxToken) -> IndentLevel {
         for ws in prev_tokens(token.clone()).filter_map(ast::Whitespace::cast) {
             let text = ws.syntax().text();
             if let Some(pos) = text.rfind('\n') {
-                let level = text[pos + 1..].chars().count() / 4;
-                return IndentLevel(level as u8);
+                // A tab counts as a whole level, like four spaces.
+                let columns: usize =
+                    text[pos + 1..].chars().map(|c| if c == '\t' { 4 } else { 1 }).sum();
+                return IndentLevel((columns / 4) as u8);
             }
         }
         IndentLevel(0)
     }
 
+    /// Whether the code around `node` is indented with tabs, judging by the line `node` starts
+    /// on or, if that one isn't indented, by the first indented line inside of it.
+    pub fn uses_tabs(node: &SyntaxNode) -> bool {
+        let preceding = node
+            .first_token()
+            .into_iter()
+            .flat_map(prev_tokens)
+            .filter_map(ast::Whitespace::cast)
+            .find(|ws| ws.text().contains('\n'));
+        let inner = node
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter_map(ast::Whitespace::cast)
+            .filter(|ws| ws.text().contains('\n'));
+        preceding
+            .into_iter()
+            .chain(inner)
+            .find_map(|ws| ws.text().rsplit('\n').next().and_then(|line| line.chars().next()))
+            == Some('\t')
+    }
+
+    /// This indentation as text, made of tabs if `node` is indented with tabs.
+    pub fn text_like(self, node: &SyntaxNode) -> String {
+        if Self::uses_tabs(node) { "\t".repeat(self.0 as usize) } else { self.to_string() }
+    }
+
     /// XXX: this intentionally doesn't change the indent of the very first token.
     /// For example, in something like:
     /// ```
@@ -92,7 +120,7 @@ pub(super) fn increase_indent(self, node: &SyntaxNode) {
             if let Some(ws) = ast::Whitespace::cast(token)
                 && ws.text().contains('\n')
             {
-                let new_ws = make::tokens::whitespace(&format!("{}{self}", ws.syntax()));
+                let new_ws = make::tokens::whitespace(&self.indent_whitespace(ws.text()));
                 ted::replace(ws.syntax(), &new_ws);
             }
         }
@@ -110,7 +138,7 @@ pub(super) fn clone_increase_indent(self, node: &SyntaxNode) -> SyntaxNode {
             .filter_map(ast::Whitespace::cast)
             .filter(|ws| ws.text().contains('\n'));
         for ws in tokens {
-            let new_ws = make::tokens::whitespace(&format!("{}{self}", ws.syntax()));
+            let new_ws = make::tokens::whitespace(&self.indent_whitespace(ws.text()));
             editor.replace(ws.syntax(), &new_ws);
         }
         editor.finish().new_root().clone()
@@ -125,9 +153,7 @@ pub(super) fn decrease_indent(self, node: &SyntaxNode) {
             if let Some(ws) = ast::Whitespace::cast(token)
                 && ws.text().contains('\n')
             {
-                let new_ws = make::tokens::whitespace(
-                    &ws.syntax().text().replace(&format!("\n{self}"), "\n"),
-                );
+                let new_ws = make::tokens::whitespace(&self.dedent_whitespace(ws.text()));
                 ted::replace(ws.syntax(), &new_ws);
             }
         }
@@ -145,12 +171,91 @@ pub(super) fn clone_decrease_indent(self, node: &SyntaxNode) -> SyntaxNode {
             .filter_map(ast::Whitespace::cast)
             .filter(|ws| ws.text().contains('\n'));
         for ws in tokens {
-            let new_ws =
-                make::tokens::whitespace(&ws.syntax().text().replace(&format!("\n{self}"), "\n"));
+            let new_ws = make::tokens::whitespace(&self.dedent_whitespace(ws.text()));
             editor.replace(ws.syntax(), &new_ws);
         }
         editor.finish().new_root().clone()
     }
+
+    /// Indents the line following the last newline of `ws`, using tabs if that
+    /// line is already indented with tabs.
+    fn indent_whitespace(self, ws: &str) -> String {
+        let last_line = ws.rsplit('\n').next().unwrap_or_default();
+        if last_line.starts_with('\t') {
+            format!("{ws}{}", "\t".repeat(self.0 as usize))
+        } else {
+            format!("{ws}{self}")
+        }
+    }
+
+    /// Removes this much indentation from every line of `ws` but the first.
+    /// Lines that are indented less than that are left alone.
+    fn dedent_whitespace(self, ws: &str) -> String {
+        let width = self.0 as usize * 4;
+        let mut lines = ws.split('\n');
+        let mut res = lines.next().unwrap_or_default().to_owned();
+        for line in lines {
+            res.push('\n');
+            res.push_str(strip_indent(line, width).unwrap_or(line));
+        }
+        res
+    }
+}
+
+/// Replaces the indentation of every line of `ws` but the first with tabs, one per four columns.
+fn spaces_to_tabs(ws: &str) -> String {
+    let mut lines = ws.split('\n');
+    let mut res = lines.next().unwrap_or_default().to_owned();
+    for line in lines {
+        let rest = line.trim_start_matches([' ', '\t']);
+        let indent = &line[..line.len() - rest.len()];
+        let columns: usize = indent.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum();
+        res.push('\n');
+        res.extend(iter::repeat_n('\t', columns / 4).chain(iter::repeat_n(' ', columns % 4)));
+        res.push_str(rest);
+    }
+    res
+}
+
+fn clone_with_tabs(node: &SyntaxNode) -> SyntaxNode {
+    let node = node.clone_subtree();
+    let mut editor = SyntaxEditor::new(node.clone());
+    let tokens = node
+        .preorder_with_tokens()
+        .filter_map(|event| match event {
+            rowan::WalkEvent::Leave(NodeOrToken::Token(it)) => Some(it),
+            _ => None,
+        })
+        .filter_map(ast::Whitespace::cast)
+        .filter(|ws| ws.text().contains('\n'));
+    for ws in tokens {
+        // Nodes from `make` can have the indentation in a separate whitespace token.
+        let rest = iter::successors(ws.syntax().next_token(), |it| it.next_token())
+            .map_while(ast::Whitespace::cast)
+            .take_while(|it| !it.text().contains('\n'))
+            .collect::<Vec<_>>();
+        let text = iter::once(&ws).chain(&rest).map(|it| it.text()).collect::<String>();
+        editor.replace(ws.syntax(), make::tokens::whitespace(&spaces_to_tabs(&text)));
+        for it in rest {
+            editor.delete(it.syntax());
+        }
+    }
+    editor.finish().new_root().clone()
+}
+
+fn strip_indent(line: &str, width: usize) -> Option<&str> {
+    let mut columns = 0;
+    for (idx, c) in line.char_indices() {
+        if columns == width {
+            return Some(&line[idx..]);
+        }
+        columns += match c {
+            ' ' => 1,
+            '\t' => 4,
+            _ => return None,
+        };
+    }
+    (columns == width).then_some("")
 }
 
 fn prev_tokens(token: SyntaxToken) -> impl Iterator<Item = SyntaxToken> {
@@ -165,6 +270,17 @@ fn indent_level(&self) -> IndentLevel {
     fn indent(&self, level: IndentLevel) -> Self {
         Self::cast(level.clone_increase_indent(self.syntax())).unwrap()
     }
+    /// Like [`AstNodeEdit::indent`], but switches the whole node over to tab indentation if
+    /// `like` is indented with tabs, so that nodes built with [`make`] fit into such files.
+    #[must_use]
+    fn indent_like(&self, level: IndentLevel, like: &SyntaxNode) -> Self {
+        let node = self.indent(level);
+        if IndentLevel::uses_tabs(like) {
+            Self::cast(clone_with_tabs(node.syntax())).unwrap()
+        } else {
+            node
+        }
+    }
     #[must_use]
     fn indent_with_mapping(&self, level: IndentLevel, make: &SyntaxFactory) -> Self {
         let new_node = self.indent(level);
@@ -212,3 +328,62 @@ fn test_increase_indent() {
         }"
     );
 }
+
+#[cfg(test)]
+fn parse<N: AstNode>(text: &str, nth: usize) -> N {
+    let parse = crate::SourceFile::parse(text, crate::Edition::CURRENT);
+    parse.tree().syntax().descendants().filter_map(N::cast).nth(nth).unwrap()
+}
+
+#[test]
+fn test_indent_with_tabs() {
+    let stmt: ast::LetStmt = parse("fn f() {\n\tif a {\n\t\tlet x = 1;\n\t}\n}", 0);
+    assert_eq!(stmt.indent_level(), IndentLevel(2));
+
+    let if_expr: ast::IfExpr = parse("fn f() {\n\tif a {\n\t\tb\n\t}\n}", 0);
+    assert_eq!(if_expr.indent_level(), IndentLevel(1));
+    assert_eq!(if_expr.indent(IndentLevel(1)).syntax().to_string(), "if a {\n\t\t\tb\n\t\t}");
+    assert_eq!(if_expr.dedent(IndentLevel(1)).syntax().to_string(), "if a {\n\tb\n}");
+}
+
+#[test]
+fn test_dedent_partially_indented() {
+    let block: ast::BlockExpr = parse("fn f() {\n    {\n        a;\n  b;\n    }\n}", 1);
+    assert_eq!(block.reset_indent().syntax().to_string(), "{\n    a;\n  b;\n}");
+}
+
+#[test]
+fn test_indent_keeps_string_literals() {
+    let body: ast::BlockExpr = parse("fn f() {\n    let s = \"a\n    b\";\n}", 0);
+    assert_eq!(
+        body.indent(IndentLevel(1)).syntax().to_string(),
+        "{\n        let s = \"a\n    b\";\n    }"
+    );
+    assert_eq!(body.dedent(IndentLevel(1)).syntax().to_string(), "{\nlet s = \"a\n    b\";\n}");
+}
+
+#[test]
+fn test_indent_like_tab_indented_node() {
+    let if_expr: ast::IfExpr = parse("fn f() {\n\tif a {\n\t\tb\n\t}\n}", 0);
+    assert!(IndentLevel::uses_tabs(if_expr.syntax()));
+    assert_eq!(IndentLevel(2).text_like(if_expr.syntax()), "\t\t");
+
+    let arm_list = {
+        let arm = make::match_arm(make::wildcard_pat().into(), None, make::ext::expr_unit());
+        make::match_arm_list([arm.clone(), arm])
+    };
+    assert!(!IndentLevel::uses_tabs(arm_list.syntax()));
+    assert_eq!(
+        arm_list.indent_like(IndentLevel(1), if_expr.syntax()).syntax().to_string(),
+        "{\n\t\t_ => (),\n\t\t_ => (),\n\t}"
+    );
+    let block = make::block_expr(None, Some(make::ext::expr_unit()));
+    assert_eq!(
+        block.indent_like(IndentLevel(2), if_expr.syntax()).syntax().to_string(),
+        "{\n\t\t\t()\n\t\t}"
+    );
+    assert_eq!(
+        arm_list.indent_like(IndentLevel(1), arm_list.syntax()).syntax().to_string(),
+        "{\n        _ => (),\n        _ => (),\n    }"
+    );
+}
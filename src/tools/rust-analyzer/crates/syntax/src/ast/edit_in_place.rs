@@ -11,6 +11,7 @@
     algo::{self, neighbor},
     ast::{self, HasGenericParams, edit::IndentLevel, make},
     ted::{self, Position},
+    trivia,
 };
 
 use super::{GenericParam, HasName};
@@ -528,19 +529,19 @@ pub fn add_use_tree(&self, use_tree: ast::UseTree) {
 
 impl Removable for ast::Use {
     fn remove(&self) {
-        let next_ws = self
-            .syntax()
-            .next_sibling_or_token()
-            .and_then(|it| it.into_token())
-            .and_then(ast::Whitespace::cast);
-        if let Some(next_ws) = next_ws {
+        if let Some(next_ws) = trivia::trailing_newline(self.syntax()) {
             let ws_text = next_ws.syntax().text();
-            if let Some(rest) = ws_text.strip_prefix('\n') {
-                if rest.is_empty() {
-                    ted::remove(next_ws.syntax());
-                } else {
-                    ted::replace(next_ws.syntax(), make::tokens::whitespace(rest));
-                }
+            // A blank line in front of the `use` already separates its neighbours, don't keep
+            // the one after it as well.
+            let rest = if trivia::leading_blank_lines(self.syntax()) > 0 {
+                ws_text.trim_start_matches('\n')
+            } else {
+                &ws_text[1..]
+            };
+            if rest.is_empty() {
+                ted::remove(next_ws.syntax());
+            } else {
+                ted::replace(next_ws.syntax(), make::tokens::whitespace(rest));
             }
         }
         let prev_ws = self
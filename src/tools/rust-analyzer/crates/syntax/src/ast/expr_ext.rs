@@ -3,7 +3,7 @@
 //! These methods should only do simple, shallow tasks related to the syntax of the node itself.
 
 use crate::{
-    AstToken,
+    AstToken, Direction,
     SyntaxKind::{self, *},
     SyntaxNode, SyntaxToken, T,
     ast::{
@@ -75,7 +75,7 @@ impl ast::IfExpr {
     pub fn condition(&self) -> Option<ast::Expr> {
         // If the condition is a BlockExpr, check if the then body is missing.
         // If it is assume the condition is the expression that is missing instead.
-        let mut exprs = support::children(self.syntax());
+        let mut exprs = self.exprs_before_else();
         let first = exprs.next();
         match first {
             Some(ast::Expr::BlockExpr(_)) => exprs.next().and(first),
@@ -84,19 +84,34 @@ pub fn condition(&self) -> Option<ast::Expr> {
     }
 
     pub fn then_branch(&self) -> Option<ast::BlockExpr> {
-        match support::children(self.syntax()).nth(1)? {
+        let mut exprs = self.exprs_before_else();
+        let first = exprs.next()?;
+        // A lone block is the body of an `if` that's missing its condition.
+        match exprs.next().unwrap_or(first) {
             ast::Expr::BlockExpr(block) => Some(block),
             _ => None,
         }
     }
 
     pub fn else_branch(&self) -> Option<ElseBranch> {
-        match support::children(self.syntax()).nth(2)? {
+        let else_token = self.else_token()?;
+        let expr = else_token
+            .siblings_with_tokens(Direction::Next)
+            .filter_map(|it| it.into_node())
+            .find_map(ast::Expr::cast)?;
+        match expr {
             ast::Expr::BlockExpr(block) => Some(ElseBranch::Block(block)),
             ast::Expr::IfExpr(elif) => Some(ElseBranch::IfExpr(elif)),
             _ => None,
         }
     }
+
+    fn exprs_before_else(&self) -> impl Iterator<Item = ast::Expr> {
+        let else_start = self.else_token().map(|it| it.text_range().start());
+        support::children(self.syntax()).take_while(move |expr: &ast::Expr| {
+            else_start.is_none_or(|start| expr.syntax().text_range().start() < start)
+        })
+    }
 }
 
 #[test]
@@ -157,6 +172,48 @@ fn test() {
     assert_eq!(else_.syntax().text(), r#"{ "else" }"#);
 }
 
+#[test]
+fn if_without_else() {
+    let parse = ast::SourceFile::parse(
+        r#"
+        fn test() {
+            if { true } { "if" }
+        }
+        "#,
+        parser::Edition::CURRENT,
+    );
+    let if_ = parse.tree().syntax().descendants().find_map(ast::IfExpr::cast).unwrap();
+    assert_eq!(if_.condition().unwrap().syntax().text(), "{ true }");
+    assert_eq!(if_.then_branch().unwrap().syntax().text(), r#"{ "if" }"#);
+    assert!(if_.else_branch().is_none());
+}
+
+#[test]
+fn if_with_missing_parts() {
+    let parse = ast::SourceFile::parse(
+        r#"
+        fn test() {
+            if { "if" } else { "else" };
+            if true else { "else" };
+        }
+        "#,
+        parser::Edition::CURRENT,
+    );
+    let mut ifs = parse.tree().syntax().descendants().filter_map(ast::IfExpr::cast);
+
+    let if_ = ifs.next().unwrap();
+    assert!(if_.condition().is_none());
+    assert_eq!(if_.then_branch().unwrap().syntax().text(), r#"{ "if" }"#);
+    let Some(ElseBranch::Block(else_)) = if_.else_branch() else { panic!("should be `else`") };
+    assert_eq!(else_.syntax().text(), r#"{ "else" }"#);
+
+    let if_ = ifs.next().unwrap();
+    assert_eq!(if_.condition().unwrap().syntax().text(), "true");
+    assert!(if_.then_branch().is_none());
+    let Some(ElseBranch::Block(else_)) = if_.else_branch() else { panic!("should be `else`") };
+    assert_eq!(else_.syntax().text(), r#"{ "else" }"#);
+}
+
 impl ast::PrefixExpr {
     pub fn op_kind(&self) -> Option<UnaryOp> {
         let res = match self.op_token()?.kind() {
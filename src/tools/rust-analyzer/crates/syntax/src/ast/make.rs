@@ -514,6 +514,15 @@ fn from_text(text: &str) -> ast::RecordExprField {
     }
 }
 
+/// Like [`record_expr_field`], but collapses `name: name` into the shorthand `name`.
+pub fn record_expr_field_shorthand(name: ast::NameRef, expr: ast::Expr) -> ast::RecordExprField {
+    let is_shorthand =
+        matches!(expr, ast::Expr::PathExpr(_)) && expr.syntax().text() == name.text().as_str();
+    let field = record_expr_field(name, (!is_shorthand).then_some(expr));
+    debug_assert_eq!(field.name_ref().is_none(), is_shorthand);
+    field
+}
+
 pub fn record_field(
     visibility: Option<ast::Visibility>,
     name: ast::Name,
@@ -1488,6 +1497,41 @@ fn check(node: impl AstNode, expect: expect_test::Expect) {
         expect.assert_eq(&node_debug);
     }
 
+    #[test]
+    fn test_record_expr_field_shorthand() {
+        let field = |name: &str, expr: &str| {
+            record_expr_field_shorthand(name_ref(name), expr_path(path_from_text(expr))).to_string()
+        };
+        assert_eq!(field("x", "x"), "x");
+        assert_eq!(field("x", "y"), "x: y");
+        assert_eq!(field("x", "self::x"), "x: self::x");
+    }
+
+    #[test]
+    fn test_match_arm_with_record_expr() {
+        let fields = record_expr_field_list([
+            record_expr_field_shorthand(name_ref("a"), expr_path(ext::ident_path("a"))),
+            record_expr_field_shorthand(name_ref("b"), expr_path(ext::ident_path("c"))),
+        ]);
+        let arm = match_arm_with_guard(
+            [ext::simple_ident_pat(name("c")).into()],
+            expr_path(ext::ident_path("cond")),
+            record_expr(ext::ident_path("S"), fields).into(),
+        );
+        let where_clause = where_clause([where_pred(
+            Either::Right(ty("T")),
+            [type_bound_text("Clone"), type_bound_text("Copy")],
+        )]);
+        assert_eq!(arm.to_string(), "c if cond => S { a, b: c }");
+        assert_eq!(where_clause.to_string(), "where T: Clone + Copy");
+
+        let match_ = expr_match(expr_path(ext::ident_path("x")), match_arm_list([arm]));
+        let parse = SourceFile::parse(&format!("fn f() {{ {match_} }}"), Edition::CURRENT);
+        assert!(parse.errors().is_empty(), "{:?}", parse.errors());
+        let reparsed = parse.tree().syntax().descendants().find_map(ast::MatchExpr::cast).unwrap();
+        assert_eq!(reparsed.to_string(), match_.to_string());
+    }
+
     #[test]
     fn test_unnamed_param() {
         check(
@@ -10,7 +10,8 @@
 use rowan::{GreenNodeData, GreenTokenData};
 
 use crate::{
-    NodeOrToken, SmolStr, SyntaxElement, SyntaxElementChildren, SyntaxToken, T, TokenText,
+    Direction, NodeOrToken, SmolStr, SyntaxElement, SyntaxElementChildren, SyntaxToken, T,
+    TokenText,
     ast::{
         self, AstNode, AstToken, HasAttrs, HasGenericArgs, HasGenericParams, HasName,
         HasTypeBounds, SyntaxNode, support,
@@ -229,6 +230,10 @@ pub fn kind(&self) -> AttrKind {
         }
     }
 
+    pub fn meta_kind(&self) -> Option<MetaKind> {
+        self.meta()?.kind()
+    }
+
     pub fn path(&self) -> Option<ast::Path> {
         self.meta()?.path()
     }
@@ -447,24 +452,38 @@ pub fn remove_unnecessary_braces(mut self) {
 
 impl ast::Impl {
     pub fn self_ty(&self) -> Option<ast::Type> {
-        match self.target() {
-            (Some(t), None) | (_, Some(t)) => Some(t),
-            _ => None,
-        }
+        self.target().1
     }
 
     pub fn trait_(&self) -> Option<ast::Type> {
-        match self.target() {
-            (Some(t), Some(_)) => Some(t),
+        self.target().0
+    }
+
+    /// The path of the implemented trait, `None` for inherent impls.
+    pub fn trait_path(&self) -> Option<ast::Path> {
+        match self.trait_()? {
+            ast::Type::PathType(it) => it.path(),
             _ => None,
         }
     }
 
+    /// Whether this is a negative impl, like `impl !Send for Foo {}`.
+    pub fn is_negative(&self) -> bool {
+        self.excl_token().is_some()
+    }
+
+    /// Splits the types in the impl header at the `for` keyword.
     fn target(&self) -> (Option<ast::Type>, Option<ast::Type>) {
-        let mut types = support::children(self.syntax());
-        let first = types.next();
-        let second = types.next();
-        (first, second)
+        let Some(for_token) = self.for_token() else {
+            return (None, support::child(self.syntax()));
+        };
+        let for_start = for_token.text_range().start();
+        let trait_ = support::child::<ast::Type>(self.syntax())
+            .filter(|it| it.syntax().text_range().end() <= for_start);
+        let self_ty = for_token
+            .siblings_with_tokens(Direction::Next)
+            .find_map(|it| it.into_node().and_then(ast::Type::cast));
+        (trait_, self_ty)
     }
 
     pub fn for_trait_name_ref(name_ref: &ast::NameRef) -> Option<ast::Impl> {
@@ -480,9 +499,7 @@ pub fn for_trait_name_ref(name_ref: &ast::NameRef) -> Option<ast::Impl> {
 // [#15778](https://github.com/rust-lang/rust-analyzer/issues/15778)
 impl ast::PathSegment {
     pub fn qualifying_trait(&self) -> Option<ast::PathType> {
-        let mut path_types = support::children(self.type_anchor()?.syntax());
-        let first = path_types.next()?;
-        path_types.next().or(Some(first))
+        support::last_child(self.type_anchor()?.syntax())
     }
 }
 
@@ -1003,10 +1020,48 @@ pub fn parent_meta(&self) -> Option<ast::Meta> {
     }
 }
 
+/// The shape of the contents of an attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaKind {
+    /// `#[path]`
+    Path(ast::Path),
+    /// `#[path = "literal"]`
+    KeyValue { path: ast::Path, value: ast::Literal },
+    /// `#[path(token tree)]`
+    Call { path: ast::Path, token_tree: ast::TokenTree },
+}
+
+impl MetaKind {
+    pub fn path(&self) -> &ast::Path {
+        match self {
+            MetaKind::Path(path)
+            | MetaKind::KeyValue { path, .. }
+            | MetaKind::Call { path, .. } => path,
+        }
+    }
+}
+
 impl ast::Meta {
     pub fn parent_attr(&self) -> Option<ast::Attr> {
         self.syntax().parent().and_then(ast::Attr::cast)
     }
+
+    /// Looks through `unsafe(...)`. Returns `None` for key-value attributes whose value isn't
+    /// a literal, like `#[doc = concat!(...)]`.
+    pub fn kind(&self) -> Option<MetaKind> {
+        let path = self.path()?;
+        if self.eq_token().is_some() {
+            let value = match self.expr()? {
+                ast::Expr::Literal(it) => it,
+                _ => return None,
+            };
+            return Some(MetaKind::KeyValue { path, value });
+        }
+        Some(match self.token_tree() {
+            Some(token_tree) => MetaKind::Call { path, token_tree },
+            None => MetaKind::Path(path),
+        })
+    }
 }
 
 impl ast::GenericArgList {
@@ -20,7 +20,7 @@ pub fn kind(&self) -> CommentKind {
     }
 
     pub fn is_doc(&self) -> bool {
-        self.kind().doc.is_some()
+        self.kind().is_doc()
     }
 
     pub fn is_inner(&self) -> bool {
@@ -31,6 +31,11 @@ pub fn is_outer(&self) -> bool {
         self.kind().doc == Some(CommentPlacement::Outer)
     }
 
+    /// Number of line breaks inside the comment, only ever non-zero for block comments.
+    pub fn newline_count(&self) -> usize {
+        self.text().matches('\n').count()
+    }
+
     pub fn prefix(&self) -> &'static str {
         let &(prefix, _kind) = CommentKind::BY_PREFIX
             .iter()
@@ -57,6 +62,38 @@ pub fn doc_comment(&self) -> Option<(&str, TextSize)> {
             _ => None,
         }
     }
+
+    /// Returns the content of a doc comment the way it is rendered: without the comment markers
+    /// and a single leading space. For block comments, the common indentation or `*` gutter of
+    /// the lines following the opening one is stripped as well.
+    pub fn doc_content(&self) -> Option<Cow<'_, str>> {
+        let (text, _) = self.doc_comment()?;
+        if self.kind().shape.is_line() || !text.contains('\n') {
+            return Some(Cow::Borrowed(text.strip_prefix(' ').unwrap_or(text)));
+        }
+
+        let mut lines = text.split('\n');
+        let first = lines.next().filter(|it| !it.trim().is_empty());
+        let mut rest = lines.collect::<Vec<_>>();
+        if rest.last().is_some_and(|it| it.trim().is_empty()) {
+            rest.pop();
+        }
+        let non_blank = || rest.iter().filter(|it| !it.trim().is_empty());
+        let rest = if non_blank().all(|it| it.trim_start().starts_with('*')) {
+            rest.iter()
+                .map(|it| {
+                    it.trim_start()
+                        .strip_prefix('*')
+                        .map_or("", |it| it.strip_prefix(' ').unwrap_or(it))
+                })
+                .collect::<Vec<_>>()
+        } else {
+            let indent = non_blank().map(|it| it.len() - it.trim_start().len()).min().unwrap_or(0);
+            rest.iter().map(|it| it.get(indent..).unwrap_or_else(|| it.trim_start())).collect()
+        };
+        let first = first.map(|it| it.strip_prefix(' ').unwrap_or(it));
+        Some(Cow::Owned(first.into_iter().chain(rest).collect::<Vec<_>>().join("\n")))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -88,6 +125,10 @@ pub enum CommentPlacement {
 }
 
 impl CommentKind {
+    pub fn is_doc(self) -> bool {
+        self.doc.is_some()
+    }
+
     const BY_PREFIX: [(&'static str, CommentKind); 9] = [
         ("/**/", CommentKind { shape: CommentShape::Block, doc: None }),
         ("/***", CommentKind { shape: CommentShape::Block, doc: None }),
@@ -116,9 +157,13 @@ pub fn prefix(&self) -> &'static str {
 }
 
 impl ast::Whitespace {
+    pub fn newline_count(&self) -> usize {
+        self.text().matches('\n').count()
+    }
+
+    /// Whether the whitespace contains a blank line, i.e. at least two line breaks.
     pub fn spans_multiple_lines(&self) -> bool {
-        let text = self.text();
-        text.find('\n').is_some_and(|idx| text[idx + 1..].contains('\n'))
+        self.newline_count() > 1
     }
 }
 
@@ -129,7 +129,7 @@ pub fn from_syntax_node(syntax_node: &ast::SyntaxNode) -> DocCommentIter {
     #[cfg(test)]
     pub fn doc_comment_text(self) -> Option<String> {
         let docs = itertools::Itertools::join(
-            &mut self.filter_map(|comment| comment.doc_comment().map(|it| it.0.to_owned())),
+            &mut self.filter_map(|comment| comment.doc_content().map(std::borrow::Cow::into_owned)),
             "\n",
         );
         if docs.is_empty() { None } else { Some(docs) }
@@ -0,0 +1,144 @@
+//! A configurable textual representation of syntax trees, for debugging and golden tests.
+//!
+//! The `{:#?}` output of a `SyntaxNode` prints absolute ranges and cuts long tokens at an
+//! arbitrary byte offset. The dump here can print ranges relative to the dumped node, so that
+//! the dump of a subtree doesn't change when the code in front of it does.
+
+use std::borrow::Cow;
+
+use rowan::{NodeOrToken, TextSize, WalkEvent};
+use stdx::format_to;
+
+use crate::SyntaxNode;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpConfig {
+    /// Print ranges relative to the start of the dumped node.
+    pub relative_ranges: bool,
+    /// Truncate token text longer than this many characters, marking the cut with `…`.
+    pub max_token_len: Option<usize>,
+    /// Include whitespace and comment tokens.
+    pub trivia: bool,
+}
+
+impl Default for DumpConfig {
+    fn default() -> Self {
+        DumpConfig { relative_ranges: true, max_token_len: Some(40), trivia: true }
+    }
+}
+
+/// Dumps `node` one element per line, indenting children by two spaces. Token text is escaped
+/// like a Rust string literal, so every token fits on a single line.
+pub fn debug_dump(node: &SyntaxNode, config: &DumpConfig) -> String {
+    let offset = if config.relative_ranges { node.text_range().start() } else { TextSize::from(0) };
+    let mut buf = String::new();
+    let mut level = 0;
+    for event in node.preorder_with_tokens() {
+        let element = match event {
+            WalkEvent::Enter(element) => element,
+            WalkEvent::Leave(element) => {
+                if element.as_node().is_some() {
+                    level -= 1;
+                }
+                continue;
+            }
+        };
+        let range = element.text_range() - offset;
+        let indent = level * 2;
+        match element {
+            NodeOrToken::Node(node) => {
+                format_to!(buf, "{:indent$}{:?}@{range:?}\n", "", node.kind());
+                level += 1;
+            }
+            NodeOrToken::Token(token) => {
+                if !config.trivia && token.kind().is_trivia() {
+                    continue;
+                }
+                let text = truncate(token.text(), config.max_token_len);
+                format_to!(buf, "{:indent$}{:?}@{range:?} {text:?}\n", "", token.kind());
+            }
+        }
+    }
+    buf
+}
+
+fn truncate(text: &str, max_len: Option<usize>) -> Cow<'_, str> {
+    match max_len.and_then(|max_len| text.char_indices().nth(max_len)) {
+        Some((idx, _)) => Cow::Owned(format!("{}…", &text[..idx])),
+        None => Cow::Borrowed(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{Expect, expect};
+    use parser::Edition;
+
+    use crate::{AstNode, SourceFile, ast};
+
+    use super::*;
+
+    fn check_fn(text: &str, config: &DumpConfig, expect: Expect) {
+        let file = SourceFile::parse(text, Edition::CURRENT).tree();
+        let func = file.syntax().descendants().find_map(ast::Fn::cast).unwrap();
+        expect.assert_eq(&debug_dump(func.syntax(), config));
+    }
+
+    #[test]
+    fn truncates_and_escapes_tokens() {
+        check_fn(
+            "fn f() { \"a long string\nwith a newline\" }",
+            &DumpConfig { max_token_len: Some(16), trivia: false, ..DumpConfig::default() },
+            expect![[r#"
+                FN@0..41
+                  FN_KW@0..2 "fn"
+                  NAME@3..4
+                    IDENT@3..4 "f"
+                  PARAM_LIST@4..6
+                    L_PAREN@4..5 "("
+                    R_PAREN@5..6 ")"
+                  BLOCK_EXPR@7..41
+                    STMT_LIST@7..41
+                      L_CURLY@7..8 "{"
+                      LITERAL@9..39
+                        STRING@9..39 "\"a long string\nw…"
+                      R_CURLY@40..41 "}"
+            "#]],
+        );
+    }
+
+    #[test]
+    fn ranges_are_relative_to_the_node() {
+        let func = r#"fn f() { "a string literal that is much longer than forty characters" }"#;
+        let dump = |text: &str| {
+            let file = SourceFile::parse(text, Edition::CURRENT).tree();
+            let func = file.syntax().descendants().find_map(ast::Fn::cast).unwrap();
+            debug_dump(func.syntax(), &DumpConfig::default())
+        };
+        assert_eq!(dump(func), dump(&format!("struct S;\n\n{func}")));
+
+        check_fn(
+            func,
+            &DumpConfig::default(),
+            expect![[r#"
+                FN@0..71
+                  FN_KW@0..2 "fn"
+                  WHITESPACE@2..3 " "
+                  NAME@3..4
+                    IDENT@3..4 "f"
+                  PARAM_LIST@4..6
+                    L_PAREN@4..5 "("
+                    R_PAREN@5..6 ")"
+                  WHITESPACE@6..7 " "
+                  BLOCK_EXPR@7..71
+                    STMT_LIST@7..71
+                      L_CURLY@7..8 "{"
+                      WHITESPACE@8..9 " "
+                      LITERAL@9..69
+                        STRING@9..69 "\"a string literal that is much longer th…"
+                      WHITESPACE@69..70 " "
+                      R_CURLY@70..71 "}"
+            "#]],
+        );
+    }
+}
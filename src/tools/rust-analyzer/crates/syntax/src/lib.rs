@@ -35,11 +35,13 @@
 
 pub mod algo;
 pub mod ast;
+pub mod debug_dump;
 #[doc(hidden)]
 pub mod fuzz;
 pub mod hacks;
 pub mod syntax_editor;
 pub mod ted;
+pub mod trivia;
 pub mod utils;
 
 use std::{marker::PhantomData, ops::Range};
@@ -232,8 +234,18 @@ fn drop(&mut self) {
 
 impl SourceFile {
     pub fn parse(text: &str, edition: Edition) -> Parse<SourceFile> {
+        SourceFile::parse_with_recovery_limit(text, edition, parser::DEFAULT_NESTING_LIMIT)
+    }
+
+    /// Like [`SourceFile::parse`], but expressions and types nested deeper than
+    /// `nesting_limit` are reported as errors and skipped instead of being parsed.
+    pub fn parse_with_recovery_limit(
+        text: &str,
+        edition: Edition,
+        nesting_limit: u32,
+    ) -> Parse<SourceFile> {
         let _p = tracing::info_span!("SourceFile::parse").entered();
-        let (green, errors) = parsing::parse_text(text, edition);
+        let (green, errors) = parsing::parse_text(text, edition, nesting_limit);
         let root = SyntaxNode::new_root(green.clone());
 
         assert_eq!(root.kind(), SyntaxKind::SOURCE_FILE);
@@ -9,11 +9,16 @@
 
 pub(crate) use crate::parsing::reparsing::incremental_reparse;
 
-pub(crate) fn parse_text(text: &str, edition: parser::Edition) -> (GreenNode, Vec<SyntaxError>) {
+pub(crate) fn parse_text(
+    text: &str,
+    edition: parser::Edition,
+    nesting_limit: u32,
+) -> (GreenNode, Vec<SyntaxError>) {
     let _p = tracing::info_span!("parse_text").entered();
     let lexed = parser::LexedStr::new(edition, text);
     let parser_input = lexed.to_input(edition);
-    let parser_output = parser::TopEntryPoint::SourceFile.parse(&parser_input);
+    let parser_output =
+        parser::TopEntryPoint::SourceFile.parse_with_nesting_limit(&parser_input, nesting_limit);
     let (node, errors, _eof) = build_tree(lexed, parser_output);
     (node, errors)
 }
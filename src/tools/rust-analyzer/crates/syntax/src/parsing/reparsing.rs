@@ -3,17 +3,17 @@
 //! We use two simple strategies for this:
 //!   - if the edit modifies only a single token (like changing an identifier's
 //!     letter), we replace only this token.
-//!   - otherwise, we search for the nearest `{}` block which contains the edit
-//!     and try to parse only this block.
+//!   - otherwise, we search for the nearest `{}` block or token tree which
+//!     contains the edit and try to parse only this block.
 
 use std::ops::Range;
 
 use parser::{Edition, Reparser};
 
 use crate::{
-    SyntaxError,
+    AstNode, SyntaxError, SyntaxKind,
     SyntaxKind::*,
-    T, TextRange, TextSize,
+    T, TextRange, TextSize, ast,
     parsing::build_tree,
     syntax_node::{GreenNode, GreenToken, NodeOrToken, SyntaxElement, SyntaxNode},
 };
@@ -105,11 +105,11 @@ fn reparse_block(
 
     let lexed = parser::LexedStr::new(edition, text.as_str());
     let parser_input = lexed.to_input(edition);
-    if !is_balanced(&lexed) {
+    if !is_balanced(&lexed, node.first_token()?.kind()) {
         return None;
     }
 
-    let tree_traversal = reparser.parse(&parser_input);
+    let tree_traversal = reparser.parse(&parser_input, nesting_depth(&node));
 
     let (green, new_parser_errors, _eof) = build_tree(lexed, tree_traversal);
 
@@ -141,24 +141,63 @@ fn find_reparsable_node(node: &SyntaxNode, range: TextRange) -> Option<(SyntaxNo
     })
 }
 
-fn is_balanced(lexed: &parser::LexedStr<'_>) -> bool {
-    if lexed.is_empty() || lexed.kind(0) != T!['{'] || lexed.kind(lexed.len() - 1) != T!['}'] {
+/// How many expressions and types `node` is nested in, as tracked by the parser.
+fn nesting_depth(node: &SyntaxNode) -> u32 {
+    let depth = node
+        .ancestors()
+        .skip(1)
+        .filter(|it| ast::Expr::can_cast(it.kind()) || ast::Type::can_cast(it.kind()))
+        .count();
+    depth.try_into().unwrap_or(u32::MAX)
+}
+
+fn is_balanced(lexed: &parser::LexedStr<'_>, open: SyntaxKind) -> bool {
+    let close = match open {
+        T!['{'] => T!['}'],
+        T!['('] => T![')'],
+        T!['['] => T![']'],
+        _ => return false,
+    };
+    if lexed.is_empty() || lexed.kind(0) != open || lexed.kind(lexed.len() - 1) != close {
         return false;
     }
-    let mut balance = 0usize;
-    for i in 1..lexed.len() - 1 {
-        match lexed.kind(i) {
-            T!['{'] => balance += 1,
-            T!['}'] => {
-                balance = match balance.checked_sub(1) {
-                    Some(b) => b,
-                    None => return false,
+    let inner = (1..lexed.len() - 1).map(|i| lexed.kind(i));
+
+    if open == T!['{'] {
+        let mut balance = 0usize;
+        for kind in inner {
+            match kind {
+                T!['{'] => balance += 1,
+                T!['}'] => {
+                    balance = match balance.checked_sub(1) {
+                        Some(b) => b,
+                        None => return false,
+                    }
+                }
+                _ => (),
+            }
+        }
+        return balance == 0;
+    }
+
+    // Unlike a `}`, a stray delimiter inside a `()` or `[]` token tree is not where the parser
+    // recovers, so all of them need to match for the tree to end at the same place as in a full
+    // parse.
+    let mut stack = Vec::new();
+    for kind in inner {
+        match kind {
+            T!['{'] => stack.push(T!['}']),
+            T!['('] => stack.push(T![')']),
+            T!['['] => stack.push(T![']']),
+            T!['}'] | T![')'] | T![']'] => {
+                if stack.pop() != Some(kind) {
+                    return false;
                 }
             }
             _ => (),
         }
     }
-    balance == 0
+    stack.is_empty()
 }
 
 fn merge_errors(
@@ -320,7 +359,24 @@ fn foo() {
 }
 ",
             "123",
-            14,
+            2,
+        );
+        do_check(
+            r"
+fn foo() {
+    foo!(a, b$0$0);
+}
+",
+            ", c[3]",
+            6,
+        );
+        do_check(
+            r"
+#[cfg(all(a, b$0$0))]
+fn foo() {}
+",
+            ", c",
+            6,
         );
         do_check(
             r"
@@ -462,4 +518,55 @@ fn reparse_block_with_error_in_middle_fixed() {
             105,
         )
     }
+
+    #[test]
+    fn reparse_token_tree_falls_back_when_unbalanced() {
+        let (range, before) = extract_range("fn foo() {\n    foo!(a, [b$0$0]);\n}\n");
+        let before = SourceFile::parse(&before, Edition::CURRENT);
+        for insert in [")", "}", "(", "[b"] {
+            let reparsed = incremental_reparse(
+                before.tree().syntax(),
+                range,
+                insert,
+                Vec::new(),
+                Edition::CURRENT,
+            );
+            assert!(reparsed.is_none(), "{insert:?} should not be reparsed incrementally");
+        }
+    }
+
+    #[test]
+    fn reparse_edits_inside_token_trees_like_full_parse() {
+        let text = "fn main() {\n    foo!(a, [b; 2], {c});\n    bar![(x), \"s\"];\n}\n";
+        let parse = SourceFile::parse(text, Edition::CURRENT);
+        let token_trees = parse
+            .tree()
+            .syntax()
+            .descendants()
+            .filter(|it| it.kind() == TOKEN_TREE)
+            .map(|it| it.text_range())
+            .collect::<Vec<_>>();
+
+        for tree in token_trees {
+            for start in u32::from(tree.start()) + 1..u32::from(tree.end()) {
+                for len in 0..2 {
+                    let delete = TextRange::at(start.into(), len.into());
+                    if delete.end() >= tree.end() {
+                        continue;
+                    }
+                    for insert in ["", "z", ", ", "(", ")", "[0]", "{", "}", "\""] {
+                        let mut after = text.to_owned();
+                        after.replace_range(Range::<usize>::from(delete), insert);
+                        let full = SourceFile::parse(&after, Edition::CURRENT);
+                        let incremental = parse.reparse(delete, insert, Edition::CURRENT);
+                        assert_eq!(
+                            format!("{:#?}", full.tree().syntax()),
+                            format!("{:#?}", incremental.tree().syntax()),
+                            "replacing {delete:?} with {insert:?}",
+                        );
+                    }
+                }
+            }
+        }
+    }
 }
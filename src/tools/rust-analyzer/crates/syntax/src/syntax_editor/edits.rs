@@ -1,13 +1,14 @@
 //! Structural editing for ast using `SyntaxEditor`
 
 use crate::{
-    AstToken, Direction, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, T,
+    Direction, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, T,
     algo::neighbor,
     ast::{
         self, AstNode, Fn, GenericParam, HasGenericParams, HasName, edit::IndentLevel, make,
         syntax_factory::SyntaxFactory,
     },
     syntax_editor::{Position, SyntaxEditor},
+    trivia,
 };
 
 impl SyntaxEditor {
@@ -112,6 +113,7 @@ pub fn add_items(&self, editor: &mut SyntaxEditor, items: Vec<ast::AssocItem>) {
                 None => (IndentLevel::single(), Position::last_child_of(self.syntax()), "\n"),
             },
         };
+        let indent = indent.text_like(self.syntax());
 
         let elements: Vec<SyntaxElement> = items
             .into_iter()
@@ -212,25 +214,7 @@ fn remove(&self, editor: &mut SyntaxEditor) {
 
 impl Removable for ast::Use {
     fn remove(&self, editor: &mut SyntaxEditor) {
-        let make = SyntaxFactory::without_mappings();
-
-        let next_ws = self
-            .syntax()
-            .next_sibling_or_token()
-            .and_then(|it| it.into_token())
-            .and_then(ast::Whitespace::cast);
-        if let Some(next_ws) = next_ws {
-            let ws_text = next_ws.syntax().text();
-            if let Some(rest) = ws_text.strip_prefix('\n') {
-                if rest.is_empty() {
-                    editor.delete(next_ws.syntax());
-                } else {
-                    editor.replace(next_ws.syntax(), make.whitespace(rest));
-                }
-            }
-        }
-
-        editor.delete(self.syntax());
+        trivia::replace_preserving_blank_lines(editor, self.syntax(), Vec::new());
     }
 }
 
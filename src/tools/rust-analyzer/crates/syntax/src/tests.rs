@@ -7,10 +7,14 @@
 use expect_test::expect_file;
 use parser::Edition;
 use rayon::prelude::*;
-use stdx::format_to_acc;
+use stdx::{format_to, format_to_acc};
 use test_utils::{bench, bench_fixture, project_root};
 
-use crate::{AstNode, SourceFile, SyntaxError, ast, fuzz};
+use crate::{
+    AstNode, Parse, SourceFile, SyntaxError, TextRange, ast,
+    debug_dump::{DumpConfig, debug_dump},
+    fuzz,
+};
 
 #[test]
 fn parse_smoke_test() {
@@ -54,10 +58,88 @@ fn validation_tests() {
         let parse = SourceFile::parse(text, Edition::CURRENT);
         let errors = parse.errors();
         assert_errors_are_present(&errors, path);
-        parse.debug_dump()
+        dump_parse(&parse)
     });
 }
 
+/// Dumps a parse for comparison with the expectation files. Set `RA_STABLE_DUMP=1` together
+/// with `UPDATE_EXPECT=1` to rewrite them in the format of [`debug_dump`] instead.
+fn dump_parse(parse: &Parse<SourceFile>) -> String {
+    if std::env::var("RA_STABLE_DUMP").is_err() {
+        return parse.debug_dump();
+    }
+    let config = DumpConfig { max_token_len: None, ..DumpConfig::default() };
+    let mut buf = debug_dump(&parse.syntax_node(), &config);
+    for err in parse.errors() {
+        format_to!(buf, "error {:?}: {}\n", err.range(), err);
+    }
+    buf
+}
+
+#[test]
+fn nesting_limit_is_reported() {
+    let code = "fn f() -> &&&&u8 { ((((1)))) }";
+    let parse = SourceFile::parse_with_recovery_limit(code, Edition::CURRENT, 3);
+    assert_eq!(parse.syntax_node().text().to_string(), code);
+    let errors = parse.errors().iter().map(|it| it.to_string()).collect::<Vec<_>>();
+    assert!(errors.iter().any(|it| it == "expression is too deeply nested"), "{errors:?}");
+    assert!(errors.iter().any(|it| it == "type is too deeply nested"), "{errors:?}");
+    fuzz::check_parser(code);
+
+    let parse = SourceFile::parse(code, Edition::CURRENT);
+    assert!(parse.errors().is_empty());
+}
+
+#[test]
+fn deeply_nested_parens_do_not_overflow_the_stack() {
+    let code = format!("fn f() {{ {} }}", "(".repeat(10_000));
+    // The same stack size rust-analyzer runs its own threads with.
+    std::thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            let parse = SourceFile::parse(&code, Edition::CURRENT);
+            assert_eq!(parse.syntax_node().text().to_string(), code);
+            assert!(
+                parse.errors().iter().any(|it| it.to_string() == "expression is too deeply nested")
+            );
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn reparse_respects_nesting_limit() {
+    let depth = parser::DEFAULT_NESTING_LIMIT as usize - 2;
+    let open = "(".repeat(depth);
+    let close = ")".repeat(depth);
+    let code = format!("fn f() {{ {open}{{ ((1)) }}{close} }}");
+    std::thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            let offset = code.find("1").unwrap();
+            let delete = TextRange::at(offset.try_into().unwrap(), 1.into());
+            let parse = SourceFile::parse(&code, Edition::CURRENT);
+            let reparse = parse.reparse(delete, "2", Edition::CURRENT);
+            let edited = code.replacen('1', "2", 1);
+            let full_parse = SourceFile::parse(&edited, Edition::CURRENT);
+            assert_eq!(reparse.syntax_node().text().to_string(), edited);
+            assert_eq!(
+                format!("{:#?}", reparse.syntax_node()),
+                format!("{:#?}", full_parse.syntax_node())
+            );
+            assert!(
+                reparse
+                    .errors()
+                    .iter()
+                    .any(|it| it.to_string() == "expression is too deeply nested")
+            );
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
 #[test]
 fn parser_fuzz_tests() {
     for (_, text) in collect_rust_files(&test_data_dir(), &["parser/fuzz-failures"]) {
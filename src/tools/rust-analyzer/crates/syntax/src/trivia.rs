@@ -0,0 +1,141 @@
+//! Helpers for keeping the whitespace around edited nodes intact.
+//!
+//! Edits that splice nodes in and out of a list of items tend to either collapse or duplicate
+//! the blank lines separating them. The functions here look at the trivia surrounding a node so
+//! that a blank line which separated two items before an edit still separates them after it.
+
+use crate::{
+    AstToken, SyntaxElement, SyntaxNode,
+    ast::{self, syntax_factory::SyntaxFactory},
+    syntax_editor::SyntaxEditor,
+};
+
+/// Number of blank lines between `node` and whatever precedes it.
+pub fn leading_blank_lines(node: &SyntaxNode) -> usize {
+    leading_whitespace(node).map_or(0, |ws| ws.newline_count().saturating_sub(1))
+}
+
+/// The whitespace directly following `node`, if it starts with the line break ending `node`.
+pub fn trailing_newline(node: &SyntaxNode) -> Option<ast::Whitespace> {
+    node.next_sibling_or_token()
+        .and_then(|it| it.into_token())
+        .and_then(ast::Whitespace::cast)
+        .filter(|ws| ws.text().starts_with('\n'))
+}
+
+/// Replaces `old` with `new`, removing `old` altogether if `new` is empty.
+///
+/// On removal, the line break ending `old` goes with it, and the whitespace on both sides of
+/// `old` is joined so that it keeps as many blank lines as the larger of the two, rather than
+/// adding them up.
+pub fn replace_preserving_blank_lines(
+    editor: &mut SyntaxEditor,
+    old: &SyntaxNode,
+    new: Vec<SyntaxElement>,
+) {
+    if !new.is_empty() {
+        editor.replace_with_many(old, new);
+        return;
+    }
+
+    if let Some(next_ws) = trailing_newline(old) {
+        let text = next_ws.text();
+        let mut rest = text[1..].to_owned();
+        if let Some(prev_ws) = leading_whitespace(old) {
+            // Blank lines are written without indentation, only the line of the next node is
+            // indented.
+            let blank_lines = leading_blank_lines(old).max(next_ws.newline_count() - 1);
+            let indent = &text[text.rfind('\n').map_or(0, |it| it + 1)..];
+            let joined = format!("{}{indent}", "\n".repeat(blank_lines + 1));
+            // Prefer touching only the whitespace after `old`, so that removing several
+            // adjacent nodes doesn't edit the same token twice.
+            match joined.strip_prefix(prev_ws.text()) {
+                Some(it) => rest = it.to_owned(),
+                // The whitespace before `old` ends in its indentation, which would be left
+                // behind on a line of its own.
+                None => {
+                    let make = SyntaxFactory::without_mappings();
+                    editor.replace(prev_ws.syntax(), make.whitespace(&joined));
+                    rest.clear();
+                }
+            }
+        }
+        if rest.is_empty() {
+            editor.delete(next_ws.syntax());
+        } else {
+            let make = SyntaxFactory::without_mappings();
+            editor.replace(next_ws.syntax(), make.whitespace(&rest));
+        }
+    }
+    editor.delete(old);
+}
+
+fn leading_whitespace(node: &SyntaxNode) -> Option<ast::Whitespace> {
+    node.prev_sibling_or_token().and_then(|it| it.into_token()).and_then(ast::Whitespace::cast)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AstNode, Edition, SourceFile, ast};
+
+    use super::*;
+
+    fn check_remove(before: &str, nth_fn: usize, after: &str) {
+        let file = SourceFile::parse(before, Edition::CURRENT).tree();
+        let func = file.syntax().descendants().filter_map(ast::Fn::cast).nth(nth_fn).unwrap();
+
+        let mut editor = SyntaxEditor::new(file.syntax().clone());
+        replace_preserving_blank_lines(&mut editor, func.syntax(), Vec::new());
+        assert_eq!(editor.finish().new_root().to_string(), after);
+    }
+
+    #[test]
+    fn blank_lines_and_trailing_newline() {
+        let file = SourceFile::parse("fn a() {}\n\n\nfn b() {} fn c() {}\n", Edition::CURRENT);
+        let fns = file.tree().syntax().descendants().filter_map(ast::Fn::cast).collect::<Vec<_>>();
+
+        assert_eq!(leading_blank_lines(fns[0].syntax()), 0);
+        assert_eq!(leading_blank_lines(fns[1].syntax()), 2);
+        assert_eq!(leading_blank_lines(fns[2].syntax()), 0);
+
+        assert_eq!(trailing_newline(fns[0].syntax()).unwrap().newline_count(), 3);
+        assert!(trailing_newline(fns[1].syntax()).is_none());
+        assert_eq!(trailing_newline(fns[2].syntax()).unwrap().newline_count(), 1);
+    }
+
+    #[test]
+    fn remove_keeps_single_blank_line() {
+        check_remove("fn a() {}\n\nfn b() {}\n\nfn c() {}\n", 1, "fn a() {}\n\nfn c() {}\n");
+        check_remove("fn a() {}\nfn b() {}\n\nfn c() {}\n", 1, "fn a() {}\n\nfn c() {}\n");
+        check_remove("fn a() {}\n\nfn b() {}\nfn c() {}\n", 1, "fn a() {}\n\nfn c() {}\n");
+        check_remove("fn a() {}\nfn b() {}\nfn c() {}\n", 1, "fn a() {}\nfn c() {}\n");
+    }
+
+    #[test]
+    fn remove_keeps_indentation() {
+        check_remove(
+            "mod m {\n    fn a() {}\n\n    fn b() {}\n\n    fn c() {}\n}\n",
+            1,
+            "mod m {\n    fn a() {}\n\n    fn c() {}\n}\n",
+        );
+        check_remove(
+            "mod m {\n    fn a() {}\n    fn b() {}\n    fn c() {}\n}\n",
+            1,
+            "mod m {\n    fn a() {}\n    fn c() {}\n}\n",
+        );
+    }
+
+    #[test]
+    fn remove_does_not_leave_indented_blank_line() {
+        check_remove(
+            "mod m {\n    fn a() {}\n    fn b() {}\n\n    fn c() {}\n}\n",
+            1,
+            "mod m {\n    fn a() {}\n\n    fn c() {}\n}\n",
+        );
+        check_remove(
+            "mod m {\n    fn a() {}\n\n    fn b() {}\n    fn c() {}\n}\n",
+            1,
+            "mod m {\n    fn a() {}\n\n    fn c() {}\n}\n",
+        );
+    }
+}
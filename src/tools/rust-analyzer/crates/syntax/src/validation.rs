@@ -12,7 +12,7 @@
 
 use crate::{
     AstNode, SyntaxError,
-    SyntaxKind::{CONST, FN, INT_NUMBER, TYPE_ALIAS},
+    SyntaxKind::{ABI, CONST, FN, INT_NUMBER, TYPE_ALIAS},
     SyntaxNode, SyntaxToken, T, TextSize, algo,
     ast::{self, HasAttrs, HasVisibility, IsString, RangeItem},
     match_ast,
@@ -29,6 +29,7 @@ pub(crate) fn validate(root: &SyntaxNode, errors: &mut Vec<SyntaxError>) {
             match node {
                 ast::Literal(it) => validate_literal(it, errors),
                 ast::Const(it) => validate_const(it, errors),
+                ast::Fn(it) => validate_fn_qualifiers(it, errors),
                 ast::BlockExpr(it) => block::validate_block_expr(it, errors),
                 ast::FieldExpr(it) => validate_numeric_name(it.name_ref(), errors),
                 ast::RecordExprField(it) => validate_numeric_name(it.name_ref(), errors),
@@ -437,6 +438,56 @@ fn validate_const(const_: ast::Const, errors: &mut Vec<SyntaxError>) {
     }
 }
 
+fn validate_fn_qualifiers(fn_: ast::Fn, errors: &mut Vec<SyntaxError>) {
+    // The parser accepts qualifiers in any order, but they have to be written as
+    // `default const async gen unsafe extern fn`.
+    let mut latest: Option<(usize, &str)> = None;
+    for qualifier in fn_.syntax().children_with_tokens() {
+        let (rank, text) = match qualifier.kind() {
+            T![default] => (0, "default"),
+            T![const] => (1, "const"),
+            T![async] => (2, "async"),
+            T![gen] => (3, "gen"),
+            T![unsafe] => (4, "unsafe"),
+            T![safe] => (4, "safe"),
+            ABI => (5, "extern"),
+            T![fn] => break,
+            _ => continue,
+        };
+        match latest {
+            Some((latest_rank, latest_text)) if rank < latest_rank => {
+                errors.push(SyntaxError::new(
+                    format!("`{text}` must come before `{latest_text}`"),
+                    qualifier.text_range(),
+                ));
+            }
+            _ => latest = Some((rank, text)),
+        }
+    }
+    if fn_.const_token().is_some() {
+        if let Some(async_token) = fn_.async_token() {
+            errors.push(SyntaxError::new(
+                "functions cannot be both `const` and `async`",
+                async_token.text_range(),
+            ));
+        }
+        if let Some(gen_token) = fn_.gen_token() {
+            errors.push(SyntaxError::new(
+                "functions cannot be both `const` and `gen`",
+                gen_token.text_range(),
+            ));
+        }
+    }
+    if let Some(safe_token) = fn_.safe_token()
+        && fn_.unsafe_token().is_some()
+    {
+        errors.push(SyntaxError::new(
+            "functions cannot be both `safe` and `unsafe`",
+            safe_token.text_range(),
+        ));
+    }
+}
+
 fn validate_let_expr(let_: ast::LetExpr, errors: &mut Vec<SyntaxError>) {
     let mut token = let_.syntax().clone();
     loop {
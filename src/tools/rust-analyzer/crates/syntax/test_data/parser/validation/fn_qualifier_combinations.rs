@@ -0,0 +1,4 @@
+async const fn foo() {}
+const gen fn bar() {}
+unsafe default fn baz() {}
+extern "C" const fn qux() {}
@@ -11,13 +11,15 @@
     fs,
     path::{Component, Path},
     sync::atomic::AtomicUsize,
+    time::Duration,
 };
 
 use crossbeam_channel::{Receiver, Sender, select, unbounded};
 use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use paths::{AbsPath, AbsPathBuf, Utf8PathBuf};
+use paths::{AbsPath, AbsPathBuf, Utf8Path, Utf8PathBuf};
 use rayon::iter::{IndexedParallelIterator as _, IntoParallelIterator as _, ParallelIterator};
 use rustc_hash::FxHashSet;
+use stdx::thread::JoinError;
 use vfs::loader::{self, LoadingProgress};
 use walkdir::WalkDir;
 
@@ -25,7 +27,7 @@
 pub struct NotifyHandle {
     // Relative order of fields below is significant.
     sender: Sender<Message>,
-    _thread: stdx::thread::JoinHandle,
+    thread: Option<stdx::thread::JoinHandle>,
 }
 
 #[derive(Debug)]
@@ -41,7 +43,7 @@ fn spawn(sender: loader::Sender) -> NotifyHandle {
         let thread = stdx::thread::Builder::new(stdx::thread::ThreadIntent::Worker, "VfsLoader")
             .spawn(move || actor.run(receiver))
             .expect("failed to spawn thread");
-        NotifyHandle { sender, _thread: thread }
+        NotifyHandle { sender, thread: Some(thread) }
     }
 
     fn set_config(&mut self, config: loader::Config) {
@@ -55,6 +57,15 @@ fn invalidate(&mut self, path: AbsPathBuf) {
     fn load_sync(&mut self, path: &AbsPath) -> Option<Vec<u8>> {
         read(path)
     }
+
+    fn stop(&mut self, timeout: Duration) -> Result<(), JoinError> {
+        // Disconnect the actor's inbox so that it exits its loop.
+        self.sender = unbounded().0;
+        match self.thread.take() {
+            Some(thread) => thread.join_timeout(timeout),
+            None => Ok(()),
+        }
+    }
 }
 
 type NotifyEvent = notify::Result<notify::Event>;
@@ -63,6 +74,8 @@ struct NotifyActor {
     sender: loader::Sender,
     watched_file_entries: FxHashSet<AbsPathBuf>,
     watched_dir_entries: Vec<loader::Directories>,
+    /// Files we reported as existing, used to spot files that vanished between two loads.
+    loaded_files: FxHashSet<AbsPathBuf>,
     // Drop order is significant.
     watcher: Option<(RecommendedWatcher, Receiver<NotifyEvent>)>,
 }
@@ -79,6 +92,7 @@ fn new(sender: loader::Sender) -> NotifyActor {
             sender,
             watched_dir_entries: Vec::new(),
             watched_file_entries: FxHashSet::default(),
+            loaded_files: FxHashSet::default(),
             watcher: None,
         }
     }
@@ -130,6 +144,7 @@ fn run(mut self, inbox: Receiver<Message>) {
 
                         let (entry_tx, entry_rx) = unbounded();
                         let (watch_tx, watch_rx) = unbounded();
+                        let (loaded_tx, loaded_rx) = unbounded();
                         let processed = AtomicUsize::new(0);
 
                         config.load.into_par_iter().enumerate().for_each(|(i, entry)| {
@@ -152,6 +167,11 @@ fn run(mut self, inbox: Receiver<Message>) {
                                     });
                                 },
                             );
+                            for (path, contents) in &files {
+                                if contents.is_some() {
+                                    _ = loaded_tx.send(path.clone());
+                                }
+                            }
                             self.send(loader::Message::Loaded { files });
                             self.send(loader::Message::Progress {
                                 n_total,
@@ -168,6 +188,24 @@ fn run(mut self, inbox: Receiver<Message>) {
                             self.watch(&path);
                         }
 
+                        // Files that were there during the previous load but are gone from disk
+                        // now have been deleted in the meantime, report them as such. Files that
+                        // still exist were merely excluded by the new config.
+                        drop(loaded_tx);
+                        let loaded_files = loaded_rx.into_iter().collect::<FxHashSet<_>>();
+                        let deleted = self
+                            .loaded_files
+                            .drain()
+                            .filter(|path| {
+                                !loaded_files.contains(path) && fs::metadata(path).is_err()
+                            })
+                            .map(|path| (path, None))
+                            .collect::<Vec<_>>();
+                        self.loaded_files = loaded_files;
+                        if !deleted.is_empty() {
+                            self.send(loader::Message::Changed { files: deleted });
+                        }
+
                         drop(entry_tx);
                         for entry in entry_rx {
                             match entry {
@@ -190,6 +228,7 @@ fn run(mut self, inbox: Receiver<Message>) {
                     Message::Invalidate(path) => {
                         let contents = read(path.as_path());
                         let files = vec![(path, contents)];
+                        self.record_changes(&files);
                         self.send(loader::Message::Changed { files });
                     }
                 },
@@ -208,7 +247,10 @@ fn run(mut self, inbox: Receiver<Message>) {
                                 )
                             })
                             .filter_map(|path| -> Option<(AbsPathBuf, Option<Vec<u8>>)> {
-                                let meta = fs::metadata(&path).ok()?;
+                                let Ok(meta) = fs::metadata(&path) else {
+                                    // The path is gone, which is a deletion if it was one of ours.
+                                    return self.is_watched_file(&path).then_some((path, None));
+                                };
                                 if meta.file_type().is_dir()
                                     && self
                                         .watched_dir_entries
@@ -223,19 +265,15 @@ fn run(mut self, inbox: Receiver<Message>) {
                                     return None;
                                 }
 
-                                if !(self.watched_file_entries.contains(&path)
-                                    || self
-                                        .watched_dir_entries
-                                        .iter()
-                                        .any(|dir| dir.contains_file(&path)))
-                                {
+                                if !self.is_watched_file(&path) {
                                     return None;
                                 }
 
                                 let contents = read(&path);
                                 Some((path, contents))
                             })
-                            .collect();
+                            .collect::<Vec<_>>();
+                        self.record_changes(&files);
                         self.send(loader::Message::Changed { files });
                     }
                 }
@@ -279,6 +317,11 @@ fn load_entry(
                             // We want to filter out subdirectories that are roots themselves, because they will be visited separately.
                             dirs.exclude.iter().all(|it| it != path)
                                 && (root == path || dirs.include.iter().all(|it| it != path))
+                                && !Utf8Path::from_path(path)
+                                    .and_then(|path| <&AbsPath>::try_from(path).ok())
+                                    .is_some_and(|path| {
+                                        dirs.exclude_globs.iter().any(|glob| glob.matches(path))
+                                    })
                         });
 
                     let files = walkdir.filter_map(|it| it.ok()).filter_map(|entry| {
@@ -302,6 +345,9 @@ fn load_entry(
                         if dirs.extensions.iter().all(|it| it.as_str() != ext) {
                             return None;
                         }
+                        if dirs.exclude_globs.iter().any(|glob| glob.matches(&abs_path)) {
+                            return None;
+                        }
                         Some(abs_path)
                     });
 
@@ -315,6 +361,21 @@ fn load_entry(
         }
     }
 
+    fn is_watched_file(&self, path: &AbsPath) -> bool {
+        self.watched_file_entries.contains(path)
+            || self.watched_dir_entries.iter().any(|dir| dir.contains_file(path))
+    }
+
+    fn record_changes(&mut self, files: &[(AbsPathBuf, Option<Vec<u8>>)]) {
+        for (path, contents) in files {
+            if contents.is_some() {
+                self.loaded_files.insert(path.clone());
+            } else {
+                self.loaded_files.remove(path);
+            }
+        }
+    }
+
     fn watch(&mut self, path: &Path) {
         if let Some((watcher, _)) = &mut self.watcher {
             log_notify_error(watcher.watch(path, RecursiveMode::NonRecursive));
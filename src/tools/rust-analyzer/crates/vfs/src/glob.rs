@@ -0,0 +1,154 @@
+//! Minimal path globs for excluding directories from loading and watching.
+use paths::{AbsPath, AbsPathBuf};
+
+/// A pattern matching paths relative to a base directory.
+///
+/// The pattern is split into `/`-separated components. `**` matches any number
+/// of components (including none), `*` matches any part of a single component
+/// and everything else has to match literally; matching is case-sensitive.
+///
+/// A path matches if it or one of its ancestors matches, so a pattern naming a
+/// directory excludes everything beneath it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    base: AbsPathBuf,
+    components: Vec<String>,
+}
+
+impl Glob {
+    pub fn new(base: AbsPathBuf, pattern: &str) -> Glob {
+        let components = pattern
+            .split(['/', '\\'])
+            .filter(|it| !it.is_empty() && *it != ".")
+            .map(ToOwned::to_owned)
+            .collect();
+        Glob { base, components }
+    }
+
+    /// Returns `true` if `pattern` contains wildcards, and is not just a plain path.
+    pub fn is_glob(pattern: &str) -> bool {
+        pattern.contains('*')
+    }
+
+    /// Returns `true` if `path` or one of its ancestors matches the pattern.
+    pub fn matches(&self, path: &AbsPath) -> bool {
+        let Some(rel) = path.strip_prefix(&self.base) else {
+            return false;
+        };
+        let components = rel.as_utf8_path().components().map(|it| it.as_str()).collect::<Vec<_>>();
+        !self.components.is_empty() && match_prefix(&self.components, &components)
+    }
+}
+
+/// Returns `true` if `pattern` matches a prefix of `path`.
+fn match_prefix(pattern: &[String], path: &[&str]) -> bool {
+    match pattern {
+        [] => true,
+        [first, rest @ ..] if first == "**" => {
+            (0..=path.len()).any(|skip| match_prefix(rest, &path[skip..]))
+        }
+        [first, rest @ ..] => match path {
+            [component, path_rest @ ..] => {
+                match_component(first, component) && match_prefix(rest, path_rest)
+            }
+            [] => false,
+        },
+    }
+}
+
+/// Matches a single path component against a pattern where `*` matches any substring.
+fn match_component(pattern: &str, component: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == component;
+    };
+    let Some(mut component) = component.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = rest.split('*').peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            // The last part has to match the end of the component.
+            return component.len() >= part.len() && component.ends_with(part);
+        }
+        match component.find(part) {
+            Some(idx) => component = &component[idx + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str) -> Glob {
+        Glob::new(AbsPathBuf::assert_utf8(std::env::temp_dir().join("root")), pattern)
+    }
+
+    fn path(rel: &str) -> AbsPathBuf {
+        AbsPathBuf::assert_utf8(std::env::temp_dir().join("root").join(rel))
+    }
+
+    #[test]
+    fn literal_patterns_are_relative_to_base() {
+        let g = glob("target");
+        assert!(g.matches(&path("target")));
+        assert!(g.matches(&path("target/debug/build/out.rs")));
+        assert!(!g.matches(&path("crates/foo/target/lib.rs")));
+        assert!(!g.matches(&path("targets/lib.rs")));
+        assert!(!g.matches(&AbsPathBuf::assert_utf8(std::env::temp_dir().join("target"))));
+
+        let g = glob("./code/node_modules/");
+        assert!(g.matches(&path("code/node_modules/pkg/index.rs")));
+        assert!(!g.matches(&path("code/src/main.rs")));
+    }
+
+    #[test]
+    fn double_star_matches_nested_dirs() {
+        let g = glob("**/node_modules");
+        assert!(g.matches(&path("node_modules")));
+        assert!(g.matches(&path("code/node_modules/pkg/lib.rs")));
+        assert!(g.matches(&path("a/b/c/node_modules")));
+        assert!(!g.matches(&path("a/b/c/node_modules_old/lib.rs")));
+
+        let g = glob("crates/**/generated");
+        assert!(g.matches(&path("crates/generated/lib.rs")));
+        assert!(g.matches(&path("crates/foo/src/generated/lib.rs")));
+        assert!(!g.matches(&path("generated/lib.rs")));
+    }
+
+    #[test]
+    fn single_star_matches_within_component() {
+        let g = glob("*.tmp");
+        assert!(g.matches(&path("build.tmp/lib.rs")));
+        assert!(!g.matches(&path("build.tmp2/lib.rs")));
+        assert!(!g.matches(&path("src/build.tmp")));
+
+        let g = glob("bench_*_data/*");
+        assert!(g.matches(&path("bench_big_data/file.rs")));
+        assert!(g.matches(&path("bench__data/file.rs")));
+        assert!(!g.matches(&path("bench_big_data")));
+        assert!(!g.matches(&path("bench_data/file.rs")));
+
+        let g = glob("a*b*a");
+        assert!(g.matches(&path("aba")));
+        assert!(g.matches(&path("abxba")));
+        assert!(!g.matches(&path("ab")));
+    }
+
+    #[test]
+    fn matching_is_case_sensitive() {
+        let g = glob("**/Target");
+        assert!(g.matches(&path("foo/Target/lib.rs")));
+        assert!(!g.matches(&path("foo/target/lib.rs")));
+    }
+
+    #[test]
+    fn is_glob() {
+        assert!(Glob::is_glob("**/node_modules"));
+        assert!(Glob::is_glob("*.tmp"));
+        assert!(!Glob::is_glob("target"));
+        assert!(!Glob::is_glob("code/node_modules"));
+    }
+}
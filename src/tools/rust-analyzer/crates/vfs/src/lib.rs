@@ -40,6 +40,7 @@
 
 mod anchored_path;
 pub mod file_set;
+mod glob;
 pub mod loader;
 mod path_interner;
 mod vfs_path;
@@ -50,6 +51,7 @@
 
 pub use crate::{
     anchored_path::{AnchoredPath, AnchoredPathBuf},
+    glob::Glob,
     vfs_path::VfsPath,
 };
 use indexmap::{IndexMap, map::Entry};
@@ -1,7 +1,10 @@
 //! Dynamically compatible interface for file watching and reading.
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use paths::{AbsPath, AbsPathBuf};
+use stdx::thread::JoinError;
+
+use crate::Glob;
 
 /// A set of files on the file system.
 #[derive(Debug, Clone)]
@@ -18,6 +21,7 @@ pub enum Entry {
 ///   * it has included extension
 ///   * it is under an `include` path
 ///   * it is not under `exclude` path
+///   * neither it nor one of its ancestors matches an `exclude_globs` pattern
 ///
 /// If many include/exclude paths match, the longest one wins.
 ///
@@ -27,6 +31,7 @@ pub struct Directories {
     pub extensions: Vec<String>,
     pub include: Vec<AbsPathBuf>,
     pub exclude: Vec<AbsPathBuf>,
+    pub exclude_globs: Vec<Glob>,
 }
 
 /// [`Handle`]'s configuration.
@@ -90,6 +95,13 @@ fn spawn(sender: Sender) -> Self
     /// Load the content of the given file, returning [`None`] if it does not
     /// exists.
     fn load_sync(&mut self, path: &AbsPath) -> Option<Vec<u8>>;
+
+    /// Stop the handle, waiting at most `timeout` for its worker thread to exit.
+    ///
+    /// Returns an error if the worker panicked or didn't stop in time.
+    fn stop(&mut self, _timeout: Duration) -> Result<(), JoinError> {
+        Ok(())
+    }
 }
 
 impl Entry {
@@ -178,6 +190,7 @@ pub fn contains_dir(&self, path: &AbsPath) -> bool {
     ///   - An element in `self.include` is a prefix of `path`.
     ///   - This path is longer than any element in `self.exclude` that is a prefix
     ///     of `path`. In case of equality, exclusion wins.
+    ///   - No pattern in `self.exclude_globs` matches `path`.
     fn includes_path(&self, path: &AbsPath) -> bool {
         let mut include: Option<&AbsPathBuf> = None;
         for incl in &self.include {
@@ -195,6 +208,7 @@ fn includes_path(&self, path: &AbsPath) -> bool {
         };
 
         !self.exclude.iter().any(|excl| path.starts_with(excl) && excl.starts_with(include))
+            && !self.exclude_globs.iter().any(|glob| glob.matches(path))
     }
 }
 
@@ -208,7 +222,12 @@ fn includes_path(&self, path: &AbsPath) -> bool {
 /// ```
 fn dirs(base: AbsPathBuf, exclude: &[&str]) -> Directories {
     let exclude = exclude.iter().map(|it| base.join(it)).collect::<Vec<_>>();
-    Directories { extensions: vec!["rs".to_owned()], include: vec![base], exclude }
+    Directories {
+        extensions: vec!["rs".to_owned()],
+        include: vec![base],
+        exclude,
+        exclude_globs: Vec::new(),
+    }
 }
 
 impl fmt::Debug for Message {
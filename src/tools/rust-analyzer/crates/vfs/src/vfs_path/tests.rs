@@ -28,3 +28,18 @@ fn virtual_path_extensions() {
         Some(("file", Some("rs")))
     );
 }
+
+#[test]
+#[cfg(not(windows))]
+fn real_paths_are_normalized() {
+    let path = |it: &str| VfsPath::from(AbsPathBuf::assert_utf8(it.into()));
+    assert_eq!(path("/ws/./crate/../crate/src//lib.rs"), path("/ws/crate/src/lib.rs"));
+    assert_eq!(path("/ws/crate/src/"), path("/ws/crate/src"));
+    // Normalization is purely lexical: casing is preserved and symlinks are not resolved.
+    assert_ne!(path("/ws/Crate/src/lib.rs"), path("/ws/crate/src/lib.rs"));
+
+    let mut vfs = crate::Vfs::default();
+    vfs.set_file_contents(path("/ws/crate/src/lib.rs"), Some(Vec::new()));
+    vfs.set_file_contents(path("/ws/crate/../crate/src/lib.rs"), Some(b"fn main() {}".to_vec()));
+    assert_eq!(vfs.iter().count(), 1);
+}
@@ -995,6 +995,24 @@ export function openExternalDocs(ctx: CtxInit): Cmd {
     };
 }
 
+export function copyQualifiedName(ctx: CtxInit): Cmd {
+    return async () => {
+        const editor = vscode.window.activeTextEditor;
+        if (!editor) {
+            return;
+        }
+        const client = ctx.client;
+
+        const position = editor.selection.active;
+        const textDocument = { uri: editor.document.uri.toString() };
+
+        const name = await client.sendRequest(ra.qualifiedName, { position, textDocument });
+        if (name) {
+            await vscode.env.clipboard.writeText(name);
+        }
+    };
+}
+
 export function cancelFlycheck(ctx: CtxInit): Cmd {
     return async () => {
         await ctx.client.sendNotification(ra.cancelFlycheck);
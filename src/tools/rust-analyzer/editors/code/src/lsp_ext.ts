@@ -189,6 +189,11 @@ export interface DocsUrls {
 export const openDocs = new lc.RequestType<lc.TextDocumentPositionParams, DocsUrls, void>(
     "experimental/externalDocs",
 );
+export const qualifiedName = new lc.RequestType<
+    lc.TextDocumentPositionParams,
+    string | null,
+    void
+>("rust-analyzer/qualifiedName");
 export const parentModule = new lc.RequestType<
     lc.TextDocumentPositionParams,
     lc.LocationLink[] | null,
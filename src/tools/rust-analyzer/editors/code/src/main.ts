@@ -179,6 +179,7 @@ function createCommands(): Record<string, CommandFactory> {
         newDebugConfig: { enabled: commands.newDebugConfig },
         openDocs: { enabled: commands.openDocs },
         openExternalDocs: { enabled: commands.openExternalDocs },
+        copyQualifiedName: { enabled: commands.copyQualifiedName },
         openCargoToml: { enabled: commands.openCargoToml },
         peekTests: { enabled: commands.peekTests },
         moveItemUp: { enabled: commands.moveItemUp },
@@ -1,5 +1,5 @@
 use std::{
-    fmt, fs, mem,
+    fmt, mem,
     path::{Path, PathBuf},
 };
 
@@ -7,7 +7,7 @@
 
 use crate::{
     flags::{self, CodegenType},
-    project_root,
+    project_root, util,
 };
 
 pub(crate) mod assists_doc_tests;
@@ -21,13 +21,14 @@ impl flags::Codegen {
     pub(crate) fn run(self, _sh: &Shell) -> anyhow::Result<()> {
         match self.codegen_type.unwrap_or_default() {
             flags::CodegenType::All => {
-                grammar::generate(self.check);
-                assists_doc_tests::generate(self.check);
-                parser_inline_tests::generate(self.check);
-                feature_docs::generate(self.check);
-                diagnostics_docs::generate(self.check);
+                grammar::generate(self.check)?;
+                assists_doc_tests::generate(self.check)?;
+                parser_inline_tests::generate(self.check)?;
+                feature_docs::generate(self.check)?;
+                diagnostics_docs::generate(self.check)?;
                 // lints::generate(self.check) Updating clones the rust repo, so don't run it unless
                 // explicitly asked for
+                Ok(())
             }
             flags::CodegenType::Grammar => grammar::generate(self.check),
             flags::CodegenType::AssistsDocTests => assists_doc_tests::generate(self.check),
@@ -36,7 +37,6 @@ pub(crate) fn run(self, _sh: &Shell) -> anyhow::Result<()> {
             flags::CodegenType::ParserTests => parser_inline_tests::generate(self.check),
             flags::CodegenType::FeatureDocs => feature_docs::generate(self.check),
         }
-        Ok(())
     }
 }
 
@@ -169,15 +169,17 @@ fn add_preamble(cg: CodegenType, mut text: String) -> String {
 }
 
 /// Checks that the `file` has the specified `contents`. If that is not the
-/// case, updates the file and then fails the test.
+/// case, updates the file and then fails the test. Returns whether the file was updated.
 #[allow(clippy::print_stderr)]
-fn ensure_file_contents(cg: CodegenType, file: &Path, contents: &str, check: bool) -> bool {
-    let contents = normalize_newlines(contents);
-    if let Ok(old_contents) = fs::read_to_string(file)
-        && normalize_newlines(&old_contents) == contents
-    {
+fn ensure_file_contents(
+    cg: CodegenType,
+    file: &Path,
+    contents: &str,
+    check: bool,
+) -> anyhow::Result<bool> {
+    if util::has_contents(file, contents) {
         // File is already up to date.
-        return false;
+        return Ok(false);
     }
 
     let display_path = file.strip_prefix(project_root()).unwrap_or(file);
@@ -199,14 +201,6 @@ fn ensure_file_contents(cg: CodegenType, file: &Path, contents: &str, check: boo
             display_path.display()
         );
 
-        if let Some(parent) = file.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-        fs::write(file, contents).unwrap();
-        true
+        util::write_if_changed(file, &util::normalize_newlines(contents))
     }
 }
-
-fn normalize_newlines(s: &str) -> String {
-    s.replace("\r\n", "\n")
-}
@@ -2,6 +2,8 @@
 
 use std::{fmt, fs, path::Path};
 
+use anyhow::Context;
+
 use stdx::format_to_acc;
 
 use crate::{
@@ -10,7 +12,7 @@
     util::list_rust_files,
 };
 
-pub(crate) fn generate(check: bool) {
+pub(crate) fn generate(check: bool) -> anyhow::Result<()> {
     let assists = Assist::collect();
 
     {
@@ -50,12 +52,12 @@ fn doctest_{}() {{
             &project_root().join("crates/ide-assists/src/tests/generated.rs"),
             &buf,
             check,
-        );
+        )?;
     }
 
     // Do not generate assists manual when run with `--check`
     if check {
-        return;
+        return Ok(());
     }
 
     {
@@ -68,8 +70,9 @@ fn doctest_{}() {{
             assists.into_iter().map(|it| it.to_string()).collect::<Vec<_>>().join("\n\n"),
         );
         let dst = project_root().join("docs/book/src/assists_generated.md");
-        fs::write(dst, contents).unwrap();
+        fs::write(&dst, contents).with_context(|| format!("failed to write {}", dst.display()))?;
     }
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -203,5 +206,5 @@ fn reveal_hash_comments(text: &str) -> String {
 
 #[test]
 fn test() {
-    generate(true);
+    generate(true).unwrap();
 }
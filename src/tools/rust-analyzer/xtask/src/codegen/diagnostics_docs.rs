@@ -2,23 +2,26 @@
 
 use std::{fmt, fs, io, path::PathBuf};
 
+use anyhow::Context;
+
 use crate::{
     codegen::{CommentBlock, Location, add_preamble},
     project_root,
     util::list_rust_files,
 };
 
-pub(crate) fn generate(check: bool) {
-    let diagnostics = Diagnostic::collect().unwrap();
+pub(crate) fn generate(check: bool) -> anyhow::Result<()> {
+    let diagnostics = Diagnostic::collect()?;
     // Do not generate docs when run with `--check`
     if check {
-        return;
+        return Ok(());
     }
     let contents =
         diagnostics.into_iter().map(|it| it.to_string()).collect::<Vec<_>>().join("\n\n");
     let contents = add_preamble(crate::flags::CodegenType::DiagnosticsDocs, contents);
     let dst = project_root().join("docs/book/src/diagnostics_generated.md");
-    fs::write(dst, contents).unwrap();
+    fs::write(&dst, contents).with_context(|| format!("failed to write {}", dst.display()))?;
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -2,22 +2,25 @@
 
 use std::{fmt, fs, io, path::PathBuf};
 
+use anyhow::Context;
+
 use crate::{
     codegen::{CommentBlock, Location, add_preamble},
     project_root,
     util::list_rust_files,
 };
 
-pub(crate) fn generate(check: bool) {
-    let features = Feature::collect().unwrap();
+pub(crate) fn generate(check: bool) -> anyhow::Result<()> {
+    let features = Feature::collect()?;
     // Do not generate docs when run with `--check`
     if check {
-        return;
+        return Ok(());
     }
     let contents = features.into_iter().map(|it| it.to_string()).collect::<Vec<_>>().join("\n\n");
     let contents = add_preamble(crate::flags::CodegenType::FeatureDocs, contents);
     let dst = project_root().join("docs/book/src/features_generated.md");
-    fs::write(dst, contents).unwrap();
+    fs::write(&dst, contents).with_context(|| format!("failed to write {}", dst.display()))?;
+    Ok(())
 }
 
 #[derive(Debug)]
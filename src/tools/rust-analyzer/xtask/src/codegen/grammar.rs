@@ -26,7 +26,7 @@
 mod ast_src;
 use self::ast_src::{AstEnumSrc, AstNodeSrc, AstSrc, Cardinality, Field, KindsSrc};
 
-pub(crate) fn generate(check: bool) {
+pub(crate) fn generate(check: bool) -> anyhow::Result<()> {
     let grammar = fs::read_to_string(project_root().join("crates/syntax/rust.ungram"))
         .unwrap()
         .parse()
@@ -41,7 +41,7 @@ pub(crate) fn generate(check: bool) {
         syntax_kinds_file.as_path(),
         &syntax_kinds,
         check,
-    );
+    )?;
 
     let ast_tokens = generate_tokens(&ast);
     let ast_tokens_file = project_root().join("crates/syntax/src/ast/generated/tokens.rs");
@@ -50,7 +50,7 @@ pub(crate) fn generate(check: bool) {
         ast_tokens_file.as_path(),
         &ast_tokens,
         check,
-    );
+    )?;
 
     let ast_nodes = generate_nodes(kinds_src, &ast);
     let ast_nodes_file = project_root().join("crates/syntax/src/ast/generated/nodes.rs");
@@ -59,7 +59,8 @@ pub(crate) fn generate(check: bool) {
         ast_nodes_file.as_path(),
         &ast_nodes,
         check,
-    );
+    )?;
+    Ok(())
 }
 
 fn generate_tokens(grammar: &AstSrc) -> String {
@@ -1165,5 +1166,5 @@ fn remove_field(&mut self, to_remove: Vec<usize>) {
 
 #[test]
 fn test() {
-    generate(true);
+    generate(true).unwrap();
 }
@@ -22,7 +22,7 @@
 const DESTINATION: &str = "crates/ide-db/src/generated/lints.rs";
 
 /// This clones rustc repo, and so is not worth to keep up-to-date on a constant basis.
-pub(crate) fn generate(check: bool) {
+pub(crate) fn generate(check: bool) -> anyhow::Result<()> {
     let sh = &Shell::new().unwrap();
 
     let rust_repo = project_root().join("./target/rust");
@@ -97,7 +97,8 @@ pub struct LintGroup {
         destination.as_path(),
         &contents,
         check,
-    );
+    )?;
+    Ok(())
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
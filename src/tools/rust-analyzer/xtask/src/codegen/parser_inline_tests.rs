@@ -15,10 +15,10 @@
 use crate::{
     codegen::{CommentBlock, ensure_file_contents, reformat},
     project_root,
-    util::list_rust_files,
+    util::{list_rust_files, read_dir_sorted},
 };
 
-pub(crate) fn generate(check: bool) {
+pub(crate) fn generate(check: bool) -> Result<()> {
     let parser_crate_root = project_root().join("crates/parser");
     let parser_test_data = parser_crate_root.join("test_data");
     let parser_test_data_inline = parser_test_data.join("parser/inline");
@@ -26,15 +26,12 @@ pub(crate) fn generate(check: bool) {
     let tests = tests_from_dir(&parser_crate_root.join("src/grammar"));
 
     let mut some_file_was_updated = false;
-    some_file_was_updated |=
-        install_tests(&tests.ok, parser_test_data_inline.join("ok"), check).unwrap();
-    some_file_was_updated |=
-        install_tests(&tests.err, parser_test_data_inline.join("err"), check).unwrap();
+    some_file_was_updated |= install_tests(&tests.ok, parser_test_data_inline.join("ok"), check)?;
+    some_file_was_updated |= install_tests(&tests.err, parser_test_data_inline.join("err"), check)?;
 
     if some_file_was_updated {
-        let _ = fs::File::open(parser_crate_root.join("src/tests.rs"))
-            .unwrap()
-            .set_modified(SystemTime::now());
+        let _ =
+            fs::File::open(parser_crate_root.join("src/tests.rs"))?.set_modified(SystemTime::now());
     }
 
     let ok_tests = tests.ok.values().sorted_by(|a, b| a.name.cmp(&b.name)).map(|test| {
@@ -95,7 +92,8 @@ mod err {
         parser_test_data.join("generated/runner.rs").as_ref(),
         &pretty,
         check,
-    );
+    )?;
+    Ok(())
 }
 
 fn install_tests(tests: &HashMap<String, Test>, tests_dir: PathBuf, check: bool) -> Result<bool> {
@@ -111,10 +109,15 @@ fn install_tests(tests: &HashMap<String, Test>, tests_dir: PathBuf, check: bool)
 
     for (name, test) in tests {
         let path = match existing.get(name) {
+            // Check mode accepts the old `NNNN_name.rs` files, write mode renames them.
+            Some((path, _test)) if !check && path.file_stem().is_some_and(|it| it != &**name) => {
+                some_file_was_updated = true;
+                migrate_legacy_test(path, name)?
+            }
             Some((path, _test)) => path.clone(),
             None => tests_dir.join(name).with_extension("rs"),
         };
-        if ensure_file_contents(crate::flags::CodegenType::ParserTests, &path, &test.text, check) {
+        if ensure_file_contents(crate::flags::CodegenType::ParserTests, &path, &test.text, check)? {
             some_file_was_updated = true;
         }
     }
@@ -201,25 +204,105 @@ fn process_file(res: &mut Tests, path: &Path) {
 
 fn existing_tests(dir: &Path, ok: TestKind) -> Result<HashMap<String, (PathBuf, Test)>> {
     let mut res = HashMap::new();
-    for file in fs::read_dir(dir)? {
-        let path = file?.path();
+    for path in read_dir_sorted(dir)? {
         let rust_file = path.extension().and_then(|ext| ext.to_str()) == Some("rs");
 
         if rust_file {
-            let name = path.file_stem().map(|x| x.to_string_lossy().to_string()).unwrap();
+            let stem = path.file_stem().map(|x| x.to_string_lossy().to_string()).unwrap();
+            let is_legacy = legacy_test_name(&stem).is_some();
+            let name = legacy_test_name(&stem).unwrap_or(&stem).to_owned();
             let text = fs::read_to_string(&path)?;
             let edition =
                 text.lines().next().and_then(|it| it.strip_prefix("// ")).map(ToOwned::to_owned);
             let test = Test { name: name.clone(), text, kind: ok, edition };
-            if let Some(old) = res.insert(name, (path, test)) {
-                println!("Duplicate test: {old:?}");
+            match res.get(&name) {
+                Some(old) => {
+                    println!("Duplicate test: {old:?}");
+                    // Prefer the file that already follows the current naming scheme.
+                    if !is_legacy {
+                        res.insert(name, (path, test));
+                    }
+                }
+                None => {
+                    res.insert(name, (path, test));
+                }
             }
         }
     }
     Ok(res)
 }
 
+/// Test files used to be named `NNNN_name.rs`, with an index derived from the number of tests.
+fn legacy_test_name(file_stem: &str) -> Option<&str> {
+    let (index, name) = file_stem.split_once('_')?;
+    let is_index = index.len() == 4 && index.bytes().all(|it| it.is_ascii_digit());
+    (is_index && !name.is_empty()).then_some(name)
+}
+
+/// Renames a legacy `NNNN_name.rs` test and its `.rast` to `name.rs` and `name.rast`.
+fn migrate_legacy_test(path: &Path, name: &str) -> Result<PathBuf> {
+    let new_path = path.with_file_name(name).with_extension("rs");
+    fs::rename(path, &new_path)?;
+    let rast = path.with_extension("rast");
+    if rast.exists() {
+        fs::rename(&rast, new_path.with_extension("rast"))?;
+    }
+    println!("Renamed {} to {}", path.display(), new_path.display());
+    Ok(new_path)
+}
+
 #[test]
 fn test() {
-    generate(true);
+    generate(true).unwrap();
+}
+
+#[test]
+fn legacy_test_files_are_migrated() {
+    let dir = std::env::temp_dir().join(format!("xtask-legacy-tests-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, text) in [
+        ("0001_foo.rs", "fn foo() {}\n"),
+        ("0001_foo.rast", "SOURCE_FILE\n"),
+        ("0042_bar_baz.rs", "fn bar() {}\n"),
+        ("qux.rs", "fn qux() {}\n"),
+    ] {
+        fs::write(dir.join(file), text).unwrap();
+    }
+
+    assert_eq!(legacy_test_name("0001_foo"), Some("foo"));
+    assert_eq!(legacy_test_name("0042_bar_baz"), Some("bar_baz"));
+    assert_eq!(legacy_test_name("qux"), None);
+    assert_eq!(legacy_test_name("let_else"), None);
+    assert_eq!(legacy_test_name("0001_"), None);
+
+    let existing = existing_tests(&dir, TestKind::Ok).unwrap();
+    assert_eq!(existing.keys().sorted().collect::<Vec<_>>(), ["bar_baz", "foo", "qux"]);
+
+    let tests = existing
+        .iter()
+        .map(|(name, (_, test))| {
+            let test = Test {
+                name: name.clone(),
+                text: test.text.clone(),
+                kind: test.kind,
+                edition: None,
+            };
+            (name.clone(), test)
+        })
+        .collect::<HashMap<_, _>>();
+    // Check mode accepts the legacy names and leaves them alone.
+    assert!(!install_tests(&tests, dir.clone(), true).unwrap());
+    assert!(dir.join("0001_foo.rs").exists());
+
+    assert!(install_tests(&tests, dir.clone(), false).unwrap());
+    let files = read_dir_sorted(&dir)
+        .unwrap()
+        .into_iter()
+        .map(|it| it.file_name().unwrap().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(files, ["bar_baz.rs", "foo.rast", "foo.rs", "qux.rs"]);
+    assert_eq!(fs::read_to_string(dir.join("foo.rast")).unwrap(), "SOURCE_FILE\n");
+
+    fs::remove_dir_all(&dir).unwrap();
 }
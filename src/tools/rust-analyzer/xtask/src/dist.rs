@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{Context, bail};
 use flate2::{Compression, write::GzEncoder};
 use std::{
     fs::File,
@@ -13,7 +13,7 @@
     date_iso,
     flags::{self, Malloc, PgoTrainingCrate},
     project_root,
-    util::detect_target,
+    util::{detect_target, remove_file_if_exists},
 };
 
 const VERSION_STABLE: &str = "0.3";
@@ -25,7 +25,12 @@ pub(crate) fn run(self, sh: &Shell) -> anyhow::Result<()> {
         let stable = sh.var("GITHUB_REF").unwrap_or_default().as_str() == "refs/heads/release";
 
         let project_root = project_root();
-        let target = Target::get(&project_root, sh);
+        let target = Target::get(&project_root, sh, self.target.clone());
+        let use_cross = self.use_cross && cross_available(sh);
+        if self.target.is_some() && !use_cross {
+            // `cross` brings its own toolchain, so only check rustup when building with cargo.
+            check_target_installed(sh, &target.name)?;
+        }
         let allocator = self.allocator();
         let dist = project_root.join("dist");
         sh.remove_path(&dist)?;
@@ -38,29 +43,33 @@ pub(crate) fn run(self, sh: &Shell) -> anyhow::Result<()> {
                 // A hack to make VS Code prefer nightly over stable.
                 format!("{VERSION_NIGHTLY}.{patch_version}")
             };
-            dist_server(
+            let artifact = dist_server(
                 sh,
                 &format!("{version}-standalone"),
                 &target,
                 allocator,
                 self.zig,
+                use_cross,
                 self.pgo,
                 // Profiling requires debug information.
                 self.enable_profiling,
             )?;
+            write_manifest(sh, &target, &artifact)?;
             let release_tag = if stable { date_iso(sh)? } else { "nightly".to_owned() };
             dist_client(sh, &version, &release_tag, &target)?;
         } else {
-            dist_server(
+            let artifact = dist_server(
                 sh,
                 "0.0.0-standalone",
                 &target,
                 allocator,
                 self.zig,
+                use_cross,
                 self.pgo,
                 // Profiling requires debug information.
                 self.enable_profiling,
             )?;
+            write_manifest(sh, &target, &artifact)?;
         }
         Ok(())
     }
@@ -74,6 +83,10 @@ fn dist_client(
 ) -> anyhow::Result<()> {
     let bundle_path = Path::new("editors").join("code").join("server");
     sh.create_dir(&bundle_path)?;
+    // Don't ship the binaries of a previous build for another target along with this one.
+    for stale in ["rust-analyzer", "rust-analyzer.exe", "rust_analyzer.pdb"] {
+        remove_file_if_exists(&bundle_path.join(stale))?;
+    }
     sh.copy_file(&target.server_path, &bundle_path)?;
     if let Some(symbols_path) = &target.symbols_path {
         sh.copy_file(symbols_path, &bundle_path)?;
@@ -102,9 +115,10 @@ fn dist_server(
     target: &Target,
     allocator: Malloc,
     zig: bool,
+    use_cross: bool,
     pgo: Option<PgoTrainingCrate>,
     dev_rel: bool,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<PathBuf> {
     let _e = sh.push_env("CFG_RELEASE", release);
     let _e = sh.push_env("CARGO_PROFILE_RELEASE_LTO", "thin");
     let _e = sh.push_env("CARGO_PROFILE_DEV_REL_LTO", "thin");
@@ -116,11 +130,13 @@ fn dist_server(
 
     let linux_target = target.is_linux();
     let target_name = match &target.libc_suffix {
-        Some(libc_suffix) if zig => format!("{}.{libc_suffix}", target.name),
+        // Only `cargo zigbuild` understands the glibc version suffix.
+        Some(libc_suffix) if zig && !use_cross => format!("{}.{libc_suffix}", target.name),
         _ => target.name.to_owned(),
     };
     let features = allocator.to_features();
-    let command = if linux_target && zig { "zigbuild" } else { "build" };
+    let command = if linux_target && zig && !use_cross { "zigbuild" } else { "build" };
+    let program = if use_cross { "cross" } else { "cargo" };
 
     let pgo_profile = if let Some(train_crate) = pgo {
         Some(crate::pgo::gather_pgo_profile(
@@ -133,7 +149,7 @@ fn dist_server(
         None
     };
 
-    let mut cmd = build_command(sh, command, &target_name, features, dev_rel);
+    let mut cmd = build_command(sh, program, command, &target_name, features, dev_rel);
     let mut rustflags = Vec::new();
 
     if let Some(profile) = pgo_profile {
@@ -151,17 +167,40 @@ fn dist_server(
     cmd.run().context("cannot build Rust Analyzer")?;
 
     let dst = Path::new("dist").join(&target.artifact_name);
-    if target_name.contains("-windows-") {
-        zip(&target.server_path, target.symbols_path.as_ref(), &dst.with_extension("zip"))?;
+    let dst = if is_windows(&target.name) {
+        let dst = dst.with_extension("zip");
+        zip(&target.server_path, target.symbols_path.as_ref(), &dst)?;
+        dst
     } else {
-        gzip(&target.server_path, &dst.with_extension("gz"))?;
-    }
+        let dst = dst.with_extension("gz");
+        gzip(&target.server_path, &dst)?;
+        dst
+    };
 
+    Ok(dst)
+}
+
+/// Records which target each artifact in `dist` was built for, so that cross-built
+/// artifacts can be told apart without relying on their file names.
+fn write_manifest(sh: &Shell, target: &Target, artifact: &Path) -> anyhow::Result<()> {
+    let artifact_name = artifact.file_name().unwrap().to_str().unwrap();
+    sh.write_file(Path::new("dist").join("manifest.json"), manifest(&target.name, artifact_name))?;
     Ok(())
 }
 
+fn manifest(target_name: &str, artifact_name: &str) -> String {
+    let mut buf = String::new();
+    write_json::object(&mut buf)
+        .array("artifacts")
+        .object()
+        .string("name", artifact_name)
+        .string("target", target_name);
+    buf
+}
+
 fn build_command<'a>(
     sh: &'a Shell,
+    program: &str,
     command: &str,
     target_name: &str,
     features: &[&str],
@@ -170,10 +209,35 @@ fn build_command<'a>(
     let profile = if dev_rel { "dev-rel" } else { "release" };
     cmd!(
         sh,
-        "cargo {command} --manifest-path ./crates/rust-analyzer/Cargo.toml --bin rust-analyzer --target {target_name} {features...} --profile {profile}"
+        "{program} {command} --manifest-path ./crates/rust-analyzer/Cargo.toml --bin rust-analyzer --target {target_name} {features...} --profile {profile}"
     )
 }
 
+fn cross_available(sh: &Shell) -> bool {
+    if cmd!(sh, "cross --version").read().is_ok() {
+        return true;
+    }
+    eprintln!("`cross` is not installed, falling back to cargo (`cargo install cross` to use it)");
+    false
+}
+
+fn check_target_installed(sh: &Shell, target_name: &str) -> anyhow::Result<()> {
+    let installed = cmd!(sh, "rustup target list --installed")
+        .read()
+        .context("cannot list installed targets, is rustup available?")?;
+    if !parse_installed_targets(&installed).contains(&target_name) {
+        bail!(
+            "the standard library for `{target_name}` is not installed\n\
+             help: run `rustup target add {target_name}` or pass `--use-cross`"
+        );
+    }
+    Ok(())
+}
+
+fn parse_installed_targets(output: &str) -> Vec<&str> {
+    output.lines().map(str::trim).filter(|line| !line.is_empty()).collect()
+}
+
 fn gzip(src_path: &Path, dest_path: &Path) -> anyhow::Result<()> {
     let mut encoder = GzEncoder::new(File::create(dest_path)?, Compression::best());
     let mut input = io::BufReader::new(File::open(src_path)?);
@@ -227,20 +291,16 @@ struct Target {
 }
 
 impl Target {
-    fn get(project_root: &Path, sh: &Shell) -> Self {
-        let name = detect_target(sh);
+    fn get(project_root: &Path, sh: &Shell, explicit: Option<String>) -> Self {
+        let name = explicit.unwrap_or_else(|| detect_target(sh));
         let (name, libc_suffix) = match name.split_once('.') {
             Some((l, r)) => (l.to_owned(), Some(r.to_owned())),
             None => (name, None),
         };
         let out_path = project_root.join("target").join(&name).join("release");
-        let (exe_suffix, symbols_path) = if name.contains("-windows-") {
-            (".exe".into(), Some(out_path.join("rust_analyzer.pdb")))
-        } else {
-            (String::new(), None)
-        };
-        let server_path = out_path.join(format!("rust-analyzer{exe_suffix}"));
-        let artifact_name = format!("rust-analyzer-{name}{exe_suffix}");
+        let symbols_path = is_windows(&name).then(|| out_path.join("rust_analyzer.pdb"));
+        let server_path = out_path.join(format!("rust-analyzer{}", exe_suffix(&name)));
+        let artifact_name = artifact_name(&name);
         Self { name, libc_suffix, server_path, symbols_path, artifact_name }
     }
 
@@ -249,6 +309,18 @@ fn is_linux(&self) -> bool {
     }
 }
 
+fn is_windows(target_name: &str) -> bool {
+    target_name.contains("-windows-")
+}
+
+fn exe_suffix(target_name: &str) -> &'static str {
+    if is_windows(target_name) { ".exe" } else { "" }
+}
+
+fn artifact_name(target_name: &str) -> String {
+    format!("rust-analyzer-{target_name}{}", exe_suffix(target_name))
+}
+
 struct Patch {
     path: PathBuf,
     original_contents: String,
@@ -281,3 +353,40 @@ fn drop(&mut self) {
         // write_file(&self.path, &self.original_contents).unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifact_names() {
+        let cases = [
+            ("x86_64-unknown-linux-gnu", "rust-analyzer-x86_64-unknown-linux-gnu"),
+            ("aarch64-unknown-linux-musl", "rust-analyzer-aarch64-unknown-linux-musl"),
+            ("aarch64-apple-darwin", "rust-analyzer-aarch64-apple-darwin"),
+            ("x86_64-pc-windows-msvc", "rust-analyzer-x86_64-pc-windows-msvc.exe"),
+            ("aarch64-pc-windows-msvc", "rust-analyzer-aarch64-pc-windows-msvc.exe"),
+        ];
+        for (target, expected) in cases {
+            assert_eq!(artifact_name(target), expected);
+        }
+    }
+
+    #[test]
+    fn manifest_records_target() {
+        assert_eq!(
+            manifest("aarch64-pc-windows-msvc", "rust-analyzer-aarch64-pc-windows-msvc.zip"),
+            r#"{"artifacts":[{"name":"rust-analyzer-aarch64-pc-windows-msvc.zip","target":"aarch64-pc-windows-msvc"}]}"#
+        );
+    }
+
+    #[test]
+    fn installed_targets_parsing() {
+        let output = "aarch64-apple-darwin\nx86_64-unknown-linux-gnu\n\n";
+        assert_eq!(
+            parse_installed_targets(output),
+            ["aarch64-apple-darwin", "x86_64-unknown-linux-gnu"]
+        );
+        assert!(parse_installed_targets("").is_empty());
+    }
+}
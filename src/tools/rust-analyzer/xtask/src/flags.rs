@@ -80,6 +80,10 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
             optional --zig
             /// Apply PGO optimizations
             optional --pgo pgo: PgoTrainingCrate
+            /// Build for the given target triple instead of the host.
+            optional --target target: String
+            /// Build with `cross` instead of cargo, if it is installed.
+            optional --use-cross
         }
         /// Read a changelog AsciiDoc file and update the GitHub Releases entry in Markdown.
         cmd publish-release-notes {
@@ -156,6 +160,8 @@ pub struct Dist {
     pub client_patch_version: Option<String>,
     pub zig: bool,
     pub pgo: Option<PgoTrainingCrate>,
+    pub target: Option<String>,
+    pub use_cross: bool,
 }
 
 #[derive(Debug)]
@@ -6,7 +6,7 @@
 use std::path::{Path, PathBuf};
 use xshell::{Cmd, Shell, cmd};
 
-use crate::flags::PgoTrainingCrate;
+use crate::{flags::PgoTrainingCrate, util::read_dir_sorted};
 
 /// Decorates `ra_build_cmd` to add PGO instrumentation, and then runs the PGO instrumented
 /// Rust Analyzer on itself to gather a PGO profile.
@@ -53,15 +53,10 @@ pub(crate) fn gather_pgo_profile<'a>(
 
     // Merge profiles into a single file
     let merged_profile = pgo_dir.join("merged.profdata");
-    let profile_files = std::fs::read_dir(pgo_dir)?
-        .filter_map(|entry| {
-            let entry = entry.ok()?;
-            if entry.path().extension() == Some(OsStr::new("profraw")) {
-                Some(entry.path().to_str().unwrap().to_owned())
-            } else {
-                None
-            }
-        })
+    let profile_files = read_dir_sorted(&pgo_dir)?
+        .into_iter()
+        .filter(|path| path.extension() == Some(OsStr::new("profraw")))
+        .map(|path| path.to_str().unwrap().to_owned())
         .collect::<Vec<_>>();
 
     if profile_files.is_empty() {
@@ -1,5 +1,9 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
+use anyhow::Context;
 use xshell::{Shell, cmd};
 
 pub(crate) fn list_rust_files(dir: &Path) -> Vec<PathBuf> {
@@ -29,9 +33,71 @@ pub(crate) fn list_files(dir: &Path) -> Vec<PathBuf> {
             }
         }
     }
+    // Directory iteration order is platform dependent, keep the output stable.
+    res.sort();
     res
 }
 
+/// Returns whether `path` exists and holds `contents`, ignoring differences in line endings.
+pub(crate) fn has_contents(path: &Path, contents: &str) -> bool {
+    fs::read_to_string(path)
+        .is_ok_and(|old_contents| normalize_newlines(&old_contents) == normalize_newlines(contents))
+}
+
+/// Writes `contents` to `path` unless it already holds them, creating parent directories as
+/// needed. Returns whether the file was written.
+pub(crate) fn write_if_changed(path: &Path, contents: &str) -> anyhow::Result<bool> {
+    if has_contents(path, contents) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Lists the entries of `dir`, sorted by path so that the order doesn't depend on the platform.
+pub(crate) fn read_dir_sorted(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut res = fs::read_dir(dir)
+        .and_then(|entries| entries.map(|entry| Ok(entry?.path())).collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("failed to read {}", dir.display()))?;
+    res.sort();
+    Ok(res)
+}
+
+/// Recursively copies the contents of `src` into `dst`, creating `dst` if needed.
+#[allow(dead_code)]
+pub(crate) fn copy_dir_all(src: &Path, dst: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(dst).with_context(|| format!("failed to create {}", dst.display()))?;
+    for path in read_dir_sorted(src)? {
+        let dst = dst.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir_all(&path, &dst)?;
+        } else {
+            fs::copy(&path, &dst).with_context(|| {
+                format!("failed to copy {} to {}", path.display(), dst.display())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes the file at `path`, doing nothing if it doesn't exist.
+pub(crate) fn remove_file_if_exists(path: &Path) -> anyhow::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            Err(err).with_context(|| format!("failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn normalize_newlines(s: &str) -> String {
+    s.replace("\r\n", "\n")
+}
+
 pub(crate) fn detect_target(sh: &Shell) -> String {
     match std::env::var("RA_TARGET") {
         Ok(target) => target,
@@ -41,3 +107,94 @@ pub(crate) fn detect_target(sh: &Shell) -> String {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("xtask-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn write_if_changed_skips_unchanged_files() {
+        let dir = temp_dir("write-if-changed");
+        let file = dir.join("nested/file.txt");
+
+        assert!(write_if_changed(&file, "a\nb\n").unwrap());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a\nb\n");
+        assert!(!write_if_changed(&file, "a\nb\n").unwrap());
+        assert!(!write_if_changed(&file, "a\r\nb\r\n").unwrap());
+        assert!(write_if_changed(&file, "a\n").unwrap());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn read_dir_sorted_lists_entries_in_order() {
+        let dir = temp_dir("read-dir-sorted");
+        for file in ["c.rs", "a.rs", "b/d.rs"] {
+            write_if_changed(&dir.join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            read_dir_sorted(&dir).unwrap(),
+            [dir.join("a.rs"), dir.join("b"), dir.join("c.rs")]
+        );
+        let err = read_dir_sorted(&dir.join("missing")).unwrap_err();
+        assert!(err.to_string().contains("missing"), "{err}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn copy_dir_all_copies_nested_files() {
+        let dir = temp_dir("copy-dir-all");
+        let src = dir.join("src");
+        write_if_changed(&src.join("a.txt"), "a").unwrap();
+        write_if_changed(&src.join("nested/b.txt"), "b").unwrap();
+
+        let dst = dir.join("dst");
+        copy_dir_all(&src, &dst).unwrap();
+        assert_eq!(fs::read_to_string(dst.join("a.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dst.join("nested/b.txt")).unwrap(), "b");
+        assert!(copy_dir_all(&dir.join("missing"), &dir.join("dst2")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn remove_file_if_exists_ignores_missing_files() {
+        let dir = temp_dir("remove-file-if-exists");
+        let file = dir.join("file.txt");
+        write_if_changed(&file, "").unwrap();
+
+        remove_file_if_exists(&file).unwrap();
+        assert!(!file.exists());
+        remove_file_if_exists(&file).unwrap();
+        let err = remove_file_if_exists(&dir).unwrap_err();
+        assert!(err.to_string().contains("failed to remove"), "{err}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn list_files_is_sorted_and_skips_hidden() {
+        let dir = temp_dir("list-files");
+        for file in ["b.rs", "a/z.rs", "a/c.txt", ".hidden/x.rs", ".y.rs"] {
+            write_if_changed(&dir.join(file), "").unwrap();
+        }
+
+        let files = list_files(&dir);
+        let files = files.iter().map(|it| it.strip_prefix(&dir).unwrap()).collect::<Vec<_>>();
+        assert_eq!(files, [Path::new("a/c.txt"), Path::new("a/z.rs"), Path::new("b.rs")]);
+        let rust_files = list_rust_files(&dir);
+        assert_eq!(rust_files, [dir.join("a/z.rs"), dir.join("b.rs")]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}